@@ -58,7 +58,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use structopt::StructOpt;
 
-use runestick::{Unit, Value, VmExecution};
+use runestick::{Coverage, Unit, Value, VmExecution};
 
 pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"));
 
@@ -108,9 +108,24 @@ struct Args {
     /// Only test that the specified files compile, but don't execute them.
     #[structopt(long)]
     test: bool,
+    /// Check type annotations against the inferred types of expressions
+    /// before running (same as -O type-check=true).
+    ///
+    /// Combine with `--test` to only check the specified files.
+    #[structopt(long)]
+    types: bool,
+    /// Record coverage while running and write it in LCOV format to the given
+    /// path (requires -O debug-info=true).
+    ///
+    /// Can't be combined with `--test`, since it doesn't run the scripts.
+    #[structopt(long, parse(from_os_str), conflicts_with = "test")]
+    coverage: Option<PathBuf>,
     /// Rune scripts to run.
     #[structopt(parse(from_os_str))]
     paths: Vec<PathBuf>,
+    /// Arguments passed to scripts, which are available through `env::args`.
+    #[structopt(last = true)]
+    script_args: Vec<String>,
     /// Set the given compiler option (see `--help` for available options).
     ///
     /// memoize-instance-fn[=<true/false>] - Inline the lookup of an instance function where appropriate.
@@ -122,6 +137,8 @@ struct Args {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// type-check[=<true/false>] - Check type annotations while compiling.
     #[structopt(name = "option", short = "O", number_of_values = 1)]
     compiler_options: Vec<String>,
 }
@@ -160,6 +177,10 @@ async fn try_main() -> Result<ExitCode> {
         options.parse_option(opt)?;
     }
 
+    if args.types {
+        options.type_check(true);
+    }
+
     if args.paths.is_empty() {
         println!("Invalid usage: Missing Input Paths (at least one file required)");
         return Ok(ExitCode::Failure);
@@ -168,10 +189,18 @@ async fn try_main() -> Result<ExitCode> {
     let paths = walk_paths(args.recursive, std::mem::take(&mut args.paths));
     let mut status = ExitCode::Success;
 
+    let mut lcov = match &args.coverage {
+        Some(path) => Some(io::BufWriter::new(
+            fs::File::create(path)
+                .with_context(|| format!("creating coverage file: {}", path.display()))?,
+        )),
+        None => None,
+    };
+
     for path in paths {
         let path = path?;
 
-        match run_path(&args, &options, &path).await? {
+        match run_path(&args, &options, &path, lcov.as_mut()).await? {
             ExitCode::Success => (),
             other => {
                 if args.test {
@@ -184,6 +213,11 @@ async fn try_main() -> Result<ExitCode> {
         }
     }
 
+    if let Some(lcov) = lcov.as_mut() {
+        use std::io::Write as _;
+        lcov.flush()?;
+    }
+
     Ok(status)
 }
 
@@ -225,13 +259,18 @@ fn walk_paths(recursive: bool, paths: Vec<PathBuf>) -> impl Iterator<Item = io::
 }
 
 /// Run a single path.
-async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<ExitCode> {
+async fn run_path(
+    args: &Args,
+    options: &rune::Options,
+    path: &Path,
+    lcov: Option<&mut io::BufWriter<fs::File>>,
+) -> Result<ExitCode> {
     if args.test {
         println!("testing: {}", path.display());
     }
 
     let bytecode_path = path.with_extension("rnc");
-    let mut context = rune::default_context()?;
+    let mut context = rune::default_context_with_args(args.script_args.clone())?;
 
     if args.experimental {
         context.install(&rune_macros::module()?)?;
@@ -300,7 +339,15 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
         }
     };
 
-    let vm = runestick::Vm::new(context.clone(), unit.clone());
+    let mut vm = runestick::Vm::new(context.clone(), unit.clone());
+
+    let coverage = if lcov.is_some() {
+        let coverage = Arc::new(Coverage::new(unit.clone()));
+        vm.set_coverage(Some(coverage.clone()));
+        Some(coverage)
+    } else {
+        None
+    };
 
     if args.dump_native_functions {
         println!("# functions");
@@ -451,6 +498,10 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
         }
     };
 
+    if let (Some(lcov), Some(coverage)) = (lcov, &coverage) {
+        rune::coverage::emit_lcov(lcov, coverage, &sources)?;
+    }
+
     if args.dump_stack {
         println!("# full stack dump after halting");
 
@@ -0,0 +1,477 @@
+//! The native `csv` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["csv"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::csv::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use csv::Reader;
+//! use csv::Writer;
+//!
+//! async fn main() {
+//!     let reader = Reader::from_path("people.csv", #{}).await?;
+//!
+//!     for record in reader {
+//!         let record = record?;
+//!         println(`{record.name} is {record.age} years old`);
+//!     }
+//!
+//!     let reader = Reader::from_string("1;2\n3;4\n", #{delimiter: ";", headers: false})?;
+//!     dbg(reader.read_all()?);
+//!
+//!     let writer = Writer::new(#{columns: ["name", "age"], quote_style: "always"});
+//!     writer.write(#{name: "Bob", age: 42})?;
+//!     println(writer.to_string()?);
+//! }
+//! ```
+//!
+//! Readers produce records as objects keyed by the headers in the first row,
+//! or as vectors of strings if the `headers` option is `false`. The supported
+//! options for readers are:
+//! * `delimiter` - the field delimiter, defaults to `","`.
+//! * `quote` - the quote character, defaults to `"\""`.
+//! * `headers` - if the first row contains headers, defaults to `true`.
+//! * `flexible` - if records can have a varying number of fields, defaults to
+//!   `false`.
+//! * `trim` - if whitespace around fields should be trimmed, defaults to
+//!   `false`.
+//!
+//! Writers accept records as vectors, tuples or objects, where the fields of
+//! objects are written in the order of the `columns` option. If a writer has
+//! columns, they are written as a header row before the first record.
+//!
+//! Without columns, the first record decides how the writer behaves. If it's
+//! an object, its fields sorted by name are used as the columns and written as
+//! the header row. Every record which follows must then be of the same kind as
+//! the first one, either objects or vectors and tuples.
+//!
+//! The supported options for writers are `delimiter`, `quote`, `columns`, and
+//! `quote_style` which is one of `"necessary"` (the default), `"always"`,
+//! `"non_numeric"` or `"never"`.
+//!
+//! Paths read from are checked against the [sandbox][runestick::sandbox] of the
+//! virtual machine.
+
+use crate::path;
+use runestick::sandbox::{self, Capability};
+use runestick::{Any, Bytes, ContextError, Module, Object, Shared, Value, VmError};
+use std::fmt;
+use std::fmt::Write as _;
+use std::io;
+
+/// Construct the `csv` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["csv"]);
+
+    module.ty::<Reader>()?;
+    module.ty::<Writer>()?;
+    module.ty::<Error>()?;
+
+    module.function(&["Reader", "from_string"], Reader::from_string)?;
+    module.async_function(&["Reader", "from_path"], Reader::from_path)?;
+    module.function(&["Writer", "new"], Writer::new)?;
+
+    module.inst_fn("headers", Reader::headers)?;
+    module.inst_fn("read_all", Reader::read_all)?;
+    module.inst_fn("next", Reader::next)?;
+    module.inst_fn(runestick::NEXT, Reader::next)?;
+    module.inst_fn(runestick::INTO_ITER, Reader::into_iter)?;
+
+    module.inst_fn("write", Writer::write)?;
+    module.inst_fn("to_string", Writer::written_string)?;
+    module.inst_fn("to_bytes", Writer::written_bytes)?;
+
+    module.inst_fn("line", Error::line)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+    Ok(module)
+}
+
+/// An error raised when reading or writing CSV.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: csv_crate::Error,
+}
+
+impl Error {
+    /// The line at which the error occurred, starting at 1, if known.
+    fn line(&self) -> Option<u64> {
+        Some(self.inner.position()?.line())
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+impl From<csv_crate::Error> for Error {
+    fn from(inner: csv_crate::Error) -> Self {
+        Self { inner }
+    }
+}
+
+/// A reader of CSV records.
+#[derive(Any)]
+pub struct Reader {
+    inner: csv_crate::Reader<Box<dyn io::Read>>,
+    headers: Option<Vec<String>>,
+}
+
+impl fmt::Debug for Reader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reader")
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+impl Reader {
+    /// Construct a reader over the given string or bytes.
+    fn from_string(data: Value, options: &Object) -> Result<Result<Self, Error>, VmError> {
+        let data = match data {
+            Value::String(s) => s.borrow_ref()?.as_bytes().to_vec(),
+            Value::StaticString(s) => s.as_bytes().to_vec(),
+            Value::Bytes(b) => b.borrow_ref()?.to_vec(),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        };
+
+        Self::new(Box::new(io::Cursor::new(data)), options)
+    }
+
+    /// Construct a reader over the file at the given path.
+    ///
+    /// The file is read in full when the reader is constructed.
+    async fn from_path(path: Value, options: Object) -> Result<Result<Self, Error>, VmError> {
+        let path = path::from_value(&path)?;
+        sandbox::check(Capability::Path(&path))?;
+
+        let data = match tokio::fs::read(path).await {
+            Ok(data) => data,
+            Err(error) => return Ok(Err(Error::from(csv_crate::Error::from(error)))),
+        };
+
+        Self::new(Box::new(io::Cursor::new(data)), &options)
+    }
+
+    fn new(input: Box<dyn io::Read>, options: &Object) -> Result<Result<Self, Error>, VmError> {
+        let mut builder = csv_crate::ReaderBuilder::new();
+
+        if let Some(delimiter) = byte_option(options, "delimiter")? {
+            builder.delimiter(delimiter);
+        }
+
+        if let Some(quote) = byte_option(options, "quote")? {
+            builder.quote(quote);
+        }
+
+        let headers = bool_option(options, "headers")?.unwrap_or(true);
+        builder.has_headers(headers);
+        builder.flexible(bool_option(options, "flexible")?.unwrap_or_default());
+
+        if bool_option(options, "trim")?.unwrap_or_default() {
+            builder.trim(csv_crate::Trim::All);
+        }
+
+        let mut inner = builder.from_reader(input);
+
+        let headers = if headers {
+            match inner.headers() {
+                Ok(record) => Some(record.iter().map(String::from).collect()),
+                Err(error) => return Ok(Err(Error::from(error))),
+            }
+        } else {
+            None
+        };
+
+        Ok(Ok(Self { inner, headers }))
+    }
+
+    /// Get the headers of the records, or `None` if the reader doesn't have
+    /// headers.
+    fn headers(&self) -> Option<Vec<String>> {
+        self.headers.clone()
+    }
+
+    /// Read all remaining records.
+    fn read_all(&mut self) -> Result<Vec<Value>, Error> {
+        let mut records = Vec::new();
+
+        for record in self {
+            records.push(record?);
+        }
+
+        Ok(records)
+    }
+
+    /// Convert a record into either an object keyed by the headers or a vector
+    /// of strings.
+    fn convert(&self, record: &csv_crate::StringRecord) -> Value {
+        match &self.headers {
+            Some(headers) => {
+                let mut object = Object::with_capacity(headers.len());
+
+                for (header, field) in headers.iter().zip(record.iter()) {
+                    object.insert(header.clone(), Value::from(field.to_owned()));
+                }
+
+                Value::from(object)
+            }
+            None => {
+                let fields = record
+                    .iter()
+                    .map(|field| Value::from(field.to_owned()))
+                    .collect::<Vec<_>>();
+
+                Value::Vec(Shared::new(fields))
+            }
+        }
+    }
+}
+
+impl Iterator for Reader {
+    type Item = Result<Value, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = csv_crate::StringRecord::new();
+
+        match self.inner.read_record(&mut record) {
+            Ok(true) => Some(Ok(self.convert(&record))),
+            Ok(false) => None,
+            Err(error) => Some(Err(Error::from(error))),
+        }
+    }
+}
+
+/// The kind of a record written by a [Writer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// A vector or a tuple.
+    Sequence,
+    /// An object.
+    Object,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sequence => write!(f, "vector or tuple"),
+            Self::Object => write!(f, "object"),
+        }
+    }
+}
+
+/// A writer of CSV records into memory.
+#[derive(Any)]
+pub struct Writer {
+    inner: csv_crate::Writer<Vec<u8>>,
+    columns: Option<Vec<String>>,
+    /// If the columns were specified when constructing the writer.
+    has_columns: bool,
+    /// The kind of the first record written, if any.
+    first: Option<Kind>,
+}
+
+impl fmt::Debug for Writer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Writer")
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+impl Writer {
+    /// Construct a new writer with the given options.
+    fn new(options: &Object) -> Result<Self, VmError> {
+        let mut builder = csv_crate::WriterBuilder::new();
+
+        if let Some(delimiter) = byte_option(options, "delimiter")? {
+            builder.delimiter(delimiter);
+        }
+
+        if let Some(quote) = byte_option(options, "quote")? {
+            builder.quote(quote);
+        }
+
+        if let Some(style) = string_option(options, "quote_style")? {
+            builder.quote_style(match style.as_str() {
+                "necessary" => csv_crate::QuoteStyle::Necessary,
+                "always" => csv_crate::QuoteStyle::Always,
+                "non_numeric" => csv_crate::QuoteStyle::NonNumeric,
+                "never" => csv_crate::QuoteStyle::Never,
+                other => {
+                    return Err(VmError::panic(format!(
+                        "unsupported quote style `{}`",
+                        other
+                    )))
+                }
+            });
+        }
+
+        let columns = match options.get("columns") {
+            Some(Value::Vec(columns)) => {
+                let mut out = Vec::new();
+
+                for column in &*columns.borrow_ref()? {
+                    out.push(field(column)?);
+                }
+
+                Some(out)
+            }
+            Some(actual) => return Err(VmError::expected::<Vec<Value>>(actual.type_info()?)),
+            None => None,
+        };
+
+        Ok(Self {
+            inner: builder.from_writer(Vec::new()),
+            has_columns: columns.is_some(),
+            columns,
+            first: None,
+        })
+    }
+
+    /// Write a single record, which is either a vector, a tuple or an object.
+    ///
+    /// Unless the writer has columns, every record must be of the same kind as
+    /// the first one.
+    fn write(&mut self, record: Value) -> Result<Result<(), Error>, VmError> {
+        let kind = match &record {
+            Value::Vec(..) | Value::Tuple(..) => Kind::Sequence,
+            Value::Object(..) => Kind::Object,
+            actual => return Err(VmError::expected::<Object>(actual.type_info()?)),
+        };
+
+        if let Some(first) = self.first {
+            if first != kind && !self.has_columns {
+                return Err(VmError::panic(format!(
+                    "cannot write {} record after {} record to a writer without columns",
+                    kind, first
+                )));
+            }
+        }
+
+        let fields = match record {
+            Value::Vec(vec) => fields(&vec.borrow_ref()?)?,
+            Value::Tuple(tuple) => fields(&tuple.borrow_ref()?)?,
+            Value::Object(object) => {
+                let object = object.borrow_ref()?;
+
+                if self.columns.is_none() {
+                    let mut columns = object.iter().map(|(k, _)| k.clone()).collect::<Vec<_>>();
+                    columns.sort();
+                    self.columns = Some(columns);
+                }
+
+                let mut fields = Vec::new();
+
+                for column in self.columns.iter().flatten() {
+                    fields.push(match object.get(column) {
+                        Some(value) => field(value)?,
+                        None => String::new(),
+                    });
+                }
+
+                fields
+            }
+            actual => return Err(VmError::expected::<Object>(actual.type_info()?)),
+        };
+
+        if self.first.replace(kind).is_none() {
+            if let Some(columns) = &self.columns {
+                if let Err(error) = self.inner.write_record(columns) {
+                    return Ok(Err(Error::from(error)));
+                }
+            }
+        }
+
+        Ok(self.inner.write_record(&fields).map_err(Error::from))
+    }
+
+    /// Get everything written so far as a string.
+    fn written_string(&mut self) -> Result<String, Error> {
+        self.inner.flush().map_err(csv_crate::Error::from)?;
+        Ok(String::from_utf8_lossy(self.inner.get_ref()).into_owned())
+    }
+
+    /// Get everything written so far as bytes.
+    fn written_bytes(&mut self) -> Result<Bytes, Error> {
+        self.inner.flush().map_err(csv_crate::Error::from)?;
+        Ok(Bytes::from_vec(self.inner.get_ref().clone()))
+    }
+}
+
+/// Convert the given values into fields.
+fn fields(values: &[Value]) -> Result<Vec<String>, VmError> {
+    values.iter().map(field).collect()
+}
+
+/// Convert the given value into a field.
+fn field(value: &Value) -> Result<String, VmError> {
+    Ok(match value {
+        Value::String(s) => s.borrow_ref()?.clone(),
+        Value::StaticString(s) => s.as_str().to_owned(),
+        Value::Integer(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Char(c) => c.to_string(),
+        Value::Unit => String::new(),
+        Value::Option(option) => match &*option.borrow_ref()? {
+            Some(value) => field(value)?,
+            None => String::new(),
+        },
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    })
+}
+
+fn string_option(options: &Object, key: &str) -> Result<Option<String>, VmError> {
+    Ok(match options.get(key) {
+        Some(Value::String(s)) => Some(s.borrow_ref()?.clone()),
+        Some(Value::StaticString(s)) => Some(s.as_str().to_owned()),
+        Some(actual) => return Err(VmError::expected::<String>(actual.type_info()?)),
+        None => None,
+    })
+}
+
+fn bool_option(options: &Object, key: &str) -> Result<Option<bool>, VmError> {
+    Ok(match options.get(key) {
+        Some(Value::Bool(b)) => Some(*b),
+        Some(actual) => return Err(VmError::expected::<bool>(actual.type_info()?)),
+        None => None,
+    })
+}
+
+/// Get an option which must be a single byte, like a delimiter.
+fn byte_option(options: &Object, key: &str) -> Result<Option<u8>, VmError> {
+    let string = match options.get(key) {
+        Some(Value::Char(c)) => c.to_string(),
+        Some(_) => match string_option(options, key)? {
+            Some(string) => string,
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+
+    match string.as_bytes() {
+        [b] => Ok(Some(*b)),
+        _ => Err(VmError::panic(format!(
+            "option `{}` must be a single byte, but was `{}`",
+            key, string
+        ))),
+    }
+}
@@ -0,0 +1,210 @@
+//! The native `encoding` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["encoding"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::encoding::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use encoding::base64;
+//! use encoding::hex;
+//!
+//! fn verify(secret, body, signature) {
+//!     let expected = hex::encode(encoding::hmac_sha256(secret, body));
+//!     expected == signature
+//! }
+//!
+//! fn main() {
+//!     let token = base64::encode("user:password");
+//!     dbg(base64::decode(token)?);
+//!     dbg(encoding::url::encode("a b&c"));
+//!     dbg(hex::encode(encoding::sha256("hello")));
+//!     dbg(encoding::crc32("hello"));
+//! }
+//! ```
+//!
+//! Functions which take data accept both strings and bytes.
+
+use hmac::{Mac as _, NewMac as _};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+use runestick::{Any, Bytes, ContextError, Module, Value, VmError};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// The characters which are percent-encoded in URL components, which is
+/// everything except for the unreserved characters of RFC 3986.
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Construct the `encoding` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["encoding"]);
+
+    module.ty::<Error>()?;
+
+    module.function(&["base64", "encode"], base64_encode)?;
+    module.function(&["base64", "decode"], base64_decode)?;
+    module.function(&["base64", "encode_url_safe"], base64_encode_url_safe)?;
+    module.function(&["base64", "decode_url_safe"], base64_decode_url_safe)?;
+
+    module.function(&["hex", "encode"], hex_encode)?;
+    module.function(&["hex", "decode"], hex_decode)?;
+
+    module.function(&["url", "encode"], url_encode)?;
+    module.function(&["url", "decode"], url_decode)?;
+
+    module.function(&["sha1"], digest::<sha1::Sha1>)?;
+    module.function(&["sha256"], digest::<sha2::Sha256>)?;
+    module.function(&["sha512"], digest::<sha2::Sha512>)?;
+    module.function(&["md5"], digest::<md5::Md5>)?;
+    module.function(&["crc32"], crc32)?;
+
+    module.function(&["hmac_sha1"], hmac::<sha1::Sha1>)?;
+    module.function(&["hmac_sha256"], hmac::<sha2::Sha256>)?;
+    module.function(&["hmac_sha512"], hmac::<sha2::Sha512>)?;
+
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+    Ok(module)
+}
+
+/// An error raised when decoding.
+#[derive(Debug, Any)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Base64(base64::DecodeError),
+    Hex(hex::FromHexError),
+    Utf8(std::str::Utf8Error),
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Base64(error) => write!(buf, "{}", error),
+            ErrorKind::Hex(error) => write!(buf, "{}", error),
+            ErrorKind::Utf8(error) => write!(buf, "{}", error),
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+/// Encode the given data as base64 with the standard alphabet.
+fn base64_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| base64::encode(data))
+}
+
+/// Decode the given base64 with the standard alphabet.
+fn base64_decode(string: &str) -> Result<Bytes, Error> {
+    match base64::decode(string) {
+        Ok(bytes) => Ok(Bytes::from_vec(bytes)),
+        Err(error) => Err(Error::from(ErrorKind::Base64(error))),
+    }
+}
+
+/// Encode the given data as base64 with the URL and filename safe alphabet.
+fn base64_encode_url_safe(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| base64::encode_config(data, base64::URL_SAFE))
+}
+
+/// Decode the given base64 with the URL and filename safe alphabet.
+fn base64_decode_url_safe(string: &str) -> Result<Bytes, Error> {
+    match base64::decode_config(string, base64::URL_SAFE) {
+        Ok(bytes) => Ok(Bytes::from_vec(bytes)),
+        Err(error) => Err(Error::from(ErrorKind::Base64(error))),
+    }
+}
+
+/// Encode the given data as lowercase hex.
+fn hex_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| hex::encode(data))
+}
+
+/// Decode the given hex, which can be in either case.
+fn hex_decode(string: &str) -> Result<Bytes, Error> {
+    match hex::decode(string) {
+        Ok(bytes) => Ok(Bytes::from_vec(bytes)),
+        Err(error) => Err(Error::from(ErrorKind::Hex(error))),
+    }
+}
+
+/// Percent-encode the given data so that it can be used as a URL component,
+/// like a query parameter or a path segment.
+fn url_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| {
+        percent_encoding::percent_encode(data, URL_COMPONENT).to_string()
+    })
+}
+
+/// Decode the given percent-encoded string.
+fn url_decode(string: &str) -> Result<String, Error> {
+    match percent_encoding::percent_decode_str(string).decode_utf8() {
+        Ok(string) => Ok(string.into_owned()),
+        Err(error) => Err(Error::from(ErrorKind::Utf8(error))),
+    }
+}
+
+/// Calculate the digest of the given data.
+fn digest<D>(data: Value) -> Result<Bytes, VmError>
+where
+    D: sha2::Digest,
+{
+    with_bytes(&data, |data| Bytes::from_vec(D::digest(data).to_vec()))
+}
+
+/// Calculate the CRC32 checksum of the given data.
+fn crc32(data: Value) -> Result<u32, VmError> {
+    with_bytes(&data, crc32fast::hash)
+}
+
+/// Calculate the HMAC of the given data with the given key.
+fn hmac<D>(key: Value, data: Value) -> Result<Bytes, VmError>
+where
+    D: sha2::digest::Update + sha2::digest::BlockInput + sha2::digest::FixedOutput,
+    D: sha2::digest::Reset + Default + Clone,
+{
+    let mut mac = with_bytes(&key, |key| {
+        // NB: HMAC accepts keys of any length.
+        hmac::Hmac::<D>::new_varkey(key).expect("keys of any length are valid")
+    })?;
+
+    with_bytes(&data, |data| mac.update(data))?;
+    Ok(Bytes::from_vec(mac.finalize().into_bytes().to_vec()))
+}
+
+/// Call the given closure with the bytes of a string or bytes value.
+fn with_bytes<T>(value: &Value, f: impl FnOnce(&[u8]) -> T) -> Result<T, VmError> {
+    Ok(match value {
+        Value::String(s) => f(s.borrow_ref()?.as_bytes()),
+        Value::StaticString(s) => f(s.as_bytes()),
+        Value::Bytes(b) => f(&b.borrow_ref()?),
+        actual => return Err(VmError::expected::<Bytes>(actual.type_info()?)),
+    })
+}
@@ -0,0 +1,91 @@
+//! The native `env` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["env"]}
+//! ```
+//!
+//! Install it into your context, optionally with the arguments which should be
+//! available to scripts through `env::args`:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::env::module_with_args(vec![String::from("--verbose")])?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn main() {
+//!     let home = env::var("HOME").unwrap_or("/");
+//!     let dir = env::current_dir()?;
+//!
+//!     for arg in env::args() {
+//!         println(arg);
+//!     }
+//!
+//!     dbg(env::vars());
+//! }
+//! ```
+
+use crate::path::PathBuf;
+use runestick::{ContextError, Module, Object, ToValue as _, Value, VmError, VmErrorKind};
+use std::env;
+use std::io;
+
+/// Construct the `env` module, where `env::args` returns no arguments.
+pub fn module() -> Result<Module, ContextError> {
+    module_with_args(Vec::new())
+}
+
+/// Construct the `env` module, where `env::args` returns the given arguments.
+pub fn module_with_args(args: Vec<String>) -> Result<Module, ContextError> {
+    let mut module = Module::new(&["env"]);
+    module.function(&["var"], var)?;
+    module.function(&["vars"], vars)?;
+    module.raw_fn(&["args"], move |stack, count| {
+        if count != 0 {
+            return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                actual: count,
+                expected: 0,
+            }));
+        }
+
+        stack.push(args.clone().to_value()?);
+        Ok(())
+    })?;
+    module.function(&["current_dir"], current_dir)?;
+    Ok(module)
+}
+
+/// Get the value of the given environment variable, or `None` if it isn't set
+/// or isn't valid unicode.
+fn var(key: &str) -> Option<String> {
+    env::var(key).ok()
+}
+
+/// Get all environment variables which are valid unicode as an object.
+fn vars() -> Object {
+    let mut object = Object::new();
+
+    for (key, value) in env::vars_os() {
+        if let (Ok(key), Ok(value)) = (key.into_string(), value.into_string()) {
+            object.insert(key, Value::from(value));
+        }
+    }
+
+    object
+}
+
+/// Get the current working directory.
+fn current_dir() -> io::Result<PathBuf> {
+    Ok(PathBuf::from(env::current_dir()?))
+}
@@ -23,22 +23,306 @@
 //! Use it in Rune:
 //!
 //! ```rust,ignore
-//! fn main() {
+//! async fn main() {
 //!     let file = fs::read_to_string("file.txt").await?;
 //!     println(`{file}`);
+//!
+//!     fs::create_dir_all("out").await?;
+//!     fs::write("out/copy.txt", file).await?;
+//!
+//!     let entries = fs::read_dir("out").await?;
+//!
+//!     while let Some(entry) = entries.next().await {
+//!         let entry = entry?;
+//!         let metadata = entry.metadata().await?;
+//!         println(`{entry.path()}: {metadata.len()} bytes`);
+//!     }
+//!
+//!     let file = fs::File::open("file.txt").await?;
+//!
+//!     while let Some(line) = file.read_line().await? {
+//!         println(line);
+//!     }
 //! }
 //! ```
+//!
+//! Paths can either be strings or values from the [path module][crate::path].
+//! Any paths used are checked against the [sandbox][runestick::sandbox] of the
+//! virtual machine.
 
+use crate::path;
+use runestick::sandbox::{self, Capability};
+use runestick::{Any, Bytes, Stream, Value, VmError};
 use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs;
+use tokio::io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufStream};
+use tokio::stream::StreamExt as _;
 
 /// Construct the `fs` module.
 pub fn module() -> Result<runestick::Module, runestick::ContextError> {
     let mut module = runestick::Module::new(&["fs"]);
+    module.ty::<DirEntry>()?;
+    module.ty::<Metadata>()?;
+    module.ty::<File>()?;
+
+    module.async_function(&["read"], read)?;
     module.async_function(&["read_to_string"], read_to_string)?;
+    module.async_function(&["write"], write)?;
+    module.async_function(&["append"], append)?;
+    module.async_function(&["copy"], copy)?;
+    module.async_function(&["rename"], rename)?;
+    module.async_function(&["remove_file"], remove_file)?;
+    module.async_function(&["create_dir_all"], create_dir_all)?;
+    module.async_function(&["remove_dir_all"], remove_dir_all)?;
+    module.async_function(&["read_dir"], read_dir)?;
+    module.async_function(&["metadata"], metadata)?;
+
+    module.inst_fn("path", DirEntry::path)?;
+    module.inst_fn("file_name", DirEntry::file_name)?;
+    module.async_inst_fn("metadata", DirEntry::metadata)?;
+
+    module.inst_fn("len", Metadata::len)?;
+    module.inst_fn("is_dir", Metadata::is_dir)?;
+    module.inst_fn("is_file", Metadata::is_file)?;
+    module.inst_fn("modified", Metadata::modified)?;
+    module.inst_fn("readonly", Metadata::readonly)?;
+
+    module.async_function(&["File", "open"], File::open)?;
+    module.async_function(&["File", "create"], File::create)?;
+    module.async_inst_fn("read_line", File::read_line)?;
+    module.async_inst_fn("read_to_string", File::read_to_string)?;
+    module.async_inst_fn("write_all", File::write_all)?;
+    module.async_inst_fn("flush", File::flush)?;
     Ok(module)
 }
 
-async fn read_to_string(path: &str) -> io::Result<String> {
-    fs::read_to_string(path).await
+/// An entry in a directory.
+#[derive(Debug, Any)]
+struct DirEntry {
+    inner: fs::DirEntry,
+}
+
+impl DirEntry {
+    /// Get the full path of the entry.
+    fn path(&self) -> String {
+        self.inner.path().display().to_string()
+    }
+
+    /// Get the file name of the entry.
+    fn file_name(&self) -> String {
+        self.inner.file_name().to_string_lossy().into_owned()
+    }
+
+    /// Get the metadata of the entry.
+    async fn metadata(&self) -> io::Result<Metadata> {
+        let inner = self.inner.metadata().await?;
+        Ok(Metadata { inner })
+    }
+}
+
+/// Metadata for a file or directory.
+#[derive(Debug, Any)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// Get the size of the file in bytes.
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// Test if the metadata is for a directory.
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Test if the metadata is for a regular file.
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// Get the last modification time in seconds since the unix epoch.
+    fn modified(&self) -> io::Result<i64> {
+        Ok(unix_seconds(self.inner.modified()?))
+    }
+
+    /// Test if the file is read-only.
+    fn readonly(&self) -> bool {
+        self.inner.permissions().readonly()
+    }
+}
+
+/// A buffered handle to an open file.
+#[derive(Debug, Any)]
+struct File {
+    inner: BufStream<fs::File>,
+}
+
+impl File {
+    /// Open an existing file for reading.
+    async fn open(path: Value) -> Result<io::Result<Self>, VmError> {
+        let path = check(&path)?;
+
+        Ok(match fs::File::open(path).await {
+            Ok(file) => Ok(Self::new(file)),
+            Err(error) => Err(error),
+        })
+    }
+
+    /// Create a file for writing, truncating it if it already exists.
+    async fn create(path: Value) -> Result<io::Result<Self>, VmError> {
+        let path = check(&path)?;
+
+        Ok(match fs::File::create(path).await {
+            Ok(file) => Ok(Self::new(file)),
+            Err(error) => Err(error),
+        })
+    }
+
+    fn new(file: fs::File) -> Self {
+        Self {
+            inner: BufStream::new(file),
+        }
+    }
+
+    /// Read the next line from the file without the trailing newline, or
+    /// `None` if the end of the file has been reached.
+    async fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+
+        if self.inner.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    /// Read the remainder of the file into a string.
+    async fn read_to_string(&mut self) -> io::Result<String> {
+        let mut string = String::new();
+        self.inner.read_to_string(&mut string).await?;
+        Ok(string)
+    }
+
+    /// Write all of the given string or bytes to the file.
+    async fn write_all(&mut self, contents: Value) -> Result<io::Result<()>, VmError> {
+        let contents = into_bytes(contents)?;
+        Ok(self.inner.write_all(&contents).await)
+    }
+
+    /// Flush any buffered writes to the file.
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+}
+
+async fn read(path: Value) -> Result<io::Result<Bytes>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::read(path).await.map(Bytes::from_vec))
+}
+
+async fn read_to_string(path: Value) -> Result<io::Result<String>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::read_to_string(path).await)
+}
+
+async fn write(path: Value, contents: Value) -> Result<io::Result<()>, VmError> {
+    let path = check(&path)?;
+    let contents = into_bytes(contents)?;
+    Ok(fs::write(path, contents).await)
+}
+
+async fn append(path: Value, contents: Value) -> Result<io::Result<()>, VmError> {
+    let path = check(&path)?;
+    let contents = into_bytes(contents)?;
+
+    let mut options = fs::OpenOptions::new();
+    options.append(true).create(true);
+
+    let mut file = match options.open(path).await {
+        Ok(file) => file,
+        Err(error) => return Ok(Err(error)),
+    };
+
+    Ok(file.write_all(&contents).await)
+}
+
+async fn copy(from: Value, to: Value) -> Result<io::Result<u64>, VmError> {
+    let from = check(&from)?;
+    let to = check(&to)?;
+    Ok(fs::copy(from, to).await)
+}
+
+async fn rename(from: Value, to: Value) -> Result<io::Result<()>, VmError> {
+    let from = check(&from)?;
+    let to = check(&to)?;
+    Ok(fs::rename(from, to).await)
+}
+
+async fn remove_file(path: Value) -> Result<io::Result<()>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::remove_file(path).await)
+}
+
+async fn create_dir_all(path: Value) -> Result<io::Result<()>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::create_dir_all(path).await)
+}
+
+async fn remove_dir_all(path: Value) -> Result<io::Result<()>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::remove_dir_all(path).await)
+}
+
+/// Read the entries of a directory, as a stream of results with an entry
+/// each.
+async fn read_dir(path: Value) -> Result<io::Result<Stream>, VmError> {
+    let path = check(&path)?;
+
+    Ok(fs::read_dir(path).await.map(|entries| {
+        let entries = entries.map(|entry| entry.map(|inner| DirEntry { inner }));
+        Stream::from_stream(entries.map(Ok::<_, VmError>))
+    }))
+}
+
+async fn metadata(path: Value) -> Result<io::Result<Metadata>, VmError> {
+    let path = check(&path)?;
+    Ok(fs::metadata(path).await.map(|inner| Metadata { inner }))
+}
+
+/// Convert a string or path value into a path, and check that the sandbox
+/// permits access to it.
+pub(crate) fn check(path: &Value) -> Result<PathBuf, VmError> {
+    let path = path::from_value(path)?;
+    sandbox::check(Capability::Path(&path))?;
+    Ok(path)
+}
+
+/// Convert a string or bytes value into the bytes to write.
+fn into_bytes(value: Value) -> Result<Vec<u8>, VmError> {
+    Ok(match value {
+        Value::String(s) => s.borrow_ref()?.as_bytes().to_vec(),
+        Value::StaticString(s) => s.as_bytes().to_vec(),
+        Value::Bytes(b) => b.borrow_ref()?.to_vec(),
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    })
+}
+
+/// Convert a system time into seconds since the unix epoch.
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs() as i64),
+    }
 }
@@ -49,10 +49,47 @@
 //!     dbg(response);
 //! }
 //! ```
+//!
+//! ## Serving requests
+//!
+//! A [Server] routes incoming requests to Rune functions. A handler receives
+//! a [ServerRequest] and its return value is used as the response:
+//!
+//! * A [ServerResponse] is sent as-is.
+//! * A string or bytes are sent with the status `200 OK`.
+//! * A unit is sent as `204 No Content`.
+//! * Any other value is serialized as JSON.
+//!
+//! If the handler errors, the error is sent with the status
+//! `500 Internal Server Error`.
+//!
+//! ```rust,ignore
+//! use http;
+//!
+//! async fn hello(request) {
+//!     let name = request.query()["name"];
+//!     `Hello {name}!`
+//! }
+//!
+//! async fn main() {
+//!     let server = http::Server::bind("127.0.0.1:8080").await?;
+//!     server.route("GET", "/hello", hello);
+//!     server.serve().await?;
+//! }
+//! ```
 
-use runestick::{Any, Bytes, Value};
+use futures_util::future::{self, Either};
+use futures_util::stream::{FuturesUnordered, StreamExt as _};
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use runestick::sandbox::{self, Capability, Denied};
+use runestick::{Any, Bytes, Function, Object, Stream, Value, VmError, VmErrorKind};
+use std::convert::{Infallible, TryFrom as _};
 use std::fmt;
 use std::fmt::Write as _;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+use tokio::net::TcpListener;
 
 /// Construct the `http` module.
 pub fn module() -> Result<runestick::Module, runestick::ContextError> {
@@ -63,22 +100,59 @@ pub fn module() -> Result<runestick::Module, runestick::ContextError> {
     module.ty::<RequestBuilder>()?;
     module.ty::<StatusCode>()?;
     module.ty::<Error>()?;
+    module.ty::<Server>()?;
+    module.ty::<ServerRequest>()?;
+    module.ty::<ServerResponse>()?;
 
     module.function(&["Client", "new"], Client::new)?;
     module.async_function(&["get"], get)?;
 
     module.async_inst_fn("get", Client::get)?;
     module.async_inst_fn("post", Client::post)?;
+    module.async_inst_fn("put", Client::put)?;
+    module.async_inst_fn("patch", Client::patch)?;
+    module.async_inst_fn("delete", Client::delete)?;
+    module.async_inst_fn("head", Client::head)?;
 
     module.async_inst_fn("text", Response::text)?;
     module.async_inst_fn("json", Response::json)?;
+    module.inst_fn("bytes_stream", Response::bytes_stream)?;
     module.inst_fn("status", Response::status)?;
+    module.inst_fn("header", Response::header)?;
+    module.inst_fn("headers", Response::headers)?;
 
     module.async_inst_fn("send", RequestBuilder::send)?;
     module.inst_fn("header", RequestBuilder::header)?;
+    module.inst_fn("query", RequestBuilder::query)?;
+    module.inst_fn("basic_auth", RequestBuilder::basic_auth)?;
+    module.inst_fn("bearer_auth", RequestBuilder::bearer_auth)?;
+    module.inst_fn("json", RequestBuilder::json)?;
+    module.inst_fn("timeout", RequestBuilder::timeout)?;
     module.async_inst_fn("body_bytes", RequestBuilder::body_bytes)?;
 
     module.inst_fn(runestick::STRING_DISPLAY, StatusCode::display)?;
+    module.inst_fn("as_u16", StatusCode::as_u16)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+
+    module.async_function(&["Server", "bind"], Server::bind)?;
+    module.inst_fn("route", Server::route)?;
+    module.inst_fn("local_addr", Server::local_addr)?;
+    module.async_inst_fn("serve", Server::serve)?;
+    module.async_inst_fn("serve_requests", Server::serve_requests)?;
+
+    module.inst_fn("method", ServerRequest::method)?;
+    module.inst_fn("path", ServerRequest::path)?;
+    module.inst_fn("query", ServerRequest::query)?;
+    module.inst_fn("header", ServerRequest::header)?;
+    module.inst_fn("headers", ServerRequest::headers)?;
+    module.inst_fn("body", ServerRequest::body)?;
+    module.inst_fn("text", ServerRequest::text)?;
+    module.inst_fn("json", ServerRequest::json)?;
+
+    module.function(&["ServerResponse", "new"], ServerResponse::new)?;
+    module.inst_fn("header", ServerResponse::header)?;
+    module.inst_fn("body", ServerResponse::body)?;
+    module.inst_fn("json", ServerResponse::json)?;
     Ok(module)
 }
 
@@ -87,6 +161,12 @@ pub struct Error {
     inner: reqwest::Error,
 }
 
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(inner: reqwest::Error) -> Self {
         Self { inner }
@@ -112,6 +192,11 @@ impl StatusCode {
     fn display(&self, buf: &mut String) -> fmt::Result {
         write!(buf, "{}", self.inner)
     }
+
+    /// Get the status code as a number.
+    fn as_u16(&self) -> i64 {
+        self.inner.as_u16() as i64
+    }
 }
 
 impl Response {
@@ -125,12 +210,33 @@ impl Response {
         Ok(text)
     }
 
+    /// Consume the response into a stream of chunks of the body, as they
+    /// arrive.
+    fn bytes_stream(self) -> Stream {
+        Stream::from_stream(self.response.bytes_stream().map(|chunk| {
+            Ok::<_, VmError>(match chunk {
+                Ok(chunk) => Ok(Bytes::from_vec(chunk.to_vec())),
+                Err(error) => Err(Error::from(error)),
+            })
+        }))
+    }
+
     /// Get the status code of the response.
     fn status(&self) -> StatusCode {
         let inner = self.response.status();
 
         StatusCode { inner }
     }
+
+    /// Get the value of the given response header, if present.
+    fn header(&self, key: &str) -> Option<String> {
+        header(self.response.headers(), key)
+    }
+
+    /// Get all response headers as an object.
+    fn headers(&self) -> Object {
+        headers(self.response.headers())
+    }
 }
 
 #[derive(Debug, Any)]
@@ -152,6 +258,55 @@ impl RequestBuilder {
         }
     }
 
+    /// Append query parameters to the URL, from either an object or a vector
+    /// of pairs.
+    fn query(self, query: Value) -> Self {
+        Self {
+            request: self.request.query(&query),
+        }
+    }
+
+    /// Use basic authentication for the request.
+    fn basic_auth(self, username: &str, password: Option<String>) -> Self {
+        Self {
+            request: self.request.basic_auth(username, password),
+        }
+    }
+
+    /// Use bearer authentication for the request.
+    fn bearer_auth(self, token: &str) -> Self {
+        Self {
+            request: self.request.bearer_auth(token),
+        }
+    }
+
+    /// Set the request body to the given value, serialized as JSON.
+    fn json(self, value: Value) -> Self {
+        Self {
+            request: self.request.json(&value),
+        }
+    }
+
+    /// Set a timeout in seconds for the request, which applies from when it's
+    /// sent until the response body has been received.
+    fn timeout(self, seconds: Value) -> Result<Self, VmError> {
+        let seconds = match seconds {
+            Value::Integer(seconds) => seconds as f64,
+            Value::Float(seconds) => seconds,
+            actual => return Err(VmError::expected::<f64>(actual.type_info()?)),
+        };
+
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err(VmError::panic(
+                "timeout must be a positive number of seconds",
+            ));
+        }
+
+        Ok(Self {
+            request: self.request.timeout(Duration::from_secs_f64(seconds)),
+        })
+    }
+
     /// Set the request body from bytes.
     async fn body_bytes(self, bytes: Bytes) -> Result<Self, Error> {
         let bytes = bytes.into_vec();
@@ -163,28 +318,526 @@ impl RequestBuilder {
 }
 
 impl Client {
+    /// Construct a new client.
+    ///
+    /// Redirects followed by the client are checked against the sandbox which
+    /// is in use when it's constructed.
     fn new() -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: new_client(),
         }
     }
 
     /// Construct a builder to GET the given URL.
-    async fn get(&self, url: &str) -> Result<RequestBuilder, Error> {
+    async fn get(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
         let request = self.client.get(url);
-        Ok(RequestBuilder { request })
+        Ok(Ok(RequestBuilder { request }))
     }
 
     /// Construct a builder to POST to the given URL.
-    async fn post(&self, url: &str) -> Result<RequestBuilder, Error> {
+    async fn post(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
         let request = self.client.post(url);
-        Ok(RequestBuilder { request })
+        Ok(Ok(RequestBuilder { request }))
+    }
+
+    /// Construct a builder to PUT to the given URL.
+    async fn put(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
+        let request = self.client.put(url);
+        Ok(Ok(RequestBuilder { request }))
+    }
+
+    /// Construct a builder to PATCH the given URL.
+    async fn patch(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
+        let request = self.client.patch(url);
+        Ok(Ok(RequestBuilder { request }))
+    }
+
+    /// Construct a builder to DELETE the given URL.
+    async fn delete(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
+        let request = self.client.delete(url);
+        Ok(Ok(RequestBuilder { request }))
+    }
+
+    /// Construct a builder to HEAD the given URL.
+    async fn head(&self, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        check_url(url)?;
+        let request = self.client.head(url);
+        Ok(Ok(RequestBuilder { request }))
     }
 }
 
 /// Shorthand for generating a get request.
-async fn get(url: &str) -> Result<Response, Error> {
-    Ok(Response {
-        response: reqwest::get(url).await?,
+async fn get(url: &str) -> Result<Result<Response, Error>, VmError> {
+    check_url(url)?;
+
+    Ok(match new_client().get(url).send().await {
+        Ok(response) => Ok(Response { response }),
+        Err(error) => Err(Error::from(error)),
     })
 }
+
+/// A server which routes incoming requests to functions.
+///
+/// The server only speaks HTTP/1.1 and closes every connection after a single
+/// request. Requests are handled concurrently, but on the task which is
+/// serving, so the server can be driven from within a virtual machine.
+#[derive(Debug, Any)]
+pub struct Server {
+    listener: TcpListener,
+    routes: Vec<Route>,
+}
+
+#[derive(Debug)]
+struct Route {
+    /// The method to match, or `None` to match any method.
+    method: Option<hyper::Method>,
+    /// The path to match. A path ending in `*` matches anything that starts
+    /// with what comes before it.
+    path: String,
+    handler: Function,
+}
+
+impl Route {
+    fn matches_path(&self, path: &str) -> bool {
+        match self.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => self.path == path,
+        }
+    }
+}
+
+impl Server {
+    /// Bind a server to the given address, like `127.0.0.1:8080`.
+    ///
+    /// Binding to port `0` picks any free port, which can be looked up through
+    /// [local_addr][Self::local_addr].
+    pub async fn bind(addr: &str) -> Result<io::Result<Self>, VmError> {
+        let host = match addr.rfind(':') {
+            Some(n) => &addr[..n],
+            None => addr,
+        };
+
+        sandbox::check(Capability::Host(
+            host.trim_start_matches('[').trim_end_matches(']'),
+        ))?;
+
+        Ok(match TcpListener::bind(addr).await {
+            Ok(listener) => Ok(Self {
+                listener,
+                routes: Vec::new(),
+            }),
+            Err(error) => Err(error),
+        })
+    }
+
+    /// Route requests with the given method and path to the given handler.
+    ///
+    /// The method `*` matches any method, and a path ending in `*` matches any
+    /// path starting with what comes before it. `GET` routes also serve `HEAD`
+    /// requests. Routes are tried in the order
+    /// they were added.
+    pub fn route(&mut self, method: &str, path: &str, handler: Function) -> Result<(), VmError> {
+        let method = match method {
+            "*" => None,
+            method => match hyper::Method::from_bytes(method.as_bytes()) {
+                Ok(method) => Some(method),
+                Err(error) => return Err(VmError::panic(error)),
+            },
+        };
+
+        self.routes.push(Route {
+            method,
+            path: path.to_owned(),
+            handler,
+        });
+
+        Ok(())
+    }
+
+    /// Get the address the server is bound to.
+    pub fn local_addr(&self) -> io::Result<String> {
+        Ok(self.listener.local_addr()?.to_string())
+    }
+
+    /// Serve requests forever, or until accepting a connection fails.
+    pub async fn serve(self) -> io::Result<()> {
+        self.serve_inner(None).await
+    }
+
+    /// Serve the given number of requests, and then stop once they have been
+    /// responded to.
+    pub async fn serve_requests(self, count: usize) -> io::Result<()> {
+        self.serve_inner(Some(count)).await
+    }
+
+    async fn serve_inner(self, limit: Option<usize>) -> io::Result<()> {
+        let Self {
+            mut listener,
+            routes,
+        } = self;
+
+        let routes = Rc::new(routes);
+        let mut connections = FuturesUnordered::new();
+        let mut accepted = 0;
+
+        while limit.map(|limit| accepted < limit).unwrap_or(true) {
+            let result = if connections.is_empty() {
+                listener.accept().await
+            } else {
+                match future::select(Box::pin(listener.accept()), connections.next()).await {
+                    Either::Left((result, _)) => result,
+                    Either::Right(..) => continue,
+                }
+            };
+
+            let (stream, _) = result?;
+            accepted += 1;
+
+            let routes = routes.clone();
+
+            let service = hyper::service::service_fn(move |request| {
+                let routes = routes.clone();
+
+                async move {
+                    let response = match dispatch(&routes, request).await {
+                        Ok(response) => response,
+                        Err(error) => ServerResponse::internal_error(&error),
+                    };
+
+                    Ok::<_, Infallible>(response.into_hyper())
+                }
+            });
+
+            // Errors in individual connections, like clients going away, don't
+            // affect the server.
+            let connection = hyper::server::conn::Http::new()
+                .with_executor(LocalExec)
+                .http1_only(true)
+                .http1_keep_alive(false)
+                .serve_connection(stream, service);
+
+            connections.push(async move {
+                let _ = connection.await;
+            });
+        }
+
+        while connections.next().await.is_some() {}
+        Ok(())
+    }
+}
+
+/// Route the given request to its handler and call it.
+async fn dispatch(
+    routes: &[Route],
+    request: hyper::Request<hyper::Body>,
+) -> Result<ServerResponse, VmError> {
+    let (parts, body) = request.into_parts();
+
+    let mut candidates = routes
+        .iter()
+        .filter(|route| route.matches_path(parts.uri.path()))
+        .peekable();
+
+    if candidates.peek().is_none() {
+        return Ok(ServerResponse::status(hyper::StatusCode::NOT_FOUND));
+    }
+
+    // HEAD requests are served by GET routes, where the body is discarded.
+    let route = candidates.find(|route| match &route.method {
+        Some(method) => {
+            *method == parts.method
+                || (*method == hyper::Method::GET && parts.method == hyper::Method::HEAD)
+        }
+        None => true,
+    });
+
+    let route = match route {
+        Some(route) => route,
+        None => {
+            return Ok(ServerResponse::status(
+                hyper::StatusCode::METHOD_NOT_ALLOWED,
+            ))
+        }
+    };
+
+    let body = match hyper::body::to_bytes(body).await {
+        Ok(body) => body.to_vec(),
+        Err(..) => return Ok(ServerResponse::status(hyper::StatusCode::BAD_REQUEST)),
+    };
+
+    let request = ServerRequest {
+        method: parts.method,
+        uri: parts.uri,
+        headers: parts.headers,
+        body,
+    };
+
+    let value = match route.handler.call::<_, Value>((request,))? {
+        Value::Future(future) => future.take()?.await?,
+        value => value,
+    };
+
+    ServerResponse::from_handler(value)
+}
+
+/// The executor used for connections, which is never used since we only serve
+/// HTTP/1.1. This exists since the default executor requires futures to be
+/// `Send`, which calling into a virtual machine isn't.
+#[derive(Clone, Copy)]
+struct LocalExec;
+
+impl<F> hyper::rt::Executor<F> for LocalExec {
+    fn execute(&self, _: F) {
+        unreachable!("the server only serves HTTP/1.1")
+    }
+}
+
+/// A request received by a [Server].
+#[derive(Debug, Any)]
+pub struct ServerRequest {
+    method: hyper::Method,
+    uri: hyper::Uri,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl ServerRequest {
+    /// Get the method of the request, like `GET`.
+    pub fn method(&self) -> String {
+        self.method.to_string()
+    }
+
+    /// Get the path of the request.
+    pub fn path(&self) -> String {
+        self.uri.path().to_owned()
+    }
+
+    /// Get the query parameters of the request as an object.
+    ///
+    /// If a parameter is repeated, the last value is used.
+    pub fn query(&self) -> Object {
+        let mut object = Object::new();
+
+        let mut url = reqwest::Url::parse("http://localhost/").expect("valid url");
+        url.set_query(self.uri.query());
+
+        for (key, value) in url.query_pairs() {
+            object.insert(key.into_owned(), Value::from(value.into_owned()));
+        }
+
+        object
+    }
+
+    /// Get the value of the given request header, if present.
+    pub fn header(&self, key: &str) -> Option<String> {
+        header(&self.headers, key)
+    }
+
+    /// Get all request headers as an object.
+    pub fn headers(&self) -> Object {
+        headers(&self.headers)
+    }
+
+    /// Get the body of the request.
+    pub fn body(&self) -> Bytes {
+        Bytes::from_vec(self.body.clone())
+    }
+
+    /// Get the body of the request as a string.
+    pub fn text(&self) -> Result<String, VmError> {
+        match String::from_utf8(self.body.clone()) {
+            Ok(text) => Ok(text),
+            Err(error) => Err(VmError::panic(error)),
+        }
+    }
+
+    /// Deserialize the body of the request from JSON.
+    pub fn json(&self) -> Result<Value, VmError> {
+        match serde_json::from_slice(&self.body) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(VmError::panic(error)),
+        }
+    }
+}
+
+/// A response to send from a [Server] handler.
+#[derive(Debug, Clone, Any)]
+pub struct ServerResponse {
+    status: hyper::StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl ServerResponse {
+    /// Construct a new empty response with the given status code.
+    pub fn new(status: i64) -> Result<Self, VmError> {
+        let status = u16::try_from(status).unwrap_or_default();
+
+        match hyper::StatusCode::from_u16(status) {
+            Ok(status) => Ok(Self::status(status)),
+            Err(error) => Err(VmError::panic(error)),
+        }
+    }
+
+    /// Set a header in the response.
+    pub fn header(mut self, key: &str, value: &str) -> Result<Self, VmError> {
+        let key = HeaderName::from_bytes(key.as_bytes()).map_err(VmError::panic)?;
+        let value = HeaderValue::from_str(value).map_err(VmError::panic)?;
+        self.headers.insert(key, value);
+        Ok(self)
+    }
+
+    /// Set the body of the response to a string or bytes.
+    pub fn body(mut self, body: Value) -> Result<Self, VmError> {
+        self.body = match body {
+            Value::String(string) => string.borrow_ref()?.as_bytes().to_vec(),
+            Value::StaticString(string) => string.as_bytes().to_vec(),
+            Value::Bytes(bytes) => bytes.borrow_ref()?.to_vec(),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        };
+
+        Ok(self)
+    }
+
+    /// Set the body of the response to the given value, serialized as JSON.
+    pub fn json(mut self, value: Value) -> Result<Self, VmError> {
+        self.body = serde_json::to_vec(&value).map_err(VmError::panic)?;
+        self.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Ok(self)
+    }
+
+    fn status(status: hyper::StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn with_content_type(body: Vec<u8>, content_type: &'static str) -> Self {
+        let mut response = Self::status(hyper::StatusCode::OK);
+        response.body = body;
+        response
+            .headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        response
+    }
+
+    fn internal_error(error: &VmError) -> Self {
+        let mut response =
+            Self::with_content_type(error.to_string().into_bytes(), "text/plain; charset=utf-8");
+        response.status = hyper::StatusCode::INTERNAL_SERVER_ERROR;
+        response
+    }
+
+    /// Convert the value returned by a handler into a response.
+    ///
+    /// NB: the value might still be used by the script, like a captured
+    /// string which is returned for every request, so it's copied.
+    fn from_handler(value: Value) -> Result<Self, VmError> {
+        Ok(match value {
+            Value::Unit => Self::status(hyper::StatusCode::NO_CONTENT),
+            Value::String(string) => Self::with_content_type(
+                string.borrow_ref()?.as_bytes().to_vec(),
+                "text/plain; charset=utf-8",
+            ),
+            Value::StaticString(string) => {
+                Self::with_content_type(string.as_bytes().to_vec(), "text/plain; charset=utf-8")
+            }
+            Value::Bytes(bytes) => {
+                Self::with_content_type(bytes.borrow_ref()?.to_vec(), "application/octet-stream")
+            }
+            Value::Any(any) if any.borrow_ref()?.is::<Self>() => {
+                any.downcast_borrow_ref::<Self>()?.clone()
+            }
+            value => Self::status(hyper::StatusCode::OK).json(value)?,
+        })
+    }
+
+    fn into_hyper(self) -> hyper::Response<hyper::Body> {
+        let mut response = hyper::Response::new(hyper::Body::from(self.body));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+/// Get the value of the given header, if present.
+fn header(headers: &HeaderMap, key: &str) -> Option<String> {
+    let value = headers.get(key)?;
+    Some(String::from_utf8_lossy(value.as_bytes()).into_owned())
+}
+
+/// Convert headers into an object, where repeated headers are joined by
+/// commas.
+fn headers(headers: &HeaderMap) -> Object {
+    let mut object = Object::new();
+
+    for key in headers.keys() {
+        let values = headers
+            .get_all(key)
+            .iter()
+            .map(|value| String::from_utf8_lossy(value.as_bytes()))
+            .collect::<Vec<_>>();
+
+        object.insert(key.as_str().to_owned(), Value::from(values.join(", ")));
+    }
+
+    object
+}
+
+/// The maximum number of redirects a client follows, same as the default
+/// policy of `reqwest`.
+const MAX_REDIRECTS: usize = 10;
+
+/// Construct a new client, which checks the host of every redirect it follows
+/// against the sandbox currently in use.
+fn new_client() -> reqwest::Client {
+    let sandbox = sandbox::current();
+
+    let policy = reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error("too many redirects");
+        }
+
+        if let Some(sandbox) = &sandbox {
+            let host = attempt.url().host_str().unwrap_or_default();
+
+            if let Err(error) = sandbox.check(Capability::Host(host)) {
+                let error = error.to_string();
+                return attempt.error(error);
+            }
+        }
+
+        attempt.follow()
+    });
+
+    // NB: building only fails if the TLS backend can't be initialized, which
+    // `reqwest::Client::new` panics on as well.
+    reqwest::Client::builder()
+        .redirect(policy)
+        .build()
+        .expect("failed to build http client")
+}
+
+/// Check that the sandbox permits connecting to the host of the given URL.
+///
+/// If a sandbox is in use, URLs which can't be parsed or which don't have a
+/// host are denied.
+fn check_url(url: &str) -> Result<(), VmError> {
+    let parsed = reqwest::Url::parse(url);
+
+    match parsed.as_ref().ok().and_then(|url| url.host_str()) {
+        Some(host) => sandbox::check(Capability::Host(host)),
+        None if sandbox::current().is_some() => Err(VmError::from(VmErrorKind::PermissionDenied {
+            denied: Denied::Host(url.to_owned()),
+        })),
+        None => Ok(()),
+    }
+}
@@ -25,40 +25,357 @@
 //! ```rust,ignore
 //! use json;
 //!
-//! fn main() {
-//!     let data = json::from_string("{\"key\": 42}");
+//! struct Person { name, age }
+//!
+//! enum Shape { Circle { radius }, Square(side) }
+//!
+//! async fn main() {
+//!     let data = json::from_string("{\"key\": 42}")?;
 //!     dbg(data);
+//!
+//!     let person = json::from_string_as("{\"name\": \"Bob\", \"age\": 42}", Person)?;
+//!     println(json::to_string_pretty(person)?);
+//!
+//!     let shape = Shape::Circle { radius: 1.0 };
+//!     println(json::to_string_with(shape, #{repr: "internal", tag: "type"})?);
+//!
+//!     for value in json::lines("{\"a\": 1}\n{\"a\": 2}\n") {
+//!         dbg(value?);
+//!     }
+//!
+//!     let lines = json::read_lines("events.jsonl").await?;
+//!
+//!     while let Some(value) = lines.next().await {
+//!         dbg(value?);
+//!     }
 //! }
 //! ```
+//!
+//! Structs serialize as objects and tuple structs as arrays. How enums are
+//! serialized is controlled through the `repr` option of `json::to_string_with`,
+//! which can be one of:
+//! * `"external"` (the default) - `{"Circle": {"radius": 1.0}}`.
+//! * `"internal"` - `{"type": "Circle", "radius": 1.0}`, where the name of the
+//!   field is specified through the `tag` option.
+//! * `"adjacent"` - `{"type": "Circle", "content": {"radius": 1.0}}`, where the
+//!   names of the fields are specified through the `tag` and `content` options.
+//! * `"untagged"` - `{"radius": 1.0}`.
+//!
+//! `Result` values are serialized as an enum with the variants `Ok` and `Err`.
+//!
+//! `json::read_lines` is only available if the `fs` feature is enabled, and
+//! the path it reads from is checked against the
+//! [sandbox][runestick::sandbox] of the virtual machine.
 
-use runestick::{Bytes, ContextError, Module, Value};
+use runestick::{Any, Bytes, ContextError, EnumRepr, Module, Object, Value, VmError, VmErrorKind};
+use std::fmt;
+use std::fmt::Write as _;
 
 /// Construct the `json` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::new(&["json"]);
+
+    module.ty::<Error>()?;
+    module.ty::<Lines>()?;
+
     module.function(&["from_bytes"], from_bytes)?;
     module.function(&["from_string"], from_string)?;
+    module.function(&["from_bytes_as"], from_bytes_as)?;
+    module.function(&["from_string_as"], from_string_as)?;
     module.function(&["to_string"], to_string)?;
+    module.function(&["to_string_pretty"], to_string_pretty)?;
+    module.function(&["to_string_with"], to_string_with)?;
     module.function(&["to_bytes"], to_bytes)?;
+    module.function(&["lines"], lines)?;
+
+    #[cfg(feature = "fs")]
+    module.async_function(&["read_lines"], read_lines)?;
+
+    module.inst_fn("next", Lines::next)?;
+    module.inst_fn(runestick::NEXT, Lines::next)?;
+    module.inst_fn(runestick::INTO_ITER, Lines::into_iter)?;
+
+    module.inst_fn("line", Error::line)?;
+    module.inst_fn("column", Error::column)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
     Ok(module)
 }
 
-fn from_bytes(bytes: &[u8]) -> runestick::Result<Value> {
+/// An error raised when converting to or from JSON.
+#[derive(Debug, Any)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Json(serde_json::Error),
+    Message(String),
+    #[cfg(feature = "fs")]
+    Io(std::io::Error),
+}
+
+impl Error {
+    /// The line at which the error occurred, starting at 1, if known.
+    fn line(&self) -> Option<usize> {
+        match &self.kind {
+            ErrorKind::Json(error) if error.line() > 0 => Some(error.line()),
+            _ => None,
+        }
+    }
+
+    /// The column at which the error occurred, starting at 1, if known.
+    fn column(&self) -> Option<usize> {
+        match &self.kind {
+            ErrorKind::Json(error) if error.line() > 0 => Some(error.column()),
+            _ => None,
+        }
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Json(error) => write!(buf, "{}", error),
+            ErrorKind::Message(message) => write!(buf, "{}", message),
+            #[cfg(feature = "fs")]
+            ErrorKind::Io(error) => write!(buf, "{}", error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self {
+            kind: ErrorKind::Json(error),
+        }
+    }
+}
+
+/// An iterator over the values of JSON Lines.
+#[derive(Debug, Any)]
+pub struct Lines {
+    iter: std::vec::IntoIter<String>,
+}
+
+impl Iterator for Lines {
+    type Item = Result<Value, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.iter.next()?;
+        Some(from_string(&line))
+    }
+}
+
+/// Get value from json bytes.
+fn from_bytes(bytes: &[u8]) -> Result<Value, Error> {
     Ok(serde_json::from_slice(&bytes)?)
 }
 
 /// Get value from json string.
-fn from_string(string: &str) -> runestick::Result<Value> {
+fn from_string(string: &str) -> Result<Value, Error> {
     Ok(serde_json::from_str(string)?)
 }
 
+/// Get an instance of the given struct from json bytes.
+fn from_bytes_as(bytes: &[u8], ty: Value) -> Result<Result<Value, Error>, VmError> {
+    match from_bytes(bytes) {
+        Ok(value) => into_struct(value, ty),
+        Err(error) => Ok(Err(error)),
+    }
+}
+
+/// Get an instance of the given struct from a json string.
+fn from_string_as(string: &str, ty: Value) -> Result<Result<Value, Error>, VmError> {
+    match from_string(string) {
+        Ok(value) => into_struct(value, ty),
+        Err(error) => Ok(Err(error)),
+    }
+}
+
 /// Convert any value to a json string.
-fn to_string(value: Value) -> runestick::Result<String> {
+fn to_string(value: Value) -> Result<String, Error> {
     Ok(serde_json::to_string(&value)?)
 }
 
+/// Convert any value to a pretty-printed json string.
+fn to_string_pretty(value: Value) -> Result<String, Error> {
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// Convert any value to a json string with the given options.
+///
+/// The supported options are `repr`, `tag` and `content` which determine how
+/// enums are represented, and `pretty` to pretty-print the output.
+fn to_string_with(value: Value, options: &Object) -> Result<Result<String, Error>, VmError> {
+    let repr = enum_repr(options)?;
+
+    let pretty = match options.get("pretty") {
+        Some(Value::Bool(pretty)) => *pretty,
+        Some(actual) => return Err(VmError::expected::<bool>(actual.type_info()?)),
+        None => false,
+    };
+
+    let value = value.serialize_with(&repr);
+
+    let result = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+
+    Ok(result.map_err(Error::from))
+}
+
 /// Convert any value to json bytes.
-fn to_bytes(value: Value) -> runestick::Result<Bytes> {
+fn to_bytes(value: Value) -> Result<Bytes, Error> {
     let bytes = serde_json::to_vec(&value)?;
     Ok(Bytes::from_vec(bytes))
 }
+
+/// Iterate over the values of the given JSON Lines, which is either a string
+/// or bytes. Empty lines are skipped.
+fn lines(data: Value) -> Result<Lines, VmError> {
+    let lines = match &data {
+        Value::String(s) => split_lines(&s.borrow_ref()?),
+        Value::StaticString(s) => split_lines(s.as_str()),
+        Value::Bytes(b) => split_lines(&String::from_utf8_lossy(&b.borrow_ref()?)),
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    };
+
+    Ok(Lines {
+        iter: lines.into_iter(),
+    })
+}
+
+/// Read the JSON Lines file at the given path as a stream of values. Empty
+/// lines are skipped.
+#[cfg(feature = "fs")]
+async fn read_lines(path: Value) -> Result<std::io::Result<runestick::Stream>, VmError> {
+    use tokio::io::AsyncBufReadExt as _;
+    use tokio::stream::StreamExt as _;
+
+    let path = crate::fs::check(&path)?;
+
+    let file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(error) => return Ok(Err(error)),
+    };
+
+    let lines = tokio::io::BufReader::new(file).lines().filter_map(|line| {
+        let value = match line {
+            Ok(line) if line.trim().is_empty() => return None,
+            Ok(line) => from_string(&line),
+            Err(error) => Err(Error {
+                kind: ErrorKind::Io(error),
+            }),
+        };
+
+        Some(Ok::<_, VmError>(value))
+    });
+
+    Ok(Ok(runestick::Stream::from_stream(lines)))
+}
+
+fn split_lines(string: &str) -> Vec<String> {
+    string
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parse the enum representation out of the given options.
+fn enum_repr(options: &Object) -> Result<EnumRepr, VmError> {
+    let string = |key: &str| -> Result<Option<String>, VmError> {
+        Ok(match options.get(key) {
+            Some(Value::String(s)) => Some(s.borrow_ref()?.clone()),
+            Some(Value::StaticString(s)) => Some(s.as_str().to_owned()),
+            Some(actual) => return Err(VmError::expected::<String>(actual.type_info()?)),
+            None => None,
+        })
+    };
+
+    let missing = |key: &str, repr: &str| {
+        VmError::panic(format!("missing option `{}` for `{}` enums", key, repr))
+    };
+
+    let repr = match string("repr")? {
+        Some(repr) => repr,
+        None => return Ok(EnumRepr::External),
+    };
+
+    Ok(match repr.as_str() {
+        "external" => EnumRepr::External,
+        "internal" => EnumRepr::Internal {
+            tag: string("tag")?.ok_or_else(|| missing("tag", "internal"))?,
+        },
+        "adjacent" => EnumRepr::Adjacent {
+            tag: string("tag")?.ok_or_else(|| missing("tag", "adjacent"))?,
+            content: string("content")?.ok_or_else(|| missing("content", "adjacent"))?,
+        },
+        "untagged" => EnumRepr::Untagged,
+        other => {
+            return Err(VmError::panic(format!(
+                "unsupported enum representation `{}`",
+                other
+            )))
+        }
+    })
+}
+
+/// Convert the given deserialized value into an instance of the struct
+/// identified by the given type.
+fn into_struct(value: Value, ty: Value) -> Result<Result<Value, Error>, VmError> {
+    let hash = match ty {
+        Value::Type(hash) => hash,
+        actual => {
+            return Err(VmError::panic(format!(
+                "expected a struct type, but found `{}`",
+                actual.type_info()?
+            )))
+        }
+    };
+
+    let rtti = runestick::rtti::lookup(hash)
+        .ok_or_else(|| VmError::from(VmErrorKind::MissingRtti { hash }))?;
+
+    let fields = match &rtti.fields {
+        Some(fields) => fields,
+        None => {
+            return Err(VmError::panic(format!(
+                "`{}` is not a struct with named fields",
+                rtti.item
+            )))
+        }
+    };
+
+    let message = |message: String| {
+        Ok(Err(Error {
+            kind: ErrorKind::Message(message),
+        }))
+    };
+
+    let object = match value {
+        Value::Object(object) => object.take()?,
+        actual => {
+            return message(format!(
+                "expected an object for `{}`, but found `{}`",
+                rtti.name(),
+                actual.type_info()?
+            ))
+        }
+    };
+
+    for field in fields.iter() {
+        if !object.contains_key(field) {
+            return message(format!("missing field `{}` for `{}`", field, rtti.name()));
+        }
+    }
+
+    for (key, _) in object.iter() {
+        if !rtti.has_field(key) {
+            return message(format!("unknown field `{}` for `{}`", key, rtti.name()));
+        }
+    }
+
+    Ok(Ok(Value::from(runestick::TypedObject::new(rtti, object))))
+}
@@ -52,6 +52,13 @@
 //! * [fs]
 //! * [process]
 //! * [signal]
+//! * [regex]
+//! * [rand]
+//! * [env]
+//! * [path]
+//! * [encoding]
+//! * [yaml]
+//! * [csv]
 //!
 //! ## Features
 //!
@@ -63,6 +70,14 @@
 //! * `fs` for the [fs module]][fs]
 //! * `process` for the [process module]][process]
 //! * `signal` for the [process module]][signal]
+//! * `regex` for the [regex module][regex]
+//! * `rand` for the [rand module][rand]
+//! * `env` for the [env module][env]
+//! * `path` for the [path module][path], which is also enabled by `fs`, `process`,
+//!   `env`, and `csv`
+//! * `encoding` for the [encoding module][encoding]
+//! * `yaml` for the [yaml module][yaml]
+//! * `csv` for the [csv module][csv]
 //!
 //! [http]: https://docs.rs/rune-modules/0/rune_modules/http/
 //! [json]: https://docs.rs/rune-modules/0/rune_modules/json/
@@ -71,6 +86,13 @@
 //! [fs]: https://docs.rs/rune-modules/0/rune_modules/fs/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
+//! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [env]: https://docs.rs/rune-modules/0/rune_modules/env/
+//! [path]: https://docs.rs/rune-modules/0/rune_modules/path/
+//! [encoding]: https://docs.rs/rune-modules/0/rune_modules/encoding/
+//! [yaml]: https://docs.rs/rune-modules/0/rune_modules/yaml/
+//! [csv]: https://docs.rs/rune-modules/0/rune_modules/csv/
 
 #[cfg(feature = "http")]
 pub mod http;
@@ -92,3 +114,24 @@ pub mod process;
 
 #[cfg(feature = "signal")]
 pub mod signal;
+
+#[cfg(feature = "regex")]
+pub mod regex;
+
+#[cfg(feature = "rand")]
+pub mod rand;
+
+#[cfg(feature = "env")]
+pub mod env;
+
+#[cfg(feature = "path")]
+pub mod path;
+
+#[cfg(feature = "encoding")]
+pub mod encoding;
+
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+#[cfg(feature = "csv")]
+pub mod csv;
@@ -0,0 +1,211 @@
+//! The native `path` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["path"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::path::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use path::Path;
+//!
+//! async fn main() {
+//!     let path = Path::new("out").join("report.txt");
+//!     dbg(path.file_name());
+//!
+//!     if !path.parent()?.exists() {
+//!         fs::create_dir_all(path.parent()?).await?;
+//!     }
+//!
+//!     fs::write(path.with_extension("csv"), "a,b\n").await?;
+//! }
+//! ```
+//!
+//! Paths can be used anywhere the `fs` and `process` modules expect a path.
+//! Querying the filesystem through `exists`, `is_dir` and `is_file` is checked
+//! against the [sandbox][runestick::sandbox] of the virtual machine.
+
+use runestick::sandbox::{self, Capability};
+use runestick::{Any, ContextError, Module, Value, VmError};
+use std::fmt;
+use std::fmt::Write as _;
+use std::path;
+
+/// Construct the `path` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["path"]);
+
+    module.ty::<PathBuf>()?;
+
+    module.function(&["PathBuf", "new"], PathBuf::new)?;
+    module.function(&["PathBuf", "from"], PathBuf::from_value)?;
+    module.function(&["Path", "new"], PathBuf::from_value)?;
+
+    module.inst_fn("join", PathBuf::join)?;
+    module.inst_fn("push", PathBuf::push)?;
+    module.inst_fn("pop", PathBuf::pop)?;
+    module.inst_fn("parent", PathBuf::parent)?;
+    module.inst_fn("file_name", PathBuf::file_name)?;
+    module.inst_fn("file_stem", PathBuf::file_stem)?;
+    module.inst_fn("extension", PathBuf::extension)?;
+    module.inst_fn("with_extension", PathBuf::with_extension)?;
+    module.inst_fn("components", PathBuf::components)?;
+    module.inst_fn("is_absolute", PathBuf::is_absolute)?;
+    module.inst_fn("is_relative", PathBuf::is_relative)?;
+    module.inst_fn("exists", PathBuf::exists)?;
+    module.inst_fn("is_dir", PathBuf::is_dir)?;
+    module.inst_fn("is_file", PathBuf::is_file)?;
+    module.inst_fn("to_str", PathBuf::to_str)?;
+    module.inst_fn(runestick::EQ, PathBuf::eq)?;
+    module.inst_fn(runestick::STRING_DISPLAY, PathBuf::display)?;
+    Ok(module)
+}
+
+/// An owned filesystem path.
+#[derive(Debug, Clone, Any)]
+pub struct PathBuf {
+    inner: path::PathBuf,
+}
+
+impl PathBuf {
+    /// Construct an empty path.
+    fn new() -> Self {
+        Self {
+            inner: path::PathBuf::new(),
+        }
+    }
+
+    /// Construct a path from a string or another path.
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        Ok(Self::from(from_value(&value)?))
+    }
+
+    /// Construct a new path by adjoining the given path to this one.
+    ///
+    /// If the given path is absolute it replaces this path.
+    fn join(&self, other: Value) -> Result<Self, VmError> {
+        Ok(Self::from(self.inner.join(from_value(&other)?)))
+    }
+
+    /// Adjoin the given path to this path in place.
+    fn push(&mut self, other: Value) -> Result<(), VmError> {
+        self.inner.push(from_value(&other)?);
+        Ok(())
+    }
+
+    /// Truncate this path to its parent, returning `false` if it has none.
+    fn pop(&mut self) -> bool {
+        self.inner.pop()
+    }
+
+    /// Get the parent of the path, or `None` if it doesn't have one.
+    fn parent(&self) -> Option<Self> {
+        Some(Self::from(self.inner.parent()?.to_owned()))
+    }
+
+    /// Get the final component of the path, if there is one.
+    fn file_name(&self) -> Option<String> {
+        Some(self.inner.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Get the file name of the path without its extension.
+    fn file_stem(&self) -> Option<String> {
+        Some(self.inner.file_stem()?.to_string_lossy().into_owned())
+    }
+
+    /// Get the extension of the file name, if there is one.
+    fn extension(&self) -> Option<String> {
+        Some(self.inner.extension()?.to_string_lossy().into_owned())
+    }
+
+    /// Construct a new path with the extension of the file name replaced.
+    fn with_extension(&self, extension: &str) -> Self {
+        Self::from(self.inner.with_extension(extension))
+    }
+
+    /// Get the components of the path as strings.
+    fn components(&self) -> Vec<String> {
+        self.inner
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn is_absolute(&self) -> bool {
+        self.inner.is_absolute()
+    }
+
+    fn is_relative(&self) -> bool {
+        self.inner.is_relative()
+    }
+
+    /// Test if the path points to something that exists.
+    fn exists(&self) -> Result<bool, VmError> {
+        sandbox::check(Capability::Path(&self.inner))?;
+        Ok(self.inner.exists())
+    }
+
+    /// Test if the path points to a directory.
+    fn is_dir(&self) -> Result<bool, VmError> {
+        sandbox::check(Capability::Path(&self.inner))?;
+        Ok(self.inner.is_dir())
+    }
+
+    /// Test if the path points to a regular file.
+    fn is_file(&self) -> Result<bool, VmError> {
+        sandbox::check(Capability::Path(&self.inner))?;
+        Ok(self.inner.is_file())
+    }
+
+    /// Convert the path into a string, or `None` if it isn't valid unicode.
+    fn to_str(&self) -> Option<String> {
+        Some(self.inner.to_str()?.to_owned())
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner.display())
+    }
+}
+
+impl From<path::PathBuf> for PathBuf {
+    fn from(inner: path::PathBuf) -> Self {
+        Self { inner }
+    }
+}
+
+/// Convert a value which is either a string or a [PathBuf] into a path.
+pub(crate) fn from_value(value: &Value) -> Result<path::PathBuf, VmError> {
+    Ok(match value {
+        Value::String(string) => path::PathBuf::from(&*string.borrow_ref()?),
+        Value::StaticString(string) => path::PathBuf::from(string.as_str()),
+        Value::Any(any) => {
+            let any = any.borrow_ref()?;
+
+            match any.downcast_borrow_ref::<PathBuf>() {
+                Some(path) => path.inner.clone(),
+                None => return Err(VmError::expected::<PathBuf>(value.type_info()?)),
+            }
+        }
+        actual => return Err(VmError::expected::<PathBuf>(actual.type_info()?)),
+    })
+}
@@ -24,34 +24,95 @@
 //!
 //! ```rust,ignore
 //! use process::Command;
+//! use process::Stdio;
 //!
-//! fn main() {
+//! async fn main() {
 //!     let command = Command::new("ls");
-//!     command.run().await;
+//!     command.current_dir("/tmp");
+//!     command.env("LC_ALL", "C");
+//!     command.timeout(10);
+//!     let status = command.status().await?;
+//!     println(`ls exited with {status}`);
+//!
+//!     let command = Command::new("sort");
+//!     command.stdin(Stdio::piped());
+//!     command.stdout(Stdio::piped());
+//!     let child = command.spawn()?;
+//!
+//!     let stdin = child.stdin()?;
+//!     stdin.write_all("b\na\n").await?;
+//!     stdin.close();
+//!
+//!     let lines = child.stdout()?;
+//!
+//!     while let Some(line) = lines.next().await {
+//!         println(line?);
+//!     }
+//!
+//!     child.await?;
 //! }
 //! ```
+//!
+//! Executables, arguments, and working directories can either be strings or
+//! values from the [path module][crate::path]. Executables and working
+//! directories are checked against the [sandbox][runestick::sandbox] of the
+//! virtual machine. Since executables are looked up through `PATH`, a command
+//! can't change or clear `PATH` while a sandbox is in use.
 
-use runestick::{Any, Bytes, Shared, Value, VmError};
+use crate::path;
+use runestick::sandbox::{self, Capability, Denied};
+use runestick::{Any, Bytes, Shared, Stream, Value, VmError, VmErrorKind};
 use std::fmt;
 use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
 use tokio::process;
+use tokio::stream::StreamExt as _;
+use tokio::time;
 
 /// Construct the `process` module.
 pub fn module() -> Result<runestick::Module, runestick::ContextError> {
     let mut module = runestick::Module::new(&["process"]);
     module.ty::<Command>()?;
     module.ty::<Child>()?;
+    module.ty::<ChildStdin>()?;
     module.ty::<ExitStatus>()?;
     module.ty::<Output>()?;
+    module.ty::<Stdio>()?;
 
     module.function(&["Command", "new"], Command::new)?;
     module.inst_fn("spawn", Command::spawn)?;
     module.inst_fn("arg", Command::arg)?;
     module.inst_fn("args", Command::args)?;
+    module.inst_fn("env", Command::env)?;
+    module.inst_fn("env_remove", Command::env_remove)?;
+    module.inst_fn("env_clear", Command::env_clear)?;
+    module.inst_fn("current_dir", Command::current_dir)?;
+    module.inst_fn("stdin", Command::stdin)?;
+    module.inst_fn("stdout", Command::stdout)?;
+    module.inst_fn("stderr", Command::stderr)?;
+    module.inst_fn("timeout", Command::timeout)?;
+    module.async_inst_fn("status", Command::status)?;
+
+    module.function(&["Stdio", "piped"], Stdio::piped)?;
+    module.function(&["Stdio", "null"], Stdio::null)?;
+    module.function(&["Stdio", "inherit"], Stdio::inherit)?;
+
     module.async_inst_fn(runestick::INTO_FUTURE, Child::into_future)?;
     module.async_inst_fn("wait_with_output", Child::wait_with_output)?;
+    module.inst_fn("id", Child::id)?;
+    module.inst_fn("kill", Child::kill)?;
+    module.inst_fn("stdin", Child::stdin)?;
+    module.inst_fn("stdout", Child::stdout)?;
+    module.inst_fn("stderr", Child::stderr)?;
+
+    module.async_inst_fn("write_all", ChildStdin::write_all)?;
+    module.async_inst_fn("flush", ChildStdin::flush)?;
+    module.inst_fn("close", ChildStdin::close)?;
+
     module.inst_fn(runestick::STRING_DISPLAY, ExitStatus::display)?;
     module.inst_fn("code", ExitStatus::code)?;
+    module.inst_fn("success", ExitStatus::success)?;
 
     module.getter("status", Output::status)?;
     module.getter("stdout", Output::stdout)?;
@@ -59,49 +120,173 @@ pub fn module() -> Result<runestick::Module, runestick::ContextError> {
     Ok(module)
 }
 
+/// Check that the given environment variable of a process can be changed.
+///
+/// The executable of a command is looked up through the `PATH` of the process,
+/// so changing it while a sandbox is in use would permit launching any
+/// executable with a permitted name.
+fn check_env(key: &str) -> Result<(), VmError> {
+    if key.eq_ignore_ascii_case("PATH") && sandbox::current().is_some() {
+        return Err(VmError::from(VmErrorKind::PermissionDenied {
+            denied: Denied::Environment(key.to_owned()),
+        }));
+    }
+
+    Ok(())
+}
+
 #[derive(Any)]
 struct Command {
     inner: process::Command,
+    timeout: Option<Duration>,
 }
 
 impl Command {
     /// Construct a new command.
-    fn new(command: &str) -> Self {
-        Self {
+    fn new(command: Value) -> Result<Self, VmError> {
+        let command = path::from_value(&command)?;
+        sandbox::check(Capability::Executable(&command.to_string_lossy()))?;
+
+        Ok(Self {
             inner: process::Command::new(command),
-        }
+            timeout: None,
+        })
     }
 
     /// Add arguments.
     fn args(&mut self, args: &[Value]) -> Result<(), VmError> {
         for arg in args {
-            match arg {
-                Value::String(s) => {
-                    self.inner.arg(&*s.borrow_ref()?);
-                }
-                Value::StaticString(s) => {
-                    self.inner.arg(&***s);
-                }
-                actual => {
-                    return Err(VmError::expected::<String>(actual.type_info()?));
-                }
-            }
+            self.inner.arg(path::from_value(arg)?);
         }
 
         Ok(())
     }
 
     /// Add an argument.
-    fn arg(&mut self, arg: &str) {
-        self.inner.arg(arg);
+    fn arg(&mut self, arg: Value) -> Result<(), VmError> {
+        self.inner.arg(path::from_value(&arg)?);
+        Ok(())
+    }
+
+    /// Set an environment variable for the process.
+    fn env(&mut self, key: &str, value: &str) -> Result<(), VmError> {
+        check_env(key)?;
+        self.inner.env(key, value);
+        Ok(())
+    }
+
+    /// Remove an environment variable for the process.
+    fn env_remove(&mut self, key: &str) -> Result<(), VmError> {
+        check_env(key)?;
+        self.inner.env_remove(key);
+        Ok(())
+    }
+
+    /// Clear all environment variables for the process, including those
+    /// which would otherwise be inherited.
+    fn env_clear(&mut self) -> Result<(), VmError> {
+        check_env("PATH")?;
+        self.inner.env_clear();
+        Ok(())
+    }
+
+    /// Set the working directory of the process.
+    fn current_dir(&mut self, dir: Value) -> Result<(), VmError> {
+        let dir = path::from_value(&dir)?;
+        sandbox::check(Capability::Path(&dir))?;
+        self.inner.current_dir(dir);
+        Ok(())
+    }
+
+    /// Configure the stdin of the process.
+    fn stdin(&mut self, stdio: Stdio) {
+        self.inner.stdin(stdio.into_std());
+    }
+
+    /// Configure the stdout of the process.
+    fn stdout(&mut self, stdio: Stdio) {
+        self.inner.stdout(stdio.into_std());
+    }
+
+    /// Configure the stderr of the process.
+    fn stderr(&mut self, stdio: Stdio) {
+        self.inner.stderr(stdio.into_std());
+    }
+
+    /// Set a timeout in seconds, after which the process is killed while
+    /// waiting for it.
+    fn timeout(&mut self, seconds: Value) -> Result<(), VmError> {
+        let seconds = match seconds {
+            Value::Integer(seconds) => seconds as f64,
+            Value::Float(seconds) => seconds,
+            actual => return Err(VmError::expected::<f64>(actual.type_info()?)),
+        };
+
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err(VmError::panic(
+                "timeout must be a positive number of seconds",
+            ));
+        }
+
+        self.timeout = Some(Duration::from_secs_f64(seconds));
+        self.inner.kill_on_drop(true);
+        Ok(())
     }
 
     /// Spawn the command.
     fn spawn(mut self) -> io::Result<Child> {
         Ok(Child {
             inner: Some(self.inner.spawn()?),
+            timeout: self.timeout,
         })
     }
+
+    /// Run the command to completion without capturing its output, returning
+    /// its exit status.
+    async fn status(&mut self) -> io::Result<ExitStatus> {
+        let child = self.inner.spawn()?;
+
+        let status = match self.timeout {
+            Some(timeout) => time::timeout(timeout, child).await.map_err(timed_out)??,
+            None => child.await?,
+        };
+
+        Ok(ExitStatus { status })
+    }
+}
+
+/// Configuration for the standard I/O streams of a process.
+#[derive(Debug, Clone, Copy, Any)]
+enum Stdio {
+    Piped,
+    Null,
+    Inherit,
+}
+
+impl Stdio {
+    /// A new pipe should be arranged to connect the parent and child
+    /// processes.
+    fn piped() -> Self {
+        Self::Piped
+    }
+
+    /// The stream should be ignored.
+    fn null() -> Self {
+        Self::Null
+    }
+
+    /// The stream should be inherited from the parent.
+    fn inherit() -> Self {
+        Self::Inherit
+    }
+
+    fn into_std(self) -> std::process::Stdio {
+        match self {
+            Self::Piped => std::process::Stdio::piped(),
+            Self::Null => std::process::Stdio::null(),
+            Self::Inherit => std::process::Stdio::inherit(),
+        }
+    }
 }
 
 #[derive(Any)]
@@ -111,15 +296,24 @@ struct Child {
     //
     // TODO: enapculate this pattern in some better way.
     inner: Option<process::Child>,
+    timeout: Option<Duration>,
 }
 
 impl Child {
     /// Convert the child into a future, use for `.await`.
     async fn into_future(mut self) -> Result<io::Result<ExitStatus>, VmError> {
+        let timeout = self.timeout;
+
         let result = match &mut self.inner {
-            Some(inner) => match inner.await {
-                Ok(status) => Ok(ExitStatus { status }),
-                Err(e) => Err(e),
+            Some(inner) => match timeout {
+                Some(timeout) => match time::timeout(timeout, &mut *inner).await {
+                    Ok(result) => result,
+                    Err(elapsed) => {
+                        let _ = inner.kill();
+                        Err(timed_out(elapsed))
+                    }
+                },
+                None => inner.await,
             },
             None => {
                 return Err(VmError::panic("already completed"));
@@ -127,7 +321,7 @@ impl Child {
         };
 
         self.inner = None;
-        Ok(result)
+        Ok(result.map(|status| ExitStatus { status }))
     }
 
     // Returns a future that will resolve to an Output, containing the exit
@@ -140,7 +334,16 @@ impl Child {
             }
         };
 
-        let output = match inner.wait_with_output().await {
+        // NB: the child is killed on drop if a timeout is set.
+        let output = match self.timeout {
+            Some(timeout) => match time::timeout(timeout, inner.wait_with_output()).await {
+                Ok(output) => output,
+                Err(elapsed) => return Ok(Err(timed_out(elapsed))),
+            },
+            None => inner.wait_with_output().await,
+        };
+
+        let output = match output {
             Ok(output) => output,
             Err(error) => return Ok(Err(error)),
         };
@@ -151,6 +354,75 @@ impl Child {
             stderr: Shared::new(Bytes::from_vec(output.stderr)),
         }))
     }
+
+    /// Get the operating system identifier of the process, or `None` if it
+    /// has completed.
+    fn id(&self) -> Option<u32> {
+        Some(self.inner.as_ref()?.id())
+    }
+
+    /// Forcibly kill the process.
+    fn kill(&mut self) -> Result<io::Result<()>, VmError> {
+        match &mut self.inner {
+            Some(inner) => Ok(inner.kill()),
+            None => Err(VmError::panic("already completed")),
+        }
+    }
+
+    /// Take the stdin of the process, if it was piped.
+    fn stdin(&mut self) -> Option<ChildStdin> {
+        let inner = self.inner.as_mut()?.stdin.take()?;
+        Some(ChildStdin { inner: Some(inner) })
+    }
+
+    /// Take the stdout of the process as a stream of lines, if it was piped.
+    fn stdout(&mut self) -> Option<Stream> {
+        let stdout = self.inner.as_mut()?.stdout.take()?;
+        Some(lines(stdout))
+    }
+
+    /// Take the stderr of the process as a stream of lines, if it was piped.
+    fn stderr(&mut self) -> Option<Stream> {
+        let stderr = self.inner.as_mut()?.stderr.take()?;
+        Some(lines(stderr))
+    }
+}
+
+/// The stdin of a child process.
+#[derive(Any)]
+struct ChildStdin {
+    inner: Option<process::ChildStdin>,
+}
+
+impl ChildStdin {
+    /// Write all of the given string or bytes to the process.
+    async fn write_all(&mut self, contents: Value) -> Result<io::Result<()>, VmError> {
+        let contents = match contents {
+            Value::String(s) => s.borrow_ref()?.as_bytes().to_vec(),
+            Value::StaticString(s) => s.as_bytes().to_vec(),
+            Value::Bytes(b) => b.borrow_ref()?.to_vec(),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        };
+
+        Ok(match &mut self.inner {
+            Some(inner) => inner.write_all(&contents).await,
+            None => Err(closed()),
+        })
+    }
+
+    /// Flush any buffered writes to the process.
+    async fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Some(inner) => inner.flush().await,
+            None => Err(closed()),
+        }
+    }
+
+    /// Close the stdin of the process, signalling that there's nothing more
+    /// to read.
+    fn close(&mut self) {
+        self.inner = None;
+    }
 }
 
 #[derive(Any)]
@@ -193,4 +465,24 @@ impl ExitStatus {
     fn code(&self) -> Option<i32> {
         self.status.code()
     }
+
+    fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+/// Construct a stream over the lines of the given output.
+fn lines<R>(output: R) -> Stream
+where
+    R: 'static + tokio::io::AsyncRead + Unpin,
+{
+    Stream::from_stream(BufReader::new(output).lines().map(Ok::<_, VmError>))
+}
+
+fn timed_out(elapsed: time::Elapsed) -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, elapsed)
+}
+
+fn closed() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stdin has been closed")
 }
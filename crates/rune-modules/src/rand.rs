@@ -0,0 +1,245 @@
+//! The native `rand` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["rand"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::rand::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use rand::Rng;
+//!
+//! fn main() {
+//!     let roll = rand::int_range(1, 7);
+//!     dbg(roll);
+//!
+//!     let rng = Rng::from_seed(42);
+//!     let cards = [1, 2, 3, 4, 5];
+//!     rng.shuffle(cards);
+//!     dbg(cards);
+//!
+//!     let fruit = rng.choose_weighted(["apple", "banana"], [3, 1]);
+//!     dbg(fruit);
+//! }
+//! ```
+//!
+//! The free functions use a random number generator local to the current
+//! thread. A [Rng] constructed with `Rng::from_seed` always produces the same
+//! sequence of values for the same seed, which makes it suitable for
+//! reproducible runs.
+
+use rand_chacha::ChaCha8Rng;
+use rand_crate::distr::weighted::WeightedIndex;
+use rand_crate::distr::Distribution as _;
+use rand_crate::seq::IndexedRandom as _;
+use rand_crate::seq::SliceRandom as _;
+use rand_crate::SeedableRng as _;
+use runestick::{Any, ContextError, Module, Value, VmError};
+
+/// Construct the `rand` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["rand"]);
+
+    module.ty::<Rng>()?;
+
+    module.function(&["Rng", "new"], Rng::new)?;
+    module.function(&["Rng", "from_seed"], Rng::from_seed)?;
+
+    module.inst_fn("int", Rng::int)?;
+    module.inst_fn("int_range", Rng::int_range)?;
+    module.inst_fn("float", Rng::float)?;
+    module.inst_fn("bool", Rng::bool)?;
+    module.inst_fn("choose", Rng::choose)?;
+    module.inst_fn("choose_weighted", Rng::choose_weighted)?;
+    module.inst_fn("shuffle", Rng::shuffle)?;
+
+    module.function(&["int"], || int(&mut rand_crate::rng()))?;
+    module.function(&["int_range"], |start, end| {
+        int_range(&mut rand_crate::rng(), start, end)
+    })?;
+    module.function(&["float"], || float(&mut rand_crate::rng()))?;
+    module.function(&["bool"], || bool(&mut rand_crate::rng()))?;
+    module.function(&["choose"], |values: &[Value]| {
+        choose(&mut rand_crate::rng(), values)
+    })?;
+    module.function(
+        &["choose_weighted"],
+        |values: &[Value], weights: &[Value]| {
+            choose_weighted(&mut rand_crate::rng(), values, weights)
+        },
+    )?;
+    module.function(&["shuffle"], |values: &mut [Value]| {
+        shuffle(&mut rand_crate::rng(), values)
+    })?;
+    Ok(module)
+}
+
+/// A random number generator.
+#[derive(Debug, Any)]
+pub struct Rng {
+    inner: ChaCha8Rng,
+}
+
+impl Rng {
+    /// Construct a random number generator seeded from the operating system.
+    fn new() -> Self {
+        Self {
+            inner: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    /// Construct a random number generator from the given seed.
+    ///
+    /// Generators constructed from the same seed produce the same sequence of
+    /// values on every platform, for a given version of this module. The
+    /// sequence might change in a release which upgrades the `rand` crate.
+    fn from_seed(seed: i64) -> Self {
+        Self {
+            inner: ChaCha8Rng::seed_from_u64(seed as u64),
+        }
+    }
+
+    fn int(&mut self) -> i64 {
+        int(&mut self.inner)
+    }
+
+    fn int_range(&mut self, start: i64, end: i64) -> Result<i64, VmError> {
+        int_range(&mut self.inner, start, end)
+    }
+
+    fn float(&mut self) -> f64 {
+        float(&mut self.inner)
+    }
+
+    fn bool(&mut self) -> bool {
+        bool(&mut self.inner)
+    }
+
+    fn choose(&mut self, values: &[Value]) -> Option<Value> {
+        choose(&mut self.inner, values)
+    }
+
+    fn choose_weighted(
+        &mut self,
+        values: &[Value],
+        weights: &[Value],
+    ) -> Result<Option<Value>, VmError> {
+        choose_weighted(&mut self.inner, values, weights)
+    }
+
+    fn shuffle(&mut self, values: &mut [Value]) {
+        shuffle(&mut self.inner, values)
+    }
+}
+
+/// Generate a random integer.
+fn int<R>(rng: &mut R) -> i64
+where
+    R: rand_crate::Rng,
+{
+    rng.random()
+}
+
+/// Generate a random integer in the range `start` (inclusive) to `end`
+/// (exclusive).
+fn int_range<R>(rng: &mut R, start: i64, end: i64) -> Result<i64, VmError>
+where
+    R: rand_crate::Rng,
+{
+    if start >= end {
+        return Err(VmError::panic(format!("empty range `{}..{}`", start, end)));
+    }
+
+    Ok(rng.random_range(start..end))
+}
+
+/// Generate a random float in the range `0.0` (inclusive) to `1.0`
+/// (exclusive).
+fn float<R>(rng: &mut R) -> f64
+where
+    R: rand_crate::Rng,
+{
+    rng.random()
+}
+
+/// Generate a random boolean.
+fn bool<R>(rng: &mut R) -> bool
+where
+    R: rand_crate::Rng,
+{
+    rng.random()
+}
+
+/// Pick a random element from the given values, or `None` if there are none.
+fn choose<R>(rng: &mut R, values: &[Value]) -> Option<Value>
+where
+    R: rand_crate::Rng,
+{
+    values.choose(rng).cloned()
+}
+
+/// Pick a random element from the given values, where each value is as likely
+/// to be picked as its weight relative to the sum of all weights.
+///
+/// Weights can be integers or floats, and there must be one for each value.
+fn choose_weighted<R>(
+    rng: &mut R,
+    values: &[Value],
+    weights: &[Value],
+) -> Result<Option<Value>, VmError>
+where
+    R: rand_crate::Rng,
+{
+    if values.len() != weights.len() {
+        return Err(VmError::panic(format!(
+            "expected {} weights, but got {}",
+            values.len(),
+            weights.len()
+        )));
+    }
+
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let mut converted = Vec::with_capacity(weights.len());
+
+    for weight in weights {
+        converted.push(match weight {
+            Value::Integer(weight) => *weight as f64,
+            Value::Float(weight) => *weight,
+            actual => return Err(VmError::expected::<f64>(actual.type_info()?)),
+        });
+    }
+
+    let index = match WeightedIndex::new(converted) {
+        Ok(index) => index,
+        Err(error) => return Err(VmError::panic(format!("invalid weights: {}", error))),
+    };
+
+    Ok(Some(values[index.sample(rng)].clone()))
+}
+
+/// Shuffle the given values in place.
+fn shuffle<R>(rng: &mut R, values: &mut [Value])
+where
+    R: rand_crate::Rng,
+{
+    values.shuffle(rng);
+}
@@ -0,0 +1,404 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["regex"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::regex::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new("(?P<key>\\w+)=(?P<value>\\d+)")?;
+//!
+//!     for m in re.find_iter("a=1, b=2") {
+//!         dbg(m.as_str());
+//!     }
+//!
+//!     let groups = re.captures_named("a=1")?;
+//!     dbg(groups["key"]);
+//!
+//!     let doubled = re.replace_all("a=1, b=2", |groups| {
+//!         let value = groups[2].unwrap_or("");
+//!         `{value}{value}`
+//!     });
+//!
+//!     dbg(doubled);
+//! }
+//! ```
+
+use runestick::{Any, ContextError, Function, Module, Object, Value, VmError};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write as _;
+
+/// The maximum number of compiled regexes kept in the cache.
+const CACHE_CAPACITY: usize = 256;
+
+thread_local!(static CACHE: RefCell<Cache> = RefCell::new(Cache::default()));
+
+/// A cache of compiled regexes by pattern, which evicts the least recently
+/// used regex once it's full.
+#[derive(Default)]
+struct Cache {
+    /// Compiled regexes, with the tick at which they were last used.
+    entries: HashMap<String, (regex::Regex, u64)>,
+    /// Incremented every time the cache is used.
+    tick: u64,
+}
+
+impl Cache {
+    /// Get the compiled regex for the given pattern, marking it as used.
+    fn get(&mut self, pattern: &str) -> Option<regex::Regex> {
+        self.tick += 1;
+        let (regex, used) = self.entries.get_mut(pattern)?;
+        *used = self.tick;
+        Some(regex.clone())
+    }
+
+    /// Insert a compiled regex, evicting the least recently used one if the
+    /// cache is full.
+    fn insert(&mut self, pattern: &str, regex: regex::Regex) {
+        if self.entries.len() >= CACHE_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(pattern, _)| pattern.clone());
+
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.tick += 1;
+        self.entries.insert(pattern.to_owned(), (regex, self.tick));
+    }
+}
+
+/// Construct the `regex` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["regex"]);
+
+    module.ty::<Regex>()?;
+    module.ty::<Match>()?;
+    module.ty::<Matches>()?;
+    module.ty::<CapturesIter>()?;
+    module.ty::<Error>()?;
+
+    module.function(&["Regex", "new"], Regex::new)?;
+    module.function(&["escape"], escape)?;
+
+    module.inst_fn("is_match", Regex::is_match)?;
+    module.inst_fn("find", Regex::find)?;
+    module.inst_fn("find_iter", Regex::find_iter)?;
+    module.inst_fn("captures", Regex::captures)?;
+    module.inst_fn("captures_named", Regex::captures_named)?;
+    module.inst_fn("captures_iter", Regex::captures_iter)?;
+    module.inst_fn("replace", Regex::replace)?;
+    module.inst_fn("replace_all", Regex::replace_all)?;
+    module.inst_fn("split", Regex::split)?;
+    module.inst_fn("splitn", Regex::splitn)?;
+    module.inst_fn("as_str", Regex::as_str)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Regex::display)?;
+
+    module.inst_fn("start", Match::start)?;
+    module.inst_fn("end", Match::end)?;
+    module.inst_fn("as_str", Match::as_str)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Match::display)?;
+
+    module.inst_fn("next", Matches::next)?;
+    module.inst_fn(runestick::NEXT, Matches::next)?;
+    module.inst_fn(runestick::INTO_ITER, Matches::into_iter)?;
+
+    module.inst_fn("next", CapturesIter::next)?;
+    module.inst_fn(runestick::NEXT, CapturesIter::next)?;
+    module.inst_fn(runestick::INTO_ITER, CapturesIter::into_iter)?;
+
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+    Ok(module)
+}
+
+/// An error raised when compiling a regex.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: regex::Error,
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+/// A compiled regular expression.
+#[derive(Debug, Any)]
+pub struct Regex {
+    inner: regex::Regex,
+}
+
+impl Regex {
+    /// Compile the given pattern.
+    ///
+    /// Compiled regexes are cached by pattern, so constructing the same regex
+    /// repeatedly, like in a loop or a function which is called often, only
+    /// compiles it once. The cache holds up to 256 regexes, after which the
+    /// least recently used one is evicted.
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let cached = CACHE.with(|cache| cache.borrow_mut().get(pattern));
+
+        if let Some(inner) = cached {
+            return Ok(Self { inner });
+        }
+
+        let inner = match regex::Regex::new(pattern) {
+            Ok(inner) => inner,
+            Err(inner) => return Err(Error { inner }),
+        };
+
+        CACHE.with(|cache| cache.borrow_mut().insert(pattern, inner.clone()));
+
+        Ok(Self { inner })
+    }
+
+    /// Test if the regex matches anywhere in the given text.
+    fn is_match(&self, text: &str) -> bool {
+        self.inner.is_match(text)
+    }
+
+    /// Find the first match in the given text.
+    fn find(&self, text: &str) -> Option<Match> {
+        self.inner.find(text).map(Match::new)
+    }
+
+    /// Iterate over all non-overlapping matches in the given text.
+    fn find_iter(&self, text: &str) -> Matches {
+        let matches = self
+            .inner
+            .find_iter(text)
+            .map(Match::new)
+            .collect::<Vec<_>>();
+
+        Matches {
+            iter: matches.into_iter(),
+        }
+    }
+
+    /// Get the groups of the first match in the given text, where the first
+    /// group is the whole match and groups which didn't participate in the
+    /// match are `None`.
+    fn captures(&self, text: &str) -> Option<Vec<Option<String>>> {
+        Some(groups(&self.inner.captures(text)?))
+    }
+
+    /// Get the named groups of the first match in the given text as an
+    /// object. Groups which didn't participate in the match are left out.
+    fn captures_named(&self, text: &str) -> Option<Object> {
+        let captures = self.inner.captures(text)?;
+        let mut object = Object::new();
+
+        for name in self.inner.capture_names().flatten() {
+            if let Some(group) = captures.name(name) {
+                object.insert(name.to_owned(), Value::from(group.as_str().to_owned()));
+            }
+        }
+
+        Some(object)
+    }
+
+    /// Iterate over the groups of all non-overlapping matches in the given
+    /// text, like [captures][Self::captures].
+    fn captures_iter(&self, text: &str) -> CapturesIter {
+        let captures = self
+            .inner
+            .captures_iter(text)
+            .map(|captures| groups(&captures))
+            .collect::<Vec<_>>();
+
+        CapturesIter {
+            iter: captures.into_iter(),
+        }
+    }
+
+    /// Replace the first match in the given text.
+    ///
+    /// See [replace_all][Self::replace_all] for what the replacement can be.
+    fn replace(&self, text: &str, replacement: Value) -> Result<String, VmError> {
+        self.replacen(text, 1, replacement)
+    }
+
+    /// Replace all non-overlapping matches in the given text.
+    ///
+    /// The replacement is either a string, where groups can be referenced like
+    /// `$1` or `$name`, or a function which is called with the groups of each
+    /// match and returns its replacement.
+    fn replace_all(&self, text: &str, replacement: Value) -> Result<String, VmError> {
+        self.replacen(text, 0, replacement)
+    }
+
+    /// Split the given text by the matches of the regex.
+    fn split(&self, text: &str) -> Vec<String> {
+        self.inner.split(text).map(str::to_owned).collect()
+    }
+
+    /// Split the given text by the matches of the regex, into at most `limit`
+    /// parts.
+    fn splitn(&self, text: &str, limit: usize) -> Vec<String> {
+        self.inner.splitn(text, limit).map(str::to_owned).collect()
+    }
+
+    /// Get the pattern the regex was compiled from.
+    fn as_str(&self) -> String {
+        self.inner.as_str().to_owned()
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    fn replacen(&self, text: &str, limit: usize, replacement: Value) -> Result<String, VmError> {
+        let replaced = match replacement {
+            Value::String(string) => {
+                let string = string.borrow_ref()?;
+                self.inner.replacen(text, limit, string.as_str())
+            }
+            Value::StaticString(string) => self.inner.replacen(text, limit, string.as_str()),
+            Value::Function(function) => {
+                let function = function.borrow_ref()?;
+                return self.replacen_with(text, limit, &function);
+            }
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        };
+
+        Ok(replaced.into_owned())
+    }
+
+    fn replacen_with(
+        &self,
+        text: &str,
+        limit: usize,
+        function: &Function,
+    ) -> Result<String, VmError> {
+        let mut error = None;
+
+        let replaced = self
+            .inner
+            .replacen(text, limit, |captures: &regex::Captures<'_>| {
+                if error.is_some() {
+                    return String::new();
+                }
+
+                match function.call::<_, String>((groups(captures),)) {
+                    Ok(replacement) => replacement,
+                    Err(e) => {
+                        error = Some(e);
+                        String::new()
+                    }
+                }
+            });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        Ok(replaced.into_owned())
+    }
+}
+
+/// A single match of a regex.
+#[derive(Debug, Any)]
+pub struct Match {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Match {
+    fn new(m: regex::Match<'_>) -> Self {
+        Self {
+            start: m.start(),
+            end: m.end(),
+            text: m.as_str().to_owned(),
+        }
+    }
+
+    /// The byte offset where the match starts.
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset where the match ends.
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The matched text.
+    fn as_str(&self) -> String {
+        self.text.clone()
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        buf.push_str(&self.text);
+        Ok(())
+    }
+}
+
+/// An iterator over the matches of a regex.
+#[derive(Debug, Any)]
+pub struct Matches {
+    iter: std::vec::IntoIter<Match>,
+}
+
+impl Iterator for Matches {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// An iterator over the groups of the matches of a regex.
+#[derive(Debug, Any)]
+pub struct CapturesIter {
+    iter: std::vec::IntoIter<Vec<Option<String>>>,
+}
+
+impl Iterator for CapturesIter {
+    type Item = Vec<Option<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// Escape all special characters in the given text, so that it can be used to
+/// match literally.
+fn escape(text: &str) -> String {
+    regex::escape(text)
+}
+
+/// Convert captures into a vector of optional strings.
+fn groups(captures: &regex::Captures<'_>) -> Vec<Option<String>> {
+    captures
+        .iter()
+        .map(|group| group.map(|group| group.as_str().to_owned()))
+        .collect()
+}
@@ -30,32 +30,589 @@
 //!     println("Message after 10 seconds!");
 //! }
 //! ```
+//!
+//! Durations can be added, subtracted, and compared, and so can instants and
+//! dates with durations:
+//!
+//! ```rust,ignore
+//! use time::DateTime;
+//! use time::Duration;
+//! use time::Instant;
+//!
+//! async fn main() {
+//!     let start = Instant::now();
+//!     let ticks = time::interval(Duration::from_millis(100));
+//!
+//!     while start.elapsed() < Duration::from_secs(1) {
+//!         ticks.next().await;
+//!     }
+//!
+//!     let date = DateTime::parse("2020-08-01T12:00:00+02:00")?;
+//!     let date = date + Duration::from_secs(3600);
+//!     println(date.format("%Y-%m-%d %H:%M"));
+//!
+//!     match time::timeout(slow(), Duration::from_secs(5)).await {
+//!         Ok(value) => dbg(value),
+//!         Err(error) => println(`{error}`),
+//!     }
+//! }
+//! ```
 
-use runestick::{Any, ContextError, Module};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    Datelike as _, FixedOffset, Local, NaiveDateTime, Offset as _, TimeZone as _, Timelike as _,
+    Utc,
+};
+use runestick::{Any, ContextError, Future, Module, Stream, ToValue as _, Value, VmError};
+use std::convert::TryFrom as _;
+use std::fmt;
+use std::fmt::Write as _;
+use tokio::stream::StreamExt as _;
 
 /// Construct the `time` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::new(&["time"]);
+
+    module.ty::<Duration>()?;
+    module.ty::<Instant>()?;
+    module.ty::<SystemTime>()?;
+    module.ty::<DateTime>()?;
+    module.ty::<Error>()?;
+    module.ty::<Elapsed>()?;
+
     module.function(&["Duration", "from_secs"], Duration::from_secs)?;
+    module.function(&["Duration", "from_millis"], Duration::from_millis)?;
+    module.function(&["Duration", "from_micros"], Duration::from_micros)?;
+    module.function(&["Duration", "from_nanos"], Duration::from_nanos)?;
+    module.function(&["Duration", "from_secs_f64"], Duration::from_secs_f64)?;
+    module.inst_fn("as_secs", Duration::as_secs)?;
+    module.inst_fn("as_millis", Duration::as_millis)?;
+    module.inst_fn("as_micros", Duration::as_micros)?;
+    module.inst_fn("as_nanos", Duration::as_nanos)?;
+    module.inst_fn("as_secs_f64", Duration::as_secs_f64)?;
+    module.inst_fn("subsec_millis", Duration::subsec_millis)?;
+    module.inst_fn("subsec_nanos", Duration::subsec_nanos)?;
+    module.inst_fn(runestick::ADD, Duration::add)?;
+    module.inst_fn(runestick::SUB, Duration::sub)?;
+    module.inst_fn(runestick::EQ, Duration::eq)?;
+    module.inst_fn(runestick::CMP, Duration::cmp)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Duration::display)?;
+
+    module.function(&["Instant", "now"], Instant::now)?;
+    module.inst_fn("elapsed", Instant::elapsed)?;
+    module.inst_fn("duration_since", Instant::duration_since)?;
+    module.inst_fn(runestick::ADD, Instant::add)?;
+    module.inst_fn(runestick::SUB, Instant::sub)?;
+    module.inst_fn(runestick::EQ, Instant::eq)?;
+    module.inst_fn(runestick::CMP, Instant::cmp)?;
+
+    module.function(&["SystemTime", "now"], SystemTime::now)?;
+    module.inst_fn("elapsed", SystemTime::elapsed)?;
+    module.inst_fn("duration_since", SystemTime::duration_since)?;
+    module.inst_fn("to_datetime", SystemTime::to_datetime)?;
+    module.inst_fn(runestick::ADD, SystemTime::add)?;
+    module.inst_fn(runestick::SUB, SystemTime::sub)?;
+    module.inst_fn(runestick::EQ, SystemTime::eq)?;
+    module.inst_fn(runestick::CMP, SystemTime::cmp)?;
+
+    module.function(&["DateTime", "now"], DateTime::now)?;
+    module.function(&["DateTime", "now_local"], DateTime::now_local)?;
+    module.function(&["DateTime", "from_timestamp"], DateTime::from_timestamp)?;
+    module.function(&["DateTime", "parse"], DateTime::parse)?;
+    module.function(&["DateTime", "parse_from_str"], DateTime::parse_from_str)?;
+    module.inst_fn("format", DateTime::format)?;
+    module.inst_fn("to_rfc3339", DateTime::to_rfc3339)?;
+    module.inst_fn("timestamp", DateTime::timestamp)?;
+    module.inst_fn("timestamp_millis", DateTime::timestamp_millis)?;
+    module.inst_fn("offset", DateTime::offset)?;
+    module.inst_fn("with_offset", DateTime::with_offset)?;
+    module.inst_fn("to_utc", DateTime::to_utc)?;
+    module.inst_fn("year", DateTime::year)?;
+    module.inst_fn("month", DateTime::month)?;
+    module.inst_fn("day", DateTime::day)?;
+    module.inst_fn("weekday", DateTime::weekday)?;
+    module.inst_fn("hour", DateTime::hour)?;
+    module.inst_fn("minute", DateTime::minute)?;
+    module.inst_fn("second", DateTime::second)?;
+    module.inst_fn(runestick::ADD, DateTime::add)?;
+    module.inst_fn(runestick::SUB, DateTime::sub)?;
+    module.inst_fn(runestick::EQ, DateTime::eq)?;
+    module.inst_fn(runestick::CMP, DateTime::cmp)?;
+    module.inst_fn(runestick::STRING_DISPLAY, DateTime::display)?;
+
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Elapsed::display)?;
+
     module.async_function(&["delay_for"], delay_for)?;
+    module.async_function(&["delay_until"], delay_until)?;
+    module.function(&["interval"], interval)?;
+    module.async_function(&["timeout"], timeout)?;
     Ok(module)
 }
 
+/// Convert an ordering into the representation used by the `CMP` protocol.
+fn ordering(ordering: std::cmp::Ordering) -> i64 {
+    ordering as i64
+}
+
 #[derive(Debug, Clone, Copy, Any)]
 struct Duration {
-    inner: tokio::time::Duration,
+    inner: std::time::Duration,
 }
 
 impl Duration {
     /// Construct a duration from seconds.
     fn from_secs(secs: u64) -> Self {
         Self {
-            inner: tokio::time::Duration::from_secs(secs),
+            inner: std::time::Duration::from_secs(secs),
+        }
+    }
+
+    /// Construct a duration from milliseconds.
+    fn from_millis(millis: u64) -> Self {
+        Self {
+            inner: std::time::Duration::from_millis(millis),
+        }
+    }
+
+    /// Construct a duration from microseconds.
+    fn from_micros(micros: u64) -> Self {
+        Self {
+            inner: std::time::Duration::from_micros(micros),
+        }
+    }
+
+    /// Construct a duration from nanoseconds.
+    fn from_nanos(nanos: u64) -> Self {
+        Self {
+            inner: std::time::Duration::from_nanos(nanos),
+        }
+    }
+
+    /// Construct a duration from fractional seconds.
+    fn from_secs_f64(secs: f64) -> Result<Self, VmError> {
+        if !secs.is_finite() || secs < 0.0 {
+            return Err(VmError::panic("duration must be a positive number"));
+        }
+
+        Ok(Self {
+            inner: std::time::Duration::from_secs_f64(secs),
+        })
+    }
+
+    /// The number of whole seconds in the duration.
+    fn as_secs(&self) -> u64 {
+        self.inner.as_secs()
+    }
+
+    /// The number of whole milliseconds in the duration.
+    fn as_millis(&self) -> u128 {
+        self.inner.as_millis()
+    }
+
+    /// The number of whole microseconds in the duration.
+    fn as_micros(&self) -> u128 {
+        self.inner.as_micros()
+    }
+
+    /// The number of nanoseconds in the duration.
+    fn as_nanos(&self) -> u128 {
+        self.inner.as_nanos()
+    }
+
+    /// The number of seconds in the duration, including fractions.
+    fn as_secs_f64(&self) -> f64 {
+        self.inner.as_secs_f64()
+    }
+
+    /// The fractional part of the duration in whole milliseconds.
+    fn subsec_millis(&self) -> u32 {
+        self.inner.subsec_millis()
+    }
+
+    /// The fractional part of the duration in nanoseconds.
+    fn subsec_nanos(&self) -> u32 {
+        self.inner.subsec_nanos()
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, VmError> {
+        match self.inner.checked_add(other.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(VmError::panic("overflow when adding durations")),
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, VmError> {
+        match self.inner.checked_sub(other.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(VmError::panic("overflow when subtracting durations")),
+        }
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Self) -> i64 {
+        ordering(self.inner.cmp(&other.inner))
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A monotonic clock measurement.
+#[derive(Debug, Clone, Copy, Any)]
+struct Instant {
+    inner: std::time::Instant,
+}
+
+impl Instant {
+    /// Get the current instant.
+    fn now() -> Self {
+        Self {
+            inner: std::time::Instant::now(),
+        }
+    }
+
+    /// The time elapsed since the instant.
+    fn elapsed(&self) -> Duration {
+        Duration {
+            inner: self.inner.elapsed(),
+        }
+    }
+
+    /// The time elapsed from the other instant to this one, or zero if the
+    /// other instant is later.
+    fn duration_since(&self, other: &Self) -> Duration {
+        Duration {
+            inner: self.inner.saturating_duration_since(other.inner),
         }
     }
+
+    fn add(&self, duration: &Duration) -> Result<Self, VmError> {
+        match self.inner.checked_add(duration.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(VmError::panic("overflow when adding duration to instant")),
+        }
+    }
+
+    /// Subtract either a duration, producing an instant, or another instant,
+    /// producing the duration between them.
+    fn sub(&self, other: Value) -> Result<Value, VmError> {
+        if let Value::Any(any) = &other {
+            let any = any.borrow_ref()?;
+
+            if let Some(other) = any.downcast_borrow_ref::<Self>() {
+                return self.duration_since(other).to_value();
+            }
+
+            if let Some(duration) = any.downcast_borrow_ref::<Duration>() {
+                return match self.inner.checked_sub(duration.inner) {
+                    Some(inner) => Self { inner }.to_value(),
+                    None => Err(VmError::panic(
+                        "overflow when subtracting duration from instant",
+                    )),
+                };
+            }
+        }
+
+        Err(VmError::expected::<Duration>(other.type_info()?))
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Self) -> i64 {
+        ordering(self.inner.cmp(&other.inner))
+    }
+}
+
+/// A measurement of the system clock.
+#[derive(Debug, Clone, Any)]
+struct SystemTime {
+    inner: std::time::SystemTime,
 }
 
-/// Convert any value to a json string.
+impl SystemTime {
+    /// Get the current system time.
+    fn now() -> Self {
+        Self {
+            inner: std::time::SystemTime::now(),
+        }
+    }
+
+    /// The time elapsed since the system time, or zero if the system clock has
+    /// since been adjusted to before it.
+    fn elapsed(&self) -> Duration {
+        Duration {
+            inner: self.inner.elapsed().unwrap_or_default(),
+        }
+    }
+
+    /// The time elapsed from the other system time to this one, or `None` if
+    /// the other system time is later.
+    fn duration_since(&self, other: &Self) -> Option<Duration> {
+        let inner = self.inner.duration_since(other.inner).ok()?;
+        Some(Duration { inner })
+    }
+
+    /// Convert into a UTC date.
+    fn to_datetime(&self) -> DateTime {
+        let inner = chrono::DateTime::<Utc>::from(self.inner);
+        DateTime::from(inner)
+    }
+
+    fn add(&self, duration: &Duration) -> Result<Self, VmError> {
+        match self.inner.checked_add(duration.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(VmError::panic(
+                "overflow when adding duration to system time",
+            )),
+        }
+    }
+
+    fn sub(&self, duration: &Duration) -> Result<Self, VmError> {
+        match self.inner.checked_sub(duration.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(VmError::panic(
+                "overflow when subtracting duration from system time",
+            )),
+        }
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Self) -> i64 {
+        ordering(self.inner.cmp(&other.inner))
+    }
+}
+
+/// A date and time with a fixed offset from UTC.
+#[derive(Debug, Clone, Any)]
+struct DateTime {
+    inner: chrono::DateTime<FixedOffset>,
+}
+
+impl<Tz> From<chrono::DateTime<Tz>> for DateTime
+where
+    Tz: chrono::TimeZone,
+{
+    fn from(date: chrono::DateTime<Tz>) -> Self {
+        let offset = date.offset().fix();
+
+        Self {
+            inner: date.with_timezone(&offset),
+        }
+    }
+}
+
+impl DateTime {
+    /// Get the current date and time in UTC.
+    fn now() -> Self {
+        Self::from(Utc::now())
+    }
+
+    /// Get the current date and time in the local timezone.
+    fn now_local() -> Self {
+        Self::from(Local::now())
+    }
+
+    /// Construct a UTC date from the number of seconds since the Unix epoch.
+    fn from_timestamp(secs: i64) -> Option<Self> {
+        Some(Self::from(Utc.timestamp_opt(secs, 0).single()?))
+    }
+
+    /// Parse a date in the RFC 3339 format, like `2020-08-01T12:00:00+02:00`.
+    fn parse(text: &str) -> Result<Self, Error> {
+        match chrono::DateTime::parse_from_rfc3339(text) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(inner) => Err(Error { inner }),
+        }
+    }
+
+    /// Parse a date using the given `strftime`-style format.
+    ///
+    /// If the format doesn't include an offset, the date is assumed to be in
+    /// UTC.
+    fn parse_from_str(text: &str, format: &str) -> Result<Self, Error> {
+        if let Ok(inner) = chrono::DateTime::parse_from_str(text, format) {
+            return Ok(Self { inner });
+        }
+
+        match NaiveDateTime::parse_from_str(text, format) {
+            Ok(naive) => Ok(Self::from(Utc.from_utc_datetime(&naive))),
+            Err(inner) => Err(Error { inner }),
+        }
+    }
+
+    /// Format the date using the given `strftime`-style format.
+    fn format(&self, format: &str) -> Result<String, VmError> {
+        let items = StrftimeItems::new(format).collect::<Vec<_>>();
+
+        if items.iter().any(|item| matches!(item, Item::Error)) {
+            return Err(VmError::panic(format!("invalid format `{}`", format)));
+        }
+
+        Ok(self.inner.format_with_items(items.into_iter()).to_string())
+    }
+
+    /// Format the date in the RFC 3339 format.
+    fn to_rfc3339(&self) -> String {
+        self.inner.to_rfc3339()
+    }
+
+    /// The number of seconds since the Unix epoch.
+    fn timestamp(&self) -> i64 {
+        self.inner.timestamp()
+    }
+
+    /// The number of milliseconds since the Unix epoch.
+    fn timestamp_millis(&self) -> i64 {
+        self.inner.timestamp_millis()
+    }
+
+    /// The offset from UTC in seconds.
+    fn offset(&self) -> i64 {
+        self.inner.offset().local_minus_utc() as i64
+    }
+
+    /// Convert the date into the given offset from UTC in seconds.
+    fn with_offset(&self, secs: i64) -> Result<Self, VmError> {
+        let offset = i32::try_from(secs)
+            .ok()
+            .and_then(FixedOffset::east_opt)
+            .ok_or_else(|| VmError::panic(format!("invalid offset `{}`", secs)))?;
+
+        Ok(Self {
+            inner: self.inner.with_timezone(&offset),
+        })
+    }
+
+    /// Convert the date into UTC.
+    fn to_utc(&self) -> Self {
+        Self::from(self.inner.with_timezone(&Utc))
+    }
+
+    fn year(&self) -> i32 {
+        self.inner.year()
+    }
+
+    fn month(&self) -> u32 {
+        self.inner.month()
+    }
+
+    fn day(&self) -> u32 {
+        self.inner.day()
+    }
+
+    /// The day of the week, where Monday is `1` and Sunday is `7`.
+    fn weekday(&self) -> u32 {
+        self.inner.weekday().number_from_monday()
+    }
+
+    fn hour(&self) -> u32 {
+        self.inner.hour()
+    }
+
+    fn minute(&self) -> u32 {
+        self.inner.minute()
+    }
+
+    fn second(&self) -> u32 {
+        self.inner.second()
+    }
+
+    fn add(&self, duration: &Duration) -> Result<Self, VmError> {
+        chrono::Duration::from_std(duration.inner)
+            .ok()
+            .and_then(|duration| self.inner.checked_add_signed(duration))
+            .map(|inner| Self { inner })
+            .ok_or_else(|| VmError::panic("overflow when adding duration to date"))
+    }
+
+    fn sub(&self, duration: &Duration) -> Result<Self, VmError> {
+        chrono::Duration::from_std(duration.inner)
+            .ok()
+            .and_then(|duration| self.inner.checked_sub_signed(duration))
+            .map(|inner| Self { inner })
+            .ok_or_else(|| VmError::panic("overflow when subtracting duration from date"))
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Self) -> i64 {
+        ordering(self.inner.cmp(&other.inner))
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        buf.push_str(&self.inner.to_rfc3339());
+        Ok(())
+    }
+}
+
+/// An error raised when parsing a date.
+#[derive(Debug, Any)]
+struct Error {
+    inner: chrono::ParseError,
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+/// The error returned when a future passed to `timeout` didn't complete in
+/// time.
+#[derive(Debug, Any)]
+struct Elapsed {
+    inner: tokio::time::Elapsed,
+}
+
+impl Elapsed {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+/// Wait for the given duration.
 async fn delay_for(duration: &Duration) {
     tokio::time::delay_for(duration.inner).await;
 }
+
+/// Wait until the given instant.
+async fn delay_until(instant: &Instant) {
+    tokio::time::delay_until(tokio::time::Instant::from_std(instant.inner)).await;
+}
+
+/// Construct a stream which produces an instant every time the given period
+/// has elapsed, starting immediately.
+fn interval(period: &Duration) -> Result<Stream, VmError> {
+    if period.inner == std::time::Duration::default() {
+        return Err(VmError::panic("interval period must be non-zero"));
+    }
+
+    let interval = tokio::time::interval(period.inner).map(|instant| {
+        Ok::<_, VmError>(Instant {
+            inner: instant.into_std(),
+        })
+    });
+
+    Ok(Stream::from_stream(interval))
+}
+
+/// Wait for the given future to complete, or for the given duration to elapse,
+/// whichever comes first.
+async fn timeout(future: Future, duration: &Duration) -> Result<Result<Value, Elapsed>, VmError> {
+    Ok(match tokio::time::timeout(duration.inner, future).await {
+        Ok(value) => Ok(value?),
+        Err(inner) => Err(Elapsed { inner }),
+    })
+}
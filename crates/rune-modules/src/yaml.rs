@@ -0,0 +1,103 @@
+//! The native `yaml` module for the [Rune Language].
+//!
+//! [Rune Language]: https://github.com/rune-rs/rune
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.6.16", features = ["yaml"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::yaml::module()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use yaml;
+//!
+//! fn main() {
+//!     let data = yaml::from_string("hello:\n  world: 42")?;
+//!     dbg(data);
+//!     println(yaml::to_string(data)?);
+//! }
+//! ```
+
+use runestick::{Any, Bytes, ContextError, Module, Value};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `yaml` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["yaml"]);
+
+    module.ty::<Error>()?;
+
+    module.function(&["from_bytes"], from_bytes)?;
+    module.function(&["from_string"], from_string)?;
+    module.function(&["to_string"], to_string)?;
+    module.function(&["to_bytes"], to_bytes)?;
+
+    module.inst_fn("line", Error::line)?;
+    module.inst_fn("column", Error::column)?;
+    module.inst_fn(runestick::STRING_DISPLAY, Error::display)?;
+    Ok(module)
+}
+
+/// An error raised when converting to or from YAML.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: serde_yaml::Error,
+}
+
+impl Error {
+    /// The line at which the error occurred, starting at 1, if known.
+    fn line(&self) -> Option<usize> {
+        Some(self.inner.location()?.line())
+    }
+
+    /// The column at which the error occurred, starting at 1, if known.
+    fn column(&self) -> Option<usize> {
+        Some(self.inner.location()?.column())
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(inner: serde_yaml::Error) -> Self {
+        Self { inner }
+    }
+}
+
+/// Get value from yaml bytes.
+fn from_bytes(bytes: &[u8]) -> Result<Value, Error> {
+    Ok(serde_yaml::from_slice(bytes)?)
+}
+
+/// Get value from yaml string.
+fn from_string(string: &str) -> Result<Value, Error> {
+    Ok(serde_yaml::from_str(string)?)
+}
+
+/// Convert any value to a yaml string.
+fn to_string(value: Value) -> Result<String, Error> {
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Convert any value to yaml bytes.
+fn to_bytes(value: Value) -> Result<Bytes, Error> {
+    let bytes = serde_yaml::to_vec(&value)?;
+    Ok(Bytes::from_vec(bytes))
+}
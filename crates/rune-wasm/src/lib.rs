@@ -1,7 +1,6 @@
 use wasm_bindgen::prelude::*;
 
 use rune::{EmitDiagnostics as _, Spanned as _};
-use runestick::budget;
 use runestick::{ContextError, Module, Panic, Stack, Value, VmError};
 use serde::Serialize;
 use std::cell;
@@ -220,7 +219,8 @@ fn inner_compile(input: &str, budget: usize) -> CompileResult {
         }
     };
 
-    let vm = runestick::Vm::new(context, unit);
+    let mut vm = runestick::Vm::new(context, unit);
+    vm.set_fuel(Some(budget));
 
     let mut execution = match vm.execute(&["main"], ()) {
         Ok(execution) => execution,
@@ -233,9 +233,7 @@ fn inner_compile(input: &str, budget: usize) -> CompileResult {
         }
     };
 
-    let future = budget::with(budget, execution.async_complete());
-
-    let output = match futures_executor::block_on(future) {
+    let output = match futures_executor::block_on(execution.async_complete()) {
         Ok(output) => output,
         Err(error) => {
             if let Ok(vm) = execution.vm() {
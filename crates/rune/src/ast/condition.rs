@@ -1,5 +1,6 @@
 use crate::ast;
-use crate::{Parse, ParseError, Parser};
+use crate::{IntoTokens, Parse, ParseError, Parser, Spanned};
+use runestick::Span;
 
 impl_enum_ast! {
     /// An if condition.
@@ -8,6 +9,46 @@ impl_enum_ast! {
         Expr(Box<ast::Expr>),
         /// A pattern match.
         ExprLet(Box<ast::ExprLet>),
+        /// A chain of conditions joined by `&&`, where at least one of them is
+        /// a pattern match.
+        Chain(Box<ConditionChain>),
+    }
+}
+
+impl Condition {
+    /// Parse a single link in a chain of conditions.
+    ///
+    /// Links are parsed with a precedence higher than `&&`, so that a chain
+    /// can be split up into its conditions.
+    fn parse_link(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let precedence = ast::BinOp::And.precedence() + 1;
+
+        if !parser.peek::<ast::Let>()? {
+            return Ok(Self::Expr(Box::new(ast::Expr::parse_with_precedence(
+                parser, precedence,
+            )?)));
+        }
+
+        let let_ = parser.parse()?;
+        let pat = parser.parse()?;
+        let ty = parser.parse()?;
+        let eq = parser.parse()?;
+        let mut expr = ast::Expr::parse_with_precedence(parser, precedence)?;
+
+        // NB: a scrutinee which isn't followed by `&&` keeps consuming
+        // operators, like `let x = a || b`.
+        if !parser.peek::<ast::And>()? {
+            expr = ast::Expr::parse_binary_without_eager_brace(parser, expr)?;
+        }
+
+        Ok(Self::ExprLet(Box::new(ast::ExprLet {
+            let_,
+            pat,
+            ty,
+            eq,
+            expr: Box::new(expr),
+            else_: None,
+        })))
     }
 }
 
@@ -20,16 +61,82 @@ impl_enum_ast! {
 ///
 /// parse_all::<ast::Condition>("true").unwrap();
 /// parse_all::<ast::Condition>("let [a, ..] = v").unwrap();
+/// parse_all::<ast::Condition>("let x = a || b").unwrap();
+///
+/// let condition = parse_all::<ast::Condition>("a && b || c").unwrap();
+/// assert!(matches!(condition, ast::Condition::Expr(..)));
+///
+/// let condition = parse_all::<ast::Condition>("let Some(x) = a && x > 3").unwrap();
+/// assert!(matches!(condition, ast::Condition::Chain(..)));
 /// ```
 impl Parse for Condition {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
-        let token = parser.token_peek_eof()?;
+        let first = Self::parse_link(parser)?;
+        let mut rest = Vec::new();
+
+        while parser.peek::<ast::And>()? {
+            let and = parser.parse()?;
+            rest.push((and, Self::parse_link(parser)?));
+        }
+
+        let mut lhs = match first {
+            Self::Expr(lhs) if rest.iter().all(|(_, c)| matches!(c, Self::Expr(..))) => *lhs,
+            first if rest.is_empty() => return Ok(first),
+            first => {
+                return Ok(Self::Chain(Box::new(ConditionChain {
+                    first: Box::new(first),
+                    rest,
+                })))
+            }
+        };
 
-        Ok(match token.kind {
-            ast::Kind::Let => {
-                Self::ExprLet(Box::new(ast::ExprLet::parse_without_eager_brace(parser)?))
+        // NB: without a pattern match this is a regular expression, so fold
+        // the links back into `&&` operations and parse whatever follows.
+        for (and, rhs) in rest {
+            if let Self::Expr(rhs) = rhs {
+                lhs = ast::Expr::ExprBinary(ast::ExprBinary {
+                    lhs: Box::new(lhs),
+                    t1: and.token,
+                    t2: None,
+                    rhs,
+                    op: ast::BinOp::And,
+                });
             }
-            _ => Self::Expr(Box::new(ast::Expr::parse_without_eager_brace(parser)?)),
-        })
+        }
+
+        let expr = ast::Expr::parse_binary_without_eager_brace(parser, lhs)?;
+        Ok(Self::Expr(Box::new(expr)))
+    }
+}
+
+/// A chain of conditions joined by `&&`, like `let Some(x) = a && x > 3`.
+#[derive(Debug, Clone)]
+pub struct ConditionChain {
+    /// The first condition.
+    pub first: Box<Condition>,
+    /// The rest of the conditions.
+    pub rest: Vec<(ast::And, Condition)>,
+}
+
+impl ConditionChain {
+    /// Iterate over all conditions in the chain.
+    pub fn conditions(&self) -> impl Iterator<Item = &Condition> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, c)| c))
+    }
+}
+
+impl Spanned for ConditionChain {
+    fn span(&self) -> Span {
+        match self.rest.last() {
+            Some((_, condition)) => self.first.span().join(condition.span()),
+            None => self.first.span(),
+        }
+    }
+}
+
+impl IntoTokens for ConditionChain {
+    fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
+        self.first.into_tokens(context, stream);
+        self.rest.into_tokens(context, stream);
     }
 }
@@ -85,6 +85,8 @@ impl_enum_ast! {
         ExprSelect(ast::ExprSelect),
         /// A closure expression.
         ExprClosure(ast::ExprClosure),
+        /// Spreading the values of an expression as arguments.
+        ExprSpread(ast::ExprSpread),
         /// A literal expression.
         ExprLit(ast::ExprLit),
     }
@@ -164,6 +166,25 @@ impl Expr {
         Ok(Self::parse_expr_binary(parser, lhs, 0, eager_brace)?)
     }
 
+    /// Parse an expression without eager brace, which only consumes binary
+    /// operators with a precedence of at least `min_precedence`.
+    pub(crate) fn parse_with_precedence(
+        parser: &mut Parser<'_>,
+        min_precedence: usize,
+    ) -> Result<Self, ParseError> {
+        let lhs = Self::parse_primary(parser, EagerBrace(false), ExprChain(true))?;
+        Self::parse_expr_binary(parser, lhs, min_precedence, EagerBrace(false))
+    }
+
+    /// Continue parsing binary operators without eager brace on an already
+    /// parsed left-hand side.
+    pub(crate) fn parse_binary_without_eager_brace(
+        parser: &mut Parser<'_>,
+        lhs: Self,
+    ) -> Result<Self, ParseError> {
+        Self::parse_expr_binary(parser, lhs, 0, EagerBrace(false))
+    }
+
     /// Parse expressions that start with an identifier.
     pub(crate) fn parse_ident_start(
         parser: &mut Parser<'_>,
@@ -250,6 +271,7 @@ impl Expr {
                         Self::ExprClosure(expr_closure) => Self::ExprClosure(ast::ExprClosure {
                             attributes: take(&mut attributes),
                             async_: Some(async_),
+                            move_: expr_closure.move_,
                             args: expr_closure.args,
                             body: expr_closure.body,
                         }),
@@ -266,7 +288,7 @@ impl Expr {
                         }
                     }
                 }
-                ast::Kind::PipePipe | ast::Kind::Pipe => Self::ExprClosure(
+                ast::Kind::PipePipe | ast::Kind::Pipe | ast::Kind::Move => Self::ExprClosure(
                     ast::ExprClosure::parse_with_attributes(parser, take(&mut attributes))?,
                 ),
                 ast::Kind::Self_ => Self::Self_(parser.parse()?),
@@ -318,6 +340,7 @@ impl Expr {
                 ast::Kind::Break => Self::ExprBreak(parser.parse()?),
                 ast::Kind::Yield => Self::ExprYield(parser.parse()?),
                 ast::Kind::Return => Self::ExprReturn(parser.parse()?),
+                ast::Kind::DotDot => Self::ExprSpread(parser.parse()?),
                 _ => {
                     return Err(ParseError::new(
                         token,
@@ -566,6 +589,7 @@ impl Peek for Expr {
 
         match t.kind {
             ast::Kind::Async => true,
+            ast::Kind::Move => true,
             ast::Kind::Self_ => true,
             ast::Kind::Select => true,
             ast::Kind::Label(..) => matches!(t2.map(|t| t.kind), Some(ast::Kind::Colon)),
@@ -589,6 +613,7 @@ impl Peek for Expr {
             ast::Kind::Ident(..) => true,
             ast::Kind::Break => true,
             ast::Kind::Return => true,
+            ast::Kind::DotDot => true,
             _ => false,
         }
     }
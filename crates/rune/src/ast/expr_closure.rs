@@ -9,6 +9,9 @@ pub struct ExprClosure {
     pub attributes: Vec<ast::Attribute>,
     /// If the closure is async or not.
     pub async_: Option<ast::Async>,
+    /// If the closure captures variables by copying them, instead of by
+    /// shared cells.
+    pub move_: Option<ast::Move>,
     /// Arguments to the closure.
     pub args: ExprClosureArgs,
     /// The body of the closure.
@@ -18,6 +21,7 @@ pub struct ExprClosure {
 into_tokens!(ExprClosure {
     attributes,
     async_,
+    move_,
     args,
     body
 });
@@ -27,6 +31,8 @@ impl ExprClosure {
     pub fn item_span(&self) -> Span {
         if let Some(async_) = &self.async_ {
             async_.span().join(self.args.span())
+        } else if let Some(move_) = &self.move_ {
+            move_.span().join(self.args.span())
         } else {
             self.args.span()
         }
@@ -38,6 +44,7 @@ impl ExprClosure {
         attributes: Vec<ast::Attribute>,
     ) -> Result<Self, ParseError> {
         let async_ = parser.parse()?;
+        let move_ = parser.parse()?;
 
         let args = if let Some(token) = parser.parse::<Option<ast::Or>>()? {
             ExprClosureArgs::Empty { token }
@@ -65,6 +72,7 @@ impl ExprClosure {
         Ok(Self {
             attributes,
             async_,
+            move_,
             args,
             body: Box::new(parser.parse()?),
         })
@@ -75,6 +83,8 @@ impl Spanned for ExprClosure {
     fn span(&self) -> Span {
         if let Some(async_) = &self.async_ {
             async_.span().join(self.body.span())
+        } else if let Some(move_) = &self.move_ {
+            move_.span().join(self.body.span())
         } else {
             self.args.span().join(self.body.span())
         }
@@ -92,6 +102,12 @@ impl Spanned for ExprClosure {
 /// parse_all::<ast::ExprClosure>("|| 42").unwrap();
 /// parse_all::<ast::ExprClosure>("|| { 42 }").unwrap();
 ///
+/// let expr = parse_all::<ast::ExprClosure>("move |a| a + 1").unwrap();
+/// assert!(expr.move_.is_some());
+///
+/// let expr = parse_all::<ast::ExprClosure>("async move || 42").unwrap();
+/// assert!(expr.move_.is_some());
+///
 /// let expr = parse_all::<ast::ExprClosure>("#[retry(n=3)]  || 43").unwrap();
 /// assert_eq!(expr.attributes.len(), 1);
 ///
@@ -2,24 +2,33 @@ use crate::ast;
 use crate::{Parse, ParseError, Parser, Spanned};
 use runestick::Span;
 
-/// A let expression `let <name> = <expr>;`
+/// A let expression `let <name> = <expr>;`, optionally followed by a block
+/// which is evaluated if the pattern doesn't match `let <pat> = <expr> else {
+/// .. };`.
 #[derive(Debug, Clone)]
 pub struct ExprLet {
     /// The `let` keyword.
     pub let_: ast::Let,
     /// The name of the binding.
     pub pat: ast::Pat,
+    /// The optional type annotation of the binding.
+    pub ty: Option<Box<(ast::Colon, ast::Type)>>,
     /// The equality keyword.
     pub eq: ast::Eq,
     /// The expression the binding is assigned to.
     pub expr: Box<ast::Expr>,
+    /// The block to evaluate if the pattern doesn't match, which must not
+    /// complete normally.
+    pub else_: Option<Box<(ast::Else, ast::Block)>>,
 }
 
 into_tokens!(ExprLet {
     let_,
     pat,
+    ty,
     eq,
-    expr
+    expr,
+    else_
 });
 
 impl ExprLet {
@@ -28,8 +37,10 @@ impl ExprLet {
         Ok(Self {
             let_: parser.parse()?,
             pat: parser.parse()?,
+            ty: parser.parse()?,
             eq: parser.parse()?,
             expr: Box::new(ast::Expr::parse_without_eager_brace(parser)?),
+            else_: None,
         })
     }
 }
@@ -37,17 +48,38 @@ impl ExprLet {
 impl Spanned for ExprLet {
     /// Access the span of the expression.
     fn span(&self) -> Span {
-        self.let_.span().join(self.expr.span())
+        match self.else_.as_deref() {
+            Some((_, block)) => self.let_.span().join(block.span()),
+            None => self.let_.span().join(self.expr.span()),
+        }
     }
 }
 
+/// Parse implementation for a let expression.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// let expr = parse_all::<ast::ExprLet>("let x = 1").unwrap();
+/// assert!(expr.ty.is_none());
+///
+/// let expr = parse_all::<ast::ExprLet>("let x: int = 1").unwrap();
+/// assert!(expr.ty.is_some());
+///
+/// let expr = parse_all::<ast::ExprLet>("let Some(x) = x else { return; }").unwrap();
+/// assert!(expr.else_.is_some());
+/// ```
 impl Parse for ExprLet {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
         Ok(Self {
             let_: parser.parse()?,
             pat: parser.parse()?,
+            ty: parser.parse()?,
             eq: parser.parse()?,
             expr: Box::new(parser.parse()?),
+            else_: parser.parse()?,
         })
     }
 }
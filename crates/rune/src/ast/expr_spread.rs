@@ -0,0 +1,44 @@
+use crate::ast;
+use crate::{IntoTokens, Parse, ParseError, Parser, Spanned};
+use runestick::Span;
+
+/// Spreading the values of an expression as arguments `..<expr>`.
+#[derive(Debug, Clone)]
+pub struct ExprSpread {
+    /// The spread token.
+    pub dot_dot: ast::DotDot,
+    /// The expression being spread.
+    pub expr: Box<ast::Expr>,
+}
+
+impl Spanned for ExprSpread {
+    fn span(&self) -> Span {
+        self.dot_dot.span().join(self.expr.span())
+    }
+}
+
+/// Parse a spread expression.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::ExprSpread>("..args").unwrap();
+/// parse_all::<ast::ExprSpread>("..[1, 2, 3]").unwrap();
+/// ```
+impl Parse for ExprSpread {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            dot_dot: parser.parse()?,
+            expr: Box::new(parser.parse()?),
+        })
+    }
+}
+
+impl IntoTokens for ExprSpread {
+    fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
+        self.dot_dot.into_tokens(context, stream);
+        self.expr.into_tokens(context, stream);
+    }
+}
@@ -7,30 +7,90 @@ use runestick::Span;
 pub enum FnArg {
     /// The `self` parameter.
     Self_(ast::Self_),
-    /// Ignoring the argument with `_`.
-    Ignore(ast::Underscore),
-    /// Binding the argument to an ident.
-    Ident(ast::Ident),
+    /// Ignoring the argument with `_`, with an optional type annotation.
+    Ignore(ast::Underscore, Option<(ast::Colon, ast::Type)>),
+    /// Binding the argument to an ident, with an optional type annotation and
+    /// default value.
+    Ident(
+        ast::Ident,
+        Option<(ast::Colon, ast::Type)>,
+        Option<(ast::Eq, Box<ast::Expr>)>,
+    ),
+    /// Collecting the remaining arguments into a vector with `..rest`.
+    Rest(ast::DotDot, ast::Ident),
+}
+
+impl FnArg {
+    /// Get the type annotation of the argument, if present.
+    pub fn ty(&self) -> Option<&ast::Type> {
+        match self {
+            Self::Self_(..) | Self::Rest(..) => None,
+            Self::Ignore(_, ty) | Self::Ident(_, ty, _) => ty.as_ref().map(|(_, ty)| ty),
+        }
+    }
+
+    /// Get the default value of the argument, if present.
+    pub fn default_value(&self) -> Option<&ast::Expr> {
+        match self {
+            Self::Ident(_, _, Some((_, expr))) => Some(&**expr),
+            _ => None,
+        }
+    }
+
+    /// Test if this argument collects the remaining arguments, like `..rest`.
+    pub fn is_rest(&self) -> bool {
+        matches!(self, Self::Rest(..))
+    }
 }
 
 impl Spanned for FnArg {
     fn span(&self) -> Span {
         match self {
             Self::Self_(s) => s.span(),
-            Self::Ignore(ignore) => ignore.span(),
-            Self::Ident(ident) => ident.span(),
+            Self::Ignore(ignore, ty) => match ty {
+                Some((_, ty)) => ignore.span().join(ty.span()),
+                None => ignore.span(),
+            },
+            Self::Ident(ident, ty, default) => match (ty, default) {
+                (_, Some((_, expr))) => ident.span().join(expr.span()),
+                (Some((_, ty)), None) => ident.span().join(ty.span()),
+                (None, None) => ident.span(),
+            },
+            Self::Rest(dot_dot, ident) => dot_dot.span().join(ident.span()),
         }
     }
 }
 
+/// Parse implementation for a function argument.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::FnArg>("self").unwrap();
+/// parse_all::<ast::FnArg>("_").unwrap();
+/// parse_all::<ast::FnArg>("value").unwrap();
+///
+/// let arg = parse_all::<ast::FnArg>("value: int").unwrap();
+/// assert!(arg.ty().is_some());
+///
+/// let arg = parse_all::<ast::FnArg>("port: int = 80").unwrap();
+/// assert!(arg.ty().is_some());
+/// assert!(arg.default_value().is_some());
+///
+/// let arg = parse_all::<ast::FnArg>("..rest").unwrap();
+/// assert!(arg.is_rest());
+/// ```
 impl Parse for FnArg {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
         let token = parser.token_peek_eof()?;
 
         Ok(match token.kind {
             ast::Kind::Self_ => Self::Self_(parser.parse()?),
-            ast::Kind::Underscore => Self::Ignore(parser.parse()?),
-            ast::Kind::Ident(..) => Self::Ident(parser.parse()?),
+            ast::Kind::Underscore => Self::Ignore(parser.parse()?, parser.parse()?),
+            ast::Kind::Ident(..) => Self::Ident(parser.parse()?, parser.parse()?, parser.parse()?),
+            ast::Kind::DotDot => Self::Rest(parser.parse()?, parser.parse()?),
             _ => {
                 return Err(ParseError::new(
                     token,
@@ -45,8 +105,19 @@ impl IntoTokens for FnArg {
     fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
         match self {
             Self::Self_(s) => s.into_tokens(context, stream),
-            Self::Ignore(ignore) => ignore.into_tokens(context, stream),
-            Self::Ident(ident) => ident.into_tokens(context, stream),
+            Self::Ignore(ignore, ty) => {
+                ignore.into_tokens(context, stream);
+                ty.into_tokens(context, stream);
+            }
+            Self::Ident(ident, ty, default) => {
+                ident.into_tokens(context, stream);
+                ty.into_tokens(context, stream);
+                default.into_tokens(context, stream);
+            }
+            Self::Rest(dot_dot, ident) => {
+                dot_dot.into_tokens(context, stream);
+                ident.into_tokens(context, stream);
+            }
         }
     }
 }
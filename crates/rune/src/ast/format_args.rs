@@ -0,0 +1,300 @@
+use crate::ast;
+use crate::{Parse, ParseError, ParseErrorKind, Parser, Resolve, Spanned, Storage};
+use runestick::{FormatSpec, Source, Span};
+use std::borrow::Cow;
+
+/// The arguments to the built-in `format!`, `print!`, and `println!` macros.
+///
+/// This is a format string followed by positional and named arguments, like
+/// `"{} is {age:>3}", name, age = 42`.
+#[derive(Debug, Clone)]
+pub struct FormatArgs {
+    /// The token corresponding to the format string.
+    token: ast::Token,
+    /// The source string of the format string.
+    source: ast::LitStrSource,
+    /// The arguments to the format string.
+    pub args: Vec<(ast::Comma, FormatArg)>,
+}
+
+into_tokens!(FormatArgs { token, args });
+
+impl FormatArgs {
+    /// Iterate over the expressions of all arguments.
+    pub fn exprs(&self) -> impl Iterator<Item = &ast::Expr> {
+        self.args.iter().map(|(_, arg)| arg.expr())
+    }
+}
+
+impl Spanned for FormatArgs {
+    fn span(&self) -> Span {
+        match self.args.last() {
+            Some((_, arg)) => self.token.span().join(arg.span()),
+            None => self.token.span(),
+        }
+    }
+}
+
+/// A single argument to a format string.
+#[derive(Debug, Clone)]
+pub enum FormatArg {
+    /// A positional argument, like `value`.
+    Positional(ast::Expr),
+    /// A named argument, like `name = value`.
+    Named(ast::Ident, ast::Eq, ast::Expr),
+}
+
+impl FormatArg {
+    /// Access the expression of the argument.
+    pub fn expr(&self) -> &ast::Expr {
+        match self {
+            Self::Positional(expr) => expr,
+            Self::Named(_, _, expr) => expr,
+        }
+    }
+}
+
+impl crate::IntoTokens for FormatArg {
+    fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
+        match self {
+            Self::Positional(expr) => expr.into_tokens(context, stream),
+            Self::Named(name, eq, expr) => {
+                name.into_tokens(context, stream);
+                eq.into_tokens(context, stream);
+                expr.into_tokens(context, stream);
+            }
+        }
+    }
+}
+
+impl Spanned for FormatArg {
+    fn span(&self) -> Span {
+        match self {
+            Self::Positional(expr) => expr.span(),
+            Self::Named(name, _, expr) => name.span().join(expr.span()),
+        }
+    }
+}
+
+/// A component of a resolved format string.
+#[derive(Debug)]
+pub enum FormatComponent {
+    /// A literal string.
+    String(String),
+    /// An argument to format, with an optional format specification.
+    ///
+    /// Arguments are indexed in the order they are passed to the macro,
+    /// followed by the expressions captured by the format string.
+    Arg(usize, Option<FormatSpec>),
+}
+
+/// A resolved format string.
+#[derive(Debug)]
+pub struct Format {
+    pub(crate) size_hint: usize,
+    pub(crate) components: Vec<FormatComponent>,
+    /// Expressions captured from the format string itself, like `{value}`.
+    pub(crate) captures: Vec<ast::Expr>,
+}
+
+impl<'a> Resolve<'a> for FormatArgs {
+    type Output = Format;
+
+    fn resolve(&self, storage: &Storage, source: &'a Source) -> Result<Self::Output, ParseError> {
+        let span = self.token.span();
+
+        let (span, text, escaped) = match self.source {
+            ast::LitStrSource::Text(text) => {
+                let span = span.narrow(1);
+
+                let string = source
+                    .source(span)
+                    .ok_or_else(|| ParseError::new(span, ParseErrorKind::BadSlice))?;
+
+                (span, Cow::Borrowed(string), text.escaped)
+            }
+            ast::LitStrSource::Synthetic(id) => {
+                let string = storage.get_string(id).ok_or_else(|| {
+                    ParseError::new(
+                        span,
+                        ParseErrorKind::BadSyntheticId {
+                            kind: "format string",
+                            id,
+                        },
+                    )
+                })?;
+
+                (Span::new(0, string.len()), Cow::Owned(string), false)
+            }
+        };
+
+        let mut named = Vec::new();
+
+        for (index, (_, arg)) in self.args.iter().enumerate() {
+            if let FormatArg::Named(name, _, _) = arg {
+                named.push((name.resolve(storage, source)?, index));
+            }
+        }
+
+        let mut it = text
+            .char_indices()
+            .map(|(n, c)| (span.start + n, c))
+            .peekable();
+
+        let mut size_hint = 0;
+        let mut buf = String::new();
+        let mut components = Vec::new();
+        let mut captures = Vec::new();
+        let mut used = vec![false; self.args.len()];
+        let mut next = 0;
+
+        while let Some((n, c)) = it.next() {
+            match c {
+                '\\' if escaped => {
+                    let c = ast::utils::parse_char_escape(
+                        span.with_start(n),
+                        &mut it,
+                        ast::utils::WithBrace(false),
+                    )?;
+                    buf.push(c);
+                }
+                '{' if it.next_if(|(_, c)| *c == '{').is_some() => {
+                    buf.push('{');
+                }
+                '}' if it.next_if(|(_, c)| *c == '}').is_some() => {
+                    buf.push('}');
+                }
+                '}' => {
+                    return Err(ParseError::new(
+                        Span::new(n, n + 1),
+                        ParseErrorKind::UnexpectedFormatCloseBrace,
+                    ));
+                }
+                '{' => {
+                    if !buf.is_empty() {
+                        size_hint += buf.len();
+                        components.push(FormatComponent::String(std::mem::take(&mut buf)));
+                    }
+
+                    let arg_span = ast::utils::template_expr(span, &mut it)?;
+
+                    let arg = text
+                        .get(arg_span.start - span.start..arg_span.end - span.start)
+                        .ok_or_else(|| ParseError::new(arg_span, ParseErrorKind::BadSlice))?;
+
+                    let (len, spec) = ast::utils::template_spec(arg_span, arg)?;
+                    let arg_span = Span::new(arg_span.start, arg_span.start + len);
+                    let arg = arg[..len].trim();
+
+                    let explicit = if arg.is_empty() {
+                        next += 1;
+                        Some(next - 1)
+                    } else if let Ok(index) = arg.parse::<usize>() {
+                        Some(index)
+                    } else {
+                        named
+                            .iter()
+                            .find(|(name, _)| name == arg)
+                            .map(|(_, index)| *index)
+                    };
+
+                    let index = match explicit {
+                        Some(index) => match used.get_mut(index) {
+                            Some(used) => {
+                                *used = true;
+                                index
+                            }
+                            None => {
+                                return Err(ParseError::new(
+                                    arg_span,
+                                    ParseErrorKind::MissingFormatArgument { index },
+                                ));
+                            }
+                        },
+                        None => {
+                            let source = &source.as_str()[..arg_span.end];
+                            let mut parser = Parser::new_with_start(source, arg_span.start);
+                            captures.push(ast::Expr::parse(&mut parser)?);
+                            self.args.len() + captures.len() - 1
+                        }
+                    };
+
+                    components.push(FormatComponent::Arg(index, spec));
+                }
+                c => {
+                    buf.push(c);
+                }
+            }
+        }
+
+        if !buf.is_empty() {
+            size_hint += buf.len();
+            components.push(FormatComponent::String(buf));
+        }
+
+        for ((_, arg), used) in self.args.iter().zip(used) {
+            if !used {
+                return Err(ParseError::new(
+                    arg.span(),
+                    ParseErrorKind::UnusedFormatArgument,
+                ));
+            }
+        }
+
+        Ok(Format {
+            size_hint,
+            components,
+            captures,
+        })
+    }
+}
+
+/// Parse the arguments to a format macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::FormatArgs>("\"hello world\"").unwrap();
+/// parse_all::<ast::FormatArgs>("\"{} and {name:>8}\", a, name = b,").unwrap();
+/// ```
+impl Parse for FormatArgs {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let token = parser.token_next()?;
+
+        let source = match token.kind {
+            ast::Kind::LitStr(source) => source,
+            _ => {
+                return Err(ParseError::new(
+                    token,
+                    ParseErrorKind::ExpectedString { actual: token.kind },
+                ))
+            }
+        };
+
+        let mut args = Vec::new();
+
+        while parser.peek::<ast::Comma>()? {
+            let comma = parser.parse()?;
+
+            if parser.is_eof()? {
+                break;
+            }
+
+            let arg = if parser.peek::<ast::Ident>()? && parser.peek2::<ast::Eq>()? {
+                FormatArg::Named(parser.parse()?, parser.parse()?, parser.parse()?)
+            } else {
+                FormatArg::Positional(parser.parse()?)
+            };
+
+            args.push((comma, arg));
+        }
+
+        Ok(Self {
+            token,
+            source,
+            args,
+        })
+    }
+}
@@ -17,6 +17,8 @@ impl_enum_ast! {
         ItemImpl(ast::ItemImpl),
         /// A module declaration.
         ItemMod(ast::ItemMod),
+        /// A const declaration.
+        ItemConst(ast::ItemConst),
         /// A macro call expanding into an item.
         MacroCall(ast::MacroCall),
     }
@@ -51,6 +53,7 @@ impl Item {
             }
             ast::Kind::Fn => true,
             ast::Kind::Mod => true,
+            ast::Kind::Const => true,
             _ => false,
         })
     }
@@ -79,6 +82,9 @@ impl Item {
             ast::Kind::Mod => {
                 Self::ItemMod(ast::ItemMod::parse_with_attributes(parser, attributes)?)
             }
+            ast::Kind::Const => {
+                Self::ItemConst(ast::ItemConst::parse_with_attributes(parser, attributes)?)
+            }
             ast::Kind::Ident(..) => Self::MacroCall(parser.parse()?),
             _ => {
                 return Err(ParseError::new(
@@ -111,6 +117,7 @@ impl Peek for Item {
             }
             ast::Kind::Fn => true,
             ast::Kind::Mod => true,
+            ast::Kind::Const => true,
             ast::Kind::Ident(..) => true,
             _ => ast::Attribute::peek(t1, t2),
         }
@@ -141,6 +148,9 @@ impl Parse for Item {
             ast::Kind::Mod => {
                 Self::ItemMod(ast::ItemMod::parse_with_attributes(parser, attributes)?)
             }
+            ast::Kind::Const => {
+                Self::ItemConst(ast::ItemConst::parse_with_attributes(parser, attributes)?)
+            }
             ast::Kind::Ident(..) => Self::MacroCall(parser.parse()?),
             _ => {
                 return Err(ParseError::new(
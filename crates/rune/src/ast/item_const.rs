@@ -0,0 +1,74 @@
+use crate::ast;
+use crate::{Parse, ParseError, Parser, Spanned};
+use runestick::Span;
+
+/// A const declaration.
+#[derive(Debug, Clone)]
+pub struct ItemConst {
+    /// The attributes of the const.
+    pub attributes: Vec<ast::Attribute>,
+    /// The `const` keyword.
+    pub const_: ast::Const,
+    /// The name of the constant.
+    pub name: ast::Ident,
+    /// The equals token.
+    pub eq: ast::Eq,
+    /// The expression that the constant is evaluated from.
+    pub expr: Box<ast::Expr>,
+    /// Terminating semicolon.
+    pub semi: ast::SemiColon,
+}
+
+into_tokens!(ItemConst {
+    attributes,
+    const_,
+    name,
+    eq,
+    expr,
+    semi,
+});
+
+impl ItemConst {
+    /// Parse a `const` item with the given attributes
+    pub fn parse_with_attributes(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self {
+            attributes,
+            const_: parser.parse()?,
+            name: parser.parse()?,
+            eq: parser.parse()?,
+            expr: Box::new(parser.parse()?),
+            semi: parser.parse()?,
+        })
+    }
+}
+
+impl Spanned for ItemConst {
+    fn span(&self) -> Span {
+        if let Some(first) = self.attributes.first() {
+            first.span().join(self.semi.span())
+        } else {
+            self.const_.span().join(self.semi.span())
+        }
+    }
+}
+
+/// Parse implementation for a const.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::ItemConst>("const ANSWER = 42;").unwrap();
+/// parse_all::<ast::ItemConst>("const GREETING = `hello {NAME}`;").unwrap();
+/// parse_all::<ast::ItemConst>("const LIMITS = #{ min: 0, max: MAX * 2 };").unwrap();
+/// ```
+impl Parse for ItemConst {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let attributes = parser.parse()?;
+        Self::parse_with_attributes(parser, attributes)
+    }
+}
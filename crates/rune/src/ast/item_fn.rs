@@ -15,6 +15,8 @@ pub struct ItemFn {
     pub name: ast::Ident,
     /// The arguments of the function.
     pub args: ast::Parenthesized<ast::FnArg, ast::Comma>,
+    /// The optional return type annotation of the function.
+    pub output: Option<(ast::Arrow, ast::Type)>,
     /// The body of the function.
     pub body: ast::Block,
 }
@@ -45,6 +47,7 @@ impl ItemFn {
             fn_: parser.parse()?,
             name: parser.parse()?,
             args: parser.parse()?,
+            output: parser.parse()?,
             body: parser.parse()?,
         })
     }
@@ -93,6 +96,10 @@ impl Peek for ItemFn {
 /// assert_eq!(item.args.items.len(), 2);
 /// assert_eq!(item.attributes.len(), 1);
 ///
+/// let item = parse_all::<ast::ItemFn>("fn hello(foo: int, bar) -> String {}").unwrap();
+/// assert!(item.args.items[0].0.ty().is_some());
+/// assert!(item.args.items[1].0.ty().is_none());
+/// assert!(item.output.is_some());
 /// ```
 impl Parse for ItemFn {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -107,6 +114,7 @@ impl IntoTokens for ItemFn {
         self.fn_.into_tokens(context, stream);
         self.name.into_tokens(context, stream);
         self.args.into_tokens(context, stream);
+        self.output.into_tokens(context, stream);
         self.body.into_tokens(context, stream);
     }
 }
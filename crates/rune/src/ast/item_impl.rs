@@ -13,6 +13,8 @@ pub struct ItemImpl {
     pub path: ast::Path,
     /// The open brace.
     pub open: ast::OpenBrace,
+    /// The collection of associated constants.
+    pub consts: Vec<ast::ItemConst>,
     /// The collection of functions.
     pub functions: Vec<ast::ItemFn>,
     /// The close brace.
@@ -29,10 +31,17 @@ impl ItemImpl {
         let path = parser.parse()?;
         let open = parser.parse()?;
 
+        let mut consts = vec![];
         let mut functions = vec![];
+
         while !parser.peek::<ast::CloseBrace>()? {
             let attributes = parser.parse()?;
-            functions.push(ast::ItemFn::parse_with_attributes(parser, attributes)?);
+
+            if parser.peek::<ast::Const>()? {
+                consts.push(ast::ItemConst::parse_with_attributes(parser, attributes)?);
+            } else {
+                functions.push(ast::ItemFn::parse_with_attributes(parser, attributes)?);
+            }
         }
 
         let close = parser.parse()?;
@@ -42,6 +51,7 @@ impl ItemImpl {
             impl_,
             path,
             open,
+            consts,
             functions,
             close,
         })
@@ -69,6 +79,7 @@ impl Spanned for ItemImpl {
 /// parse_all::<ast::ItemImpl>("impl Foo { fn test(self) { } }").unwrap();
 /// parse_all::<ast::ItemImpl>("#[variant(enum_= \"SuperHero\", x = \"1\")] impl Foo { fn test(self) { } }").unwrap();
 /// parse_all::<ast::ItemImpl>("#[xyz] impl Foo { #[jit] fn test(self) { } }").unwrap();
+/// parse_all::<ast::ItemImpl>("impl Foo { const ORIGIN = (0, 0); fn origin() { Foo::ORIGIN } }").unwrap();
 /// ```
 impl Parse for ItemImpl {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
@@ -82,6 +93,7 @@ impl IntoTokens for ItemImpl {
         self.impl_.into_tokens(context, stream);
         self.path.into_tokens(context, stream);
         self.open.into_tokens(context, stream);
+        self.consts.into_tokens(context, stream);
         self.functions.into_tokens(context, stream);
         self.close.into_tokens(context, stream);
     }
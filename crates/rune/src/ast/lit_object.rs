@@ -179,6 +179,9 @@ pub struct LitObject {
     pub open: ast::OpenBrace,
     /// Items in the object declaration.
     pub assignments: Vec<LitObjectFieldAssign>,
+    /// The base expression of a struct update, like `..other` in
+    /// `Foo { a: 1, ..other }`.
+    pub rest: Option<(ast::DotDot, Box<ast::Expr>)>,
     /// The close bracket.
     pub close: ast::CloseBrace,
     /// Indicates if the object is completely literal and cannot have side
@@ -201,9 +204,16 @@ impl LitObject {
 
         let mut assignments = Vec::new();
 
+        let mut rest = None;
         let mut is_const = true;
 
         while !parser.peek::<ast::CloseBrace>()? {
+            if parser.peek::<ast::DotDot>()? {
+                rest = Some((parser.parse()?, Box::new(parser.parse()?)));
+                is_const = false;
+                break;
+            }
+
             let assign = parser.parse::<LitObjectFieldAssign>()?;
 
             if !assign.is_const() {
@@ -225,6 +235,7 @@ impl LitObject {
             ident,
             open,
             assignments,
+            rest,
             close,
             is_const,
         })
@@ -247,6 +258,9 @@ impl Spanned for LitObject {
 /// parse_all::<ast::LitObject>("Foo {\"foo\": 42}").unwrap();
 /// parse_all::<ast::LitObject>("#{\"foo\": 42}").unwrap();
 /// parse_all::<ast::LitObject>("#{\"foo\": 42,}").unwrap();
+///
+/// let lit_object = parse_all::<ast::LitObject>("Foo { a: 1, ..other }").unwrap();
+/// assert!(lit_object.rest.is_some());
 /// ```
 impl Parse for LitObject {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
@@ -264,6 +278,7 @@ impl IntoTokens for LitObject {
             assign.into_tokens(context, stream);
         }
 
+        self.rest.into_tokens(context, stream);
         self.close.into_tokens(context, stream);
     }
 }
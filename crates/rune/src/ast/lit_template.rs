@@ -1,6 +1,6 @@
 use crate::ast;
 use crate::{Parse, ParseError, ParseErrorKind, Parser, Resolve, Spanned, Storage};
-use runestick::{Source, Span};
+use runestick::{FormatSpec, Source, Span};
 use std::borrow::Cow;
 
 /// A string literal.
@@ -25,8 +25,9 @@ impl Spanned for LitTemplate {
 pub enum TemplateComponent {
     /// A literal string.
     String(String),
-    /// An expression inside of the template. Like `{1 + 2}`.
-    Expr(Box<ast::Expr>),
+    /// An expression inside of the template. Like `{1 + 2}`, with an optional
+    /// format specification like in `{value:>8}`.
+    Expr(Box<ast::Expr>, Option<FormatSpec>),
 }
 
 /// A resolved and parsed string template.
@@ -96,12 +97,20 @@ impl<'a> Resolve<'a> for LitTemplate {
                         buf.clear();
                     }
 
-                    let span = ast::utils::template_expr(span, &mut it)?;
-                    let source = &source.as_str()[..span.end];
+                    let expr_span = ast::utils::template_expr(span, &mut it)?;
 
-                    let mut parser = Parser::new_with_start(source, span.start);
+                    let expr = text
+                        .get(expr_span.start - span.start..expr_span.end - span.start)
+                        .ok_or_else(|| ParseError::new(expr_span, ParseErrorKind::BadSlice))?;
+
+                    let (len, spec) = ast::utils::template_spec(expr_span, expr)?;
+                    let expr_span = Span::new(expr_span.start, expr_span.start + len);
+
+                    let source = &source.as_str()[..expr_span.end];
+
+                    let mut parser = Parser::new_with_start(source, expr_span.start);
                     let expr = ast::Expr::parse(&mut parser)?;
-                    components.push(TemplateComponent::Expr(Box::new(expr)));
+                    components.push(TemplateComponent::Expr(Box::new(expr), spec));
                     has_expansions = true;
                 }
                 c => {
@@ -30,14 +30,17 @@ mod expr_loop;
 mod expr_match;
 mod expr_return;
 mod expr_select;
+mod expr_spread;
 mod expr_try;
 mod expr_unary;
 mod expr_while;
 mod expr_yield;
 mod file;
 mod fn_arg;
+mod format_args;
 mod ident;
 mod item;
+mod item_const;
 mod item_enum;
 mod item_fn;
 mod item_impl;
@@ -60,18 +63,22 @@ mod lit_vec;
 mod macro_call;
 mod parenthesized;
 mod pat;
+mod pat_binding;
 mod pat_object;
+mod pat_or;
 mod pat_path;
+mod pat_range;
 mod pat_tuple;
 mod pat_vec;
 mod path;
 mod stmt;
 mod token;
+mod type_;
 pub(super) mod utils;
 
 pub use self::attribute::Attribute;
 pub use self::block::Block;
-pub use self::condition::Condition;
+pub use self::condition::{Condition, ConditionChain};
 pub use self::expr::Expr;
 pub use self::expr_async::ExprAsync;
 pub use self::expr_await::ExprAwait;
@@ -96,14 +103,17 @@ pub use self::expr_loop::ExprLoop;
 pub use self::expr_match::{ExprMatch, ExprMatchBranch};
 pub use self::expr_return::ExprReturn;
 pub use self::expr_select::ExprSelect;
+pub use self::expr_spread::ExprSpread;
 pub use self::expr_try::ExprTry;
 pub use self::expr_unary::{ExprUnary, UnaryOp};
 pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::file::File;
 pub use self::fn_arg::FnArg;
+pub use self::format_args::{Format, FormatArg, FormatArgs, FormatComponent};
 pub use self::ident::Ident;
 pub use self::item::Item;
+pub use self::item_const::ItemConst;
 pub use self::item_enum::{ItemEnum, ItemVariant, ItemVariantBody};
 pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
@@ -128,8 +138,11 @@ pub use self::lit_vec::LitVec;
 pub use self::macro_call::MacroCall;
 pub use self::parenthesized::Parenthesized;
 pub use self::pat::Pat;
+pub use self::pat_binding::PatBinding;
 pub use self::pat_object::{PatObject, PatObjectItem};
+pub use self::pat_or::PatOr;
 pub use self::pat_path::PatPath;
+pub use self::pat_range::{PatRange, PatRangeLimits};
 pub use self::pat_tuple::PatTuple;
 pub use self::pat_vec::PatVec;
 pub use self::path::Path;
@@ -138,6 +151,7 @@ pub use self::token::{
     CopySource, Delimiter, Kind, LitByteStrSource, LitByteStrSourceText, LitStrSource,
     LitStrSourceText, Number, NumberBase, NumberSource, NumberSourceText, StringSource, Token,
 };
+pub use self::type_::Type;
 
 macro_rules! decl_tokens {
     ($(($parser:ident, $doc:expr, $($kind:tt)*),)*) => {
@@ -222,10 +236,14 @@ decl_tokens! {
     (Yield, "The `yield` keyword.", Kind::Yield),
     (Return, "The `return` keyword.", Kind::Return),
     (Rocket, "The rocket `=>`.", Kind::Rocket),
+    (Arrow, "The arrow `->`.", Kind::Arrow),
     (Hash, "The hash `#`.", Kind::Pound),
     (DotDot, "Two dots `..`.", Kind::DotDot),
+    (DotDotEq, "Two dots and an equals `..=`.", Kind::DotDotEq),
+    (At, "The at sign `@`.", Kind::At),
     (Await, "The `await` keyword.", Kind::Await),
     (Async, "The `async` keyword.", Kind::Async),
+    (Move, "The `move` keyword.", Kind::Move),
     (Select, "The `select` keyword.", Kind::Select),
     (Default, "The `default` keyword.", Kind::Default),
     (Try, "The `?` operator.", Kind::QuestionMark),
@@ -236,6 +254,7 @@ decl_tokens! {
     (Mul, "Multiply `*` operator.", Kind::Star),
     (Mod, "The `mod` keyword.", Kind::Mod),
     (Bang, "The `!` operator.", Kind::Bang),
+    (Const, "The `const` keyword.", Kind::Const),
 }
 
 #[cfg(test)]
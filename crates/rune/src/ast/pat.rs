@@ -24,6 +24,14 @@ impl_enum_ast! {
         PatTuple(ast::PatTuple),
         /// An object pattern.
         PatObject(ast::PatObject),
+        /// An or-pattern `A | B`.
+        PatOr(ast::PatOr),
+        /// A binding pattern `name @ pat`.
+        PatBinding(ast::PatBinding),
+        /// A literal range pattern `1..=9`.
+        PatRange(ast::PatRange),
+        /// A rest pattern `..` in a vector.
+        PatRest(ast::DotDot),
     }
 }
 
@@ -46,28 +54,21 @@ impl Pat {
 
                 Self::PatObject(ast::PatObject::parse_with_ident(parser, ident)?)
             }
+            ast::Kind::At => match path.try_as_ident() {
+                Some(ident) => Self::PatBinding(ast::PatBinding::parse_with_ident(parser, *ident)?),
+                None => {
+                    return Err(ParseError::new(
+                        t,
+                        ParseErrorKind::ExpectedPatError { actual: t.kind },
+                    ));
+                }
+            },
             _ => Self::PatPath(ast::PatPath { path }),
         })
     }
-}
 
-/// Parsing a block expression.
-///
-/// # Examples
-///
-/// ```rust
-/// use rune::{parse_all, ast};
-///
-/// parse_all::<ast::Pat>("()").unwrap();
-/// parse_all::<ast::Pat>("1").unwrap();
-/// parse_all::<ast::Pat>("'a'").unwrap();
-/// parse_all::<ast::Pat>("\"hello world\"").unwrap();
-/// parse_all::<ast::Pat>("var").unwrap();
-/// parse_all::<ast::Pat>("_").unwrap();
-/// parse_all::<ast::Pat>("Foo(n)").unwrap();
-/// ```
-impl Parse for Pat {
-    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+    /// Parse a pattern without alternatives.
+    pub fn parse_primary(parser: &mut Parser) -> Result<Self, ParseError> {
         let token = parser.token_peek_eof()?;
 
         Ok(match token.kind {
@@ -80,11 +81,18 @@ impl Parse for Pat {
             }
             ast::Kind::Open(ast::Delimiter::Bracket) => Self::PatVec(parser.parse()?),
             ast::Kind::Pound => Self::PatObject(parser.parse()?),
-            ast::Kind::LitByte { .. } => Self::PatByte(parser.parse()?),
-            ast::Kind::LitChar { .. } => Self::PatChar(parser.parse()?),
-            ast::Kind::LitNumber { .. } => Self::PatNumber(parser.parse()?),
+            ast::Kind::LitByte { .. } | ast::Kind::LitChar { .. } | ast::Kind::LitNumber { .. } => {
+                let start = Self::parse_literal(parser)?;
+
+                if parser.peek::<ast::DotDot>()? || parser.peek::<ast::DotDotEq>()? {
+                    Self::PatRange(ast::PatRange::parse_with_start(parser, start)?)
+                } else {
+                    start
+                }
+            }
             ast::Kind::LitStr { .. } => Self::PatString(parser.parse()?),
             ast::Kind::Underscore => Self::PatIgnore(parser.parse()?),
+            ast::Kind::DotDot => Self::PatRest(parser.parse()?),
             ast::Kind::Ident(..) => Self::parse_ident(parser)?,
             _ => {
                 return Err(ParseError::new(
@@ -94,6 +102,55 @@ impl Parse for Pat {
             }
         })
     }
+
+    /// Parse a literal pattern which can be used in a range.
+    pub fn parse_literal(parser: &mut Parser) -> Result<Self, ParseError> {
+        let token = parser.token_peek_eof()?;
+
+        Ok(match token.kind {
+            ast::Kind::LitByte { .. } => Self::PatByte(parser.parse()?),
+            ast::Kind::LitChar { .. } => Self::PatChar(parser.parse()?),
+            ast::Kind::LitNumber { .. } => Self::PatNumber(parser.parse()?),
+            _ => {
+                return Err(ParseError::new(
+                    token,
+                    ParseErrorKind::ExpectedPatError { actual: token.kind },
+                ));
+            }
+        })
+    }
+}
+
+/// Parsing a block expression.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::Pat>("()").unwrap();
+/// parse_all::<ast::Pat>("1").unwrap();
+/// parse_all::<ast::Pat>("'a'").unwrap();
+/// parse_all::<ast::Pat>("\"hello world\"").unwrap();
+/// parse_all::<ast::Pat>("var").unwrap();
+/// parse_all::<ast::Pat>("_").unwrap();
+/// parse_all::<ast::Pat>("Foo(n)").unwrap();
+/// parse_all::<ast::Pat>("1 | 2 | 3").unwrap();
+/// parse_all::<ast::Pat>("n @ Foo(..)").unwrap();
+/// parse_all::<ast::Pat>("1..=9").unwrap();
+/// parse_all::<ast::Pat>("'a'..'z'").unwrap();
+/// parse_all::<ast::Pat>("[first, .., last]").unwrap();
+/// ```
+impl Parse for Pat {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let first = Self::parse_primary(parser)?;
+
+        if parser.peek::<ast::Pipe>()? {
+            return Ok(Self::PatOr(ast::PatOr::parse_with_first(parser, first)?));
+        }
+
+        Ok(first)
+    }
 }
 
 impl Peek for Pat {
@@ -0,0 +1,36 @@
+use crate::ast;
+use crate::{ParseError, Parser, Spanned};
+use runestick::Span;
+
+/// A pattern which binds the matched value to a name `<ident> @ <pat>`.
+#[derive(Debug, Clone)]
+pub struct PatBinding {
+    /// The name the value is bound to.
+    pub ident: ast::Ident,
+    /// The `@` token.
+    pub at: ast::At,
+    /// The pattern the value must match.
+    pub pat: Box<ast::Pat>,
+}
+
+into_tokens!(PatBinding { ident, at, pat });
+
+impl PatBinding {
+    /// Parse a binding pattern with a known preceeding identifier.
+    pub fn parse_with_ident(
+        parser: &mut Parser<'_>,
+        ident: ast::Ident,
+    ) -> Result<Self, ParseError> {
+        Ok(Self {
+            ident,
+            at: parser.parse()?,
+            pat: Box::new(ast::Pat::parse_primary(parser)?),
+        })
+    }
+}
+
+impl Spanned for PatBinding {
+    fn span(&self) -> Span {
+        self.ident.span().join(self.pat.span())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::ast;
+use crate::{IntoTokens, ParseError, Parser, Spanned};
+use runestick::Span;
+
+/// An or-pattern `<pat> | <pat>`, which matches if any of its alternatives
+/// match.
+#[derive(Debug, Clone)]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<ast::Pat>,
+    /// The rest of the alternatives.
+    pub rest: Vec<(ast::Pipe, Box<ast::Pat>)>,
+}
+
+impl PatOr {
+    /// Parse the rest of an or-pattern with a known first alternative.
+    pub fn parse_with_first(parser: &mut Parser<'_>, first: ast::Pat) -> Result<Self, ParseError> {
+        let mut rest = Vec::new();
+
+        while parser.peek::<ast::Pipe>()? {
+            let pipe = parser.parse()?;
+            rest.push((pipe, Box::new(ast::Pat::parse_primary(parser)?)));
+        }
+
+        Ok(Self {
+            first: Box::new(first),
+            rest,
+        })
+    }
+
+    /// Iterate over all alternatives of the pattern.
+    pub fn alternatives(&self) -> impl Iterator<Item = &ast::Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| &**pat))
+    }
+}
+
+impl Spanned for PatOr {
+    fn span(&self) -> Span {
+        match self.rest.last() {
+            Some((_, pat)) => self.first.span().join(pat.span()),
+            None => self.first.span(),
+        }
+    }
+}
+
+impl IntoTokens for PatOr {
+    fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
+        self.first.into_tokens(context, stream);
+        self.rest.into_tokens(context, stream);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::ast;
+use crate::{ParseError, ParseErrorKind, Parser, Spanned};
+use runestick::Span;
+
+/// A literal range pattern, like `1..=9` or `'a'..'z'`.
+#[derive(Debug, Clone)]
+pub struct PatRange {
+    /// The start of the range.
+    pub start: Box<ast::Pat>,
+    /// The limits of the range.
+    pub limits: PatRangeLimits,
+    /// The end of the range.
+    pub end: Box<ast::Pat>,
+}
+
+into_tokens!(PatRange { start, limits, end });
+
+impl PatRange {
+    /// Parse the rest of a range pattern with a known start.
+    pub fn parse_with_start(parser: &mut Parser<'_>, start: ast::Pat) -> Result<Self, ParseError> {
+        let token = parser.token_peek_eof()?;
+
+        let limits = match token.kind {
+            ast::Kind::DotDot => PatRangeLimits::HalfOpen(parser.parse()?),
+            ast::Kind::DotDotEq => PatRangeLimits::Closed(parser.parse()?),
+            actual => {
+                return Err(ParseError::new(
+                    token,
+                    ParseErrorKind::ExpectedPatError { actual },
+                ))
+            }
+        };
+
+        Ok(Self {
+            start: Box::new(start),
+            limits,
+            end: Box::new(ast::Pat::parse_literal(parser)?),
+        })
+    }
+
+    /// Test if the end of the range is inclusive.
+    pub fn is_inclusive(&self) -> bool {
+        matches!(self.limits, PatRangeLimits::Closed(..))
+    }
+}
+
+impl Spanned for PatRange {
+    fn span(&self) -> Span {
+        self.start.span().join(self.end.span())
+    }
+}
+
+impl_enum_ast! {
+    /// The limits of a range pattern.
+    pub enum PatRangeLimits {
+        /// A range which excludes its end `..`.
+        HalfOpen(ast::DotDot),
+        /// A range which includes its end `..=`.
+        Closed(ast::DotDotEq),
+    }
+}
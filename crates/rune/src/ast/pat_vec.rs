@@ -8,19 +8,15 @@ pub struct PatVec {
     /// The open bracket.
     pub open: ast::OpenBracket,
     /// The numbers matched against.
+    ///
+    /// At most one of these may be a rest pattern `..`, which matches any
+    /// number of items.
     pub items: Vec<(Box<ast::Pat>, Option<ast::Comma>)>,
-    /// Indicates if the pattern is open or not.
-    pub open_pattern: Option<ast::DotDot>,
     /// The close bracket.
     pub close: ast::CloseBracket,
 }
 
-into_tokens!(PatVec {
-    open,
-    items,
-    open_pattern,
-    close
-});
+into_tokens!(PatVec { open, items, close });
 
 impl Spanned for PatVec {
     fn span(&self) -> Span {
@@ -28,19 +24,27 @@ impl Spanned for PatVec {
     }
 }
 
+/// Parse a vector pattern.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::PatVec>("[]").unwrap();
+/// parse_all::<ast::PatVec>("[a, b]").unwrap();
+/// parse_all::<ast::PatVec>("[a, ..]").unwrap();
+/// parse_all::<ast::PatVec>("[first, .., last]").unwrap();
+/// ```
 impl Parse for PatVec {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
         let open = parser.parse()?;
         let mut items = Vec::new();
 
-        let mut is_open = true;
-
-        while !parser.peek::<ast::CloseBracket>()? && !parser.peek::<ast::DotDot>()? {
+        while !parser.peek::<ast::CloseBracket>()? {
             let pat = parser.parse()?;
 
-            is_open = parser.peek::<ast::Comma>()?;
-
-            if !is_open {
+            if !parser.peek::<ast::Comma>()? {
                 items.push((Box::new(pat), None));
                 break;
             }
@@ -48,19 +52,8 @@ impl Parse for PatVec {
             items.push((Box::new(pat), Some(parser.parse()?)));
         }
 
-        let open_pattern = if is_open && parser.peek::<ast::DotDot>()? {
-            Some(parser.parse()?)
-        } else {
-            None
-        };
-
         let close = parser.parse()?;
 
-        Ok(Self {
-            open,
-            items,
-            open_pattern,
-            close,
-        })
+        Ok(Self { open, items, close })
     }
 }
@@ -226,6 +226,7 @@ kinds! {
     Colon, "`:`.",
     ColonColon, "`::`.",
     Comma, "`,`.",
+    Const, "The `const` keyword.",
     Crate, "The `crate` keyword.",
     Dash, "`-`.",
     DashEq, "`-=`.",
@@ -235,6 +236,7 @@ kinds! {
     Dollar, "`$`.",
     Dot, "`.`.",
     DotDot, "`..`.",
+    DotDotEq, "`..=`.",
     Else, "The `else` keyword.",
     Enum, "The `enum` keyword.",
     Eq, "`=`.",
@@ -321,6 +323,7 @@ impl Kind {
             "await" => Self::Await,
             "become" => Self::Become,
             "break" => Self::Break,
+            "const" => Self::Const,
             "crate" => Self::Crate,
             "default" => Self::Default,
             "do" => Self::Do,
@@ -390,6 +393,7 @@ impl fmt::Display for Kind {
             Self::Colon => write!(f, ":")?,
             Self::ColonColon => write!(f, "::")?,
             Self::Comma => write!(f, ",")?,
+            Self::Const => write!(f, "const")?,
             Self::Crate => write!(f, "crate")?,
             Self::Dash => write!(f, "-")?,
             Self::DashEq => write!(f, "-=")?,
@@ -399,6 +403,7 @@ impl fmt::Display for Kind {
             Self::Dollar => write!(f, "$")?,
             Self::Dot => write!(f, ".")?,
             Self::DotDot => write!(f, "..")?,
+            Self::DotDotEq => write!(f, "..=")?,
             Self::Else => write!(f, "else")?,
             Self::Enum => write!(f, "enum")?,
             Self::Eq => write!(f, "=")?,
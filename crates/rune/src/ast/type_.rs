@@ -0,0 +1,69 @@
+use crate::ast;
+use crate::{IntoTokens, Parse, ParseError, ParseErrorKind, Parser, Peek, Spanned};
+use runestick::Span;
+
+/// A type annotation, like `int`, `String`, or `()`.
+///
+/// Annotations on function arguments and return values are checked at
+/// runtime, the rest only when type checking is enabled.
+#[derive(Debug, Clone)]
+pub enum Type {
+    /// The unit type `()`.
+    Unit(ast::LitUnit),
+    /// A named type, like `Vec` or `foo::Bar`.
+    Path(ast::Path),
+}
+
+impl Spanned for Type {
+    fn span(&self) -> Span {
+        match self {
+            Self::Unit(unit) => unit.span(),
+            Self::Path(path) => path.span(),
+        }
+    }
+}
+
+impl Peek for Type {
+    fn peek(t1: Option<ast::Token>, t2: Option<ast::Token>) -> bool {
+        ast::LitUnit::peek(t1, t2) || ast::Path::peek(t1, t2)
+    }
+}
+
+/// Parse implementation for a type annotation.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{parse_all, ast};
+///
+/// parse_all::<ast::Type>("()").unwrap();
+/// parse_all::<ast::Type>("int").unwrap();
+/// parse_all::<ast::Type>("foo::Bar").unwrap();
+/// ```
+impl Parse for Type {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        if parser.peek::<ast::LitUnit>()? {
+            return Ok(Self::Unit(parser.parse()?));
+        }
+
+        if parser.peek::<ast::Path>()? {
+            return Ok(Self::Path(parser.parse()?));
+        }
+
+        let token = parser.token_peek_eof()?;
+
+        Err(ParseError::new(
+            token,
+            ParseErrorKind::ExpectedType { actual: token.kind },
+        ))
+    }
+}
+
+impl IntoTokens for Type {
+    fn into_tokens(&self, context: &mut crate::MacroContext, stream: &mut crate::TokenStream) {
+        match self {
+            Self::Unit(unit) => unit.into_tokens(context, stream),
+            Self::Path(path) => path.into_tokens(context, stream),
+        }
+    }
+}
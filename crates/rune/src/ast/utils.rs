@@ -1,6 +1,6 @@
 use crate::ast;
 use crate::{ParseError, ParseErrorKind};
-use runestick::Span;
+use runestick::{FormatSpec, FormatSpecError, Span};
 use std::iter::Peekable;
 use std::ops;
 
@@ -223,6 +223,80 @@ where
     }
 }
 
+/// Split a format specification off of an expression inside of a template,
+/// like `>8` in `{value:>8}`.
+///
+/// The specification is separated by the first colon which is not nested,
+/// isn't part of a path like `a::b`, and is followed by a valid specification.
+///
+/// Returns the length of the expression and the specification, if present.
+/// Errors if the specification has a width or precision which is too large,
+/// where `span` is the span of the expression.
+pub(crate) fn template_spec(
+    span: Span,
+    expr: &str,
+) -> Result<(usize, Option<FormatSpec>), ParseError> {
+    let mut level = 0usize;
+    let mut it = expr.char_indices().peekable();
+
+    while let Some((n, c)) = it.next() {
+        match c {
+            '(' | '[' | '{' => level += 1,
+            ')' | ']' | '}' => level = level.saturating_sub(1),
+            '"' => {
+                while let Some((_, c)) = it.next() {
+                    match c {
+                        '\\' => {
+                            it.next();
+                        }
+                        '"' => break,
+                        _ => (),
+                    }
+                }
+            }
+            '\'' => {
+                // NB: only skip character literals, since this might also be
+                // a label.
+                let mut lookahead = it.clone().map(|(_, c)| c);
+
+                match (lookahead.next(), lookahead.next()) {
+                    (Some('\\'), _) => {
+                        for (_, c) in it.by_ref() {
+                            if c == '\'' {
+                                break;
+                            }
+                        }
+                    }
+                    (Some(_), Some('\'')) => {
+                        it.next();
+                        it.next();
+                    }
+                    _ => (),
+                }
+            }
+            ':' if level == 0 => {
+                if it.next_if(|(_, c)| *c == ':').is_some() {
+                    continue;
+                }
+
+                match FormatSpec::parse(&expr[n + 1..]) {
+                    Ok(spec) => return Ok((n, Some(spec))),
+                    Err(FormatSpecError::TooLarge) => {
+                        return Err(ParseError::new(
+                            span.with_start(span.start + n + 1),
+                            ParseErrorKind::FormatSpecTooLarge,
+                        ));
+                    }
+                    Err(FormatSpecError::Invalid) => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok((expr.len(), None))
+}
+
 /// Test if the given expression qualifieis as a block end or not, as with a
 /// body in a match expression.
 ///
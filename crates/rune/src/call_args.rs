@@ -0,0 +1,221 @@
+//! Matching the arguments of a call with the parameters of the function being
+//! called, for calls which use named arguments or default values.
+
+use crate::ast;
+use crate::{CompileError, CompileErrorKind, CompileResult, Resolve as _, Spanned as _, Storage};
+use runestick::{CompileMeta, Source};
+
+/// A parameter of a function being called.
+pub(crate) struct Param {
+    /// The name of the parameter, if it has one.
+    pub(crate) name: Option<String>,
+    /// If the parameter has a default value.
+    pub(crate) has_default: bool,
+    /// If the parameter collects the remaining arguments.
+    pub(crate) rest: bool,
+}
+
+impl Param {
+    /// Get the parameters of a function declared in Rune.
+    pub(crate) fn from_fn_args(
+        args: &[ast::FnArg],
+        storage: &Storage,
+        source: &Source,
+    ) -> CompileResult<Vec<Self>> {
+        let mut params = Vec::with_capacity(args.len());
+
+        for arg in args {
+            let name = match arg {
+                ast::FnArg::Self_(..) => Some(String::from("self")),
+                ast::FnArg::Ident(ident, ..) | ast::FnArg::Rest(_, ident) => {
+                    Some(ident.resolve(storage, source)?.into_owned())
+                }
+                ast::FnArg::Ignore(..) => None,
+            };
+
+            params.push(Self {
+                name,
+                has_default: arg.default_value().is_some(),
+                rest: arg.is_rest(),
+            });
+        }
+
+        Ok(params)
+    }
+
+    /// Get the parameters of a native function from their declared names.
+    pub(crate) fn from_names(names: &[&str]) -> Vec<Self> {
+        names
+            .iter()
+            .map(|name| Self {
+                name: Some(String::from(*name)),
+                has_default: false,
+                rest: false,
+            })
+            .collect()
+    }
+}
+
+/// The argument passed for a parameter.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum CallArg<'a> {
+    /// The value of the argument at the given position in the call.
+    Expr(usize, &'a ast::Expr),
+    /// The default value of the parameter.
+    Default,
+}
+
+/// Split a named argument like `timeout = 5` into its name and value.
+///
+/// NB: this might also be an assignment to a local variable, like `dbg(k = 5)`,
+/// which is only known once the name has been resolved.
+pub(crate) fn named_arg(expr: &ast::Expr) -> Option<(&ast::Ident, &ast::Expr)> {
+    match expr {
+        ast::Expr::ExprBinary(expr_binary) if expr_binary.op == ast::BinOp::Assign => {
+            match &*expr_binary.lhs {
+                ast::Expr::Path(path) => Some((path.try_as_ident()?, &*expr_binary.rhs)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Test if any of the arguments of the call are named.
+pub(crate) fn has_named_args(expr_call: &ast::ExprCall) -> bool {
+    expr_call
+        .args
+        .items
+        .iter()
+        .any(|(expr, _)| named_arg(expr).is_some())
+}
+
+/// Test if any of the arguments of the call are spread, like `..args`.
+pub(crate) fn has_spread(expr_call: &ast::ExprCall) -> bool {
+    expr_call
+        .args
+        .items
+        .iter()
+        .any(|(expr, _)| matches!(expr, ast::Expr::ExprSpread(..)))
+}
+
+/// Test if the arguments of the call need to be matched with the given
+/// parameters, which is the case if it uses named arguments or leaves out
+/// arguments which have default values.
+pub(crate) fn needs_resolve(expr_call: &ast::ExprCall, params: &[Param]) -> bool {
+    has_named_args(expr_call) || params.iter().any(|p| p.has_default)
+}
+
+/// Match the arguments of the call with the given parameters, returning the
+/// argument to pass for each parameter in order.
+///
+/// If the last parameter collects the remaining arguments, the remaining
+/// arguments are returned after the other parameters.
+///
+/// An argument like `k = 5` where `k` isn't a parameter is passed as an
+/// assignment if `is_local` reports that `k` is a local variable.
+pub(crate) fn resolve<'a>(
+    expr_call: &'a ast::ExprCall,
+    meta: &CompileMeta,
+    params: &[Param],
+    storage: &Storage,
+    source: &Source,
+    is_local: impl Fn(&str) -> bool,
+) -> CompileResult<Vec<CallArg<'a>>> {
+    let span = expr_call.span();
+
+    let (params, variadic) = match params.split_last() {
+        Some((last, params)) if last.rest => (params, true),
+        _ => (params, false),
+    };
+
+    let mut args = vec![None; params.len()];
+    let mut rest = Vec::new();
+    let mut positional = 0;
+    let mut named = false;
+
+    for (index, (expr, _)) in expr_call.args.items.iter().enumerate() {
+        if let Some((ident, value)) = named_arg(expr) {
+            let name = ident.resolve(storage, source)?;
+
+            match params
+                .iter()
+                .position(|p| p.name.as_deref() == Some(name.as_ref()))
+            {
+                Some(param) => {
+                    named = true;
+
+                    if args[param].is_some() {
+                        return Err(CompileError::new(
+                            expr,
+                            CompileErrorKind::DuplicateArgument {
+                                name: name.into_owned(),
+                            },
+                        ));
+                    }
+
+                    args[param] = Some(CallArg::Expr(index, value));
+                    continue;
+                }
+                None if is_local(&name) => (),
+                None => {
+                    return Err(CompileError::new(
+                        ident,
+                        CompileErrorKind::MissingParameter {
+                            name: name.into_owned(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        if named {
+            return Err(CompileError::new(
+                expr,
+                CompileErrorKind::PositionalAfterNamedArgument,
+            ));
+        }
+
+        if positional >= params.len() && variadic {
+            rest.push(CallArg::Expr(index, expr));
+            continue;
+        }
+
+        if positional >= params.len() {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::UnsupportedArgumentCount {
+                    meta: meta.clone(),
+                    expected: params.len(),
+                    actual: expr_call.args.items.len(),
+                },
+            ));
+        }
+
+        args[positional] = Some(CallArg::Expr(index, expr));
+        positional += 1;
+    }
+
+    let mut output = Vec::with_capacity(params.len());
+
+    for (index, (arg, param)) in args.into_iter().zip(params).enumerate() {
+        output.push(match arg {
+            Some(arg) => arg,
+            None if param.has_default => CallArg::Default,
+            None => {
+                let name = match &param.name {
+                    Some(name) => name.clone(),
+                    None => format!("#{}", index),
+                };
+
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::MissingArgument { name },
+                ));
+            }
+        });
+    }
+
+    output.extend(rest);
+    Ok(output)
+}
@@ -3,7 +3,7 @@ use crate::compiler::{Compiler, Needs};
 use crate::traits::Compile;
 use crate::worker::Expanded;
 use crate::CompileResult;
-use crate::{CompileError, Spanned as _};
+use crate::{CompileError, CompileErrorKind, Spanned as _};
 use runestick::Inst;
 
 /// Compile an expression.
@@ -76,6 +76,9 @@ impl Compile<(&ast::Expr, Needs)> for Compiler<'_> {
             ast::Expr::ExprSelect(expr_select) => {
                 self.compile((expr_select, needs))?;
             }
+            ast::Expr::ExprSpread(..) => {
+                return Err(CompileError::new(span, CompileErrorKind::UnsupportedSpread));
+            }
             ast::Expr::ExprCall(expr_call) => {
                 self.compile((expr_call, needs))?;
             }
@@ -124,12 +127,18 @@ impl Compile<(&ast::Expr, Needs)> for Compiler<'_> {
                 let _guard = self.items.push_macro();
                 let item = self.items.item();
 
-                if let Some(Expanded::Expr(expr)) = self.expanded.get(&item) {
-                    self.compile((expr, needs))?;
-                } else {
-                    let span = expr_call_macro.span();
+                match self.expanded.get(&item) {
+                    Some(Expanded::Expr(expr)) => {
+                        self.compile((&**expr, needs))?;
+                    }
+                    Some(Expanded::Format(builtin, format_args)) => {
+                        self.compile((&**format_args, *builtin, needs))?;
+                    }
+                    None => {
+                        let span = expr_call_macro.span();
 
-                    return Err(CompileError::internal(span, "macro has not been expanded"));
+                        return Err(CompileError::internal(span, "macro has not been expanded"));
+                    }
                 }
             }
             // NB: declarations are not used in this compilation stage.
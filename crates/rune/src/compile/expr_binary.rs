@@ -112,7 +112,7 @@ fn compile_assign_binop(
                 let var = this
                     .scopes
                     .get_var(&*ident, this.source_id, this.visitor, span)?;
-                this.asm.push(Inst::Replace { offset: var.offset }, span);
+                var.replace(this.asm, span);
 
                 true
             }
@@ -1,9 +1,11 @@
 use crate::ast;
+use crate::call_args::{self, CallArg, Param};
 use crate::compiler::{Compiler, Needs};
+use crate::query::Query;
 use crate::traits::Compile;
 use crate::CompileResult;
 use crate::{CompileError, CompileErrorKind, Resolve as _, Spanned as _};
-use runestick::{CompileMetaKind, Hash, Inst};
+use runestick::{CompileMetaKind, ContextSignature, Hash, Inst, Item, Span};
 
 /// Compile a call expression.
 impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
@@ -11,9 +13,13 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
         let span = expr_call.span();
         log::trace!("ExprCall => {:?}", self.source.source(span));
 
+        if call_args::has_spread(expr_call) {
+            return compile_spread_call(self, expr_call, needs);
+        }
+
         let guard = self.scopes.push_child(span)?;
 
-        let args = expr_call.args.items.len();
+        let mut args = expr_call.args.items.len();
 
         // NB: either handle a proper function call by resolving it's meta hash,
         // or expand the expression.
@@ -34,25 +40,29 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
                         self.source.source(span)
                     );
 
-                    self.compile((&**expr, Needs::Value))?;
-
-                    for (expr, _) in expr_call.args.items.iter() {
-                        self.compile((expr, Needs::Value))?;
-                        self.scopes.decl_anon(span)?;
+                    // NB: the called function depends on the type of the
+                    // receiver, so its parameters aren't known here.
+                    for (expr, _) in &expr_call.args.items {
+                        if is_named_arg(self, expr)? {
+                            return Err(CompileError::new(
+                                expr,
+                                CompileErrorKind::UnsupportedInstanceNamedArgument,
+                            ));
+                        }
                     }
 
-                    let ident = ident.resolve(&self.storage, &*self.source)?;
+                    self.compile((&**expr, Needs::Value))?;
+                    self.scopes.decl_anon(span)?;
+                    compile_args(self, expr_call)?;
+
+                    let ident = ident.resolve(self.storage, &self.source)?;
                     let hash = Hash::instance_fn_name(ident.as_ref());
                     self.asm.push(Inst::CallInstance { hash, args }, span);
                 }
                 expr => {
                     log::trace!("ExprCall(Other) => {:?}", self.source.source(span));
 
-                    for (expr, _) in expr_call.args.items.iter() {
-                        self.compile((expr, Needs::Value))?;
-                        self.scopes.decl_anon(span)?;
-                    }
-
+                    compile_args(self, expr_call)?;
                     self.compile((expr, Needs::Value))?;
                     self.asm.push(Inst::CallFn { args }, span);
                 }
@@ -66,19 +76,17 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
             return Ok(());
         };
 
-        for (expr, _) in &expr_call.args.items {
-            self.compile((expr, Needs::Value))?;
-            self.scopes.decl_anon(span)?;
-        }
-
         let item = self.convert_path_to_item(path)?;
 
         if let Some(name) = item.as_local() {
-            if let Some(var) =
-                self.scopes
-                    .try_get_var(name, self.source_id, self.visitor, path.span())
-            {
-                var.copy(&mut self.asm, span, format!("var `{}`", name));
+            let var = self
+                .scopes
+                .try_get_var(name, self.source_id, self.visitor, path.span())
+                .cloned();
+
+            if let Some(var) = var {
+                compile_args(self, expr_call)?;
+                var.copy(self.asm, span, format!("var `{}`", name));
                 self.asm.push(Inst::CallFn { args }, span);
 
                 if !needs.value() {
@@ -90,9 +98,14 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
             }
         }
 
+        let mut temporaries = 0;
+
         let meta = match self.lookup_meta(&item, path.span())? {
             Some(meta) => meta,
             None => {
+                // NB: errors in the arguments are reported first.
+                compile_args(self, expr_call)?;
+
                 return Err(CompileError::new(
                     span,
                     CompileErrorKind::MissingFunction { item },
@@ -123,9 +136,29 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
                     );
                 }
 
+                compile_args(self, expr_call)?;
                 tuple.item.clone()
             }
-            CompileMetaKind::Function { item, .. } => item.clone(),
+            CompileMetaKind::Function { item, .. } => match call_params(self, expr_call, item)? {
+                Some(params) => {
+                    let resolved = call_args::resolve(
+                        expr_call,
+                        &meta,
+                        &params,
+                        self.storage,
+                        &self.source,
+                        |name| self.scopes.contains_var(name),
+                    )?;
+
+                    temporaries = compile_resolved_args(self, item, &resolved, span)?;
+                    args = resolved.len();
+                    item.clone()
+                }
+                None => {
+                    compile_args(self, expr_call)?;
+                    item.clone()
+                }
+            },
             _ => {
                 return Err(CompileError::new(
                     span,
@@ -138,6 +171,10 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
         self.asm
             .push_with_comment(Inst::Call { hash, args }, span, format!("fn `{}`", item));
 
+        if temporaries > 0 {
+            self.asm.push(Inst::Clean { count: temporaries }, span);
+        }
+
         // NB: we put it here to preserve the call in case it has side effects.
         // But if we don't need the value, then pop it from the stack.
         if !needs.value() {
@@ -148,3 +185,307 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
         Ok(())
     }
 }
+
+/// Compile a call which spreads some of its arguments, like `f(a, ..rest)`.
+///
+/// The arguments are collected into a single vector which is spread when the
+/// function is called, so the number of arguments is only known at runtime.
+fn compile_spread_call(
+    this: &mut Compiler<'_>,
+    expr_call: &ast::ExprCall,
+    needs: Needs,
+) -> CompileResult<()> {
+    let span = expr_call.span();
+    let guard = this.scopes.push_child(span)?;
+
+    match &*expr_call.expr {
+        ast::Expr::Path(path) => {
+            let item = this.convert_path_to_item(path)?;
+
+            let var = match item.as_local() {
+                Some(name) => this
+                    .scopes
+                    .try_get_var(name, this.source_id, this.visitor, path.span())
+                    .cloned()
+                    .map(|var| (var, name)),
+                None => None,
+            };
+
+            compile_spread_args(this, expr_call)?;
+
+            if let Some((var, name)) = var {
+                var.copy(this.asm, span, format!("var `{}`", name));
+                this.asm.push(Inst::CallFnSpread, span);
+            } else {
+                let meta = match this.lookup_meta(&item, path.span())? {
+                    Some(meta) => meta,
+                    None => {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::MissingFunction { item },
+                        ));
+                    }
+                };
+
+                let item = match &meta.kind {
+                    CompileMetaKind::Tuple { tuple, .. }
+                    | CompileMetaKind::TupleVariant { tuple, .. } => tuple.item.clone(),
+                    CompileMetaKind::Function { item, .. } => item.clone(),
+                    _ => {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::MissingFunction { item },
+                        ));
+                    }
+                };
+
+                let hash = Hash::type_hash(&item);
+                this.asm.push_with_comment(
+                    Inst::CallSpread { hash },
+                    span,
+                    format!("fn `{}`", item),
+                );
+            }
+        }
+        ast::Expr::ExprFieldAccess(ast::ExprFieldAccess {
+            expr,
+            expr_field: ast::ExprField::Ident(ident),
+            ..
+        }) => {
+            this.compile((&**expr, Needs::Value))?;
+            this.scopes.decl_anon(span)?;
+            compile_spread_args(this, expr_call)?;
+
+            let ident = ident.resolve(this.storage, &this.source)?;
+            let hash = Hash::instance_fn_name(ident.as_ref());
+            this.asm.push(Inst::CallInstanceSpread { hash }, span);
+        }
+        expr => {
+            compile_spread_args(this, expr_call)?;
+            this.compile((expr, Needs::Value))?;
+            this.asm.push(Inst::CallFnSpread, span);
+        }
+    }
+
+    if !needs.value() {
+        this.asm.push(Inst::Pop, span);
+    }
+
+    this.scopes.pop(guard, span)?;
+    Ok(())
+}
+
+/// Compile the arguments of a call which spreads some of them into a single
+/// vector.
+///
+/// Consecutive arguments which aren't spread are collected into vectors, which
+/// are then concatenated with the values being spread.
+fn compile_spread_args(this: &mut Compiler<'_>, expr_call: &ast::ExprCall) -> CompileResult<()> {
+    let span = expr_call.span();
+    let mut pieces = 0;
+    let mut values = 0;
+
+    for (expr, _) in &expr_call.args.items {
+        if is_named_arg(this, expr)? {
+            return Err(CompileError::new(
+                expr,
+                CompileErrorKind::UnsupportedSpreadNamedArgument,
+            ));
+        }
+
+        match expr {
+            ast::Expr::ExprSpread(expr_spread) => {
+                collect_values(this, &mut values, &mut pieces, span)?;
+                this.compile((&*expr_spread.expr, Needs::Value))?;
+                pieces += 1;
+            }
+            expr => {
+                this.compile((expr, Needs::Value))?;
+                values += 1;
+            }
+        }
+
+        this.scopes.decl_anon(span)?;
+    }
+
+    collect_values(this, &mut values, &mut pieces, span)?;
+
+    if pieces > 1 {
+        this.asm.push(Inst::Concat { count: pieces }, span);
+        this.scopes.undecl_anon(pieces - 1, span)?;
+    }
+
+    Ok(())
+}
+
+/// Collect the values on top of the stack into a vector, which is one of the
+/// pieces of a spread call.
+fn collect_values(
+    this: &mut Compiler<'_>,
+    values: &mut usize,
+    pieces: &mut usize,
+    span: Span,
+) -> CompileResult<()> {
+    if *values == 0 {
+        return Ok(());
+    }
+
+    this.asm.push(Inst::Vec { count: *values }, span);
+    this.scopes.undecl_anon(*values - 1, span)?;
+    *pieces += 1;
+    *values = 0;
+    Ok(())
+}
+
+/// Compile the arguments of a call in order, where named arguments aren't
+/// supported.
+fn compile_args(this: &mut Compiler<'_>, expr_call: &ast::ExprCall) -> CompileResult<()> {
+    let span = expr_call.span();
+
+    for (expr, _) in &expr_call.args.items {
+        if is_named_arg(this, expr)? {
+            return Err(CompileError::new(
+                expr,
+                CompileErrorKind::UnsupportedNamedArgument,
+            ));
+        }
+
+        this.compile((expr, Needs::Value))?;
+        this.scopes.decl_anon(span)?;
+    }
+
+    Ok(())
+}
+
+/// Test if the given argument is a named argument, as opposed to an assignment
+/// to a local variable like `dbg(k = 5)`.
+fn is_named_arg(this: &Compiler<'_>, expr: &ast::Expr) -> CompileResult<bool> {
+    let ident = match call_args::named_arg(expr) {
+        Some((ident, _)) => ident,
+        None => return Ok(false),
+    };
+
+    let name = ident.resolve(this.storage, &this.source)?;
+    Ok(!this.scopes.contains_var(&name))
+}
+
+/// Get the parameters of the called function, if the arguments of the call
+/// need to be matched with them.
+fn call_params(
+    this: &Compiler<'_>,
+    expr_call: &ast::ExprCall,
+    item: &Item,
+) -> CompileResult<Option<Vec<Param>>> {
+    let named = call_args::has_named_args(expr_call);
+
+    if let Some(signature) = this.query.signatures.get(item) {
+        if !named
+            && signature
+                .args
+                .iter()
+                .all(|arg| arg.default_value().is_none())
+        {
+            return Ok(None);
+        }
+
+        let params = Param::from_fn_args(&signature.args, this.storage, &signature.source)?;
+        return Ok(Some(params));
+    }
+
+    if !named {
+        return Ok(None);
+    }
+
+    Ok(match this.context.lookup_signature(Hash::type_hash(item)) {
+        Some(ContextSignature::Function {
+            params: Some(params),
+            ..
+        }) => Some(Param::from_names(params)),
+        _ => None,
+    })
+}
+
+/// Compile arguments which have been matched with the parameters of the
+/// called function, returning the number of temporaries which have to be
+/// cleaned from the stack after the call.
+///
+/// NB: arguments are evaluated in the order they're written in the call. If
+/// that's not the order of the parameters, they're evaluated into temporaries
+/// which are then copied in the order of the parameters.
+fn compile_resolved_args(
+    this: &mut Compiler<'_>,
+    item: &Item,
+    args: &[CallArg<'_>],
+    span: Span,
+) -> CompileResult<usize> {
+    let mut written = args
+        .iter()
+        .enumerate()
+        .filter_map(|(param, arg)| match *arg {
+            CallArg::Expr(index, expr) => Some((index, param, expr)),
+            CallArg::Default => None,
+        })
+        .collect::<Vec<_>>();
+
+    // NB: default values are constants, so they can be evaluated in any order.
+    if written.windows(2).all(|w| w[0].0 < w[1].0) {
+        for (index, arg) in args.iter().enumerate() {
+            match *arg {
+                CallArg::Expr(_, expr) => this.compile((expr, Needs::Value))?,
+                CallArg::Default => compile_default_arg(this, item, index, span)?,
+            }
+
+            this.scopes.decl_anon(span)?;
+        }
+
+        return Ok(0);
+    }
+
+    written.sort_by_key(|(index, ..)| *index);
+    let mut offsets = vec![None; args.len()];
+
+    for (_, param, expr) in &written {
+        this.compile((*expr, Needs::Value))?;
+        offsets[*param] = Some(this.scopes.decl_anon(span)?);
+    }
+
+    for (index, offset) in offsets.into_iter().enumerate() {
+        match offset {
+            Some(offset) => this.asm.push(Inst::Copy { offset }, span),
+            None => compile_default_arg(this, item, index, span)?,
+        }
+
+        this.scopes.decl_anon(span)?;
+    }
+
+    Ok(written.len())
+}
+
+/// Push the default value of the parameter at the given index.
+fn compile_default_arg(
+    this: &mut Compiler<'_>,
+    item: &Item,
+    index: usize,
+    span: Span,
+) -> CompileResult<()> {
+    let default = Query::default_arg_item(item, index);
+
+    // NB: querying the constant makes sure that it's stored in the unit. If
+    // it failed to evaluate, that error has already been reported.
+    if this.query.query_meta(&default)?.is_none() && !this.query.failed.contains(&default) {
+        return Err(CompileError::new(
+            span,
+            CompileErrorKind::MissingConst { item: default },
+        ));
+    }
+
+    this.asm.push_with_comment(
+        Inst::Const {
+            hash: Hash::type_hash(&default),
+        },
+        span,
+        format!("default `{}`", default),
+    );
+
+    Ok(())
+}
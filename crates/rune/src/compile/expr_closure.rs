@@ -22,7 +22,7 @@ impl Compile<(ast::ExprClosure, &[CompileMetaCapture])> for Compiler<'_> {
                     ast::FnArg::Self_(s) => {
                         return Err(CompileError::new(s, CompileErrorKind::UnsupportedSelf))
                     }
-                    ast::FnArg::Ident(ident) => {
+                    ast::FnArg::Ident(ident, ..) | ast::FnArg::Rest(_, ident) => {
                         let ident = ident.resolve(&self.storage, &*self.source)?;
                         self.scopes.new_var(ident.as_ref(), span)?;
                     }
@@ -99,7 +99,19 @@ impl Compile<(&ast::ExprClosure, Needs)> for Compiler<'_> {
                 let var =
                     self.scopes
                         .get_var(&capture.ident, self.source_id, self.visitor, span)?;
-                var.copy(&mut self.asm, span, format!("capture `{}`", capture.ident));
+
+                // NB: variables which are assigned to by a closure are
+                // captured by a shared cell, unless it's a `move` closure
+                // which gets a copy of its own.
+                if var.cell && expr_closure.move_.is_none() {
+                    self.asm.push_with_comment(
+                        Inst::CaptureCell { offset: var.offset },
+                        span,
+                        format!("capture `{}` by cell", capture.ident),
+                    );
+                } else {
+                    var.copy(self.asm, span, format!("capture `{}`", capture.ident));
+                }
             }
 
             self.asm.push_with_comment(
@@ -105,8 +105,8 @@ fn try_immediate_field_access_optimization(
             .scopes
             .try_get_var(ident.as_ref(), this.source_id, this.visitor, path.span())
         {
-            Some(var) => var,
-            None => return Ok(false),
+            Some(var) if !var.cell => var,
+            _ => return Ok(false),
         };
 
     this.asm.push(
@@ -17,23 +17,52 @@ impl Compile<(&ast::ExprLet, Needs)> for Compiler<'_> {
             Ok(())
         };
 
-        let false_label = self.asm.new_label("let_panic");
-
-        if self.compile_pat(&expr_let.pat, false_label, &load)? {
-            self.warnings
-                .let_pattern_might_panic(self.source_id, span, self.context());
-
-            let ok_label = self.asm.new_label("let_ok");
-            self.asm.jump(ok_label, span);
-            self.asm.label(false_label)?;
-            self.asm.push(
-                Inst::Panic {
-                    reason: runestick::PanicReason::UnmatchedPattern,
-                },
-                span,
-            );
-
-            self.asm.label(ok_label)?;
+        if let Some((_, block)) = expr_let.else_.as_deref() {
+            let else_label = self.asm.new_label("let_else");
+
+            // NB: the pattern is compiled in a child scope, so that a failed
+            // match only pops the variables it has bound so far.
+            let guard = self.scopes.push_child(span)?;
+            let refutable = self.compile_pat(&expr_let.pat, else_label, &load)?;
+            let scope = self.scopes.pop(guard, span)?;
+
+            if refutable {
+                let ok_label = self.asm.new_label("let_ok");
+                self.asm.jump(ok_label, span);
+                self.asm.label(else_label)?;
+                self.compile((block, Needs::None))?;
+
+                // The else block must not complete normally.
+                self.asm.push(
+                    Inst::Panic {
+                        reason: runestick::PanicReason::UnmatchedPattern,
+                    },
+                    block.span(),
+                );
+
+                self.asm.label(ok_label)?;
+            }
+
+            self.scopes.merge(scope, span)?;
+        } else {
+            let false_label = self.asm.new_label("let_panic");
+
+            if self.compile_pat(&expr_let.pat, false_label, &load)? {
+                self.warnings
+                    .let_pattern_might_panic(self.source_id, span, self.context());
+
+                let ok_label = self.asm.new_label("let_ok");
+                self.asm.jump(ok_label, span);
+                self.asm.label(false_label)?;
+                self.asm.push(
+                    Inst::Panic {
+                        reason: runestick::PanicReason::UnmatchedPattern,
+                    },
+                    span,
+                );
+
+                self.asm.label(ok_label)?;
+            }
         }
 
         // If a value is needed for a let expression, it is evaluated as a unit.
@@ -1,9 +1,10 @@
 use crate::ast;
+use crate::collections::HashSet;
 use crate::compiler::{Compiler, Needs};
 use crate::traits::Compile;
 use crate::CompileResult;
 use crate::Spanned as _;
-use runestick::Inst;
+use runestick::{CompileMetaKind, Inst, Item};
 
 impl Compile<(&ast::ExprMatch, Needs)> for Compiler<'_> {
     fn compile(&mut self, (expr_match, needs): (&ast::ExprMatch, Needs)) -> CompileResult<()> {
@@ -63,6 +64,8 @@ impl Compile<(&ast::ExprMatch, Needs)> for Compiler<'_> {
             branches.push((branch_label, scope));
         }
 
+        check_coverage(self, expr_match)?;
+
         // what to do in case nothing matches and the pattern doesn't have any
         // default match branch.
         if needs.value() {
@@ -94,3 +97,249 @@ impl Compile<(&ast::ExprMatch, Needs)> for Compiler<'_> {
         Ok(())
     }
 }
+
+/// What a pattern in a match branch is known to match.
+enum Coverage {
+    /// The pattern matches any value.
+    All,
+    /// The pattern matches every value of the given variants of an enum.
+    Variants {
+        /// The enum the variants belong to.
+        enum_item: Item,
+        /// The variants which are matched.
+        variants: Vec<Item>,
+        /// If the pattern might also match other values.
+        partial: bool,
+    },
+    /// The pattern matches some values.
+    Partial,
+}
+
+impl Coverage {
+    /// Coverage of a single variant.
+    fn variant(enum_item: Item, item: Item) -> Self {
+        Self::Variants {
+            enum_item,
+            variants: vec![item],
+            partial: false,
+        }
+    }
+}
+
+/// Warn about match branches which can never be reached, and about matches
+/// over the variants of an enum which don't cover all of them.
+fn check_coverage(this: &mut Compiler<'_>, expr_match: &ast::ExprMatch) -> CompileResult<()> {
+    let span = expr_match.span();
+
+    let mut matches_all = false;
+    let mut enum_item = None;
+    let mut covered = HashSet::new();
+
+    for (branch, _) in &expr_match.branches {
+        let coverage = pat_coverage(this, &branch.pat)?;
+
+        let unreachable = matches_all
+            || match &coverage {
+                Coverage::Variants {
+                    variants, partial, ..
+                } => !partial && variants.iter().all(|v| covered.contains(v)),
+                _ => false,
+            };
+
+        if unreachable {
+            this.warnings
+                .unreachable_pattern(this.source_id, branch.pat.span(), Some(span));
+        }
+
+        // NB: a branch with a condition might not match, so it doesn't cover
+        // anything.
+        if branch.condition.is_some() {
+            continue;
+        }
+
+        match coverage {
+            Coverage::All => {
+                matches_all = true;
+            }
+            Coverage::Variants {
+                enum_item: item,
+                variants,
+                ..
+            } => {
+                if enum_item.get_or_insert_with(|| item.clone()) == &item {
+                    covered.extend(variants);
+                }
+            }
+            Coverage::Partial => (),
+        }
+    }
+
+    if matches_all {
+        return Ok(());
+    }
+
+    let enum_item = match enum_item {
+        Some(enum_item) => enum_item,
+        None => return Ok(()),
+    };
+
+    // NB: only enums declared in the unit have known variants.
+    let missing = match this.query.enum_variants.get(&enum_item) {
+        Some(variants) => variants
+            .iter()
+            .filter(|v| !covered.contains(*v))
+            .cloned()
+            .collect::<Vec<_>>(),
+        None => return Ok(()),
+    };
+
+    if !missing.is_empty() {
+        let span = expr_match.match_.span().join(expr_match.expr.span());
+        this.warnings
+            .non_exhaustive_match(this.source_id, span, missing);
+    }
+
+    Ok(())
+}
+
+/// Calculate what the given pattern is known to match.
+fn pat_coverage(this: &mut Compiler<'_>, pat: &ast::Pat) -> CompileResult<Coverage> {
+    Ok(match pat {
+        ast::Pat::PatIgnore(..) => Coverage::All,
+        ast::Pat::PatPath(path) => {
+            let item = this.convert_path_to_item(&path.path)?;
+
+            if let Some(meta) = this.lookup_meta(&item, path.span())? {
+                match meta.kind {
+                    CompileMetaKind::TupleVariant {
+                        enum_item, tuple, ..
+                    } if tuple.args == 0 => {
+                        return Ok(Coverage::variant(enum_item, tuple.item));
+                    }
+                    CompileMetaKind::Tuple { tuple, .. } if tuple.args == 0 => {
+                        return Ok(Coverage::Partial);
+                    }
+                    _ => (),
+                }
+            }
+
+            if item.as_local().is_some() {
+                Coverage::All
+            } else {
+                Coverage::Partial
+            }
+        }
+        ast::Pat::PatBinding(pat_binding) => pat_coverage(this, &pat_binding.pat)?,
+        ast::Pat::PatTuple(pat_tuple) => {
+            let path = match &pat_tuple.path {
+                Some(path) => path,
+                None => return Ok(Coverage::Partial),
+            };
+
+            let item = this.convert_path_to_item(path)?;
+
+            let (enum_item, tuple) = match this.lookup_meta(&item, path.span())? {
+                Some(meta) => match meta.kind {
+                    CompileMetaKind::TupleVariant {
+                        enum_item, tuple, ..
+                    } => (enum_item, tuple),
+                    _ => return Ok(Coverage::Partial),
+                },
+                None => return Ok(Coverage::Partial),
+            };
+
+            for (pat, _) in &pat_tuple.items {
+                if !matches!(pat_coverage(this, pat)?, Coverage::All) {
+                    return Ok(Coverage::Partial);
+                }
+            }
+
+            Coverage::variant(enum_item, tuple.item)
+        }
+        ast::Pat::PatObject(pat_object) => {
+            let path = match &pat_object.ident {
+                ast::LitObjectIdent::Named(path) => path,
+                ast::LitObjectIdent::Anonymous(..) => return Ok(Coverage::Partial),
+            };
+
+            let item = this.convert_path_to_item(path)?;
+
+            let (enum_item, object) = match this.lookup_meta(&item, path.span())? {
+                Some(meta) => match meta.kind {
+                    CompileMetaKind::StructVariant {
+                        enum_item, object, ..
+                    } => (enum_item, object),
+                    _ => return Ok(Coverage::Partial),
+                },
+                None => return Ok(Coverage::Partial),
+            };
+
+            for (field, _) in &pat_object.fields {
+                if let Some((_, pat)) = &field.binding {
+                    if !matches!(pat_coverage(this, pat)?, Coverage::All) {
+                        return Ok(Coverage::Partial);
+                    }
+                }
+            }
+
+            Coverage::variant(enum_item, object.item)
+        }
+        ast::Pat::PatOr(pat_or) => {
+            let mut coverage = pat_coverage(this, &pat_or.first)?;
+
+            for pat in pat_or.alternatives().skip(1) {
+                coverage = match (coverage, pat_coverage(this, pat)?) {
+                    (Coverage::All, _) | (_, Coverage::All) => Coverage::All,
+                    (
+                        Coverage::Variants {
+                            enum_item,
+                            mut variants,
+                            partial,
+                        },
+                        Coverage::Variants {
+                            enum_item: other,
+                            variants: more,
+                            partial: more_partial,
+                        },
+                    ) => {
+                        let same = enum_item == other;
+
+                        if same {
+                            variants.extend(more);
+                        }
+
+                        Coverage::Variants {
+                            enum_item,
+                            variants,
+                            partial: partial || more_partial || !same,
+                        }
+                    }
+                    (
+                        Coverage::Variants {
+                            enum_item,
+                            variants,
+                            ..
+                        },
+                        Coverage::Partial,
+                    )
+                    | (
+                        Coverage::Partial,
+                        Coverage::Variants {
+                            enum_item,
+                            variants,
+                            ..
+                        },
+                    ) => Coverage::Variants {
+                        enum_item,
+                        variants,
+                        partial: true,
+                    },
+                    (Coverage::Partial, Coverage::Partial) => Coverage::Partial,
+                };
+            }
+
+            coverage
+        }
+        _ => Coverage::Partial,
+    })
+}
@@ -25,10 +25,11 @@ impl Compile<(&ast::ExprReturn, Needs)> for Compiler<'_> {
         if let Some(expr) = &return_expr.expr {
             self.compile((&**expr, Needs::Value))?;
             self.locals_clean(total_var_count, span);
+            self.check_return(span);
             self.asm.push(Inst::Return, span);
         } else {
             self.locals_pop(total_var_count, span);
-            self.asm.push(Inst::ReturnUnit, span);
+            self.return_unit(span);
         }
 
         Ok(())
@@ -21,6 +21,7 @@ impl Compile<(&ast::ExprTry, Needs)> for Compiler<'_> {
         // Clean up all locals so far and return from the current function.
         let total_var_count = self.scopes.total_var_count(span)?;
         self.locals_clean(total_var_count, span);
+        self.check_return(span);
         self.asm.push(Inst::Return, span);
 
         self.asm.label(not_error)?;
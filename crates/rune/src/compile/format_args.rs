@@ -0,0 +1,96 @@
+use crate::ast;
+use crate::compiler::{Compiler, Needs};
+use crate::macros::BuiltInMacro;
+use crate::traits::{Compile, Resolve as _};
+use crate::CompileResult;
+use crate::Spanned as _;
+use runestick::{Hash, Inst};
+
+/// Compile the arguments to a built-in format macro, like `format!`.
+impl Compile<(&ast::FormatArgs, BuiltInMacro, Needs)> for Compiler<'_> {
+    fn compile(
+        &mut self,
+        (format_args, builtin, needs): (&ast::FormatArgs, BuiltInMacro, Needs),
+    ) -> CompileResult<()> {
+        let span = format_args.span();
+        log::trace!("FormatArgs => {:?}", self.source.source(span));
+
+        let function = match builtin {
+            BuiltInMacro::Format => None,
+            BuiltInMacro::Print => Some("print"),
+            BuiltInMacro::Println => Some("println"),
+        };
+
+        // NB: Elide the entire expression if it's not needed.
+        if function.is_none() && !needs.value() {
+            self.warnings.not_used(self.source_id, span, self.context());
+            return Ok(());
+        }
+
+        let format = format_args.resolve(self.storage, &self.source)?;
+
+        let expected = self.scopes.push_child(span)?;
+
+        // NB: arguments are evaluated once and in order, since they might be
+        // referenced any number of times by the format string.
+        let mut offsets = Vec::new();
+
+        for expr in format_args.exprs().chain(format.captures.iter()) {
+            self.compile((expr, Needs::Value))?;
+            offsets.push(self.scopes.decl_anon(span)?);
+        }
+
+        for c in format.components.iter() {
+            match c {
+                ast::FormatComponent::String(string) => {
+                    let slot = self.unit.borrow_mut().new_static_string(string)?;
+                    self.asm.push(Inst::String { slot }, span);
+                }
+                ast::FormatComponent::Arg(index, spec) => {
+                    self.asm.push(
+                        Inst::Copy {
+                            offset: offsets[*index],
+                        },
+                        span,
+                    );
+
+                    if let Some(spec) = spec {
+                        self.asm.push(Inst::Format { spec: *spec }, span);
+                    }
+                }
+            }
+
+            self.scopes.decl_anon(span)?;
+        }
+
+        self.asm.push(
+            Inst::StringConcat {
+                len: format.components.len(),
+                size_hint: format.size_hint,
+            },
+            span,
+        );
+
+        if !offsets.is_empty() {
+            self.asm.push(
+                Inst::Clean {
+                    count: offsets.len(),
+                },
+                span,
+            );
+        }
+
+        let _ = self.scopes.pop(expected, span)?;
+
+        if let Some(function) = function {
+            let hash = Hash::type_hash(["std", function]);
+            self.asm.push(Inst::Call { hash, args: 1 }, span);
+
+            if !needs.value() {
+                self.asm.push(Inst::Pop, span);
+            }
+        }
+
+        Ok(())
+    }
+}
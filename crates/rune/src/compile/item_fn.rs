@@ -1,5 +1,6 @@
 use crate::ast;
 use crate::compiler::{Compiler, Needs};
+use crate::query::Query;
 use crate::traits::Compile;
 use crate::CompileResult;
 use crate::{CompileError, CompileErrorKind, Resolve as _, Spanned as _};
@@ -12,9 +13,16 @@ impl Compile<(ast::ItemFn, bool)> for Compiler<'_> {
 
         let mut first = true;
 
-        for (arg, _) in fn_decl.args.items.iter() {
+        for (offset, (arg, _)) in fn_decl.args.items.iter().enumerate() {
             let span = arg.span();
 
+            // NB: evaluate default values up front, so that errors in them are
+            // reported even if they're never used.
+            if arg.default_value().is_some() {
+                let item = Query::default_arg_item(&self.items.item(), offset);
+                self.query.query_meta(&item)?;
+            }
+
             match arg {
                 ast::FnArg::Self_(s) => {
                     if !instance_fn || !first {
@@ -24,24 +32,60 @@ impl Compile<(ast::ItemFn, bool)> for Compiler<'_> {
                     let span = s.span();
                     self.scopes.new_var("self", span)?;
                 }
-                ast::FnArg::Ident(ident) => {
+                ast::FnArg::Ident(ident, ty, _) => {
                     let span = ident.span();
                     let name = ident.resolve(&self.storage, &*self.source)?;
                     self.scopes.new_var(name.as_ref(), span)?;
+
+                    if let Some((_, ty)) = ty {
+                        let slot = self.unit.borrow_mut().new_static_string(name.as_ref())?;
+                        let type_check = self.type_check_for_annotation(ty)?;
+
+                        self.asm.push(
+                            Inst::CheckArgument {
+                                offset,
+                                type_check,
+                                slot: Some(slot),
+                            },
+                            span,
+                        );
+                    }
                 }
-                ast::FnArg::Ignore(ignore) => {
+                ast::FnArg::Rest(_, ident) => {
+                    let span = ident.span();
+                    let name = ident.resolve(self.storage, &self.source)?;
+                    self.scopes.new_var(name.as_ref(), span)?;
+                }
+                ast::FnArg::Ignore(ignore, ty) => {
                     let span = ignore.span();
                     self.scopes.decl_anon(span)?;
+
+                    if let Some((_, ty)) = ty {
+                        let type_check = self.type_check_for_annotation(ty)?;
+
+                        self.asm.push(
+                            Inst::CheckArgument {
+                                offset,
+                                type_check,
+                                slot: None,
+                            },
+                            span,
+                        );
+                    }
                 }
             }
 
             first = false;
         }
 
+        if let Some((_, ty)) = &fn_decl.output {
+            self.return_type_check = Some(self.type_check_for_annotation(ty)?);
+        }
+
         if fn_decl.body.statements.is_empty() {
             let total_var_count = self.scopes.total_var_count(span)?;
             self.locals_pop(total_var_count, span);
-            self.asm.push(Inst::ReturnUnit, span);
+            self.return_unit(span);
             return Ok(());
         }
 
@@ -50,13 +94,14 @@ impl Compile<(ast::ItemFn, bool)> for Compiler<'_> {
 
             let total_var_count = self.scopes.total_var_count(span)?;
             self.locals_clean(total_var_count, span);
+            self.check_return(span);
             self.asm.push(Inst::Return, span);
         } else {
             self.compile((&fn_decl.body, Needs::None))?;
 
             let total_var_count = self.scopes.total_var_count(span)?;
             self.locals_pop(total_var_count, span);
-            self.asm.push(Inst::ReturnUnit, span);
+            self.return_unit(span);
         }
 
         self.scopes.pop_last(span)?;
@@ -41,6 +41,15 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
             }
         }
 
+        if let (Some((dot_dot, _)), ast::LitObjectIdent::Anonymous(..)) =
+            (&lit_object.rest, &lit_object.ident)
+        {
+            return Err(CompileError::new(
+                dot_dot,
+                CompileErrorKind::UnsupportedObjectUpdate,
+            ));
+        }
+
         for assign in lit_object.assignments.iter() {
             let span = assign.span();
 
@@ -51,6 +60,8 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
                 // side effects (without creating an object).
                 if !needs.value() {
                     self.asm.push(Inst::Pop, span);
+                } else {
+                    self.scopes.decl_anon(span)?;
                 }
             } else {
                 let key = assign.key.resolve(&self.storage, &*self.source)?;
@@ -59,18 +70,21 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
                     .get_var(&*key, self.source_id, self.visitor, span)?;
                 if needs.value() {
                     var.copy(&mut self.asm, span, format!("name `{}`", key));
+                    self.scopes.decl_anon(span)?;
                 }
             }
         }
 
         // No need to encode an object since the value is not needed.
         if !needs.value() {
+            if let Some((_, base)) = &lit_object.rest {
+                self.compile((&**base, Needs::None))?;
+            }
+
             self.warnings.not_used(self.source_id, span, self.context());
             return Ok(());
         }
 
-        let slot = self.unit.borrow_mut().new_static_object_keys(&keys)?;
-
         match &lit_object.ident {
             ast::LitObjectIdent::Named(path) => {
                 let item = self.convert_path_to_item(path)?;
@@ -87,6 +101,16 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
 
                 match &meta.kind {
                     CompileMetaKind::Struct { object, .. } => {
+                        if let Some((_, base)) = &lit_object.rest {
+                            self.compile_object_update(
+                                object.fields.as_ref(),
+                                base,
+                                &mut keys,
+                                &mut check_keys,
+                                span,
+                            )?;
+                        }
+
                         check_object_fields(
                             object.fields.as_ref(),
                             check_keys,
@@ -94,12 +118,23 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
                             &object.item,
                         )?;
 
+                        let slot = self.unit.borrow_mut().new_static_object_keys(&keys)?;
                         let hash = Hash::type_hash(&object.item);
                         self.asm.push(Inst::TypedObject { hash, slot }, span);
                     }
                     CompileMetaKind::StructVariant {
                         enum_item, object, ..
                     } => {
+                        if let Some((_, base)) = &lit_object.rest {
+                            self.compile_object_update(
+                                object.fields.as_ref(),
+                                base,
+                                &mut keys,
+                                &mut check_keys,
+                                span,
+                            )?;
+                        }
+
                         check_object_fields(
                             object.fields.as_ref(),
                             check_keys,
@@ -107,6 +142,7 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
                             &object.item,
                         )?;
 
+                        let slot = self.unit.borrow_mut().new_static_object_keys(&keys)?;
                         let enum_hash = Hash::type_hash(enum_item);
                         let hash = Hash::type_hash(&object.item);
 
@@ -130,10 +166,54 @@ impl Compile<(&ast::LitObject, Needs)> for Compiler<'_> {
                 };
             }
             ast::LitObjectIdent::Anonymous(..) => {
+                let slot = self.unit.borrow_mut().new_static_object_keys(&keys)?;
                 self.asm.push(Inst::Object { slot }, span);
             }
         }
 
+        self.scopes
+            .undecl_anon(lit_object.assignments.len(), span)?;
+
+        Ok(())
+    }
+}
+
+impl Compiler<'_> {
+    /// Compile the base of a struct update, and push the fields which are not
+    /// explicitly assigned from it.
+    ///
+    /// NB: like in Rust, the base is evaluated after the assigned fields.
+    fn compile_object_update(
+        &mut self,
+        fields: Option<&HashSet<String>>,
+        base: &ast::Expr,
+        keys: &mut Vec<String>,
+        check_keys: &mut Vec<(String, Span)>,
+        span: Span,
+    ) -> CompileResult<()> {
+        let fields = match fields {
+            Some(fields) => fields,
+            None => return Ok(()),
+        };
+
+        self.compile((base, Needs::Value))?;
+
+        let mut missing = fields
+            .iter()
+            .filter(|field| !keys.contains(field))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        missing.sort();
+
+        let slot = self.unit.borrow_mut().new_static_object_keys(&missing)?;
+        self.asm.push(Inst::ObjectFields { slot }, span);
+
+        for field in missing {
+            keys.push(field.clone());
+            check_keys.push((field, span));
+        }
+
         Ok(())
     }
 }
@@ -33,8 +33,13 @@ impl Compile<(&ast::LitTemplate, Needs)> for Compiler<'_> {
                     self.asm.push(Inst::String { slot }, span);
                     self.scopes.decl_anon(span)?;
                 }
-                ast::TemplateComponent::Expr(expr) => {
+                ast::TemplateComponent::Expr(expr, spec) => {
                     self.compile((&**expr, Needs::Value))?;
+
+                    if let Some(spec) = spec {
+                        self.asm.push(Inst::Format { spec: *spec }, span);
+                    }
+
                     self.scopes.decl_anon(span)?;
                 }
             }
@@ -19,6 +19,7 @@ impl Compile<(&ast::LitTuple, Needs)> for Compiler<'_> {
 
         for (expr, _) in lit_tuple.items.iter() {
             self.compile((expr, Needs::Value))?;
+            self.scopes.decl_anon(span)?;
         }
 
         self.asm.push(
@@ -28,6 +29,8 @@ impl Compile<(&ast::LitTuple, Needs)> for Compiler<'_> {
             span,
         );
 
+        self.scopes.undecl_anon(lit_tuple.items.len(), span)?;
+
         Ok(())
     }
 }
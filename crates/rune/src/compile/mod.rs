@@ -23,6 +23,7 @@ mod expr_try;
 mod expr_unary;
 mod expr_while;
 mod expr_yield;
+mod format_args;
 mod item_fn;
 mod lit_bool;
 mod lit_byte;
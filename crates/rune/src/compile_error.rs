@@ -1,7 +1,7 @@
 use crate::ast;
 use crate::unit_builder::UnitBuilderError;
 use crate::{ParseError, ParseErrorKind, Spanned};
-use runestick::{CompileMeta, Item, SourceId, Span};
+use runestick::{CompileMeta, Item, SourceId, Span, TypeInfo};
 use std::error;
 use std::fmt;
 use std::io;
@@ -12,10 +12,13 @@ use thiserror::Error;
 pub type CompileResult<T, E = CompileError> = std::result::Result<T, E>;
 
 /// An error raised during compiling.
+///
+/// The kind of the error is boxed, since it's large and compile errors are
+/// passed around in results everywhere in the compiler.
 #[derive(Debug)]
 pub struct CompileError {
     span: Span,
-    kind: CompileErrorKind,
+    kind: Box<CompileErrorKind>,
 }
 
 impl CompileError {
@@ -27,7 +30,7 @@ impl CompileError {
     {
         Self {
             span: spanned.span(),
-            kind: CompileErrorKind::from(err),
+            kind: Box::new(CompileErrorKind::from(err)),
         }
     }
 
@@ -38,7 +41,7 @@ impl CompileError {
 
     /// Convert into the kind of the compile error.
     pub fn into_kind(self) -> CompileErrorKind {
-        self.kind
+        *self.kind
     }
 
     /// Construct an internal error.
@@ -84,9 +87,9 @@ impl From<ParseError> for CompileError {
     fn from(error: ParseError) -> Self {
         CompileError {
             span: error.span(),
-            kind: CompileErrorKind::ParseError {
+            kind: Box::new(CompileErrorKind::ParseError {
                 error: error.into_kind(),
-            },
+            }),
         }
     }
 }
@@ -95,7 +98,7 @@ impl From<UnitBuilderError> for CompileError {
     fn from(error: UnitBuilderError) -> Self {
         CompileError {
             span: Span::empty(),
-            kind: CompileErrorKind::UnitBuilderError { error },
+            kind: Box::new(CompileErrorKind::UnitBuilderError { error }),
         }
     }
 }
@@ -279,6 +282,9 @@ pub enum CompileErrorKind {
         /// The related item.
         item: Item,
     },
+    /// Struct update syntax used on an object which isn't a named struct.
+    #[error("struct update syntax is only supported for named structs")]
+    UnsupportedObjectUpdate,
     /// When we encounter an expression that cannot be assigned to.
     #[error("cannot assign to expression")]
     UnsupportedAssignExpr,
@@ -323,6 +329,12 @@ pub enum CompileErrorKind {
     /// The pattern is not supported as a binding.
     #[error("not a valid binding")]
     UnsupportedBinding,
+    /// A variable is not bound in all alternatives of an or-pattern.
+    #[error("variable `{name}` is not bound in all alternatives of the pattern")]
+    PatOrBindingMismatch {
+        /// The name of the variable.
+        name: String,
+    },
     /// Error raised when trying to use a break outside of a loop.
     #[error("break expressions cannot be used as a value")]
     BreakOutsideOfLoop,
@@ -365,4 +377,103 @@ pub enum CompileErrorKind {
         /// The number that was an unsupported tuple index.
         number: ast::Number,
     },
+    /// Tried to use an expression which can't be evaluated at compile time in
+    /// a constant.
+    #[error("expression cannot be evaluated at compile time")]
+    NotConst,
+    /// A constant which refers to itself.
+    #[error("constant `{item}` depends on itself")]
+    ConstCycle {
+        /// The constant that depends on itself.
+        item: Item,
+    },
+    /// A constant that is referenced doesn't exist.
+    #[error("no such constant `{item}`")]
+    MissingConst {
+        /// The constant that is missing.
+        item: Item,
+    },
+    /// A binary operator in a constant was used with unsupported operands.
+    #[error("unsupported operation `{lhs} {op} {rhs}` in constant")]
+    UnsupportedConstBinaryOp {
+        /// The operator.
+        op: ast::BinOp,
+        /// The type of the left-hand side.
+        lhs: TypeInfo,
+        /// The type of the right-hand side.
+        rhs: TypeInfo,
+    },
+    /// An error raised while evaluating a constant.
+    #[error("error evaluating constant: {msg}")]
+    ConstError {
+        /// Message describing the error.
+        msg: &'static str,
+    },
+    /// A value was used where a type annotation expects a different type.
+    #[error("mismatched types, expected `{expected}` but found `{actual}`")]
+    TypeMismatch {
+        /// The type that was expected.
+        expected: String,
+        /// The type that was found.
+        actual: String,
+    },
+    /// An instance function was called on a type which doesn't have it.
+    #[error("no instance function `{name}` for type `{type_name}`")]
+    MissingInstanceFn {
+        /// The name of the instance function.
+        name: String,
+        /// The type it was called on.
+        type_name: String,
+    },
+    /// A named argument doesn't match any parameter of the called function.
+    #[error("no parameter named `{name}`")]
+    MissingParameter {
+        /// The name of the argument.
+        name: String,
+    },
+    /// A parameter was given an argument more than once.
+    #[error("argument `{name}` is given more than once")]
+    DuplicateArgument {
+        /// The name of the parameter.
+        name: String,
+    },
+    /// A parameter without a default value wasn't given an argument.
+    #[error("missing argument `{name}`")]
+    MissingArgument {
+        /// The name of the parameter.
+        name: String,
+    },
+    /// A positional argument follows a named argument.
+    #[error("positional arguments must come before named arguments")]
+    PositionalAfterNamedArgument,
+    /// Named arguments were used in a call to something which doesn't have
+    /// named parameters.
+    #[error("named arguments are only supported when calling functions with named parameters")]
+    UnsupportedNamedArgument,
+    /// Named arguments were used in a method call, where the called function
+    /// is only known at runtime.
+    #[error("named arguments aren't supported in method calls, since the called function is only known at runtime")]
+    UnsupportedInstanceNamedArgument,
+    /// A default value was used for an argument which doesn't support it.
+    #[error("default values are only supported for arguments of functions")]
+    UnsupportedArgumentDefault,
+    /// A rest argument like `..rest` which isn't the last argument.
+    #[error("`..` arguments must be the last argument")]
+    UnsupportedRestArgument,
+    /// Spreading a value like `..args` outside of the arguments of a call.
+    #[error("spreading is only supported for the arguments of a call")]
+    UnsupportedSpread,
+    /// Named arguments were used in a call which spreads its arguments.
+    #[error("named arguments can't be used together with spread arguments")]
+    UnsupportedSpreadNamedArgument,
+    /// Too few arguments in a call to a variadic function.
+    #[error("wrong number of arguments, expected at least `{expected}` but got `{actual}`")]
+    UnsupportedVariadicArgumentCount {
+        /// The function being called.
+        meta: CompileMeta,
+        /// The minimum number of arguments.
+        expected: usize,
+        /// The actual number of arguments.
+        actual: usize,
+    },
 }
@@ -6,6 +6,7 @@ use crate::loops::Loops;
 use crate::query::{Build, BuildEntry, Query};
 use crate::scopes::{Scope, ScopeGuard, Scopes};
 use crate::traits::Compile as _;
+use crate::type_check::TypeChecker;
 use crate::worker::{Expanded, LoadFileKind, Task, Worker};
 use crate::CompileResult;
 use crate::{
@@ -13,7 +14,8 @@ use crate::{
     Options, Resolve as _, SourceLoader, Sources, Spanned as _, Storage, UnitBuilder, Warnings,
 };
 use runestick::{
-    CompileMeta, CompileMetaKind, Context, Inst, InstValue, Item, Label, Source, Span, TypeCheck,
+    CompileMeta, CompileMetaKind, Context, Hash, Inst, InstValue, Item, Label, Source, Span,
+    TypeCheck,
 };
 use std::cell::RefCell;
 use std::collections::VecDeque;
@@ -181,6 +183,7 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
     } = entry;
 
     let mut asm = unit.borrow().new_assembly(source_id);
+    let cells = query.cells.remove(&item).unwrap_or_default();
 
     let mut compiler = Compiler {
         storage,
@@ -191,9 +194,10 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
         asm: &mut asm,
         items: Items::new(item.as_vec()),
         unit: unit.clone(),
-        scopes: Scopes::new(),
+        scopes: Scopes::new(cells),
         contexts: vec![],
         loops: Loops::new(),
+        return_type_check: None,
         options,
         errors,
         warnings,
@@ -207,14 +211,20 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
 
             let span = f.ast.span();
             let count = f.ast.args.items.len();
+            let variadic = is_variadic(f.ast.args.items.iter().map(|(a, _)| a));
             compiler.contexts.push(span);
+
+            if options.type_check {
+                compiler.type_check_fn(&f.ast, None)?;
+            }
+
             compiler.compile((f.ast, false))?;
 
             if unused {
                 compiler.warnings.not_used(source_id, span, None);
             } else {
                 unit.borrow_mut()
-                    .new_function(source_id, item, count, asm, f.call, args)?;
+                    .new_function(item, count, variadic, asm, f.call, args)?;
             }
         }
         Build::InstanceFunction(f) => {
@@ -222,6 +232,7 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
 
             let span = f.ast.span();
             let count = f.ast.args.items.len();
+            let variadic = is_variadic(f.ast.args.items.iter().map(|(a, _)| a));
             compiler.contexts.push(span);
 
             let source = compiler.source.clone();
@@ -245,21 +256,52 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
                 )
             })?;
 
+            if options.type_check {
+                compiler.type_check_fn(&f.ast, Some(&meta))?;
+            }
+
+            let defaults = f
+                .ast
+                .args
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, (arg, _))| arg.default_value().is_some())
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+
             compiler.compile((f.ast, true))?;
 
             if unused {
                 compiler.warnings.not_used(source_id, span, None);
             } else {
-                unit.borrow_mut().new_instance_function(
-                    source_id,
-                    item,
-                    type_of,
-                    name.as_ref(),
-                    count,
-                    asm,
-                    f.call,
-                    args,
-                )?;
+                for index in defaults {
+                    let default = Query::default_arg_item(&item, index);
+
+                    let const_value = match compiler.query.query_meta(&default)? {
+                        Some(CompileMeta {
+                            kind: CompileMetaKind::Const { const_value, .. },
+                            ..
+                        }) => const_value,
+                        _ => {
+                            return Err(CompileError::new(
+                                span,
+                                CompileErrorKind::MissingConst { item: default },
+                            ))
+                        }
+                    };
+
+                    unit.borrow_mut().new_instance_default_arg(
+                        type_of,
+                        name.as_ref(),
+                        index,
+                        const_value,
+                    );
+                }
+
+                let mut unit = unit.borrow_mut();
+                unit.new_function(item.clone(), count, variadic, asm, f.call, args)?;
+                unit.new_instance_function(&item, type_of, name.as_ref())?;
             }
         }
         Build::Closure(c) => {
@@ -271,6 +313,7 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
 
             let span = c.ast.span();
             let count = c.ast.args.len();
+            let variadic = is_variadic(c.ast.args.as_slice().iter().map(|(a, _)| a));
             compiler.contexts.push(span);
             compiler.compile((c.ast, &c.captures[..]))?;
 
@@ -278,7 +321,7 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
                 compiler.warnings.not_used(source_id, span, None);
             } else {
                 unit.borrow_mut()
-                    .new_function(source_id, item, count, asm, c.call, args)?;
+                    .new_function(item, count, variadic, asm, c.call, args)?;
             }
         }
         Build::AsyncBlock(async_block) => {
@@ -291,9 +334,9 @@ fn compile_entry(args: CompileEntryArgs<'_>) -> Result<(), CompileError> {
                 compiler.warnings.not_used(source_id, span, None);
             } else {
                 unit.borrow_mut().new_function(
-                    source_id,
                     item,
                     args,
+                    false,
                     asm,
                     async_block.call,
                     Vec::new(),
@@ -323,15 +366,26 @@ where
             ast::FnArg::Ignore(..) => {
                 args.push(String::from("_"));
             }
-            ast::FnArg::Ident(ident) => {
+            ast::FnArg::Ident(ident, ..) => {
                 args.push(ident.resolve(storage, source)?.to_string());
             }
+            ast::FnArg::Rest(_, ident) => {
+                args.push(format!("..{}", ident.resolve(storage, source)?));
+            }
         }
     }
 
     Ok(args)
 }
 
+/// Test if the last of the given arguments collects the remaining arguments.
+fn is_variadic<'a, I>(arguments: I) -> bool
+where
+    I: IntoIterator<Item = &'a ast::FnArg>,
+{
+    matches!(arguments.into_iter().last(), Some(arg) if arg.is_rest())
+}
+
 fn verify_imports(
     errors: &mut Errors,
     context: &Context,
@@ -380,7 +434,7 @@ pub(crate) struct Compiler<'a> {
     /// The current macro context.
     pub(crate) storage: &'a Storage,
     /// The context we are compiling for.
-    context: &'a Context,
+    pub(crate) context: &'a Context,
     /// Items expanded by macros.
     pub(crate) expanded: &'a HashMap<Item, Expanded>,
     /// Query system to compile required items.
@@ -397,6 +451,9 @@ pub(crate) struct Compiler<'a> {
     pub(crate) contexts: Vec<Span>,
     /// The nesting of loop we are currently in.
     pub(crate) loops: Loops,
+    /// Type check to perform on values returned from the current function,
+    /// if its return type is annotated.
+    pub(crate) return_type_check: Option<TypeCheck>,
     /// Enabled optimizations.
     pub(crate) options: &'a Options,
     /// Compilation warnings.
@@ -439,6 +496,29 @@ impl<'a> Compiler<'a> {
         Ok(None)
     }
 
+    /// Check the type annotations of the function being compiled, reporting
+    /// any mismatches as errors.
+    fn type_check_fn(
+        &mut self,
+        item_fn: &ast::ItemFn,
+        impl_meta: Option<&CompileMeta>,
+    ) -> CompileResult<()> {
+        let errors = TypeChecker::new(
+            self.context,
+            self.query,
+            self.storage,
+            self.source.clone(),
+            self.items.item(),
+        )
+        .check_fn(item_fn, impl_meta)?;
+
+        for error in errors {
+            self.errors.push(LoadError::new(self.source_id, error));
+        }
+
+        Ok(())
+    }
+
     /// Pop locals by simply popping them.
     pub(crate) fn locals_pop(&mut self, total_var_count: usize, span: Span) {
         match total_var_count {
@@ -474,6 +554,21 @@ impl<'a> Compiler<'a> {
         needs: Needs,
     ) -> CompileResult<()> {
         log::trace!("CompileMeta => {:?} {:?}", meta, needs);
+
+        if let CompileMetaKind::Const { item, .. } = &meta.kind {
+            if needs.value() {
+                self.asm.push_with_comment(
+                    Inst::Const {
+                        hash: Hash::type_hash(item),
+                    },
+                    span,
+                    format!("const `{}`", item),
+                );
+            }
+
+            return Ok(());
+        }
+
         if let Needs::Value = needs {
             match &meta.kind {
                 CompileMetaKind::Tuple { tuple, .. } if tuple.args == 0 => {
@@ -522,6 +617,17 @@ impl<'a> Compiler<'a> {
                         format!("fn `{}`", item),
                     );
                 }
+                // NB: structs with named fields evaluate to their type, so
+                // that they can be passed to native functions.
+                CompileMetaKind::Struct { type_of, object } => {
+                    self.asm.push_with_comment(
+                        Inst::Push {
+                            value: InstValue::Type(**type_of),
+                        },
+                        span,
+                        format!("struct `{}`", object.item),
+                    );
+                }
                 _ => {
                     return Err(CompileError::new(
                         span,
@@ -558,6 +664,57 @@ impl<'a> Compiler<'a> {
             .convert_path(&base, path, &self.storage, &*self.source)
     }
 
+    /// Resolve a type annotation into the type check which tests for it.
+    pub(crate) fn type_check_for_annotation(&mut self, ty: &ast::Type) -> CompileResult<TypeCheck> {
+        let path = match ty {
+            ast::Type::Unit(..) => return Ok(TypeCheck::Unit),
+            ast::Type::Path(path) => path,
+        };
+
+        let item = self.convert_path_to_item(path)?;
+
+        if let Some(meta) = self.lookup_meta(&item, path.span())? {
+            match &meta.kind {
+                CompileMetaKind::Tuple { .. }
+                | CompileMetaKind::Struct { .. }
+                | CompileMetaKind::Enum { .. } => {
+                    if let Some(type_of) = meta.type_of() {
+                        return Ok(TypeCheck::Type(*type_of));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Err(CompileError::new(
+            ty,
+            CompileErrorKind::MissingType { item },
+        ))
+    }
+
+    /// Check the value on top of the stack against the return type annotation
+    /// of the current function, if it has one.
+    pub(crate) fn check_return(&mut self, span: Span) {
+        if let Some(type_check) = self.return_type_check {
+            self.asm.push(Inst::CheckReturn { type_check }, span);
+        }
+    }
+
+    /// Return unit from the current function, checking it against the return
+    /// type annotation if it has one.
+    pub(crate) fn return_unit(&mut self, span: Span) {
+        match self.return_type_check {
+            Some(TypeCheck::Unit) | None => {
+                self.asm.push(Inst::ReturnUnit, span);
+            }
+            Some(type_check) => {
+                self.asm.push(Inst::unit(), span);
+                self.asm.push(Inst::CheckReturn { type_check }, span);
+                self.asm.push(Inst::Return, span);
+            }
+        }
+    }
+
     pub(crate) fn compile_condition(
         &mut self,
         condition: &ast::Condition,
@@ -595,12 +752,75 @@ impl<'a> Compiler<'a> {
                     self.asm.jump(then_label, span);
                 };
 
+                let scope = self.scopes.pop(expected, span)?;
+                Ok(scope)
+            }
+            ast::Condition::Chain(chain) => {
+                let span = chain.span();
+
+                let false_label = self.asm.new_label("if_condition_false");
+
+                let scope = self.scopes.child(span)?;
+                let expected = self.scopes.push(scope);
+
+                let mut refutable = false;
+
+                // NB: every link which fails to match pops all variables bound
+                // by the links before it, since they share a scope.
+                for condition in chain.conditions() {
+                    refutable |= self.compile_condition_link(condition, false_label)?;
+                }
+
+                self.asm.jump(then_label, span);
+
+                if refutable {
+                    self.asm.label(false_label)?;
+                }
+
                 let scope = self.scopes.pop(expected, span)?;
                 Ok(scope)
             }
         }
     }
 
+    /// Compile a single link in a chain of conditions, jumping to
+    /// `false_label` if it doesn't hold.
+    ///
+    /// Returns a boolean indicating if the link might fail.
+    fn compile_condition_link(
+        &mut self,
+        condition: &ast::Condition,
+        false_label: Label,
+    ) -> CompileResult<bool> {
+        let span = condition.span();
+
+        match condition {
+            ast::Condition::Expr(expr) => {
+                self.compile((&**expr, Needs::Value))?;
+                self.asm
+                    .pop_and_jump_if_not(self.scopes.local_var_count(span)?, false_label, span);
+                Ok(true)
+            }
+            ast::Condition::ExprLet(expr_let) => {
+                let load = |this: &mut Self, needs: Needs| {
+                    this.compile((&*expr_let.expr, needs))?;
+                    Ok(())
+                };
+
+                self.compile_pat(&expr_let.pat, false_label, &load)
+            }
+            ast::Condition::Chain(chain) => {
+                let mut refutable = false;
+
+                for condition in chain.conditions() {
+                    refutable |= self.compile_condition_link(condition, false_label)?;
+                }
+
+                Ok(refutable)
+            }
+        }
+    }
+
     /// Encode a vector pattern match.
     pub(crate) fn compile_pat_vec(
         &mut self,
@@ -616,6 +836,23 @@ impl<'a> Compiler<'a> {
         load(self, Needs::Value)?;
         let offset = self.scopes.decl_anon(span)?;
 
+        let mut rest = None;
+
+        for (index, (pat, _)) in pat_vec.items.iter().enumerate() {
+            if let ast::Pat::PatRest(dot_dot) = &**pat {
+                if rest.is_some() {
+                    return Err(CompileError::new(
+                        dot_dot,
+                        CompileErrorKind::UnsupportedPattern,
+                    ));
+                }
+
+                rest = Some(index);
+            }
+        }
+
+        let count = pat_vec.items.len();
+
         // Copy the temporary and check that its length matches the pattern and
         // that it is indeed a vector.
         self.asm.push(Inst::Copy { offset }, span);
@@ -623,8 +860,8 @@ impl<'a> Compiler<'a> {
         self.asm.push(
             Inst::MatchSequence {
                 type_check: TypeCheck::Vec,
-                len: pat_vec.items.len(),
-                exact: pat_vec.open_pattern.is_none(),
+                len: if rest.is_some() { count - 1 } else { count },
+                exact: rest.is_none(),
             },
             span,
         );
@@ -635,9 +872,20 @@ impl<'a> Compiler<'a> {
         for (index, (pat, _)) in pat_vec.items.iter().enumerate() {
             let span = pat.span();
 
+            // NB: items after the rest pattern are loaded relative to the end
+            // of the vector.
+            let inst = match rest {
+                Some(rest) if index == rest => continue,
+                Some(rest) if index > rest => Inst::TupleIndexGetFromEndAt {
+                    offset,
+                    index: count - index - 1,
+                },
+                _ => Inst::TupleIndexGetAt { offset, index },
+            };
+
             let load = move |this: &mut Self, needs: Needs| {
                 if needs.value() {
-                    this.asm.push(Inst::TupleIndexGetAt { offset, index }, span);
+                    this.asm.push(inst, span);
                 }
 
                 Ok(())
@@ -649,6 +897,205 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Encode an or-pattern.
+    ///
+    /// Each alternative is tested in its own scope. Variables bound by the
+    /// pattern are reserved up front and assigned by whichever alternative
+    /// matches, so that they have the same location regardless of which
+    /// alternative matched.
+    pub(crate) fn compile_pat_or(
+        &mut self,
+        pat_or: &ast::PatOr,
+        false_label: Label,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<()>,
+    ) -> CompileResult<()> {
+        let span = pat_or.span();
+        log::trace!("PatOr => {:?}", self.source.source(span));
+
+        let mut names = Vec::new();
+        self.pat_bindings(&pat_or.first, &mut names)?;
+
+        for pat in pat_or.alternatives().skip(1) {
+            let mut other = Vec::new();
+            self.pat_bindings(pat, &mut other)?;
+
+            let missing = names
+                .iter()
+                .find(|name| !other.contains(name))
+                .or_else(|| other.iter().find(|name| !names.contains(name)));
+
+            if let Some(name) = missing {
+                return Err(CompileError::new(
+                    pat,
+                    CompileErrorKind::PatOrBindingMismatch { name: name.clone() },
+                ));
+            }
+        }
+
+        load(self, Needs::Value)?;
+        let offset = self.scopes.decl_anon(span)?;
+
+        let mut slots = Vec::new();
+
+        for name in &names {
+            self.asm.push(Inst::unit(), span);
+            slots.push(self.scopes.decl_var(name, span)?);
+        }
+
+        let end_label = self.asm.new_label("pat_or_end");
+
+        for pat in pat_or.alternatives() {
+            let span = pat.span();
+            let alternative_false = self.asm.new_label("pat_or_false");
+
+            let guard = self.scopes.push_child(span)?;
+
+            let load = move |this: &mut Self, needs: Needs| {
+                if needs.value() {
+                    this.asm.push(Inst::Copy { offset }, span);
+                }
+
+                Ok(())
+            };
+
+            self.compile_pat(pat, alternative_false, &load)?;
+
+            for (name, slot) in names.iter().zip(&slots) {
+                let var = self
+                    .scopes
+                    .get_var(name, self.source_id, self.visitor, span)?;
+                var.copy(self.asm, span, format!("pat or `{}`", name));
+                self.asm.push(Inst::Replace { offset: *slot }, span);
+            }
+
+            let scope = self.scopes.pop(guard, span)?;
+            self.locals_pop(scope.local_var_count, span);
+            self.asm.jump(end_label, span);
+            self.asm.label(alternative_false)?;
+        }
+
+        // NB: none of the alternatives matched, so clean up the same way as
+        // any other failing pattern.
+        self.locals_pop(self.scopes.local_var_count(span)?, span);
+        self.asm.jump(false_label, span);
+        self.asm.label(end_label)?;
+        Ok(())
+    }
+
+    /// Collect the names of the variables bound by the given pattern.
+    fn pat_bindings(&mut self, pat: &ast::Pat, names: &mut Vec<String>) -> CompileResult<()> {
+        match pat {
+            ast::Pat::PatPath(path) => {
+                let span = path.span();
+                let item = self.convert_path_to_item(&path.path)?;
+
+                if let Some(meta) = self.lookup_meta(&item, span)? {
+                    match &meta.kind {
+                        CompileMetaKind::Tuple { tuple, .. }
+                        | CompileMetaKind::TupleVariant { tuple, .. }
+                            if tuple.args == 0 =>
+                        {
+                            return Ok(());
+                        }
+                        _ => (),
+                    }
+                }
+
+                if let Some(ident) = item.as_local() {
+                    names.push(ident.to_owned());
+                }
+            }
+            ast::Pat::PatBinding(pat_binding) => {
+                let name = pat_binding.ident.resolve(self.storage, &self.source)?;
+                names.push(name.to_string());
+                self.pat_bindings(&pat_binding.pat, names)?;
+            }
+            ast::Pat::PatOr(pat_or) => {
+                self.pat_bindings(&pat_or.first, names)?;
+            }
+            ast::Pat::PatVec(pat_vec) => {
+                for (pat, _) in &pat_vec.items {
+                    self.pat_bindings(pat, names)?;
+                }
+            }
+            ast::Pat::PatTuple(pat_tuple) => {
+                for (pat, _) in &pat_tuple.items {
+                    self.pat_bindings(pat, names)?;
+                }
+            }
+            ast::Pat::PatObject(pat_object) => {
+                for (field, _) in &pat_object.fields {
+                    match (&field.binding, &field.key) {
+                        (Some((_, pat)), _) => self.pat_bindings(pat, names)?,
+                        (None, ast::LitObjectKey::Ident(ident)) => {
+                            let name = ident.resolve(self.storage, &self.source)?;
+                            names.push(name.to_string());
+                        }
+                        (None, ast::LitObjectKey::LitStr(..)) => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    /// Encode a literal range pattern, leaving a boolean on the stack
+    /// indicating if it matched.
+    pub(crate) fn compile_pat_range(
+        &mut self,
+        pat_range: &ast::PatRange,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<()>,
+    ) -> CompileResult<()> {
+        let span = pat_range.span();
+        log::trace!("PatRange => {:?}", self.source.source(span));
+
+        let inclusive = pat_range.is_inclusive();
+
+        let inst = match (&*pat_range.start, &*pat_range.end) {
+            (ast::Pat::PatByte(start), ast::Pat::PatByte(end)) => Inst::InRangeByte {
+                start: start.resolve(self.storage, &self.source)?,
+                end: end.resolve(self.storage, &self.source)?,
+                inclusive,
+            },
+            (ast::Pat::PatChar(start), ast::Pat::PatChar(end)) => Inst::InRangeCharacter {
+                start: start.resolve(self.storage, &self.source)?,
+                end: end.resolve(self.storage, &self.source)?,
+                inclusive,
+            },
+            (ast::Pat::PatNumber(start), ast::Pat::PatNumber(end)) => Inst::InRangeInteger {
+                start: self.pat_integer(start)?,
+                end: self.pat_integer(end)?,
+                inclusive,
+            },
+            _ => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::UnsupportedPattern,
+                ));
+            }
+        };
+
+        load(self, Needs::Value)?;
+        self.asm.push(inst, span);
+        Ok(())
+    }
+
+    /// Resolve an integer literal used in a pattern.
+    fn pat_integer(&mut self, number_literal: &ast::LitNumber) -> CompileResult<i64> {
+        let span = number_literal.span();
+        let number = number_literal.resolve(self.storage, &self.source)?;
+
+        match number {
+            ast::Number::Integer(integer) => Ok(integer),
+            ast::Number::Float(..) => Err(CompileError::new(
+                span,
+                CompileErrorKind::MatchFloatInPattern,
+            )),
+        }
+    }
+
     /// Encode a vector pattern match.
     pub(crate) fn compile_pat_tuple(
         &mut self,
@@ -1005,21 +1452,10 @@ impl<'a> Compiler<'a> {
                     .push(Inst::EqCharacter { character }, lit_char.span());
             }
             ast::Pat::PatNumber(number_literal) => {
-                let span = number_literal.span();
-                let number = number_literal.resolve(&self.storage, &*self.source)?;
-
-                let integer = match number {
-                    ast::Number::Integer(integer) => integer,
-                    ast::Number::Float(..) => {
-                        return Err(CompileError::new(
-                            span,
-                            CompileErrorKind::MatchFloatInPattern,
-                        ));
-                    }
-                };
-
+                let integer = self.pat_integer(number_literal)?;
                 load(self, Needs::Value)?;
-                self.asm.push(Inst::EqInteger { integer }, span);
+                self.asm
+                    .push(Inst::EqInteger { integer }, number_literal.span());
             }
             ast::Pat::PatString(pat_string) => {
                 let span = pat_string.span();
@@ -1040,6 +1476,35 @@ impl<'a> Compiler<'a> {
                 self.compile_pat_object(object, false_label, &load)?;
                 return Ok(true);
             }
+            ast::Pat::PatOr(pat_or) => {
+                self.compile_pat_or(pat_or, false_label, &load)?;
+                return Ok(true);
+            }
+            ast::Pat::PatBinding(pat_binding) => {
+                let span = pat_binding.ident.span();
+                load(self, Needs::Value)?;
+                let name = pat_binding.ident.resolve(self.storage, &self.source)?;
+                let offset = self.scopes.decl_var(name.as_ref(), span)?;
+
+                let load = move |this: &mut Self, needs: Needs| {
+                    if needs.value() {
+                        this.asm.push(Inst::Copy { offset }, span);
+                    }
+
+                    Ok(())
+                };
+
+                return self.compile_pat(&pat_binding.pat, false_label, &load);
+            }
+            ast::Pat::PatRange(pat_range) => {
+                self.compile_pat_range(pat_range, &load)?;
+            }
+            ast::Pat::PatRest(dot_dot) => {
+                return Err(CompileError::new(
+                    dot_dot,
+                    CompileErrorKind::UnsupportedPattern,
+                ));
+            }
         }
 
         self.asm
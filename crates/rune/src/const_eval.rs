@@ -0,0 +1,406 @@
+//! Compile-time evaluation of constant expressions.
+
+use crate::ast;
+use crate::collections::HashMap;
+use crate::query::Query;
+use crate::CompileResult;
+use crate::{CompileError, CompileErrorKind, Resolve as _, Spanned as _};
+use runestick::{
+    CompileMeta, CompileMetaKind, CompileMetaTuple, ConstValue, FormatSpec, Hash, Item, Rtti,
+    Source, Span,
+};
+use std::collections::BTreeMap;
+use std::convert::TryFrom as _;
+use std::sync::Arc;
+
+/// An evaluator for constant expressions.
+///
+/// Supports literals, arithmetic and logical operators, string and template
+/// concatenation, vec, tuple, and object literals, structs declared in Rune,
+/// and references to other constants.
+pub(crate) struct ConstEval<'a> {
+    /// The query system, used to look up other constants.
+    pub(crate) query: &'a mut Query,
+    /// The source of the expression being evaluated.
+    pub(crate) source: &'a Source,
+    /// The item that paths are resolved relative to.
+    pub(crate) base: Item,
+}
+
+impl ConstEval<'_> {
+    /// Evaluate the given expression.
+    pub(crate) fn eval(&mut self, expr: &ast::Expr) -> CompileResult<ConstValue> {
+        let span = expr.span();
+        log::trace!("ConstEval => {:?}", self.source.source(span));
+
+        match expr {
+            ast::Expr::ExprLit(expr_lit) => self.eval_lit(&expr_lit.lit),
+            ast::Expr::ExprGroup(expr_group) => self.eval(&expr_group.expr),
+            ast::Expr::ExprUnary(expr_unary) => self.eval_unary(expr_unary),
+            ast::Expr::ExprBinary(expr_binary) => self.eval_binary(expr_binary),
+            ast::Expr::Path(path) => self.eval_path(path),
+            ast::Expr::ExprCall(expr_call) => self.eval_call(expr_call),
+            _ => Err(CompileError::new(span, CompileErrorKind::NotConst)),
+        }
+    }
+
+    fn eval_lit(&mut self, lit: &ast::Lit) -> CompileResult<ConstValue> {
+        let storage = &self.query.storage;
+
+        Ok(match lit {
+            ast::Lit::Unit(..) => ConstValue::Unit,
+            ast::Lit::Bool(lit_bool) => ConstValue::Bool(lit_bool.value),
+            ast::Lit::Byte(lit_byte) => ConstValue::Byte(lit_byte.resolve(storage, self.source)?),
+            ast::Lit::Char(lit_char) => ConstValue::Char(lit_char.resolve(storage, self.source)?),
+            ast::Lit::Number(lit_number) => match lit_number.resolve(storage, self.source)? {
+                ast::Number::Integer(n) => ConstValue::Integer(n),
+                ast::Number::Float(n) => ConstValue::Float(n),
+            },
+            ast::Lit::Str(lit_str) => {
+                ConstValue::String(lit_str.resolve(storage, self.source)?.into_owned())
+            }
+            ast::Lit::ByteStr(lit_byte_str) => {
+                ConstValue::Bytes(lit_byte_str.resolve(storage, self.source)?.into_owned())
+            }
+            ast::Lit::Template(lit_template) => self.eval_template(lit_template)?,
+            ast::Lit::Vec(lit_vec) => {
+                let mut vec = Vec::with_capacity(lit_vec.items.len());
+
+                for expr in &lit_vec.items {
+                    vec.push(self.eval(expr)?);
+                }
+
+                ConstValue::Vec(vec)
+            }
+            ast::Lit::Tuple(lit_tuple) => {
+                let mut tuple = Vec::with_capacity(lit_tuple.items.len());
+
+                for (expr, _) in &lit_tuple.items {
+                    tuple.push(self.eval(expr)?);
+                }
+
+                ConstValue::Tuple(tuple.into_boxed_slice())
+            }
+            ast::Lit::Object(lit_object) => self.eval_object(lit_object)?,
+        })
+    }
+
+    fn eval_template(&mut self, lit_template: &ast::LitTemplate) -> CompileResult<ConstValue> {
+        let template = lit_template.resolve(&self.query.storage, self.source)?;
+        let mut buf = String::with_capacity(template.size_hint);
+
+        for c in &template.components {
+            match c {
+                ast::TemplateComponent::String(string) => {
+                    buf.push_str(string);
+                }
+                ast::TemplateComponent::Expr(expr, spec) => {
+                    let value = self.eval(expr)?.to_value();
+
+                    spec.unwrap_or_else(FormatSpec::default)
+                        .format(&value, &mut buf, &mut |_, _, _| Ok(false))
+                        .map_err(|_| {
+                            CompileError::new(
+                                &**expr,
+                                CompileErrorKind::ConstError {
+                                    msg: "value cannot be formatted",
+                                },
+                            )
+                        })?;
+                }
+            }
+        }
+
+        Ok(ConstValue::String(buf))
+    }
+
+    fn eval_object(&mut self, lit_object: &ast::LitObject) -> CompileResult<ConstValue> {
+        if let Some((dot_dot, _)) = &lit_object.rest {
+            return Err(CompileError::new(dot_dot, CompileErrorKind::NotConst));
+        }
+
+        let mut object = BTreeMap::new();
+        let mut keys = HashMap::<String, Span>::new();
+
+        for assign in &lit_object.assignments {
+            let span = assign.span();
+            let key = assign
+                .key
+                .resolve(&self.query.storage, self.source)?
+                .into_owned();
+
+            let expr = match &assign.assign {
+                Some((_, expr)) => expr,
+                None => return Err(CompileError::new(span, CompileErrorKind::NotConst)),
+            };
+
+            if let Some(existing) = keys.insert(key.clone(), span) {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::DuplicateObjectKey {
+                        existing,
+                        object: lit_object.span(),
+                    },
+                ));
+            }
+
+            object.insert(key, self.eval(expr)?);
+        }
+
+        let path = match &lit_object.ident {
+            ast::LitObjectIdent::Anonymous(..) => return Ok(ConstValue::Object(object)),
+            ast::LitObjectIdent::Named(path) => path,
+        };
+
+        let meta = self.lookup_meta(path)?;
+
+        let struct_ = match &meta.kind {
+            CompileMetaKind::Struct { object, .. } => object,
+            _ => return Err(CompileError::new(path, CompileErrorKind::NotConst)),
+        };
+
+        let fields = match &struct_.fields {
+            Some(fields) => fields,
+            None => return Err(CompileError::new(path, CompileErrorKind::NotConst)),
+        };
+
+        if let Some((key, span)) = keys.iter().find(|(key, _)| !fields.contains(*key)) {
+            return Err(CompileError::new(
+                *span,
+                CompileErrorKind::LitObjectNotField {
+                    field: key.clone(),
+                    item: struct_.item.clone(),
+                },
+            ));
+        }
+
+        if let Some(field) = fields.iter().find(|field| !object.contains_key(*field)) {
+            return Err(CompileError::new(
+                lit_object,
+                CompileErrorKind::LitObjectMissingField {
+                    field: field.clone(),
+                    item: struct_.item.clone(),
+                },
+            ));
+        }
+
+        let rtti = Arc::new(Rtti::new(
+            Hash::type_hash(&struct_.item),
+            struct_.item.clone(),
+            struct_.declared_fields.clone(),
+        ));
+
+        Ok(ConstValue::TypedObject(rtti, object))
+    }
+
+    /// Evaluate a call, which is only constant if it constructs a tuple
+    /// struct.
+    fn eval_call(&mut self, expr_call: &ast::ExprCall) -> CompileResult<ConstValue> {
+        let path = match &*expr_call.expr {
+            ast::Expr::Path(path) => path,
+            _ => return Err(CompileError::new(expr_call, CompileErrorKind::NotConst)),
+        };
+
+        let meta = self.lookup_meta(path)?;
+
+        let tuple = match &meta.kind {
+            CompileMetaKind::Tuple { tuple, .. } => tuple,
+            _ => return Err(CompileError::new(expr_call, CompileErrorKind::NotConst)),
+        };
+
+        if tuple.args != expr_call.args.items.len() {
+            return Err(CompileError::new(
+                expr_call,
+                CompileErrorKind::UnsupportedArgumentCount {
+                    meta: meta.clone(),
+                    expected: tuple.args,
+                    actual: expr_call.args.items.len(),
+                },
+            ));
+        }
+
+        let mut values = Vec::with_capacity(tuple.args);
+
+        for (expr, _) in &expr_call.args.items {
+            values.push(self.eval(expr)?);
+        }
+
+        Ok(ConstValue::TypedTuple(
+            tuple_rtti(tuple),
+            values.into_boxed_slice(),
+        ))
+    }
+
+    fn eval_unary(&mut self, expr_unary: &ast::ExprUnary) -> CompileResult<ConstValue> {
+        let span = expr_unary.span();
+
+        match (expr_unary.op, self.eval(&expr_unary.expr)?) {
+            (ast::UnaryOp::Not, ConstValue::Bool(b)) => Ok(ConstValue::Bool(!b)),
+            (ast::UnaryOp::Not, ConstValue::Integer(n)) => Ok(ConstValue::Integer(!n)),
+            (op, _) => Err(CompileError::new(
+                span,
+                CompileErrorKind::UnsupportedUnaryOp { op },
+            )),
+        }
+    }
+
+    fn eval_binary(&mut self, expr_binary: &ast::ExprBinary) -> CompileResult<ConstValue> {
+        use ast::BinOp::*;
+
+        let span = expr_binary.span();
+        let op = expr_binary.op;
+
+        let lhs = self.eval(&expr_binary.lhs)?;
+
+        // NB: logical operators short-circuit, just like they do at runtime.
+        match (op, &lhs) {
+            (And, ConstValue::Bool(false)) | (Or, ConstValue::Bool(true)) => return Ok(lhs),
+            _ => (),
+        }
+
+        let rhs = self.eval(&expr_binary.rhs)?;
+
+        let value = match (op, lhs, rhs) {
+            (Eq, a, b) => ConstValue::Bool(a == b),
+            (Neq, a, b) => ConstValue::Bool(a != b),
+            (op, ConstValue::Integer(a), ConstValue::Integer(b)) => {
+                return eval_integer(span, op, a, b);
+            }
+            (op, ConstValue::Float(a), ConstValue::Float(b)) => match op {
+                Add => ConstValue::Float(a + b),
+                Sub => ConstValue::Float(a - b),
+                Mul => ConstValue::Float(a * b),
+                Div => ConstValue::Float(a / b),
+                Rem => ConstValue::Float(a % b),
+                Lt => ConstValue::Bool(a < b),
+                Gt => ConstValue::Bool(a > b),
+                Lte => ConstValue::Bool(a <= b),
+                Gte => ConstValue::Bool(a >= b),
+                op => return Err(unsupported_binary_op(span, op)),
+            },
+            (op, ConstValue::Bool(a), ConstValue::Bool(b)) => match op {
+                And | BitAnd => ConstValue::Bool(a & b),
+                Or | BitOr => ConstValue::Bool(a | b),
+                BitXor => ConstValue::Bool(a ^ b),
+                op => return Err(unsupported_binary_op(span, op)),
+            },
+            (Add, ConstValue::String(mut a), ConstValue::String(b)) => {
+                a.push_str(&b);
+                ConstValue::String(a)
+            }
+            (op, lhs, rhs) => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::UnsupportedConstBinaryOp {
+                        op,
+                        lhs: lhs.type_info(),
+                        rhs: rhs.type_info(),
+                    },
+                ));
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn eval_path(&mut self, path: &ast::Path) -> CompileResult<ConstValue> {
+        let meta = self.lookup_meta(path)?;
+
+        match meta.kind {
+            CompileMetaKind::Const { const_value, .. } => Ok(const_value),
+            CompileMetaKind::Tuple { tuple, .. } if tuple.args == 0 => {
+                Ok(ConstValue::TypedTuple(tuple_rtti(&tuple), Box::new([])))
+            }
+            _ => Err(CompileError::new(path, CompileErrorKind::NotConst)),
+        }
+    }
+
+    /// Look up the metadata of the given path, relative to the base of the
+    /// evaluated expression.
+    fn lookup_meta(&mut self, path: &ast::Path) -> CompileResult<CompileMeta> {
+        let span = path.span();
+
+        let item = self.query.unit.borrow().convert_path(
+            &self.base,
+            path,
+            &self.query.storage,
+            self.source,
+        )?;
+
+        let mut base = self.base.clone();
+
+        loop {
+            let current = base.join(&item);
+
+            if self.query.evaluating.contains(&current) {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::ConstCycle { item: current },
+                ));
+            }
+
+            if let Some(meta) = self.query.query_meta(&current)? {
+                return Ok(meta);
+            }
+
+            if base.pop().is_none() {
+                break;
+            }
+        }
+
+        Err(CompileError::new(
+            span,
+            CompileErrorKind::MissingConst { item },
+        ))
+    }
+}
+
+/// Construct the runtime type information of a tuple struct.
+fn tuple_rtti(tuple: &CompileMetaTuple) -> Arc<Rtti> {
+    Arc::new(Rtti::new(tuple.hash, tuple.item.clone(), None))
+}
+
+/// Evaluate a binary operation over two integers.
+fn eval_integer(span: Span, op: ast::BinOp, a: i64, b: i64) -> CompileResult<ConstValue> {
+    use ast::BinOp::*;
+
+    let n = match op {
+        Add => a.checked_add(b),
+        Sub => a.checked_sub(b),
+        Mul => a.checked_mul(b),
+        Div | Rem if b == 0 => {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::ConstError {
+                    msg: "division by zero",
+                },
+            ));
+        }
+        Div => a.checked_div(b),
+        Rem => a.checked_rem(b),
+        BitAnd => Some(a & b),
+        BitXor => Some(a ^ b),
+        BitOr => Some(a | b),
+        Shl => u32::try_from(b).ok().and_then(|b| a.checked_shl(b)),
+        Shr => u32::try_from(b).ok().and_then(|b| a.checked_shr(b)),
+        Lt => return Ok(ConstValue::Bool(a < b)),
+        Gt => return Ok(ConstValue::Bool(a > b)),
+        Lte => return Ok(ConstValue::Bool(a <= b)),
+        Gte => return Ok(ConstValue::Bool(a >= b)),
+        op => return Err(unsupported_binary_op(span, op)),
+    };
+
+    match n {
+        Some(n) => Ok(ConstValue::Integer(n)),
+        None => Err(CompileError::new(
+            span,
+            CompileErrorKind::ConstError {
+                msg: "numerical overflow",
+            },
+        )),
+    }
+}
+
+fn unsupported_binary_op(span: Span, op: ast::BinOp) -> CompileError {
+    CompileError::new(span, CompileErrorKind::UnsupportedBinaryOp { op })
+}
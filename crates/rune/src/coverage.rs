@@ -0,0 +1,154 @@
+//! Reporting of coverage collected by the virtual machine.
+//!
+//! See [runestick::Coverage] for how to collect coverage.
+
+use crate::Sources;
+use runestick::{Coverage, SourceId};
+use std::collections::BTreeMap;
+use std::io;
+
+/// Coverage for a single source file.
+#[derive(Debug, Default)]
+struct FileCoverage {
+    /// Execution count by line.
+    lines: BTreeMap<usize, usize>,
+    /// Functions declared in the file, by line, name, and execution count.
+    functions: Vec<(usize, String, usize)>,
+    /// Conditional jumps in the file, by line, instruction pointer, and the
+    /// number of times they were taken or not taken.
+    branches: Vec<(usize, usize, usize, usize)>,
+}
+
+/// Emit the collected coverage in the [LCOV tracefile format].
+///
+/// Coverage is mapped to source lines through the debug information of the
+/// unit being covered, so nothing will be emitted unless it was compiled with
+/// debug info enabled.
+///
+/// [LCOV tracefile format]: http://ltp.sourceforge.net/coverage/lcov/geninfo.1.php
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Coverage, Source, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let context = Arc::new(rune::default_context()?);
+/// let mut sources = rune::Sources::new();
+/// sources.insert(Source::new("entry", r#"
+/// fn main(n) {
+///     if n > 1 { 1 } else { 2 }
+/// }
+/// "#));
+///
+/// let mut errors = rune::Errors::new();
+/// let mut warnings = rune::Warnings::new();
+/// let unit = rune::load_sources(&*context, &Default::default(), &mut sources, &mut errors, &mut warnings)?;
+/// let unit = Arc::new(unit);
+///
+/// let coverage = Arc::new(Coverage::new(unit.clone()));
+/// let mut vm = Vm::new(context, unit);
+/// vm.set_coverage(Some(coverage.clone()));
+/// vm.execute(&["main"], (2i64,))?.complete()?;
+///
+/// let mut out = Vec::new();
+/// rune::coverage::emit_lcov(&mut out, &coverage, &sources)?;
+/// let out = String::from_utf8(out)?;
+/// assert!(out.contains("SF:entry"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn emit_lcov<O>(out: &mut O, coverage: &Coverage, sources: &Sources) -> io::Result<()>
+where
+    O: io::Write,
+{
+    let debug = match coverage.unit().debug_info() {
+        Some(debug) => debug,
+        None => return Ok(()),
+    };
+
+    let mut files = BTreeMap::<SourceId, FileCoverage>::new();
+
+    for (ip, inst) in debug.instructions.iter().enumerate() {
+        let source = match sources.get(inst.source_id) {
+            Some(source) => source,
+            None => continue,
+        };
+
+        let (line, _) = source.position_to_unicode_line_char(inst.span.start);
+        let line = line + 1;
+        let hits = coverage.hits(ip);
+        let file = files.entry(inst.source_id).or_default();
+
+        let count = file.lines.entry(line).or_default();
+        *count = usize::max(*count, hits);
+
+        if let Some((_, signature)) = debug.function_at(ip) {
+            file.functions
+                .push((line, signature.path.to_string(), hits));
+        }
+
+        if let Some((taken, not_taken)) = coverage.branch_hits(ip) {
+            file.branches.push((line, ip, taken, not_taken));
+        }
+    }
+
+    for (source_id, file) in files {
+        let source = match sources.get(source_id) {
+            Some(source) => source,
+            None => continue,
+        };
+
+        writeln!(out, "TN:")?;
+
+        match source.path() {
+            Some(path) => writeln!(out, "SF:{}", path.display())?,
+            None => writeln!(out, "SF:{}", source.name())?,
+        }
+
+        for (line, name, _) in &file.functions {
+            writeln!(out, "FN:{},{}", line, name)?;
+        }
+
+        for (_, name, hits) in &file.functions {
+            writeln!(out, "FNDA:{},{}", hits, name)?;
+        }
+
+        let functions_hit = file.functions.iter().filter(|f| f.2 > 0).count();
+        writeln!(out, "FNF:{}", file.functions.len())?;
+        writeln!(out, "FNH:{}", functions_hit)?;
+
+        let mut branches_hit = 0;
+
+        for (line, ip, taken, not_taken) in &file.branches {
+            let line_hit = file.lines.get(line).copied().unwrap_or_default() > 0;
+
+            for (branch, count) in [*taken, *not_taken].iter().enumerate() {
+                if *count > 0 {
+                    branches_hit += 1;
+                }
+
+                if line_hit {
+                    writeln!(out, "BRDA:{},{},{},{}", line, ip, branch, count)?;
+                } else {
+                    writeln!(out, "BRDA:{},{},{},-", line, ip, branch)?;
+                }
+            }
+        }
+
+        writeln!(out, "BRF:{}", file.branches.len() * 2)?;
+        writeln!(out, "BRH:{}", branches_hit)?;
+
+        for (line, hits) in &file.lines {
+            writeln!(out, "DA:{},{}", line, hits)?;
+        }
+
+        let lines_hit = file.lines.values().filter(|hits| **hits > 0).count();
+        writeln!(out, "LF:{}", file.lines.len())?;
+        writeln!(out, "LH:{}", lines_hit)?;
+        writeln!(out, "end_of_record")?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Runtime helpers for loading code and emitting diagnostics.
 
 use crate::unit_builder::LinkerError;
+use crate::warning::DisplayVariants;
 use crate::{
     CompileErrorKind, Errors, LoadError, LoadErrorKind, ParseErrorKind, Sources, Spanned as _,
     WarningKind, Warnings,
@@ -144,6 +145,26 @@ impl EmitDiagnostics for Warnings {
                             .with_message("unnecessary semicolon"),
                     );
 
+                    None
+                }
+                WarningKind::UnreachablePattern { span, context } => {
+                    labels.push(
+                        Label::primary(w.source_id, span.start..span.end)
+                            .with_message("unreachable pattern"),
+                    );
+
+                    *context
+                }
+                WarningKind::NonExhaustiveMatch { span, missing } => {
+                    labels.push(
+                        Label::primary(w.source_id, span.start..span.end)
+                            .with_message(format!("match is missing {}", DisplayVariants(missing))),
+                    );
+
+                    notes.push(String::from(
+                        "Hint: Add branches for the missing variants, or a `_` branch",
+                    ));
+
                     None
                 }
             };
@@ -2,7 +2,9 @@ use crate::ast;
 use crate::collections::HashMap;
 use crate::index_scopes::IndexScopes;
 use crate::items::Items;
-use crate::query::{Build, BuildEntry, Function, Indexed, IndexedEntry, InstanceFunction, Query};
+use crate::query::{
+    Build, BuildEntry, FnSignature, Function, Indexed, IndexedEntry, InstanceFunction, Query,
+};
 use crate::worker::{Import, LoadFileKind, Macro, MacroKind, Task};
 use crate::CompileResult;
 use crate::{
@@ -94,6 +96,29 @@ impl<'a> Indexer<'a> {
 
         Ok(())
     }
+
+    /// Index a constant, which is evaluated once it's queried for.
+    fn index_const(&mut self, item_const: &ast::ItemConst) -> CompileResult<()> {
+        if let Some(first) = item_const.attributes.first() {
+            return Err(CompileError::internal(
+                first,
+                "const attributes are not supported",
+            ));
+        }
+
+        let name = item_const.name.resolve(&self.storage, &self.source)?;
+        let _guard = self.items.push_name(name.as_ref());
+
+        self.query.index_const(
+            self.items.item(),
+            item_const.span(),
+            item_const.expr.clone(),
+            self.source.clone(),
+            self.source_id,
+        )?;
+
+        Ok(())
+    }
 }
 
 pub(crate) trait Index<T> {
@@ -138,13 +163,34 @@ impl Index<ast::ItemFn> for Indexer<'_> {
 
         let guard = self.scopes.push_function(decl_fn.async_.is_some());
 
-        for (arg, _) in &decl_fn.args.items {
+        let count = decl_fn.args.items.len();
+
+        for (index, (arg, _)) in decl_fn.args.items.iter().enumerate() {
+            if arg.is_rest() && index + 1 != count {
+                return Err(CompileError::new(
+                    arg,
+                    CompileErrorKind::UnsupportedRestArgument,
+                ));
+            }
+
+            // NB: default values are evaluated as constants, relative to the
+            // function.
+            if let Some(expr) = arg.default_value() {
+                self.query.index_const(
+                    Query::default_arg_item(&item, index),
+                    expr.span(),
+                    Box::new(expr.clone()),
+                    self.source.clone(),
+                    self.source_id,
+                )?;
+            }
+
             match arg {
                 ast::FnArg::Self_(s) => {
                     let span = s.span();
                     self.scopes.declare("self", span)?;
                 }
-                ast::FnArg::Ident(ident) => {
+                ast::FnArg::Ident(ident, ..) | ast::FnArg::Rest(_, ident) => {
                     let span = ident.span();
                     let ident = ident.resolve(&self.storage, &*self.source)?;
                     self.scopes.declare(ident.as_ref(), span)?;
@@ -157,6 +203,22 @@ impl Index<ast::ItemFn> for Indexer<'_> {
 
         let f = guard.into_function(span)?;
         let call = Self::call(f.generator, f.is_async);
+        self.query.insert_cells(item.clone(), f.cells);
+
+        self.query.signatures.insert(
+            item.clone(),
+            FnSignature {
+                source: self.source.clone(),
+                args: decl_fn
+                    .args
+                    .items
+                    .iter()
+                    .map(|(arg, _)| arg.clone())
+                    .collect(),
+                output: decl_fn.output.as_ref().map(|(_, ty)| ty.clone()),
+                call,
+            },
+        );
 
         let fun = Function {
             ast: decl_fn.clone(),
@@ -228,7 +290,7 @@ impl Index<ast::ItemFn> for Indexer<'_> {
                     span,
                     source: self.source.clone(),
                     source_id: self.source_id,
-                    indexed: Indexed::Function(fun),
+                    indexed: Indexed::Function(Box::new(fun)),
                 },
             )?;
         }
@@ -250,10 +312,12 @@ impl Index<ast::ExprAsync> for Indexer<'_> {
         }
 
         let _guard = self.items.push_async_block();
-        let guard = self.scopes.push_closure(true);
+        // NB: async blocks copy the variables they capture.
+        let guard = self.scopes.push_closure(true, true);
         self.index(&expr_async.block)?;
 
         let c = guard.into_closure(span)?;
+        self.query.insert_cells(self.items.item(), c.cells);
 
         let captures = Arc::new(c.captures);
         let call = Self::call(c.generator, c.is_async);
@@ -322,6 +386,10 @@ impl Index<ast::ExprLet> for Indexer<'_> {
         let span = expr_let.span();
         log::trace!("ExprLet => {:?}", self.source.source(span));
 
+        if let Some((_, block)) = expr_let.else_.as_deref() {
+            self.index(block)?;
+        }
+
         self.index(&expr_let.pat)?;
         self.index(&*expr_let.expr)?;
         Ok(())
@@ -359,12 +427,23 @@ impl Index<ast::Pat> for Indexer<'_> {
             ast::Pat::PatTuple(pat_tuple) => {
                 self.index(pat_tuple)?;
             }
+            ast::Pat::PatOr(pat_or) => {
+                for pat in pat_or.alternatives() {
+                    self.index(pat)?;
+                }
+            }
+            ast::Pat::PatBinding(pat_binding) => {
+                self.index(&pat_binding.ident)?;
+                self.index(&*pat_binding.pat)?;
+            }
             ast::Pat::PatByte(..) => (),
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatNumber(..) => (),
             ast::Pat::PatString(..) => (),
             ast::Pat::PatUnit(..) => (),
             ast::Pat::PatChar(..) => (),
+            ast::Pat::PatRange(..) => (),
+            ast::Pat::PatRest(..) => (),
         }
 
         Ok(())
@@ -497,6 +576,9 @@ impl Index<ast::Expr> for Indexer<'_> {
             ast::Expr::ExprSelect(expr_select) => {
                 self.index(expr_select)?;
             }
+            ast::Expr::ExprSpread(expr_spread) => {
+                self.index(&*expr_spread.expr)?;
+            }
             // ignored because they have no effect on indexing.
             ast::Expr::ExprCall(expr_call) => {
                 self.index(expr_call)?;
@@ -554,6 +636,16 @@ impl Index<ast::ExprBinary> for Indexer<'_> {
 
         self.index(&*expr_binary.lhs)?;
         self.index(&*expr_binary.rhs)?;
+
+        if expr_binary.op.is_assign() {
+            if let ast::Expr::Path(path) = &*expr_binary.lhs {
+                if let Some(ident) = path.try_as_ident() {
+                    let ident = ident.resolve(&self.storage, &self.source)?;
+                    self.scopes.mark_assign(ident.as_ref());
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -591,6 +683,11 @@ impl Index<ast::Condition> for Indexer<'_> {
             ast::Condition::ExprLet(expr_let) => {
                 self.index(&**expr_let)?;
             }
+            ast::Condition::Chain(chain) => {
+                for condition in chain.conditions() {
+                    self.index(condition)?;
+                }
+            }
         }
 
         Ok(())
@@ -728,6 +825,10 @@ impl Index<ast::Item> for Indexer<'_> {
                     guards.push(self.items.push_name(ident.as_ref()));
                 }
 
+                for item_const in &item_impl.consts {
+                    self.index_const(item_const)?;
+                }
+
                 self.impl_items.push(self.items.item());
 
                 for item_fn in &item_impl.functions {
@@ -755,6 +856,9 @@ impl Index<ast::Item> for Indexer<'_> {
                     }
                 }
             }
+            ast::Item::ItemConst(item_const) => {
+                self.index_const(item_const)?;
+            }
             ast::Item::MacroCall(macro_call) => {
                 let _guard = self.items.push_macro();
 
@@ -833,15 +937,33 @@ impl Index<ast::ExprClosure> for Indexer<'_> {
         log::trace!("ExprClosure => {:?}", self.source.source(span));
 
         let _guard = self.items.push_closure();
-        let guard = self.scopes.push_closure(expr_closure.async_.is_some());
+        let guard = self
+            .scopes
+            .push_closure(expr_closure.async_.is_some(), expr_closure.move_.is_some());
         let span = expr_closure.span();
 
-        for (arg, _) in expr_closure.args.as_slice() {
+        let args = expr_closure.args.as_slice();
+
+        for (index, (arg, _)) in args.iter().enumerate() {
+            if arg.is_rest() && index + 1 != args.len() {
+                return Err(CompileError::new(
+                    arg,
+                    CompileErrorKind::UnsupportedRestArgument,
+                ));
+            }
+
+            if let ast::FnArg::Ident(_, _, Some((_, expr))) = arg {
+                return Err(CompileError::new(
+                    &**expr,
+                    CompileErrorKind::UnsupportedArgumentDefault,
+                ));
+            }
+
             match arg {
                 ast::FnArg::Self_(s) => {
                     return Err(CompileError::new(s, CompileErrorKind::UnsupportedSelf));
                 }
-                ast::FnArg::Ident(ident) => {
+                ast::FnArg::Ident(ident, ..) | ast::FnArg::Rest(_, ident) => {
                     let ident = ident.resolve(&self.storage, &*self.source)?;
                     self.scopes.declare(ident.as_ref(), span)?;
                 }
@@ -851,7 +973,13 @@ impl Index<ast::ExprClosure> for Indexer<'_> {
 
         self.index(&*expr_closure.body)?;
 
-        let c = guard.into_closure(span)?;
+        let mut c = guard.into_closure(span)?;
+
+        // NB: captured variables might be shared cells, depending on how
+        // they're captured when the closure is constructed.
+        c.cells
+            .extend(c.captures.iter().map(|capture| capture.ident.clone()));
+        self.query.insert_cells(self.items.item(), c.cells);
 
         let captures = Arc::new(c.captures);
         let call = Self::call(c.generator, c.is_async);
@@ -1066,7 +1194,7 @@ impl Index<ast::LitTemplate> for Indexer<'_> {
 
         for c in &template.components {
             match c {
-                ast::TemplateComponent::Expr(expr) => {
+                ast::TemplateComponent::Expr(expr, _) => {
                     self.index(&**expr)?;
                 }
                 ast::TemplateComponent::String(..) => (),
@@ -1077,6 +1205,25 @@ impl Index<ast::LitTemplate> for Indexer<'_> {
     }
 }
 
+impl Index<ast::FormatArgs> for Indexer<'_> {
+    fn index(&mut self, format_args: &ast::FormatArgs) -> CompileResult<()> {
+        let span = format_args.span();
+        log::trace!("FormatArgs => {:?}", self.source.source(span));
+
+        for expr in format_args.exprs() {
+            self.index(expr)?;
+        }
+
+        let format = format_args.resolve(&self.storage, &self.source)?;
+
+        for expr in &format.captures {
+            self.index(expr)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Index<ast::LitTuple> for Indexer<'_> {
     fn index(&mut self, lit_tuple: &ast::LitTuple) -> CompileResult<()> {
         let span = lit_tuple.span();
@@ -1114,6 +1261,10 @@ impl Index<ast::LitObject> for Indexer<'_> {
             }
         }
 
+        if let Some((_, expr)) = &lit_object.rest {
+            self.index(&**expr)?;
+        }
+
         Ok(())
     }
 }
@@ -25,6 +25,7 @@ impl IndexScopeGuard {
         match level {
             IndexScopeLevel::IndexClosure(closure) => Ok(Closure {
                 captures: closure.captures,
+                cells: closure.cells,
                 generator: closure.generator,
                 is_async: closure.is_async,
                 has_await: closure.has_await,
@@ -45,6 +46,7 @@ impl IndexScopeGuard {
 
         match level {
             IndexScopeLevel::IndexFunction(fun) => Ok(Function {
+                cells: fun.cells,
                 generator: fun.generator,
                 is_async: fun.is_async,
                 has_await: fun.has_await,
@@ -78,10 +80,15 @@ impl IndexScope {
 #[derive(Debug, Clone)]
 pub struct IndexClosure {
     is_async: bool,
+    /// If the closure captures variables by copying them, like a `move`
+    /// closure.
+    is_move: bool,
     /// Variables which could not be found in the immediate scope, and
     /// marked as needed to be captured from the outer scope.
     captures: Vec<CompileMetaCapture>,
     existing: HashSet<String>,
+    /// Variables which are captured by a shared cell in a nested closure.
+    cells: HashSet<String>,
     scope: IndexScope,
     generator: bool,
     has_await: bool,
@@ -89,11 +96,13 @@ pub struct IndexClosure {
 
 impl IndexClosure {
     /// Construct a new closure.
-    pub fn new(is_async: bool) -> Self {
+    pub fn new(is_async: bool, is_move: bool) -> Self {
         Self {
             is_async,
+            is_move,
             captures: Vec::new(),
             existing: HashSet::new(),
+            cells: HashSet::new(),
             scope: IndexScope::new(),
             generator: false,
             has_await: false,
@@ -102,6 +111,7 @@ impl IndexClosure {
 }
 
 pub(crate) struct Function {
+    pub(crate) cells: HashSet<String>,
     pub(crate) generator: bool,
     pub(crate) is_async: bool,
     #[allow(dead_code)]
@@ -110,6 +120,7 @@ pub(crate) struct Function {
 
 pub(crate) struct Closure {
     pub(crate) captures: Vec<CompileMetaCapture>,
+    pub(crate) cells: HashSet<String>,
     pub(crate) generator: bool,
     pub(crate) is_async: bool,
     #[allow(dead_code)]
@@ -120,6 +131,8 @@ pub(crate) struct Closure {
 pub struct IndexFunction {
     is_async: bool,
     scope: IndexScope,
+    /// Variables which are captured by a shared cell in a nested closure.
+    cells: HashSet<String>,
     generator: bool,
     has_await: bool,
 }
@@ -130,6 +143,7 @@ impl IndexFunction {
         Self {
             is_async,
             scope: IndexScope::new(),
+            cells: HashSet::new(),
             generator: false,
             has_await: false,
         }
@@ -242,6 +256,75 @@ impl IndexScopes {
         }
     }
 
+    /// Mark that the given variable is assigned to.
+    ///
+    /// If the variable is captured by a closure, it will be captured by a
+    /// shared cell so that the assignment is visible to the scope it was
+    /// declared in. Unless it's captured by a `move` closure along the way, in
+    /// which case the assignment only affects the copy held by that closure.
+    pub fn mark_assign(&mut self, var: &str) {
+        let mut levels = self.levels.borrow_mut();
+
+        let mut crossed = false;
+        let mut owner = None;
+
+        for (index, level) in levels.iter().enumerate().rev() {
+            match level {
+                IndexScopeLevel::IndexScope(scope) => {
+                    if scope.locals.contains_key(var) {
+                        owner = Some(index);
+                        break;
+                    }
+                }
+                IndexScopeLevel::IndexClosure(closure) => {
+                    if closure.scope.locals.contains_key(var) {
+                        owner = Some(index);
+                        break;
+                    }
+
+                    if !closure.existing.contains(var) {
+                        break;
+                    }
+
+                    crossed = true;
+
+                    if closure.is_move {
+                        owner = Some(index);
+                        break;
+                    }
+                }
+                IndexScopeLevel::IndexFunction(fun) => {
+                    if fun.scope.locals.contains_key(var) {
+                        owner = Some(index);
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        let owner = match owner {
+            Some(owner) if crossed => owner,
+            _ => return,
+        };
+
+        // NB: variables declared in a regular scope belong to the closest
+        // enclosing function or closure.
+        for level in levels[..=owner].iter_mut().rev() {
+            match level {
+                IndexScopeLevel::IndexScope(..) => (),
+                IndexScopeLevel::IndexClosure(closure) => {
+                    closure.cells.insert(var.to_owned());
+                    break;
+                }
+                IndexScopeLevel::IndexFunction(fun) => {
+                    fun.cells.insert(var.to_owned());
+                    break;
+                }
+            }
+        }
+    }
+
     /// Mark that a yield was used, meaning the encapsulating function is a
     /// generator.
     pub fn mark_yield(&mut self, span: Span) -> Result<(), CompileError> {
@@ -314,10 +397,12 @@ impl IndexScopes {
     }
 
     /// Push a closure boundary.
-    pub fn push_closure(&mut self, is_async: bool) -> IndexScopeGuard {
+    pub fn push_closure(&mut self, is_async: bool, is_move: bool) -> IndexScopeGuard {
         self.levels
             .borrow_mut()
-            .push(IndexScopeLevel::IndexClosure(IndexClosure::new(is_async)));
+            .push(IndexScopeLevel::IndexClosure(IndexClosure::new(
+                is_async, is_move,
+            )));
 
         IndexScopeGuard {
             levels: self.levels.clone(),
@@ -134,12 +134,14 @@ impl<'a> Lexer<'a> {
             match c {
                 c if char::is_alphanumeric(c) => (),
                 '.' if !is_fractional => {
-                    is_fractional = true;
-
-                    // char immediately following a dot should be numerical.
+                    // char immediately following a dot should be numerical,
+                    // otherwise the dot belongs to the next token like in
+                    // `1..=9`.
                     if !it.next().map(|(_, c)| c.is_numeric()).unwrap_or_default() {
                         break self.cursor + n;
                     }
+
+                    is_fractional = true;
                 }
                 _ => break self.cursor + n,
             }
@@ -583,7 +585,13 @@ impl<'a> Lexer<'a> {
                         }
                         ('.', '.') => {
                             it.next();
-                            break ast::Kind::DotDot;
+
+                            break if matches!(it.clone().next(), Some((_, '='))) {
+                                it.next();
+                                ast::Kind::DotDotEq
+                            } else {
+                                ast::Kind::DotDot
+                            };
                         }
                         ('=', '>') => {
                             it.next();
@@ -773,6 +781,53 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_ranges() {
+        test_lexer! {
+            "1..=9 1..2",
+            ast::Token {
+                span: Span::new(0, 1),
+                kind: ast::Kind::LitNumber(ast::NumberSource::Text(ast::NumberSourceText {
+                    is_fractional: false,
+                    is_negative: false,
+                    base: ast::NumberBase::Decimal,
+                })),
+            },
+            ast::Token {
+                span: Span::new(1, 4),
+                kind: ast::Kind::DotDotEq,
+            },
+            ast::Token {
+                span: Span::new(4, 5),
+                kind: ast::Kind::LitNumber(ast::NumberSource::Text(ast::NumberSourceText {
+                    is_fractional: false,
+                    is_negative: false,
+                    base: ast::NumberBase::Decimal,
+                })),
+            },
+            ast::Token {
+                span: Span::new(6, 7),
+                kind: ast::Kind::LitNumber(ast::NumberSource::Text(ast::NumberSourceText {
+                    is_fractional: false,
+                    is_negative: false,
+                    base: ast::NumberBase::Decimal,
+                })),
+            },
+            ast::Token {
+                span: Span::new(7, 9),
+                kind: ast::Kind::DotDot,
+            },
+            ast::Token {
+                span: Span::new(9, 10),
+                kind: ast::Kind::LitNumber(ast::NumberSource::Text(ast::NumberSourceText {
+                    is_fractional: false,
+                    is_negative: false,
+                    base: ast::NumberBase::Decimal,
+                })),
+            },
+        };
+    }
+
     #[test]
     fn test_idents() {
         test_lexer! {
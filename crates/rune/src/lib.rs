@@ -173,10 +173,13 @@
 mod util_macros;
 mod assembly;
 pub mod ast;
+mod call_args;
 mod compile;
 mod compile_error;
 mod compile_visitor;
 mod compiler;
+mod const_eval;
+pub mod coverage;
 #[cfg(feature = "diagnostics")]
 pub mod diagnostics;
 mod errors;
@@ -200,6 +203,7 @@ mod sources;
 mod storage;
 mod token_stream;
 mod traits;
+mod type_check;
 mod unit_builder;
 mod warning;
 mod worker;
@@ -209,6 +213,7 @@ mod worker;
 #[macro_use]
 pub mod testing;
 #[cfg(test)]
+#[allow(missing_docs)]
 pub mod tests;
 
 /// Internal collection re-export.
@@ -248,6 +253,15 @@ pub use diagnostics::{termcolor, DiagnosticsError, EmitDiagnostics};
 ///
 /// See [load_sources](crate::load_sources) for how to use.
 pub fn default_context() -> Result<runestick::Context, runestick::ContextError> {
+    default_context_with_args(Vec::new())
+}
+
+/// Construct a default context like [default_context], where `env::args`
+/// returns the given arguments to scripts.
+#[cfg_attr(not(feature = "modules"), allow(unused_variables))]
+pub fn default_context_with_args(
+    args: Vec<String>,
+) -> Result<runestick::Context, runestick::ContextError> {
     #[allow(unused_mut)]
     let mut context = runestick::Context::with_default_modules()?;
 
@@ -260,6 +274,13 @@ pub fn default_context() -> Result<runestick::Context, runestick::ContextError>
         context.install(&rune_modules::process::module()?)?;
         context.install(&rune_modules::fs::module()?)?;
         context.install(&rune_modules::signal::module()?)?;
+        context.install(&rune_modules::regex::module()?)?;
+        context.install(&rune_modules::rand::module()?)?;
+        context.install(&rune_modules::env::module_with_args(args)?)?;
+        context.install(&rune_modules::path::module()?)?;
+        context.install(&rune_modules::encoding::module()?)?;
+        context.install(&rune_modules::yaml::module()?)?;
+        context.install(&rune_modules::csv::module()?)?;
     }
 
     Ok(context)
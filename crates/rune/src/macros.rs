@@ -3,13 +3,56 @@
 use crate::CompileResult;
 use crate::{
     ast, CompileError, CompileErrorKind, MacroContext, Options, Parse, ParseError, Parser,
-    Spanned as _, Storage, TokenStream, UnitBuilder,
+    Resolve as _, Spanned as _, Storage, TokenStream, UnitBuilder,
 };
 use runestick::{Context, Hash, Item, Source, Span};
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// A macro which is built into the compiler, and is always available.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BuiltInMacro {
+    /// `format!`, which formats a string.
+    Format,
+    /// `print!`, which formats a string and prints it.
+    Print,
+    /// `println!`, which formats a string and prints it followed by a newline.
+    Println,
+}
+
+impl BuiltInMacro {
+    /// Find the built-in macro being called, if any.
+    ///
+    /// Built-in macros are only called through a plain identifier, like
+    /// `format!`.
+    pub(crate) fn find(
+        expr_call_macro: &ast::MacroCall,
+        storage: &Storage,
+        source: &Source,
+    ) -> CompileResult<Option<Self>> {
+        let ident = match expr_call_macro.path.try_as_ident() {
+            Some(ident) => ident,
+            None => return Ok(None),
+        };
+
+        Ok(match ident.resolve(storage, source)?.as_ref() {
+            "format" => Some(Self::Format),
+            "print" => Some(Self::Print),
+            "println" => Some(Self::Println),
+            _ => None,
+        })
+    }
+
+    /// Parse the arguments to the macro.
+    pub(crate) fn parse_args(expr_call_macro: &ast::MacroCall) -> CompileResult<ast::FormatArgs> {
+        let mut parser = Parser::from_token_stream(&expr_call_macro.stream);
+        let output = parser.parse::<ast::FormatArgs>()?;
+        parser.parse_eof()?;
+        Ok(output)
+    }
+}
+
 pub(crate) struct MacroCompiler<'a> {
     pub(crate) storage: Storage,
     pub(crate) item: Item,
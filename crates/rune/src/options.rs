@@ -23,6 +23,8 @@ pub struct Options {
     pub(crate) macros: bool,
     /// Support (experimental) bytecode caching.
     pub bytecode: bool,
+    /// Check type annotations while compiling.
+    pub(crate) type_check: bool,
 }
 
 impl Options {
@@ -51,6 +53,9 @@ impl Options {
             Some("bytecode") => {
                 self.bytecode = it.next() != Some("false");
             }
+            Some("type-check") => {
+                self.type_check = it.next() != Some("false");
+            }
             _ => {
                 return Err(ConfigurationError::UnsupportedOptimizationOption {
                     option: option.to_owned(),
@@ -83,6 +88,12 @@ impl Options {
         self.bytecode = enabled;
     }
 
+    /// Set if type annotations should be checked while compiling. Defaults to
+    /// `false`. Mismatches are reported as compile errors.
+    pub fn type_check(&mut self, enabled: bool) {
+        self.type_check = enabled;
+    }
+
     /// Memoize the instance function in a loop. Defaults to `false`.
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
@@ -97,6 +108,7 @@ impl Default for Options {
             debug_info: true,
             macros: false,
             bytecode: false,
+            type_check: false,
         }
     }
 }
@@ -235,12 +235,34 @@ pub enum ParseErrorKind {
     /// When we encounter an unescaped closing brace `}`.
     #[error("closing braces must be escaped inside of templates with `\\}}`")]
     UnexpectedCloseBrace,
+    /// When we encounter an unescaped closing brace `}` in a format string.
+    #[error("closing braces must be escaped inside of format strings with `}}}}`")]
+    UnexpectedFormatCloseBrace,
+    /// A format string refers to an argument which doesn't exist.
+    #[error("format string refers to missing argument `{index}`")]
+    MissingFormatArgument {
+        /// The index of the missing argument.
+        index: usize,
+    },
+    /// An argument which isn't used by the format string.
+    #[error("argument never used by the format string")]
+    UnusedFormatArgument,
+    /// A format specification with a width or precision which doesn't fit in
+    /// a `usize`.
+    #[error("width or precision of format specification is too large")]
+    FormatSpecTooLarge,
     /// When we encounter an expression that cannot be used in a chained manner.
     #[error("unsupported field access")]
     UnsupportedFieldAccess,
     /// Trying to use a token that is not supported as a function argument.
     #[error("not supported as a function or closure argument")]
     ExpectedFunctionArgument,
+    /// Expected a type annotation.
+    #[error("expected type, like `int` or `()`, but got `{actual}`")]
+    ExpectedType {
+        /// The actual token kind.
+        actual: ast::Kind,
+    },
     /// Trying to use an expression as async when it's not supported.
     #[error("not supported as an async expression")]
     UnsupportedAsyncExpr,
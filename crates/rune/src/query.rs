@@ -2,6 +2,7 @@
 
 use crate::ast;
 use crate::collections::{HashMap, HashSet};
+use crate::const_eval::ConstEval;
 use crate::CompileResult;
 use crate::{
     CompileError, CompileErrorKind, CompileVisitor, Resolve as _, Spanned as _, Storage,
@@ -20,9 +21,10 @@ pub(crate) enum Indexed {
     Enum,
     Struct(Struct),
     Variant(Variant),
-    Function(Function),
+    Function(Box<Function>),
     Closure(Closure),
     AsyncBlock(AsyncBlock),
+    Const(Const),
 }
 
 pub struct Struct {
@@ -84,6 +86,27 @@ pub(crate) struct AsyncBlock {
     pub(crate) call: Call,
 }
 
+pub(crate) struct Const {
+    /// The expression the constant is evaluated from.
+    pub(crate) expr: Box<ast::Expr>,
+}
+
+/// The signature of a function, as declared in the source.
+///
+/// This is recorded for every function during indexing, so that named and
+/// default arguments can be resolved in calls to it, and so that calls can be
+/// checked when type checking is enabled.
+pub(crate) struct FnSignature {
+    /// The source the signature was declared in.
+    pub(crate) source: Arc<Source>,
+    /// The arguments of the function, including `self`.
+    pub(crate) args: Vec<ast::FnArg>,
+    /// The return type annotation.
+    pub(crate) output: Option<ast::Type>,
+    /// The calling convention of the function.
+    pub(crate) call: Call,
+}
+
 /// An entry in the build queue.
 pub(crate) enum Build {
     Function(Function),
@@ -118,6 +141,18 @@ pub(crate) struct Query {
     pub(crate) unit: Rc<RefCell<UnitBuilder>>,
     pub(crate) queue: VecDeque<BuildEntry>,
     pub(crate) indexed: HashMap<Item, IndexedEntry>,
+    /// Constants which are currently being evaluated, used to detect cycles.
+    pub(crate) evaluating: HashSet<Item>,
+    /// Items which failed to build, and whose errors have already been
+    /// reported.
+    pub(crate) failed: HashSet<Item>,
+    /// Signatures of all indexed functions.
+    pub(crate) signatures: HashMap<Item, FnSignature>,
+    /// The variants of all indexed enums, in the order they are declared.
+    pub(crate) enum_variants: HashMap<Item, Vec<Item>>,
+    /// The names of variables which might be captured in shared cells, by the
+    /// function or closure they're declared in.
+    pub(crate) cells: HashMap<Item, HashSet<String>>,
 }
 
 impl Query {
@@ -128,6 +163,19 @@ impl Query {
             unit,
             queue: VecDeque::new(),
             indexed: HashMap::new(),
+            evaluating: HashSet::new(),
+            failed: HashSet::new(),
+            signatures: HashMap::new(),
+            enum_variants: HashMap::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Record the variables of a function or closure which might be captured
+    /// in shared cells.
+    pub(crate) fn insert_cells(&mut self, item: Item, cells: HashSet<String>) {
+        if !cells.is_empty() {
+            self.cells.insert(item, cells);
         }
     }
 
@@ -190,6 +238,11 @@ impl Query {
     ) -> Result<(), CompileError> {
         log::trace!("new variant: {}", item);
 
+        self.enum_variants
+            .entry(enum_item.clone())
+            .or_default()
+            .push(item.clone());
+
         self.index(
             item,
             IndexedEntry {
@@ -203,6 +256,36 @@ impl Query {
         Ok(())
     }
 
+    /// Add a new constant that can be queried for.
+    pub fn index_const(
+        &mut self,
+        item: Item,
+        span: Span,
+        expr: Box<ast::Expr>,
+        source: Arc<Source>,
+        source_id: usize,
+    ) -> Result<(), CompileError> {
+        log::trace!("new const: {}", item);
+
+        self.index(
+            item,
+            IndexedEntry {
+                span,
+                source,
+                source_id,
+                indexed: Indexed::Const(Const { expr }),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// The item of the constant holding the default value of the argument at
+    /// the given position of a function.
+    pub(crate) fn default_arg_item(item: &Item, index: usize) -> Item {
+        item.extended(format!("$default{}", index))
+    }
+
     /// Add a new function that can be queried for.
     pub fn index_closure(
         &mut self,
@@ -286,13 +369,19 @@ impl Query {
         &mut self,
         visitor: &mut dyn CompileVisitor,
     ) -> Result<bool, (SourceId, CompileError)> {
-        let unused = std::mem::take(&mut self.indexed);
-
-        if unused.is_empty() {
+        if self.indexed.is_empty() {
             return Ok(false);
         }
 
-        for (item, entry) in unused {
+        // NB: entries are removed one at a time, since building an entry might
+        // query the entries which are still left, like constants referencing
+        // other constants.
+        while let Some(item) = self.indexed.keys().next().cloned() {
+            let entry = match self.indexed.remove(&item) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
             let span = entry.span;
             let source_id = entry.source_id;
 
@@ -320,7 +409,13 @@ impl Query {
             None => return Ok(None),
         };
 
-        Ok(Some(self.build_indexed_entry(item, entry, false)?))
+        match self.build_indexed_entry(item.clone(), entry, false) {
+            Ok(meta) => Ok(Some(meta)),
+            Err(error) => {
+                self.failed.insert(item);
+                Err(error)
+            }
+        }
     }
 
     /// Build a single, indexed entry and return its metadata.
@@ -355,7 +450,7 @@ impl Query {
             Indexed::Function(f) => {
                 self.queue.push_back(BuildEntry {
                     item: item.clone(),
-                    build: Build::Function(f),
+                    build: Build::Function(*f),
                     source,
                     source_id,
                     unused,
@@ -398,6 +493,26 @@ impl Query {
                     captures,
                 }
             }
+            Indexed::Const(c) => {
+                let mut base = item.clone();
+                base.pop();
+
+                self.evaluating.insert(item.clone());
+
+                let result = ConstEval {
+                    query: self,
+                    source: &source,
+                    base,
+                }
+                .eval(&c.expr);
+
+                self.evaluating.remove(&item);
+
+                CompileMetaKind::Const {
+                    item: item.clone(),
+                    const_value: result?,
+                }
+            }
         };
 
         let meta = CompileMeta {
@@ -469,15 +584,18 @@ impl Query {
         let type_of = Type::from(Hash::type_hash(item));
 
         let mut fields = HashSet::new();
+        let mut declared_fields = Vec::new();
 
         for ast::Field { name, .. } in &st.fields {
             let name = name.resolve(&self.storage, &*source)?;
             fields.insert(name.to_string());
+            declared_fields.push(name.to_string());
         }
 
         let object = CompileMetaStruct {
             item: item.clone(),
             fields: Some(fields),
+            declared_fields: Some(declared_fields.into_boxed_slice()),
         };
 
         Ok(match enum_item {
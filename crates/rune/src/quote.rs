@@ -263,6 +263,10 @@ macro_rules! quote {
         $crate::quote!(@token $ctx, $s, QuestionMark => $($tt)*);
     }};
 
+    (@push $ctx:expr, $s:expr => ..= $($tt:tt)*) => {{
+        $crate::quote!(@token $ctx, $s, DotDotEq => $($tt)*);
+    }};
+
     (@push $ctx:expr, $s:expr => .. $($tt:tt)*) => {{
         $crate::quote!(@token $ctx, $s, DotDot => $($tt)*);
     }};
@@ -444,6 +448,7 @@ mod tests {
         assert_eq!(vec![token(Bang)], quote!(ctx => !));
         assert_eq!(vec![token(QuestionMark)], quote!(ctx => ?));
         assert_eq!(vec![token(DotDot)], quote!(ctx => ..));
+        assert_eq!(vec![token(DotDotEq)], quote!(ctx => ..=));
         assert_eq!(vec![token(AmpAmp)], quote!(ctx => &&));
         assert_eq!(vec![token(PipePipe)], quote!(ctx => ||));
         assert_eq!(vec![token(Pipe)], quote!(ctx => |));
@@ -1,4 +1,4 @@
-use crate::collections::HashMap;
+use crate::collections::{HashMap, HashSet};
 use crate::CompileResult;
 use crate::{Assembly, CompileError, CompileErrorKind, CompileVisitor};
 use runestick::{Inst, SourceId, Span};
@@ -11,6 +11,8 @@ pub struct Var {
     pub(crate) offset: usize,
     /// Token assocaited with the variable.
     span: Span,
+    /// If the variable might be captured in a shared cell.
+    pub(crate) cell: bool,
 }
 
 impl Var {
@@ -24,13 +26,26 @@ impl Var {
     where
         C: AsRef<str>,
     {
-        asm.push_with_comment(
-            Inst::Copy {
-                offset: self.offset,
-            },
-            span,
-            comment,
-        );
+        let offset = self.offset;
+
+        let inst = if self.cell {
+            Inst::CopyCell { offset }
+        } else {
+            Inst::Copy { offset }
+        };
+
+        asm.push_with_comment(inst, span, comment);
+    }
+
+    /// Replace the declared variable with the value on top of the stack.
+    pub(crate) fn replace(&self, asm: &mut Assembly, span: Span) {
+        let offset = self.offset;
+
+        if self.cell {
+            asm.push(Inst::ReplaceCell { offset }, span);
+        } else {
+            asm.push(Inst::Replace { offset }, span);
+        }
     }
 }
 
@@ -77,10 +92,10 @@ impl Scope {
     }
 
     /// Insert a new local, and return the old one if there's a conflict.
-    fn new_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
+    fn new_var(&mut self, name: &str, span: Span, cell: bool) -> CompileResult<usize> {
         let offset = self.total_var_count;
 
-        let local = Var { offset, span };
+        let local = Var { offset, span, cell };
 
         self.total_var_count += 1;
         self.local_var_count += 1;
@@ -99,12 +114,13 @@ impl Scope {
     }
 
     /// Insert a new local, and return the old one if there's a conflict.
-    fn decl_var(&mut self, name: &str, span: Span) -> usize {
+    fn decl_var(&mut self, name: &str, span: Span, cell: bool) -> usize {
         let offset = self.total_var_count;
 
         log::trace!("decl {} => {}", name, offset);
 
-        self.locals.insert(name.to_owned(), Var { offset, span });
+        self.locals
+            .insert(name.to_owned(), Var { offset, span, cell });
 
         self.total_var_count += 1;
         self.local_var_count += 1;
@@ -162,13 +178,17 @@ pub(crate) struct ScopeGuard(usize);
 
 pub(crate) struct Scopes {
     scopes: Vec<Scope>,
+    /// The names of variables which might be captured in shared cells.
+    cells: HashSet<String>,
 }
 
 impl Scopes {
-    /// Construct a new collection of scopes.
-    pub(crate) fn new() -> Self {
+    /// Construct a new collection of scopes, where variables with the given
+    /// names might be captured in shared cells.
+    pub(crate) fn new(cells: HashSet<String>) -> Self {
         Self {
             scopes: vec![Scope::new()],
+            cells,
         }
     }
 
@@ -194,6 +214,14 @@ impl Scopes {
         None
     }
 
+    /// Test if there's a local with the given name.
+    pub(crate) fn contains_var(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .any(|scope| scope.get(name).is_some())
+    }
+
     /// Get the local with the given name.
     pub(crate) fn get_var(
         &self,
@@ -215,12 +243,14 @@ impl Scopes {
 
     /// Construct a new variable.
     pub(crate) fn new_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
-        self.last_mut(span)?.new_var(name, span)
+        let cell = self.cells.contains(name);
+        self.last_mut(span)?.new_var(name, span, cell)
     }
 
     /// Declare the given variable.
     pub(crate) fn decl_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
-        Ok(self.last_mut(span)?.decl_var(name, span))
+        let cell = self.cells.contains(name);
+        Ok(self.last_mut(span)?.decl_var(name, span, cell))
     }
 
     /// Declare an anonymous variable.
@@ -274,6 +304,25 @@ impl Scopes {
         Ok(self.push(scope))
     }
 
+    /// Merge a popped child scope into the top scope, as if its variables had
+    /// been declared there.
+    pub(crate) fn merge(&mut self, child: Scope, span: Span) -> CompileResult<()> {
+        let last = self.last_mut(span)?;
+
+        if child.total_var_count != last.total_var_count + child.local_var_count {
+            return Err(CompileError::internal(
+                span,
+                "child scope is not a child of the top scope",
+            ));
+        }
+
+        last.locals.extend(child.locals);
+        last.anon.extend(child.anon);
+        last.total_var_count = child.total_var_count;
+        last.local_var_count += child.local_var_count;
+        Ok(())
+    }
+
     /// Construct a new child scope.
     pub(crate) fn child(&mut self, span: Span) -> CompileResult<Scope> {
         Ok(self.last(span)?.child())
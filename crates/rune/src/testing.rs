@@ -53,6 +53,23 @@ pub fn compile_source(
     Ok((unit.into_unit(), warnings))
 }
 
+/// Compile the given source into a virtual machine which uses the given
+/// context.
+pub fn vm_with_context(
+    context: runestick::Context,
+    source: &str,
+) -> Result<runestick::Vm, RunError> {
+    let (unit, _) = compile_source(&context, source).map_err(RunError::Errors)?;
+    Ok(runestick::Vm::new(Arc::new(context), Arc::new(unit)))
+}
+
+/// Compile the given source into a virtual machine which uses the
+/// [default context][crate::default_context].
+pub fn vm(source: &str) -> Result<runestick::Vm, RunError> {
+    let context = crate::default_context().map_err(RunError::ContextError)?;
+    vm_with_context(context, source)
+}
+
 /// Call the specified function in the given script.
 pub async fn run_async<N, A, T>(function: N, args: A, source: &str) -> Result<T, RunError>
 where
@@ -62,9 +79,7 @@ where
     T: FromValue,
 {
     let context = runestick::Context::with_default_modules().map_err(RunError::ContextError)?;
-    let (unit, _) = compile_source(&context, &source).map_err(RunError::Errors)?;
-
-    let vm = runestick::Vm::new(Arc::new(context), Arc::new(unit));
+    let vm = vm_with_context(context, source)?;
 
     let output = vm
         .execute(&Item::of(function), args)
@@ -292,7 +307,7 @@ macro_rules! assert_warnings {
         $(
             let warning = it.next().expect("expected a warning");
 
-            match warning.kind {
+            match warning.kind.clone() {
                 $pat => ($cond),
                 warning => {
                     panic!("expected warning `{}` but was `{:?}`", stringify!($pat), warning);
@@ -0,0 +1,300 @@
+use crate::testing::*;
+use crate::{CompileErrorKind, Errors, LoadErrorKind, Options, Sources, Spanned as _, Warnings};
+use runestick::{Context, Source};
+
+/// Compile the given source with type checking enabled, returning all
+/// compile errors.
+fn type_check(source: &str) -> Vec<(Span, CompileErrorKind)> {
+    let context = Context::with_default_modules().unwrap();
+
+    let mut options = Options::default();
+    options.type_check(true);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source.to_owned()));
+
+    let mut errors = Errors::new();
+
+    let _ = crate::load_sources(
+        &context,
+        &options,
+        &mut sources,
+        &mut errors,
+        &mut Warnings::disabled(),
+    );
+
+    errors
+        .into_iter()
+        .map(|e| match e.into_kind() {
+            LoadErrorKind::CompileError(e) => (e.span(), e.into_kind()),
+            kind => panic!("expected compile error but was `{:?}`", kind),
+        })
+        .collect()
+}
+
+#[test]
+fn test_annotations_ignored_by_default() {
+    assert_eq! {
+        rune! { i64 =>
+            r#"
+            fn add(a: int, b: int) -> int { a + b }
+            fn main() { let n: String = add(1, 2); n }
+            "#
+        },
+        3,
+    };
+
+    assert_eq! {
+        rune! { i64 => r#"fn main() { let f = |a: int, _: int| a * 2; f(21, 0) }"# },
+        42,
+    };
+}
+
+#[test]
+fn test_well_typed() {
+    let errors = type_check(
+        r#"
+        struct Point { x, y }
+        enum Shape { Circle(r), Empty }
+
+        impl Point {
+            fn sum(self) -> int { self.x + self.y }
+        }
+
+        fn origin() -> Point { Point { x: 0, y: 0 } }
+        fn area(shape: Shape) -> float { 1.0 }
+        fn nothing() -> () {}
+
+        fn main() {
+            let p: Point = origin();
+            let n: int = p.sum();
+            let a: float = area(Shape::Circle(2));
+            let b: float = area(Shape::Empty);
+            let s: String = `{n}`;
+            let o: Option = Some(n);
+            let o: Option = None;
+            let v: Vec = [1, 2, 3];
+            let l: int = v.len();
+            let r: Result = Ok(n);
+            nothing()
+        }
+        "#,
+    );
+
+    assert!(
+        errors.is_empty(),
+        "expected no errors, but got {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_type_mismatches() {
+    let errors = type_check(
+        r#"
+        fn add(a: int, b: int) -> int { a + b }
+
+        fn main() -> () {
+            let s: String = add(1, 2);
+            add("one", 2);
+            let n: int = 1;
+            n = 2.0;
+            "hello"
+        }
+
+        fn early(n: int) -> bool {
+            if n > 0 {
+                return n;
+            }
+
+            true
+        }
+        "#,
+    );
+
+    let mut it = errors.into_iter();
+
+    let mut assert_mismatch = |e: &str, a: &str| match it.next() {
+        Some((_, TypeMismatch { expected, actual })) => {
+            assert_eq!((expected.as_str(), actual.as_str()), (e, a));
+        }
+        other => panic!("expected type mismatch but got {:?}", other),
+    };
+
+    assert_mismatch("String", "integer");
+    assert_mismatch("integer", "String");
+    assert_mismatch("integer", "float");
+    assert_mismatch("unit", "String");
+    assert_mismatch("bool", "integer");
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn test_calls() {
+    let errors = type_check(
+        r#"
+        struct Point { x, y }
+
+        fn add(a, b) { a + b }
+
+        fn main(p: Point, n: int) {
+            add(1);
+            n.length();
+            p.length();
+            dbg(n);
+            std::string::String::new(n);
+        }
+        "#,
+    );
+
+    let mut it = errors.into_iter();
+
+    assert!(matches! {
+        it.next(),
+        Some((_, UnsupportedArgumentCount { expected: 2, actual: 1, .. }))
+    });
+
+    match it.next() {
+        Some((span, MissingInstanceFn { name, type_name })) => {
+            assert_eq!(span, Span::new(132, 142));
+            assert_eq!((name.as_str(), type_name.as_str()), ("length", "integer"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    match it.next() {
+        Some((_, MissingInstanceFn { name, type_name })) => {
+            assert_eq!((name.as_str(), type_name.as_str()), ("length", "Point"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    assert!(matches! {
+        it.next(),
+        Some((_, UnsupportedArgumentCount { expected: 0, actual: 1, .. }))
+    });
+
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn test_inference_is_conservative() {
+    // NB: unannotated variables which are reassigned might change type.
+    let errors = type_check(
+        r#"
+        fn main(value) {
+            let v = 1;
+            v = "hello";
+            let len = v.len();
+            let len: String = value;
+            let (a, b) = (1, 2);
+            let a: String = a;
+        }
+        "#,
+    );
+
+    assert!(
+        errors.is_empty(),
+        "expected no errors, but got {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_derived_clone() {
+    let errors = type_check(
+        r#"
+        struct Point { x, y }
+        struct Pair(a, b);
+        enum Shape { Circle(r) }
+
+        struct Counter { n }
+
+        impl Counter {
+            fn clone(self, n) { Counter { n } }
+        }
+
+        fn main(p: Point, q: Pair, s: Shape, c: Counter) {
+            let p: Point = p.clone();
+            let q: Pair = q.clone();
+            let s: Shape = s.clone();
+            let n: int = p.clone();
+            c.clone();
+        }
+        "#,
+    );
+
+    let mut it = errors.into_iter();
+
+    match it.next() {
+        Some((_, TypeMismatch { expected, actual })) => {
+            assert_eq!((expected.as_str(), actual.as_str()), ("integer", "Point"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    assert!(matches! {
+        it.next(),
+        Some((_, UnsupportedArgumentCount { expected: 2, actual: 1, .. }))
+    });
+
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn test_missing_type() {
+    let errors = type_check(r#"fn main(n: Missing) -> Other { n }"#);
+
+    let mut it = errors.into_iter();
+
+    // NB: the compiler stops at the first unresolved signature annotation.
+    assert!(matches! {
+        it.next(),
+        Some((span, MissingType { .. })) if span == Span::new(11, 18)
+    });
+
+    assert!(it.next().is_none());
+
+    let errors = type_check(r#"fn main() { let a: Missing = 1; let f = |b: Other| b; }"#);
+
+    let mut it = errors.into_iter();
+
+    assert!(matches! {
+        it.next(),
+        Some((span, MissingType { .. })) if span == Span::new(19, 26)
+    });
+
+    assert!(matches! {
+        it.next(),
+        Some((span, MissingType { .. })) if span == Span::new(44, 49)
+    });
+
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn test_variadic_arg_count() {
+    let errors = type_check(
+        r#"
+        fn sum(first, ..rest) { first }
+        fn main() { sum(1); sum(1, 2, 3); sum(..[1]); sum() }
+        "#,
+    );
+
+    let mut it = errors.into_iter();
+
+    match it.next() {
+        Some((
+            span,
+            UnsupportedVariadicArgumentCount {
+                expected, actual, ..
+            },
+        )) => {
+            assert_eq!(span, Span::new(95, 100));
+            assert_eq!((expected, actual), (1, 0));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    assert!(it.next().is_none());
+}
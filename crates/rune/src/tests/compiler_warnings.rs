@@ -1,4 +1,5 @@
 use crate::testing::*;
+use runestick::Item;
 
 #[test]
 fn test_let_pattern_might_panic() {
@@ -29,3 +30,24 @@ fn test_remove_variant_parens() {
         }
     };
 }
+
+#[test]
+fn test_unreachable_pattern() {
+    assert_warnings! {
+        r#"enum E { A, B(b), C } fn main() { match E::A { E::A | E::B(_) => 1, E::A => 2, _ => 3 } }"#,
+        UnreachablePattern { span, .. } => {
+            assert_eq!(span, Span::new(68, 72));
+        }
+    };
+}
+
+#[test]
+fn test_non_exhaustive_match() {
+    assert_warnings! {
+        r#"enum E { A, B(b), C } fn main() { match E::A { E::A => 1, E::B(_) => 2 } }"#,
+        NonExhaustiveMatch { span, missing } => {
+            assert_eq!(span, Span::new(34, 44));
+            assert_eq!(missing, vec![Item::of(&["E", "C"])]);
+        }
+    };
+}
@@ -3,23 +3,58 @@ mod compiler_expr_assign;
 mod compiler_expr_binary;
 mod compiler_general;
 mod compiler_literals;
+mod compiler_type_check;
 mod compiler_warnings;
+#[cfg(feature = "modules")]
+mod modules_csv;
+#[cfg(feature = "modules")]
+mod modules_encoding;
+#[cfg(feature = "modules")]
+mod modules_env;
+#[cfg(feature = "modules")]
+mod modules_fs;
+#[cfg(feature = "modules")]
+mod modules_http;
+#[cfg(feature = "modules")]
+mod modules_json;
+#[cfg(feature = "modules")]
+mod modules_process;
+#[cfg(feature = "modules")]
+mod modules_rand;
+#[cfg(feature = "modules")]
+mod modules_regex;
+#[cfg(feature = "modules")]
+mod modules_time;
+#[cfg(feature = "modules")]
+mod modules_yaml;
 mod vm_arithmetic;
 mod vm_assign_exprs;
 mod vm_async_block;
 mod vm_blocks;
 mod vm_closures;
+mod vm_comparisons;
+mod vm_const;
+mod vm_coverage;
 mod vm_early_termination;
+mod vm_format;
+mod vm_fuel;
 mod vm_function;
 mod vm_general;
 mod vm_generators;
 mod vm_is;
+mod vm_let_else;
 mod vm_literals;
 mod vm_match;
+mod vm_memory;
+mod vm_named_args;
 mod vm_option;
 mod vm_pat;
 mod vm_result;
+mod vm_rtti;
+mod vm_sandbox;
+mod vm_scheduler;
 mod vm_streams;
+mod vm_structs;
 mod vm_test_external_fn_ptr;
 mod vm_test_from_value_derive;
 mod vm_test_instance_fns;
@@ -28,5 +63,7 @@ mod vm_test_mod;
 mod vm_test_references;
 mod vm_try;
 mod vm_tuples;
+mod vm_type_annotations;
 mod vm_typed_tuple;
 mod vm_types;
+mod vm_variadic;
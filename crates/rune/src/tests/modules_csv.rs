@@ -0,0 +1,127 @@
+use crate::testing::*;
+use std::fs;
+
+#[tokio::test]
+async fn test_csv_reader() {
+    let path = std::env::temp_dir().join(format!("rune-csv-{}.csv", std::process::id()));
+    fs::write(&path, "name,age\nBob,42\n\"Smith, Al\",7\n").unwrap();
+
+    let vm = vm(r#"
+        use csv::Reader;
+
+        async fn main(path) {
+            let reader = Reader::from_path(path, #{}).await?;
+            let people = [];
+
+            for record in reader {
+                let record = record?;
+                people.push(`{record.name}:{record.age}`);
+            }
+
+            let raw = Reader::from_string("1;2\n3;4\n", #{delimiter: ";", headers: false})?;
+
+            let missing = match Reader::from_path(`{path}.missing`, #{}).await {
+                Ok(..) => false,
+                Err(..) => true,
+            };
+
+            let line = match Reader::from_string("a,b\n1\n", #{})?.read_all() {
+                Ok(..) => None,
+                Err(error) => error.line(),
+            };
+
+            Ok((people, raw.read_all()?, missing, line))
+        }
+        "#)
+    .unwrap();
+
+    let output = vm
+        .execute(&["main"], (path.display().to_string(),))
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    let output =
+        Result::<(Vec<String>, Vec<Vec<String>>, bool, Option<u64>), Value>::from_value(output)
+            .unwrap()
+            .unwrap();
+
+    assert_eq!(
+        output,
+        (
+            vec![String::from("Bob:42"), String::from("Smith, Al:7")],
+            vec![
+                vec![String::from("1"), String::from("2")],
+                vec![String::from("3"), String::from("4")],
+            ],
+            true,
+            Some(2),
+        )
+    );
+}
+
+#[test]
+fn test_csv_writer() {
+    let vm = vm(r#"
+        use csv::Writer;
+
+        fn main() {
+            let objects = Writer::new(#{});
+            objects.write(#{b: 2, a: "x,y"})?;
+            objects.write(#{a: 1})?;
+
+            let vectors = Writer::new(#{delimiter: ";", quote_style: "always"});
+            vectors.write([1, "a"])?;
+            vectors.write((true, ()))?;
+
+            let columns = Writer::new(#{columns: ["b", "a"]});
+            columns.write(#{a: 1, b: 2})?;
+            columns.write([3, 4])?;
+
+            [objects.to_string()?, vectors.to_string()?, columns.to_string()?]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            "a,b\n\"x,y\",2\n1,\n",
+            "\"1\";\"a\"\n\"true\";\"\"\n",
+            "b,a\n2,1\n3,4\n",
+        ]
+    );
+}
+
+#[test]
+fn test_csv_writer_mixed_records() {
+    // NB: without columns, the kind of the first record decides if there's a
+    // header, so the kinds can't be mixed.
+    let vm = vm(r#"
+        fn main() {
+            let writer = csv::Writer::new(#{});
+            writer.write([1, 2])?;
+            writer.write(#{a: 1})
+        }
+        "#)
+    .unwrap();
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    match error.into_kind() {
+        Unwound { kind, .. } => match *kind {
+            Panic { reason } => assert_eq!(
+                reason.to_string(),
+                "cannot write object record after vector or tuple record to a writer without columns"
+            ),
+            kind => panic!("unexpected error: {:?}", kind),
+        },
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
@@ -0,0 +1,137 @@
+use crate::testing::*;
+
+#[test]
+fn test_encoding_round_trips() {
+    let vm = vm(r#"
+        use encoding::base64;
+        use encoding::hex;
+        use encoding::url;
+
+        fn main() {
+            let data = hex::decode("00fbff10")?;
+
+            [
+                base64::encode("Hello, World!"),
+                hex::encode(base64::decode("SGVsbG8sIFdvcmxkIQ==")?),
+                base64::encode(data),
+                base64::encode_url_safe(data),
+                hex::encode(base64::decode_url_safe(base64::encode_url_safe(data))?),
+                hex::encode("hello"),
+                hex::encode(hex::decode("68656C6C6F")?),
+                url::encode("a b&c/d~é"),
+                url::decode(url::encode("a b&c/d~é"))?,
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            "SGVsbG8sIFdvcmxkIQ==",
+            "48656c6c6f2c20576f726c6421",
+            "APv/EA==",
+            "APv_EA==",
+            "00fbff10",
+            "68656c6c6f",
+            "68656c6c6f",
+            "a%20b%26c%2Fd~%C3%A9",
+            "a b&c/d~é",
+        ]
+    );
+}
+
+#[test]
+fn test_encoding_digests() {
+    let vm = vm(r#"
+        use encoding::hex;
+
+        fn main() {
+            let key = "Jefe";
+            let data = "what do ya want for nothing?";
+
+            [
+                hex::encode(encoding::sha1("abc")),
+                hex::encode(encoding::sha256("abc")),
+                hex::encode(encoding::sha512("abc")),
+                hex::encode(encoding::md5("abc")),
+                hex::encode(encoding::hmac_sha1(key, data)),
+                hex::encode(encoding::hmac_sha256(key, data)),
+                hex::encode(encoding::hmac_sha512(key, data)),
+                hex::encode(encoding::sha256(hex::decode("616263")?)),
+                `{encoding::crc32("123456789")}`,
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    // NB: digests from FIPS 180-2 and RFC 1321, HMACs from RFC 2202 and RFC
+    // 4231, and the CRC-32 check value of the ISO-HDLC variant.
+    assert_eq!(
+        output,
+        vec![
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "900150983cd24fb0d6963f7d28e17f72",
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "3421780262",
+        ]
+    );
+}
+
+#[test]
+fn test_encoding_errors() {
+    let vm = vm(r#"
+        use encoding::base64;
+        use encoding::hex;
+        use encoding::url;
+
+        fn error(result) {
+            match result {
+                Ok(..) => None,
+                Err(error) => Some(`{error}`),
+            }
+        }
+
+        fn main() {
+            [
+                error(base64::decode("SGVsbG8!")),
+                error(base64::decode_url_safe("APv/EA==")),
+                error(hex::decode("6g")),
+                error(hex::decode("abc")),
+                error(url::decode("%FF")),
+                error(url::decode("%41")),
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<Option<String>>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            Some(String::from("Invalid byte 33, offset 7.")),
+            Some(String::from("Invalid byte 47, offset 3.")),
+            Some(String::from("Invalid character 'g' at position 1")),
+            Some(String::from("Odd number of digits")),
+            Some(String::from(
+                "invalid utf-8 sequence of 1 bytes from index 0"
+            )),
+            None,
+        ]
+    );
+}
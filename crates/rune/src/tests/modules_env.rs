@@ -0,0 +1,22 @@
+use crate::testing::*;
+use runestick::Vm;
+use std::sync::Arc;
+
+#[test]
+fn test_env_args() {
+    let args = vec![String::from("--verbose"), String::from("input.txt")];
+    let context = crate::default_context_with_args(args.clone()).unwrap();
+    let (unit, _) = compile_source(&context, r#"fn main() { env::args() }"#).unwrap();
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+
+    // NB: the arguments are cloned on every call.
+    for _ in 0..2 {
+        let output = vm
+            .clone()
+            .execute(&["main"], ())
+            .unwrap()
+            .complete()
+            .unwrap();
+        assert_eq!(Vec::<String>::from_value(output).unwrap(), args);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::testing::*;
+use runestick::Vm;
+use std::fs;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_fs_read_dir() {
+    let dir = std::env::temp_dir().join(format!("rune-read-dir-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.txt"), "a").unwrap();
+    fs::write(dir.join("b.txt"), "bb").unwrap();
+
+    let context = crate::default_context().unwrap();
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn main(dir) {
+            let entries = fs::read_dir(dir).await?;
+            let files = [];
+
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let metadata = entry.metadata().await?;
+                files.push((entry.file_name(), metadata.len()));
+            }
+
+            Ok(files)
+        }
+        "#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+
+    let output = vm
+        .execute(&["main"], (dir.display().to_string(),))
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    let mut files = Result::<Vec<(String, u64)>, Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    files.sort();
+    assert_eq!(
+        files,
+        vec![(String::from("a.txt"), 1), (String::from("b.txt"), 2)]
+    );
+}
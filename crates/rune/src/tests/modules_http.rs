@@ -0,0 +1,407 @@
+use crate::testing::*;
+use runestick::sandbox::Denied;
+use runestick::{Sandbox, Vm, VmHaltInfo};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpListener, TcpStream};
+
+fn new_vm(source: &str, sandbox: Option<Sandbox>) -> Vm {
+    let mut vm = vm(source).unwrap();
+    vm.set_sandbox(sandbox.map(Arc::new));
+    vm
+}
+
+/// Serve a fixed set of responses by path on a local port, returning the
+/// address of the server.
+async fn serve(routes: &'static [(&'static str, &'static str)]) -> String {
+    let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split(' ').nth(1).unwrap_or_default().to_owned();
+
+            let response = routes
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map(|(_, response)| *response)
+                .unwrap_or("HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n");
+
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Send a `GET` request for the given path to a server which is about to be
+/// bound to the given address, returning the raw response.
+async fn get(addr: &str, path: &str) -> String {
+    let mut stream = loop {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => break stream,
+            Err(..) => tokio::time::delay_for(Duration::from_millis(10)).await,
+        }
+    };
+
+    let request = format!("GET {} HTTP/1.1\r\nhost: {}\r\n\r\n", path, addr);
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Find a free local address to bind a server to.
+fn free_addr() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_http_sandbox_url() {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_host("127.0.0.1");
+
+    let vm = new_vm(
+        r#"async fn main() { http::get("not a url").await }"#,
+        Some(sandbox),
+    );
+
+    let error = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap_err();
+
+    match error.into_kind() {
+        PermissionDenied {
+            denied: Denied::Host(host),
+        } => assert_eq!(host, "not a url"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    // NB: without a sandbox the error is left to the client.
+    let vm = new_vm(
+        r#"async fn main() { http::get("not a url").await.is_err() }"#,
+        None,
+    );
+
+    let output = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    assert!(bool::from_value(output).unwrap());
+}
+
+#[tokio::test]
+async fn test_http_sandbox_redirect() {
+    let url = serve(&[
+        (
+            "/denied",
+            "HTTP/1.1 302 Found\r\nlocation: http://example.com/\r\ncontent-length: 0\r\n\r\n",
+        ),
+        (
+            "/allowed",
+            "HTTP/1.1 302 Found\r\nlocation: /ok\r\ncontent-length: 0\r\n\r\n",
+        ),
+        ("/ok", "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok"),
+    ])
+    .await;
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_host("127.0.0.1");
+
+    let vm = new_vm(
+        r#"
+        async fn main(url) {
+            let client = http::Client::new();
+            let denied = match client.get(`{url}/denied`).await?.send().await {
+                Ok(response) => `followed to {response.status()}`,
+                Err(error) => `{error}`,
+            };
+
+            let allowed = http::get(`{url}/allowed`).await?.text().await?;
+            Ok((denied, allowed))
+        }
+        "#,
+        Some(sandbox),
+    );
+
+    let output = vm
+        .execute(&["main"], (url,))
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    let (denied, allowed) = Result::<(String, String), Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    assert!(
+        denied.contains("permission denied: connecting to host `example.com`"),
+        "{}",
+        denied
+    );
+    assert_eq!(allowed, "ok");
+}
+
+#[tokio::test]
+async fn test_http_server() {
+    let vm = new_vm(
+        r#"
+        async fn hello(request) {
+            `hello {request.path()}`
+        }
+
+        async fn requests(url) {
+            let client = http::Client::new();
+            let hello = http::get(`{url}/hello/world`).await?;
+            let missing = http::get(`{url}/missing`).await?;
+            let wrong_method = client.post(`{url}/hello/world`).await?.send().await?;
+
+            let statuses = [
+                hello.status().as_u16(),
+                missing.status().as_u16(),
+                wrong_method.status().as_u16(),
+            ];
+
+            Ok((statuses, hello.text().await?))
+        }
+
+        async fn main() {
+            let server = http::Server::bind("127.0.0.1:0").await?;
+            server.route("GET", "/hello/*", hello);
+            let url = `http://{server.local_addr()?}`;
+
+            let (served, sent) = std::future::join((server.serve_requests(3), requests(url))).await;
+            served?;
+            sent
+        }
+        "#,
+        None,
+    );
+
+    let output = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    let (statuses, hello) = Result::<(Vec<i64>, String), Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(statuses, vec![200, 404, 405]);
+    assert_eq!(hello, "hello /hello/world");
+}
+
+#[tokio::test]
+async fn test_http_server_shared_response() {
+    let vm = new_vm(
+        r#"
+        async fn requests(url) {
+            let texts = [];
+
+            for path in ["/text", "/text", "/response", "/response"] {
+                texts.push(http::get(`{url}{path}`).await?.text().await?);
+            }
+
+            Ok(texts)
+        }
+
+        async fn main() {
+            let name = "world";
+            let greeting = `hello {name}`;
+            let response = http::ServerResponse::new(200).body(greeting);
+
+            let server = http::Server::bind("127.0.0.1:0").await?;
+            server.route("GET", "/text", |request| greeting);
+            server.route("GET", "/response", |request| response);
+            let url = `http://{server.local_addr()?}`;
+
+            let (served, texts) = std::future::join((server.serve_requests(4), requests(url))).await;
+            served?;
+            let texts = texts?;
+            texts.push(greeting);
+            Ok(texts)
+        }
+        "#,
+        None,
+    );
+
+    let output = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    let texts = Result::<Vec<String>, Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    // NB: the values served are still accessible to the script afterwards.
+    assert_eq!(texts, vec!["hello world"; 5]);
+}
+
+#[tokio::test]
+async fn test_http_request_builder() {
+    let vm = new_vm(
+        r#"
+        async fn echo(request) {
+            http::ServerResponse::new(200).json(#{
+                method: request.method(),
+                query: request.query(),
+                auth: request.header("authorization"),
+                custom: request.header("x-custom"),
+                body: request.json(),
+            })
+        }
+
+        async fn send(url) {
+            let client = http::Client::new();
+            let request = client.post(url).await?;
+
+            let response = request
+                .header("x-custom", "yes")
+                .query(#{a: "1"})
+                .bearer_auth("token")
+                .json(#{n: 1})
+                .send()
+                .await?;
+
+            let content_type = response.header("content-type");
+            let echo = response.json().await?;
+            Ok((content_type, echo.method, echo.query.a, echo.auth, echo.custom, echo.body.n))
+        }
+
+        async fn main() {
+            let server = http::Server::bind("127.0.0.1:0").await?;
+            server.route("*", "/echo", echo);
+            let url = `http://{server.local_addr()?}/echo`;
+
+            let (served, sent) = std::future::join((server.serve_requests(1), send(url))).await;
+            served?;
+            sent
+        }
+        "#,
+        None,
+    );
+
+    let output = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    let echo =
+        Result::<(Option<String>, String, String, String, String, i64), Value>::from_value(output)
+            .unwrap()
+            .unwrap();
+
+    assert_eq!(
+        echo,
+        (
+            Some(String::from("application/json")),
+            String::from("POST"),
+            String::from("1"),
+            String::from("Bearer token"),
+            String::from("yes"),
+            1
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_http_server_handler_sandbox() {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_host("127.0.0.1");
+
+    let vm = new_vm(
+        r#"
+        async fn leak(request) {
+            http::get("not a url").await
+        }
+
+        async fn main() {
+            let server = http::Server::bind("127.0.0.1:0").await?;
+            server.route("GET", "/", leak);
+            let url = `http://{server.local_addr()?}/`;
+
+            let (served, response) = std::future::join((server.serve_requests(1), http::get(url))).await;
+            served?;
+            let response = response?;
+            Ok((response.status().as_u16(), response.text().await?))
+        }
+        "#,
+        Some(sandbox),
+    );
+
+    let output = vm
+        .execute(&["main"], ())
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    let (status, body) = Result::<(i64, String), Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(status, 500);
+    assert!(
+        body.contains("permission denied: connecting to host `not a url`"),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_http_server_handler_fuel() {
+    let addr = free_addr();
+
+    let mut vm = new_vm(
+        r#"
+        fn spin(request) {
+            let n = 0;
+            while n < 100000 { n += 1; }
+            "done"
+        }
+
+        async fn main(addr) {
+            let server = http::Server::bind(addr).await?;
+            server.route("GET", "/", spin);
+            server.serve_requests(1).await
+        }
+        "#,
+        None,
+    );
+
+    vm.set_fuel(Some(10_000));
+
+    let mut execution = vm.execute(&["main"], (addr.clone(),)).unwrap();
+
+    let (served, response) = tokio::join!(execution.async_complete(), get(&addr, "/"));
+
+    assert!(response.starts_with("HTTP/1.1 500"), "{}", response);
+
+    assert!(matches!(
+        served.unwrap_err().into_kind(),
+        Halted {
+            halt: VmHaltInfo::Limited
+        }
+    ));
+}
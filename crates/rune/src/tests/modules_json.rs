@@ -0,0 +1,240 @@
+use crate::testing::*;
+use std::fs;
+
+#[test]
+fn test_json_to_string() {
+    let vm = vm(r#"
+        enum Shape { Circle { radius }, Square(side) }
+
+        fn main() {
+            let circle = Shape::Circle { radius: 1.5 };
+            let square = Shape::Square(2);
+
+            [
+                json::to_string_pretty(#{a: [1, 2]})?,
+                json::to_string_with(circle, #{})?,
+                json::to_string_with(circle, #{repr: "external"})?,
+                json::to_string_with(circle, #{repr: "internal", tag: "type"})?,
+                json::to_string_with(circle, #{repr: "adjacent", tag: "t", content: "c"})?,
+                json::to_string_with(circle, #{repr: "untagged"})?,
+                json::to_string_with(square, #{repr: "adjacent", tag: "t", content: "c"})?,
+                json::to_string_with(square, #{repr: "untagged", pretty: true})?,
+                json::to_string_with(Err(1), #{})?,
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}",
+            "{\"Circle\":{\"radius\":1.5}}",
+            "{\"Circle\":{\"radius\":1.5}}",
+            "{\"type\":\"Circle\",\"radius\":1.5}",
+            "{\"t\":\"Circle\",\"c\":{\"radius\":1.5}}",
+            "{\"radius\":1.5}",
+            "{\"t\":\"Square\",\"c\":2}",
+            "2",
+            "{\"Err\":1}",
+        ]
+    );
+}
+
+#[test]
+fn test_json_to_string_errors() {
+    let vm = vm(r#"
+        enum Shape { Square(side) }
+
+        fn main() {
+            match json::to_string_with(Shape::Square(2), #{repr: "internal", tag: "type"}) {
+                Ok(..) => None,
+                Err(error) => Some(`{error}`),
+            }
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+
+    assert_eq!(
+        Option::<String>::from_value(output).unwrap().as_deref(),
+        Some("cannot serialize tuple variant `Square` with internal tagging")
+    );
+}
+
+#[test]
+fn test_json_enum_repr_options() {
+    for (options, message) in &[
+        (
+            r#"#{repr: "internal"}"#,
+            "missing option `tag` for `internal` enums",
+        ),
+        (
+            r#"#{repr: "adjacent", tag: "t"}"#,
+            "missing option `content` for `adjacent` enums",
+        ),
+        (
+            r#"#{repr: "nope"}"#,
+            "unsupported enum representation `nope`",
+        ),
+    ] {
+        let vm = vm(&format!(
+            "fn main() {{ json::to_string_with(1, {}) }}",
+            options
+        ))
+        .unwrap();
+
+        let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+        match error.as_unwound().0 {
+            Panic { reason } => assert_eq!(reason.to_string(), *message),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+}
+
+#[test]
+fn test_json_from_string_as() {
+    let vm = vm(r#"
+        struct Person { name, age }
+
+        fn error(result) {
+            match result {
+                Ok(..) => None,
+                Err(error) => Some(`{error}`),
+            }
+        }
+
+        fn main() {
+            let person = json::from_string_as("{\"name\": \"Bob\", \"age\": 42}", Person)?;
+            let person = json::from_bytes_as(json::to_bytes(person)?, Person)?;
+
+            let errors = [
+                error(json::from_string_as("{\"name\": \"Bob\"}", Person)),
+                error(json::from_string_as("{\"name\": \"Bob\", \"age\": 42, \"x\": 1}", Person)),
+                error(json::from_string_as("[1, 2]", Person)),
+                error(json::from_string_as("{\"name\": }", Person)),
+            ];
+
+            (`{person.name}:{person.age}`, errors)
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = <(String, Vec<Option<String>>)>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        (
+            String::from("Bob:42"),
+            vec![
+                Some(String::from("missing field `age` for `Person`")),
+                Some(String::from("unknown field `x` for `Person`")),
+                Some(String::from(
+                    "expected an object for `Person`, but found `Vec`"
+                )),
+                Some(String::from("expected value at line 1 column 10")),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_json_error_position() {
+    let vm = vm(r#"
+        struct Person { name, age }
+
+        fn position(result) {
+            match result {
+                Ok(..) => None,
+                Err(error) => Some((error.line(), error.column())),
+            }
+        }
+
+        fn main() {
+            [
+                position(json::from_string("{\n  \"name\": }")),
+                position(json::from_string("[1,\n2,\n3")),
+                position(json::from_string_as("{\"name\": \"Bob\"}", Person)),
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<Option<(Option<usize>, Option<usize>)>>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            Some((Some(2), Some(11))),
+            Some((Some(3), Some(1))),
+            Some((None, None)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_json_lines() {
+    let path = std::env::temp_dir().join(format!("rune-json-{}.jsonl", std::process::id()));
+    fs::write(&path, "{\"a\": 1}\n\n  \n{\"a\": 2}\nnope\n").unwrap();
+
+    let vm = vm(r#"
+        fn collect(values, value) {
+            match value {
+                Ok(value) => values.push(Some(value.a)),
+                Err(error) => values.push(error.line()),
+            }
+        }
+
+        async fn main(path) {
+            let values = [];
+
+            for value in json::lines("{\"a\": 1}\n\n  \n{\"a\": 2}\nnope\n") {
+                collect(values, value);
+            }
+
+            let lines = json::read_lines(path).await?;
+
+            while let Some(value) = lines.next().await {
+                collect(values, value);
+            }
+
+            let missing = match json::read_lines(`{path}.missing`).await {
+                Ok(..) => false,
+                Err(..) => true,
+            };
+
+            Ok((values, missing))
+        }
+        "#)
+    .unwrap();
+
+    let output = vm
+        .execute(&["main"], (path.display().to_string(),))
+        .unwrap()
+        .async_complete()
+        .await
+        .unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    // NB: errors are reported by the line within the value, so every value
+    // starts at line 1.
+    let output = Result::<(Vec<Option<i64>>, bool), Value>::from_value(output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        output,
+        (
+            vec![Some(1), Some(2), Some(1), Some(1), Some(2), Some(1)],
+            true
+        )
+    );
+}
@@ -0,0 +1,46 @@
+use crate::testing::*;
+use runestick::sandbox::Denied;
+use runestick::{Sandbox, Vm};
+use std::sync::Arc;
+
+fn new_vm(source: &str, sandbox: Option<Sandbox>) -> Vm {
+    let mut vm = vm(source).unwrap();
+    vm.set_sandbox(sandbox.map(Arc::new));
+    vm
+}
+
+#[test]
+fn test_process_sandbox_path() {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_executable("git");
+
+    let sources = [
+        r#"fn main() { let c = process::Command::new("git"); c.env("PATH", "/tmp/evil"); }"#,
+        r#"fn main() { let c = process::Command::new("git"); c.env_remove("path"); }"#,
+        r#"fn main() { let c = process::Command::new("git"); c.env_clear(); }"#,
+    ];
+
+    for source in &sources {
+        let vm = new_vm(source, Some(sandbox.clone()));
+        let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+        match error.as_unwound().0 {
+            PermissionDenied {
+                denied: Denied::Environment(key),
+            } => assert!(key.eq_ignore_ascii_case("PATH")),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    let vm = new_vm(
+        r#"fn main() { let c = process::Command::new("git"); c.env("LC_ALL", "C"); }"#,
+        Some(sandbox),
+    );
+    assert!(vm.execute(&["main"], ()).unwrap().complete().is_ok());
+
+    let vm = new_vm(
+        r#"fn main() { let c = process::Command::new("git"); c.env("PATH", "/tmp"); c.env_clear(); }"#,
+        None,
+    );
+    assert!(vm.execute(&["main"], ()).unwrap().complete().is_ok());
+}
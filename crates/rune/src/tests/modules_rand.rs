@@ -0,0 +1,69 @@
+use crate::testing::*;
+use runestick::Vm;
+use std::sync::Arc;
+
+#[test]
+fn test_rand_seeded() {
+    let context = crate::default_context().unwrap();
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        use rand::Rng;
+
+        fn main() {
+            let rng = Rng::from_seed(42);
+            let values = [1, 2, 3, 4, 5];
+            rng.shuffle(values);
+
+            let ints = [rng.int(), rng.int_range(0, 100), rng.int_range(0, 100)];
+            (ints, rng.float(), rng.bool(), rng.choose(["a", "b", "c"]), values)
+        }
+        "#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+
+    // NB: seeded generators produce the same values on every platform for a
+    // given version of the module, so these have to be updated if an upgrade
+    // of the `rand` crate changes them.
+    let output = <(Vec<i64>, f64, bool, Option<String>, Vec<i64>)>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        (
+            vec![2700349467815624629, 77, 34],
+            0.7371560746401922,
+            false,
+            Some(String::from("b")),
+            vec![3, 2, 1, 5, 4],
+        )
+    );
+}
+
+#[test]
+fn test_rand_shuffle() {
+    let context = crate::default_context().unwrap();
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        use rand::Rng;
+
+        fn main() {
+            let values = [1, 2, 3, 4, 5];
+            rand::shuffle(values);
+            Rng::from_seed(42).shuffle(values);
+            values
+        }
+        "#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+
+    let mut output = Vec::<i64>::from_value(output).unwrap();
+    output.sort();
+    assert_eq!(output, vec![1, 2, 3, 4, 5]);
+}
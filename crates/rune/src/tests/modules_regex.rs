@@ -0,0 +1,173 @@
+use crate::testing::*;
+use runestick::FromValue;
+
+fn run<T>(source: &str) -> T
+where
+    T: FromValue,
+{
+    let vm = vm(source).unwrap();
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    T::from_value(output).unwrap()
+}
+
+#[test]
+fn test_regex_find() {
+    let (is_match, is_not_match, start, end, text, all) = run::<(
+        bool,
+        bool,
+        usize,
+        usize,
+        String,
+        Vec<String>,
+    )>(
+        r#"
+            use regex::Regex;
+
+            fn main() {
+                let re = Regex::new("\\w+=\\d+")?;
+                let found = re.find("x a=1")?;
+                let all = [];
+
+                for m in re.find_iter("a=1, b=2") {
+                    all.push(m.as_str());
+                }
+
+                (re.is_match("a=1"), re.is_match("nope"), found.start(), found.end(), `{found}`, all)
+            }
+            "#,
+    );
+
+    assert!(is_match);
+    assert!(!is_not_match);
+    assert_eq!((start, end), (2, 5));
+    assert_eq!(text, "a=1");
+    assert_eq!(all, vec![String::from("a=1"), String::from("b=2")]);
+}
+
+#[test]
+fn test_regex_captures() {
+    let (captures, key, named, iter) =
+        run::<(Vec<Option<String>>, String, usize, Vec<Vec<Option<String>>>)>(
+            r#"
+            use regex::Regex;
+
+            fn main() {
+                let re = Regex::new("(?P<key>\\w+)=(?P<value>\\d+)?")?;
+                let named = re.captures_named("b=")?;
+                let iter = [];
+
+                for groups in re.captures_iter("a=1 b=") {
+                    iter.push(groups);
+                }
+
+                (re.captures("b=")?, named["key"], named.len(), iter)
+            }
+            "#,
+        );
+
+    assert_eq!(
+        captures,
+        vec![Some(String::from("b=")), Some(String::from("b")), None]
+    );
+    assert_eq!(key, "b");
+    assert_eq!(named, 1);
+    assert_eq!(
+        iter,
+        vec![
+            vec![
+                Some(String::from("a=1")),
+                Some(String::from("a")),
+                Some(String::from("1"))
+            ],
+            vec![Some(String::from("b=")), Some(String::from("b")), None],
+        ]
+    );
+}
+
+#[test]
+fn test_regex_replace_split() {
+    let output = run::<Vec<String>>(
+        r#"
+        use regex::Regex;
+
+        fn main() {
+            let re = Regex::new("(?P<key>\\w+)=(?P<value>\\d+)")?;
+
+            let by_function = re.replace_all("a=1, b=2", |groups| {
+                `{groups[2].unwrap_or("")}{groups[1].unwrap_or("")}`
+            });
+
+            [
+                re.replace("a=1, b=2", "$key"),
+                re.replace_all("a=1, b=2", "$value"),
+                by_function,
+                `{Regex::new("\\s*,\\s*")?.split("a , b,c").len()}`,
+                Regex::new(",")?.splitn("a,b,c", 2)[1],
+                regex::escape("a.b*"),
+                re.as_str(),
+            ]
+        }
+        "#,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "a, b=2",
+            "1, 2",
+            "1a, 2b",
+            "3",
+            "b,c",
+            "a\\.b\\*",
+            "(?P<key>\\w+)=(?P<value>\\d+)",
+        ]
+    );
+}
+
+#[test]
+fn test_regex_error() {
+    let error = run::<String>(
+        r#"
+        fn main() {
+            match regex::Regex::new("(") {
+                Ok(..) => "ok",
+                Err(error) => `{error}`,
+            }
+        }
+        "#,
+    );
+
+    assert!(error.contains("unclosed group"), "{}", error);
+}
+
+#[test]
+fn test_regex_cache_eviction() {
+    // NB: compiles more regexes than fit in the cache, while reusing one of
+    // them so that it's never the least recently used.
+    let matched = run::<usize>(
+        r#"
+        use regex::Regex;
+
+        fn main() {
+            let matched = 0;
+            let n = 0;
+
+            while n < 300 {
+                if Regex::new(`^a{n}$`)?.is_match(`a{n}`) {
+                    matched += 1;
+                }
+
+                if Regex::new("^first$")?.is_match("first") {
+                    matched += 1;
+                }
+
+                n += 1;
+            }
+
+            matched
+        }
+        "#,
+    );
+
+    assert_eq!(matched, 600);
+}
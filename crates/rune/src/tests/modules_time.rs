@@ -0,0 +1,229 @@
+use crate::testing::*;
+
+#[test]
+fn test_time_datetime_parse_and_format() {
+    let vm = vm(r#"
+        use time::DateTime;
+
+        fn main() {
+            let date = DateTime::parse("2020-08-01T12:30:15+02:00")?;
+            let naive = DateTime::parse_from_str("2020-08-01 10:30", "%Y-%m-%d %H:%M")?;
+
+            [
+                date.to_rfc3339(),
+                `{date}`,
+                date.format("%Y-%m-%d %H:%M:%S %z"),
+                date.format("%A %d %B"),
+                naive.to_rfc3339(),
+                DateTime::from_timestamp(0)?.to_rfc3339(),
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            "2020-08-01T12:30:15+02:00",
+            "2020-08-01T12:30:15+02:00",
+            "2020-08-01 12:30:15 +0200",
+            "Saturday 01 August",
+            "2020-08-01T10:30:00+00:00",
+            "1970-01-01T00:00:00+00:00",
+        ]
+    );
+}
+
+#[test]
+fn test_time_datetime_components() {
+    let vm = vm(r#"
+        use time::DateTime;
+
+        fn main() {
+            let date = DateTime::parse("2020-08-01T12:30:15+02:00")?;
+
+            [
+                date.year(),
+                date.month(),
+                date.day(),
+                date.weekday(),
+                date.hour(),
+                date.minute(),
+                date.second(),
+                date.timestamp(),
+                date.timestamp_millis(),
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<i64>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![2020, 8, 1, 6, 12, 30, 15, 1596277815, 1596277815000]
+    );
+}
+
+#[test]
+fn test_time_datetime_parse_errors() {
+    let vm = vm(r#"
+        use time::DateTime;
+
+        fn error(result) {
+            match result {
+                Err(error) => `{error}`,
+                Ok(date) => `unexpected {date}`,
+            }
+        }
+
+        fn main() {
+            [
+                error(DateTime::parse("2020-08-01")),
+                error(DateTime::parse("2020/08/01T12:00:00Z")),
+                error(DateTime::parse_from_str("2020-13-01", "%Y-%m-%d")),
+            ]
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            "premature end of input",
+            "input contains invalid characters",
+            "input is out of range",
+        ]
+    );
+}
+
+#[test]
+fn test_time_datetime_offsets() {
+    let vm = vm(r#"
+        use time::DateTime;
+
+        fn main() {
+            let date = DateTime::parse("2020-08-01T12:30:15+02:00")?;
+            let utc = date.to_utc();
+            let eastern = date.with_offset(-5 * 3600);
+
+            (
+                [date.offset(), utc.offset(), eastern.offset()],
+                [utc.to_rfc3339(), eastern.to_rfc3339()],
+                [date == utc, utc == eastern, eastern.hour() == date.hour()],
+            )
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = <(Vec<i64>, Vec<String>, Vec<bool>)>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        (
+            vec![7200, 0, -18000],
+            vec![
+                String::from("2020-08-01T10:30:15+00:00"),
+                String::from("2020-08-01T05:30:15-05:00"),
+            ],
+            vec![true, true, false],
+        )
+    );
+}
+
+#[test]
+fn test_time_arithmetic() {
+    let vm = vm(r#"
+        use time::DateTime;
+        use time::Duration;
+
+        fn main() {
+            let date = DateTime::parse("2020-08-01T12:30:15+02:00")?;
+            let later = date + Duration::from_secs(3600 * 24);
+            let d = Duration::from_millis(1500) + Duration::from_micros(250) - Duration::from_nanos(50);
+
+            (
+                [later.to_rfc3339(), (later - Duration::from_secs(90)).to_rfc3339(), `{d}`],
+                [d.as_secs(), d.as_millis(), d.as_micros(), d.as_nanos(), d.subsec_millis(), d.subsec_nanos()],
+                [
+                    date < later,
+                    later > date,
+                    Duration::from_secs(1) < d,
+                    Duration::from_secs_f64(0.5) == Duration::from_millis(500),
+                    Duration::from_millis(1250).as_secs_f64() == 1.25,
+                ],
+            )
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = <(Vec<String>, Vec<i64>, Vec<bool>)>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        (
+            vec![
+                String::from("2020-08-02T12:30:15+02:00"),
+                String::from("2020-08-02T12:28:45+02:00"),
+                String::from("1.50024995s"),
+            ],
+            vec![1, 1500, 1500249, 1500249950, 500, 500249950],
+            vec![true, true, true, true, true],
+        )
+    );
+}
+
+#[test]
+fn test_time_arithmetic_overflow() {
+    for (expr, message) in &[
+        (
+            "Duration::from_secs(1) - Duration::from_secs(2)",
+            "overflow when subtracting durations",
+        ),
+        (
+            "Instant::now() + Duration::from_secs(9223372036854775807)",
+            "overflow when adding duration to instant",
+        ),
+        (
+            "DateTime::parse(\"2020-08-01T12:00:00Z\")? + Duration::from_secs(9223372036854775807)",
+            "overflow when adding duration to date",
+        ),
+        (
+            "DateTime::parse(\"2020-08-01T12:00:00Z\")? - Duration::from_secs(9223372036854775807)",
+            "overflow when subtracting duration from date",
+        ),
+        (
+            "DateTime::now().with_offset(86400)",
+            "invalid offset `86400`",
+        ),
+        ("DateTime::now().format(\"%Q\")", "invalid format `%Q`"),
+    ] {
+        let vm = vm(&format!(
+            r#"
+            use time::DateTime;
+            use time::Duration;
+            use time::Instant;
+
+            fn main() {{ {} }}
+            "#,
+            expr
+        ))
+        .unwrap();
+
+        let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+        match error.as_unwound().0 {
+            Panic { reason } => assert_eq!(reason.to_string(), *message),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+}
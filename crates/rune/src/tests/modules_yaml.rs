@@ -0,0 +1,45 @@
+use crate::testing::*;
+
+#[test]
+fn test_yaml() {
+    let vm = vm(r#"
+        fn main() {
+            let data = yaml::from_string("hello:\n  world: 42\nlist: [1, 2.5, true, ~, \"s\"]\n")?;
+            let back = yaml::from_bytes(yaml::to_bytes(data)?)?;
+            let list = data.list;
+
+            (data.hello.world, back.hello.world, list[1], list[3], yaml::to_string(#{a: [1, 2]})?)
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = <(i64, i64, f64, (), String)>::from_value(output).unwrap();
+
+    assert_eq!(
+        output,
+        (42, 42, 2.5, (), String::from("---\na:\n  - 1\n  - 2\n"))
+    );
+}
+
+#[test]
+fn test_yaml_error() {
+    let vm = vm(r#"
+        fn main() {
+            match yaml::from_string("a: [1, 2") {
+                Ok(..) => None,
+                Err(error) => Some((error.line(), error.column(), `{error}`)),
+            }
+        }
+        "#)
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let (line, column, message) =
+        Option::<(Option<usize>, Option<usize>, String)>::from_value(output)
+            .unwrap()
+            .unwrap();
+
+    assert_eq!((line, column), (Some(2), Some(1)));
+    assert!(message.contains("expected ',' or ']'"), "{}", message);
+}
@@ -214,3 +214,69 @@ fn test_closure_in_lit_object() -> runestick::Result<()> {
     assert_eq!(3, proxy.d);
     Ok(())
 }
+
+#[test]
+fn test_closure_capture_by_cell() {
+    assert_eq! {
+        rune!((i64, i64, i64, i64) => r#"
+        fn main() {
+            let n = 0;
+            let inc = || { n += 1; };
+            inc();
+            inc();
+            let get = || n;
+            n += 10;
+            let copy = move || { n += 100; n };
+            (n, get(), copy(), n)
+        }
+        "#),
+        (12, 12, 112, 12)
+    };
+
+    // NB: cells are shared across nested closures and loop iterations.
+    assert_eq! {
+        rune!((i64, i64) => r#"
+        fn main() {
+            let total = 0;
+
+            let outer = || {
+                let add = |x| { total = total + x; };
+                add(1);
+                add(2);
+            };
+
+            outer();
+
+            let n = 0;
+
+            for i in [0, 1, 2] {
+                n += i;
+                let double = || n = n * 2;
+                double();
+            }
+
+            (total, n)
+        }
+        "#),
+        (3, 8)
+    };
+}
+
+#[test]
+fn test_closure_capture_by_cell_escapes() -> runestick::Result<()> {
+    let (inc, get) = rune! {
+        (Function, Function) => r#"
+        fn counter() {
+            let n = 0;
+            (|| n += 1, || n)
+        }
+
+        fn main() { counter() }
+        "#
+    };
+
+    inc.call::<_, ()>(())?;
+    inc.call::<_, ()>(())?;
+    assert_eq!(2, get.call::<_, i64>(())?);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::testing::*;
+use runestick::{Any, Context, Module};
+
+#[derive(Debug, Clone, Copy, Any)]
+struct Version {
+    major: i64,
+}
+
+impl Version {
+    fn new(major: i64) -> Self {
+        Self { major }
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major
+    }
+
+    fn cmp(&self, other: &Self) -> i64 {
+        self.major.cmp(&other.major) as i64
+    }
+}
+
+fn context() -> Context {
+    let mut module = Module::new(&["version"]);
+    module.ty::<Version>().unwrap();
+    module.function(&["Version", "new"], Version::new).unwrap();
+    module.inst_fn(runestick::EQ, Version::eq).unwrap();
+    module.inst_fn(runestick::CMP, Version::cmp).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+    context
+}
+
+#[test]
+fn test_native_comparisons() {
+    let vm = vm_with_context(
+        context(),
+        r#"
+        use version::Version;
+
+        fn main() {
+            let a = Version::new(1);
+            let b = Version::new(2);
+            [a == a, a == b, a != b, a < b, a <= a, a > b, b >= a]
+        }
+        "#,
+    )
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<bool>::from_value(output).unwrap();
+    assert_eq!(output, vec![true, false, true, true, true, false, true]);
+}
+
+#[test]
+fn test_native_eq_other_types() {
+    // NB: the `EQ` protocol is only called with values of the same type.
+    let vm = vm_with_context(
+        context(),
+        r#"
+        use version::Version;
+
+        fn main() {
+            let a = Version::new(1);
+            [a == 1, a != "1", a == #{major: 1}, a != ()]
+        }
+        "#,
+    )
+    .unwrap();
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<bool>::from_value(output).unwrap();
+    assert_eq!(output, vec![false, true, false, true]);
+}
+
+#[test]
+fn test_native_comparisons_unsupported() {
+    let vm = vm_with_context(
+        context(),
+        r#"
+        fn main() {
+            #{major: 1} < #{major: 2}
+        }
+        "#,
+    )
+    .unwrap();
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(
+        error.as_unwound().0,
+        UnsupportedBinaryOperation { op: "<", .. }
+    ));
+}
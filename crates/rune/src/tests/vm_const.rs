@@ -0,0 +1,140 @@
+use crate::testing::*;
+
+#[test]
+fn test_const_items() {
+    assert_eq! {
+        rune!(i64 => r#"
+        const A = 1 + 2 * 3;
+        const B = (A << 2) - A % 4;
+        fn main() { B }
+        "#),
+        25
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        const NAME = "World";
+        const GREETING = "Hello " + NAME;
+        const MESSAGE = `{GREETING}! {ANSWER:>4}|{1.5 * 2.0}`;
+        const ANSWER = 42;
+        fn main() { MESSAGE }
+        "#),
+        "Hello World!   42|3.0"
+    };
+
+    assert_eq! {
+        rune!(bool => r#"
+        const LIMIT = 10;
+        const OK = LIMIT > 5 && !(LIMIT == 11) || false;
+        fn main() { OK }
+        "#),
+        true
+    };
+}
+
+#[test]
+fn test_const_collections() {
+    assert_eq! {
+        rune!((i64, i64, i64, i64) => r#"
+        const VALUES = [1, 2, (3, #{ a: 4 })];
+
+        fn main() {
+            let values = VALUES;
+            values.push(5);
+            let (n, object) = VALUES[2];
+            (values.len(), VALUES.len(), n, object.a)
+        }
+        "#),
+        (4, 3, 3, 4)
+    };
+}
+
+#[test]
+fn test_const_in_modules_and_impls() {
+    assert_eq! {
+        rune!((i64, i64, i64) => r#"
+        mod geo {
+            const SCALE = 10;
+
+            struct Point(x, y);
+
+            impl Point {
+                const ORIGIN = (1, 2);
+
+                fn scaled(self) {
+                    self.0 * SCALE + self.1
+                }
+            }
+        }
+
+        fn main() {
+            const LOCAL = geo::SCALE * 2;
+            let (x, y) = geo::Point::ORIGIN;
+            (geo::Point(x, y).scaled(), LOCAL, y)
+        }
+        "#),
+        (12, 20, 2)
+    };
+}
+
+#[test]
+fn test_const_errors() {
+    assert_compile_error! {
+        r#"const A = B; const B = A; fn main() { A }"#,
+        span, ConstCycle { item } => {
+            assert_eq!(item.to_string(), "A");
+            assert_eq!(span, Span::new(23, 24));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn foo() { 1 } const A = foo(); fn main() { A }"#,
+        span, NotConst => {
+            assert_eq!(span, Span::new(25, 30));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const A = 9223372036854775807 + 1; fn main() { A }"#,
+        span, ConstError { msg: "numerical overflow" } => {
+            assert_eq!(span, Span::new(10, 33));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const A = 1 + "a"; fn main() { A }"#,
+        span, UnsupportedConstBinaryOp { .. } => {
+            assert_eq!(span, Span::new(10, 17));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const A = B; fn main() { A }"#,
+        span, MissingConst { item } => {
+            assert_eq!(item.to_string(), "B");
+            assert_eq!(span, Span::new(10, 11));
+        }
+    };
+}
+
+#[test]
+fn test_unused_consts() {
+    // NB: unused constants are still evaluated, and can reference each other.
+    assert_eq! {
+        rune!(i64 => r#"
+        const A = 1;
+        const B = A + 1;
+        const C = B + 1;
+        fn main() { 1 }
+        "#),
+        1
+    };
+
+    assert_compile_error! {
+        r#"const A = B; const B = A; fn main() { 1 }"#,
+        span, ConstCycle { item } => {
+            assert!(span == Span::new(10, 11) || span == Span::new(23, 24));
+            assert!(item.to_string() == "A" || item.to_string() == "B");
+        }
+    };
+}
@@ -0,0 +1,66 @@
+use crate::testing::*;
+use runestick::{Context, Coverage, Source, Vm};
+use std::sync::Arc;
+
+#[test]
+fn test_coverage() {
+    let context = Arc::new(Context::with_default_modules().unwrap());
+
+    let mut sources = crate::Sources::new();
+    sources.insert(Source::new(
+        "main",
+        r#"
+        fn unused() {
+            1
+        }
+
+        fn main(n) {
+            if n > 1 {
+                2
+            } else {
+                3
+            }
+        }
+        "#,
+    ));
+
+    let mut errors = crate::Errors::new();
+    let mut warnings = crate::Warnings::new();
+
+    let unit = crate::load_sources(
+        &*context,
+        &Default::default(),
+        &mut sources,
+        &mut errors,
+        &mut warnings,
+    )
+    .unwrap();
+
+    let unit = Arc::new(unit);
+    let coverage = Arc::new(Coverage::new(unit.clone()));
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_coverage(Some(coverage.clone()));
+    let output = vm.execute(&["main"], (2i64,)).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 2);
+
+    let (taken, not_taken) = coverage.iter_branches().map(|(_, b)| b).next().unwrap();
+    assert_eq!(taken + not_taken, 1);
+    assert_eq!(taken * not_taken, 0);
+    assert!(coverage.iter_hits().any(|(_, hits)| hits == 0));
+
+    let mut out = Vec::new();
+    crate::coverage::emit_lcov(&mut out, &coverage, &sources).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    assert!(out.contains("SF:main\n"));
+    assert!(out.contains("FNDA:0,unused\n"));
+    assert!(out.contains("FNDA:1,main\n"));
+    assert!(out.contains("FNF:2\nFNH:1\n"));
+    assert!(out.contains("BRF:2\nBRH:1\n"));
+    assert!(out.contains("DA:3,0\n"));
+    assert!(out.contains("DA:7,1\n"));
+    assert!(out.contains("DA:8,1\n"));
+    assert!(out.contains("DA:10,0\n"));
+    assert!(out.ends_with("end_of_record\n"));
+}
@@ -0,0 +1,172 @@
+use crate::testing::*;
+
+#[test]
+fn test_template_format_specs() {
+    assert_eq! {
+        rune!(String => r#"fn main() { let x = 42; `[{x:>6}] [{x:<6}] [{x:^6}] [{x:*>6}]` }"#),
+        "[    42] [42    ] [  42  ] [****42]"
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let x = 0 - 42; `[{x:06}] [{42:+}] [{x:+06}]` }"#),
+        "[-00042] [+42] [-00042]"
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let f = 3.14159; `[{f:.2}] [{f:8.3}] [{f:e}] [{1234.5:.1E}]` }"#),
+        "[3.14] [   3.142] [3.14159e0] [1.2E3]"
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { `[{255:x}] [{255:#X}] [{8:o}] [{5:#b}] [{255:#06x}]` }"#),
+        "[ff] [0xFF] [10] [0b101] [0x00ff]"
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let s = "hello"; `[{s:.3}] [{s:^9}] [{s:?}]` }"#),
+        "[hel] [  hello  ] [\"hello\"]"
+    };
+}
+
+#[test]
+fn test_template_format_spec_paths() {
+    assert_eq! {
+        rune!(String => r#"
+        mod a { fn b() { 42 } }
+        fn main() { `{a::b():>4}|{#{a: 1}.a}|{if true { "yes" } else { "no" }:>4}` }
+        "#),
+        "  42|1| yes"
+    };
+}
+
+#[test]
+fn test_debug_format() {
+    assert_eq! {
+        rune!(String => r#"
+        fn main() {
+            let v = [1, "two", 3.0, 'c', Some(4), None, Ok(5), Err(()), (1,), (1, 2), #{b: true, a: []}];
+            `{v:?}`
+        }
+        "#),
+        "[1, \"two\", 3.0, 'c', Some(4), None, Ok(5), Err(()), (1,), (1, 2), #{\"a\": [], \"b\": true}]"
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        struct Person { name, age }
+        struct Point(x, y);
+        enum Shape { Circle { radius }, Square(side), Empty }
+
+        fn main() {
+            let values = (Person { name: "Bob", age: 42 }, Point(1, 2), Shape::Circle { radius: 1 }, Shape::Square(2), Shape::Empty);
+            `{values:?}`
+        }
+        "#),
+        "(Person { name: \"Bob\", age: 42 }, Point(1, 2), Circle { radius: 1 }, Square(2), Empty)"
+    };
+}
+
+#[test]
+fn test_pretty_debug_format() {
+    assert_eq! {
+        rune!(String => r#"
+        struct Point(x, y);
+        fn main() { let v = [1, #{a: Point(1, 2)}, []]; `{v:#?}` }
+        "#),
+        "[\n    1,\n    #{\n        \"a\": Point(\n            1,\n            2,\n        ),\n    },\n    [],\n]"
+    };
+}
+
+#[test]
+fn test_format_macros() {
+    assert_eq! {
+        rune!(String => r#"
+        fn main() {
+            let name = "Bob";
+            format!("{} is {age:>3}, {name}, {0:?} {{escaped}} {}", name, 1 + 2, age = 42)
+        }
+        "#),
+        "Bob is  42, Bob, \"Bob\" {escaped} 3"
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { format!("a\tb") }"#),
+        "a\tb"
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let v = [];
+            let s = format!("{0} {0} {0}", { v.push(1); v.len() });
+            v.len()
+        }
+        "#),
+        1
+    };
+}
+
+#[test]
+fn test_format_macro_errors() {
+    assert_compile_error! {
+        r#"fn main() { format!("{} {}", 1) }"#,
+        span, ParseError { error: MissingFormatArgument { index: 1 } } => {
+            assert_eq!(span, Span::new(25, 25));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { format!("{}", 1, 2) }"#,
+        span, ParseError { error: UnusedFormatArgument } => {
+            assert_eq!(span, Span::new(29, 30));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { format!("a } b") }"#,
+        span, ParseError { error: UnexpectedFormatCloseBrace } => {
+            assert_eq!(span, Span::new(23, 24));
+        }
+    };
+}
+
+#[test]
+fn test_format_spec_too_large() {
+    assert_compile_error! {
+        r#"fn main() { `{1:18446744073709551619}` }"#,
+        span, ParseError { error: FormatSpecTooLarge } => {
+            assert_eq!(span, Span::new(16, 36));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { `{1:99999999999999999999}` }"#,
+        span, ParseError { error: FormatSpecTooLarge } => {
+            assert_eq!(span, Span::new(16, 36));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { `{1:.18446744073709551619}` }"#,
+        span, ParseError { error: FormatSpecTooLarge } => {
+            assert_eq!(span, Span::new(16, 37));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { format!("{:18446744073709551619}", 1) }"#,
+        span, ParseError { error: FormatSpecTooLarge } => {
+            assert_eq!(span, Span::new(23, 43));
+        }
+    };
+}
+
+#[test]
+fn test_unsupported_format() {
+    assert_vm_error!(
+        r#"fn main() { let s = "hello"; `{s:x}` }"#,
+        UnsupportedFormat { spec, .. } => {
+            assert_eq!(spec.to_string(), "x");
+        }
+    );
+}
@@ -0,0 +1,73 @@
+use crate::testing::*;
+use runestick::{GeneratorState, VmHaltInfo};
+
+#[test]
+fn test_fuel_limited() {
+    let mut vm = vm(r#"fn main() { loop {} }"#).unwrap();
+    vm.set_fuel(Some(100));
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        Halted {
+            halt: VmHaltInfo::Limited
+        }
+    ));
+}
+
+#[test]
+fn test_fuel_resume() {
+    let mut vm = vm(r#"
+        fn main() {
+            let n = 0;
+            while n < 100 { n += 1; }
+            n
+        }
+        "#)
+    .unwrap();
+    vm.set_fuel(Some(10));
+
+    let mut execution = vm.execute(&["main"], ()).unwrap();
+    let mut refuels = 0;
+
+    let value = loop {
+        match execution.resume_limited().unwrap() {
+            Some(GeneratorState::Complete(value)) => break value,
+            Some(GeneratorState::Yielded(..)) => panic!("unexpected yield"),
+            None => {
+                assert_eq!(execution.remaining_fuel().unwrap(), Some(0));
+                execution.add_fuel(10).unwrap();
+                refuels += 1;
+            }
+        }
+    };
+
+    assert_eq!(i64::from_value(value).unwrap(), 100);
+    assert!(refuels > 10);
+}
+
+#[test]
+fn test_fuel_shared_with_generators() {
+    let mut vm = vm(r#"
+        fn counter() {
+            loop {}
+            yield 1;
+        }
+
+        fn main() {
+            counter().next()
+        }
+        "#)
+    .unwrap();
+    vm.set_fuel(Some(100));
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        Halted {
+            halt: VmHaltInfo::Limited
+        }
+    ));
+}
@@ -1,4 +1,6 @@
 use crate::testing::*;
+use runestick::{Context, Module, Vm};
+use std::sync::Arc;
 
 #[test]
 fn test_function() {
@@ -68,3 +70,26 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value, Value::Integer(3)));
 }
+
+#[test]
+fn test_native_function_with_state() {
+    let names = vec![String::from("a"), String::from("b")];
+
+    let mut module = Module::new(&["state"]);
+    module
+        .raw_fn(&["names"], move |stack, _| {
+            stack.push(names.clone().to_value()?);
+            Ok(())
+        })
+        .unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    let (unit, _) = compile_source(&context, r#"fn main() { state::names() }"#).unwrap();
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<String>::from_value(output).unwrap();
+    assert_eq!(output, vec![String::from("a"), String::from("b")]);
+}
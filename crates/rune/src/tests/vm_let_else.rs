@@ -0,0 +1,109 @@
+use crate::testing::*;
+
+#[test]
+fn test_let_else() {
+    assert_eq! {
+        rune!((String, String) => r#"
+        fn first(v) {
+            let [a, ..] = v else { return "empty"; };
+            `first: {a}`
+        }
+
+        fn main() {
+            (first([]), first([1, 2]))
+        }
+        "#),
+        (String::from("empty"), String::from("first: 1"))
+    };
+
+    // NB: bindings are visible to the rest of the enclosing scope.
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let a = 1;
+            let (b, Some(c)) = (2, Some(3)) else { return 0; };
+            let d = 4;
+            a + b + c + d
+        }
+        "#),
+        10
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let total = 0;
+
+            for v in [Some(1), Some(2), None, Some(4)] {
+                let Some(v) = v else { break; };
+                total += v;
+            }
+
+            total
+        }
+        "#),
+        3
+    };
+}
+
+#[test]
+fn test_let_else_falls_through() {
+    // The else block of a let-else must not complete normally.
+    assert_vm_error!(
+        r#"
+        fn main() {
+            let Some(x) = None else { 1 };
+        }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+}
+
+#[test]
+fn test_if_let_chains() {
+    assert_eq! {
+        rune!((String, String, String) => r#"
+        fn check(a) {
+            if let Some(x) = a && x > 3 && let Some(y) = Some(x * 2) {
+                `big {y}`
+            } else if let Some(x) = a {
+                `small {x}`
+            } else {
+                "none"
+            }
+        }
+
+        fn main() {
+            (check(Some(5)), check(Some(1)), check(None))
+        }
+        "#),
+        (String::from("big 10"), String::from("small 1"), String::from("none"))
+    };
+
+    // NB: chains without a pattern are regular expressions.
+    assert_eq! {
+        rune!(bool => r#"fn main() { if true && false || true { true } else { false } }"#),
+        true
+    };
+}
+
+#[test]
+fn test_while_let_chains() {
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let it = [1, 2, 30, 4].iter();
+            let total = 0;
+
+            while let Some(x) = it.next() && x < 10 {
+                total += x;
+            }
+
+            total
+        }
+        "#),
+        3
+    };
+}
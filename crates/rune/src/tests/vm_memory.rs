@@ -0,0 +1,248 @@
+use crate::testing::*;
+use runestick::{Memory, Vm};
+use std::sync::Arc;
+
+fn vm_with_memory(source: &str, limit: usize) -> (Vm, Arc<Memory>) {
+    let memory = Arc::new(Memory::new(limit));
+    let mut vm = vm(source).unwrap();
+    vm.set_memory(Some(memory.clone()));
+    (vm, memory)
+}
+
+#[test]
+fn test_memory_limit_exceeded() {
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main() {
+            let v = [];
+
+            loop {
+                v.push("hello world");
+            }
+        }
+        "#,
+        64 * 1024,
+    );
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    match error.into_kind() {
+        Unwound { kind, .. } => match *kind {
+            MemoryLimitExceeded { limit, usage } => {
+                assert_eq!(limit, 64 * 1024);
+                assert!(usage > limit);
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        },
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    assert!(memory.peak() > 64 * 1024);
+}
+
+#[test]
+fn test_memory_released() {
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main() {
+            let n = 0;
+
+            while n < 1000 {
+                let s = String::new();
+                s.push_str("hello world");
+                let v = [s, s, s];
+                v.push(n);
+                n += 1;
+            }
+
+            n
+        }
+        "#,
+        16 * 1024,
+    );
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 1000);
+    assert_eq!(memory.usage(), 0);
+    assert!(memory.peak() > 0);
+}
+
+#[test]
+fn test_memory_with_capacity() {
+    let (vm, _) = vm_with_memory(r#"fn main() { String::with_capacity(1024 * 1024) }"#, 1024);
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_heap_charged() {
+    // NB: each string only checks the limit up front, but the strings which
+    // have been allocated are charged.
+    let (vm, _) = vm_with_memory(
+        r#"
+        fn main() {
+            let v = [];
+
+            loop {
+                v.push(String::with_capacity(64 * 1024));
+            }
+        }
+        "#,
+        1024 * 1024,
+    );
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, MemoryLimitExceeded { .. }));
+
+    let (vm, _) = vm_with_memory(
+        r#"
+        fn main() {
+            let s = "hello world";
+            let v = [];
+
+            loop {
+                s = s + s;
+                v.push(s);
+            }
+        }
+        "#,
+        1024 * 1024,
+    );
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, MemoryLimitExceeded { .. }));
+
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main() {
+            let n = 0;
+
+            while n < 100 {
+                let s = String::with_capacity(64 * 1024);
+                s.push_str("hello");
+                n += 1;
+            }
+
+            n
+        }
+        "#,
+        1024 * 1024,
+    );
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 100);
+    assert_eq!(memory.usage(), 0);
+    assert!(memory.peak() >= 64 * 1024);
+}
+
+#[test]
+fn test_memory_resized_in_place() {
+    // NB: the string is allocated before the virtual machine runs, so it's
+    // charged once it grows, and given back once it's dropped.
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main(s) {
+            let n = 0;
+
+            while n < 1000 {
+                s.push_str("hello world");
+                n += 1;
+            }
+
+            s
+        }
+        "#,
+        1024 * 1024,
+    );
+
+    let output = vm
+        .execute(&["main"], (String::new(),))
+        .unwrap()
+        .complete()
+        .unwrap();
+
+    assert!(memory.usage() >= 11 * 1000);
+    drop(output);
+    assert_eq!(memory.usage(), 0);
+
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main() {
+            let s = String::with_capacity(64 * 1024);
+            s.push_str("hello");
+            s.shrink_to_fit();
+            s
+        }
+        "#,
+        1024 * 1024,
+    );
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert!(memory.usage() < 1024);
+    assert!(memory.peak() >= 64 * 1024);
+    drop(output);
+    assert_eq!(memory.usage(), 0);
+}
+
+#[test]
+fn test_memory_object() {
+    let (vm, _) = vm_with_memory(
+        r#"
+        fn main() {
+            let o = #{};
+            let n = 0;
+
+            while n < 20000 {
+                o.insert(`k{n}`, n);
+                n += 1;
+            }
+        }
+        "#,
+        64 * 1024,
+    );
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, MemoryLimitExceeded { .. }));
+
+    let (vm, _) = vm_with_memory(
+        r#"
+        fn main() {
+            let o = #{};
+            let n = 0;
+
+            while n < 20000 {
+                o[`k{n}`] = n;
+                n += 1;
+            }
+        }
+        "#,
+        64 * 1024,
+    );
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, MemoryLimitExceeded { .. }));
+
+    let (vm, memory) = vm_with_memory(
+        r#"
+        fn main() {
+            let o = #{};
+            let n = 0;
+
+            while n < 100 {
+                o[`k{n}`] = n;
+                o.clear();
+                n += 1;
+            }
+
+            o
+        }
+        "#,
+        64 * 1024,
+    );
+
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert!(memory.usage() < 1024);
+    drop(output);
+    assert_eq!(memory.usage(), 0);
+}
@@ -0,0 +1,224 @@
+use crate::testing::*;
+use runestick::{Context, Module, Vm};
+use std::sync::Arc;
+
+#[test]
+fn test_default_args() {
+    assert_eq! {
+        rune!((String, String, String) => r#"
+        const TIMEOUT = 30;
+
+        fn connect(host, port = 80, timeout = TIMEOUT * 2) {
+            `{host}:{port}/{timeout}`
+        }
+
+        fn main() {
+            (connect("a"), connect("b", 8080), connect("c", 1, 2))
+        }
+        "#),
+        (
+            String::from("a:80/60"),
+            String::from("b:8080/60"),
+            String::from("c:1/2"),
+        )
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        struct Server;
+
+        impl Server {
+            fn new(name = "main") { name }
+        }
+
+        fn main() { Server::new() }
+        "#),
+        "main"
+    };
+
+    // NB: default arguments also apply when calling with method syntax.
+    assert_eq! {
+        rune!((i64, i64, i64, i64) => r#"
+        struct Foo;
+
+        impl Foo {
+            fn m(self, x = 1, y = 2) { x * 10 + y }
+        }
+
+        fn main() {
+            let f = Foo;
+            (f.m(), f.m(3), f.m(3, 4), Foo::m(f))
+        }
+        "#),
+        (12, 32, 34, 12)
+    };
+
+    assert_vm_error! {
+        r#"
+        struct Foo;
+        impl Foo { fn m(self, x, y = 2) { x + y } }
+        fn main() { Foo.m() }
+        "#,
+        BadArgumentCount { actual, expected } => {
+            assert_eq!(actual, 1);
+            assert_eq!(expected, 3);
+        }
+    };
+}
+
+#[test]
+fn test_named_args() {
+    assert_eq! {
+        rune!((String, String) => r#"
+        fn connect(host, port = 80, timeout = 30) {
+            `{host}:{port}/{timeout}`
+        }
+
+        fn main() {
+            (connect("a", timeout = 5), connect(port = 1, host = "b"))
+        }
+        "#),
+        (String::from("a:80/5"), String::from("b:1/30"))
+    };
+
+    // NB: arguments are evaluated in the order they're written in.
+    assert_eq! {
+        rune!((Vec<i64>, Vec<i64>) => r#"
+        fn order(a, b, c = 4, d = 5) { [a, b, c, d] }
+
+        fn main() {
+            let seen = [];
+            let push = |n| { seen.push(n); n };
+            let output = order(d = push(3), a = push(1), b = push(2));
+            (output, seen)
+        }
+        "#),
+        (vec![1, 2, 4, 3], vec![3, 1, 2])
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn sub(a, b) { a - b }
+
+        fn main() {
+            let a = 10;
+            sub(b = { a = 1; 2 }, a = a) + sub(1, b = 2)
+        }
+        "#),
+        -2
+    };
+
+    // NB: an argument which isn't a parameter assigns to the local variable
+    // with the same name.
+    assert_eq! {
+        rune!((i64, (), i64, (), (), i64) => r#"
+        fn id(a) { a }
+        fn sub(a, b = 1) { a - b }
+
+        fn main() {
+            let k = 0;
+            let a = 0;
+            let b = dbg(k = 5);
+            let c = |n| n;
+            let d = c(k = k + 1);
+            (sub(a = 10), id(k = 7), k, d, b, a)
+        }
+        "#),
+        (9, (), 7, (), (), 0)
+    };
+}
+
+#[test]
+fn test_named_args_errors() {
+    assert_compile_error! {
+        r#"fn f(a, b = 1) {} fn main() { f(1, c = 2) }"#,
+        span, MissingParameter { name } => {
+            assert_eq!(span, Span::new(35, 36));
+            assert_eq!(name, "c");
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn f(a, b = 1) {} fn main() { f(1, a = 2) }"#,
+        span, DuplicateArgument { name } => {
+            assert_eq!(span, Span::new(35, 40));
+            assert_eq!(name, "a");
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn f(a, b = 1) {} fn main() { f(b = 2) }"#,
+        span, MissingArgument { name } => {
+            assert_eq!(span, Span::new(30, 38));
+            assert_eq!(name, "a");
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn f(a, b = 1) {} fn main() { f(b = 2, 1) }"#,
+        span, PositionalAfterNamedArgument => {
+            assert_eq!(span, Span::new(39, 40));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { let f = |a| a; f(a = 1) }"#,
+        span, UnsupportedNamedArgument => {
+            assert_eq!(span, Span::new(29, 34));
+        }
+    };
+
+    assert_compile_error! {
+        r#"struct S; impl S { fn m(self, a, b = 2) {} } fn main() { S.m(1, b = 5) }"#,
+        span, UnsupportedInstanceNamedArgument => {
+            assert_eq!(span, Span::new(64, 69));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn f(a = g()) {} fn g() {} fn main() {}"#,
+        span, NotConst => {
+            assert_eq!(span, Span::new(9, 12));
+        }
+    };
+}
+
+#[test]
+fn test_default_args_not_const_reported_once() {
+    let context = Context::with_default_modules().unwrap();
+
+    for source in &[
+        r#"fn g() {} fn f(a = g()) {} fn main() { f() }"#,
+        r#"fn main() { f() } fn g() {} fn f(a = g()) {}"#,
+    ] {
+        let errors = compile_source(&context, source).unwrap_err();
+        assert_eq!(errors.into_iter().count(), 1);
+    }
+}
+
+#[test]
+fn test_native_named_args() {
+    let mut module = Module::new(&["net"]);
+
+    module
+        .function(&["connect"], |host: String, port: i64| {
+            format!("{}:{}", host, port)
+        })
+        .unwrap();
+
+    module.params(&["connect"], &["host", "port"]).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"fn main() { net::connect(port = 80, host = "example.com") }"#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = String::from_value(output).unwrap();
+    assert_eq!(output, "example.com:80");
+}
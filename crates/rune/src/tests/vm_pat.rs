@@ -1,3 +1,5 @@
+use crate::testing::*;
+
 #[test]
 fn test_ignore_binding() {
     assert_eq! {
@@ -51,3 +53,123 @@ fn test_match_binding() {
         true,
     };
 }
+
+#[test]
+fn test_or_pattern() {
+    assert_eq! {
+        rune! {
+            (String, String, String) => r#"
+            fn test(n) {
+                match n {
+                    1 | 2 | 3 => "small",
+                    Some(4) | None => "option",
+                    _ => "other",
+                }
+            }
+
+            fn main() {
+                (test(2), test(None), test(5))
+            }
+            "#
+        },
+        (String::from("small"), String::from("option"), String::from("other")),
+    };
+
+    assert_eq! {
+        rune! {
+            (i64, i64) => r#"
+            enum Shape { Circle(r), Rect(w, h) }
+
+            fn size(shape) {
+                match shape {
+                    Shape::Circle(n) | Shape::Rect(n, _) => n,
+                }
+            }
+
+            fn main() {
+                let Ok(n) | Err(n) = Err(2);
+                (size(Shape::Circle(1)) + n, size(Shape::Rect(3, 4)))
+            }
+            "#
+        },
+        (3, 3),
+    };
+}
+
+#[test]
+fn test_or_pattern_errors() {
+    assert_compile_error! {
+        r#"fn main() { match 1 { a | 2 => a, _ => 0 } }"#,
+        span, PatOrBindingMismatch { name } => {
+            assert_eq!(span, Span::new(26, 27));
+            assert_eq!(name, "a");
+        }
+    };
+}
+
+#[test]
+fn test_binding_pattern() {
+    assert_eq! {
+        rune! {
+            (i64, Vec<i64>) => r#"
+            fn main() {
+                let a = match Some(42) { Some(n @ 40..50) => n, _ => 0 };
+                let b = match [1, 2] { v @ [_, 2] => v, _ => [] };
+                (a, b)
+            }
+            "#
+        },
+        (42, vec![1, 2]),
+    };
+}
+
+#[test]
+fn test_range_pattern() {
+    assert_eq! {
+        rune! {
+            (String, String, String, String) => r#"
+            fn test(c) {
+                match c {
+                    'a'..='z' => "lower",
+                    b'0'..=b'9' => "byte",
+                    0..10 => "digit",
+                    10..=10 => "ten",
+                    _ => "other",
+                }
+            }
+
+            fn main() {
+                (test('q'), test(b'5'), test(9), test(10))
+            }
+            "#
+        },
+        (
+            String::from("lower"),
+            String::from("byte"),
+            String::from("digit"),
+            String::from("ten"),
+        ),
+    };
+}
+
+#[test]
+fn test_vec_rest_pattern() {
+    assert_eq! {
+        rune! {
+            (i64, i64, i64, bool) => r#"
+            fn test(v) {
+                match v {
+                    [first, .., last] => first * 10 + last,
+                    _ => 0,
+                }
+            }
+
+            fn main() {
+                let tail = match [1, 2, 3] { [.., 2, n] => n, _ => 0 };
+                (test([1, 2]), test([1, 2, 3, 4]), tail, test([1]) == 0)
+            }
+            "#
+        },
+        (12, 14, 3, true),
+    };
+}
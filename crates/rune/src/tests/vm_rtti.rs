@@ -0,0 +1,72 @@
+use crate::testing::*;
+use runestick::{Context, Module, Value, VmError};
+
+fn type_name(value: Value) -> Result<String, VmError> {
+    Ok(match value {
+        Value::TypedObject(object) => object.borrow_ref()?.rtti().name(),
+        Value::TypedTuple(tuple) => tuple.borrow_ref()?.rtti().name(),
+        Value::VariantObject(object) => object.borrow_ref()?.rtti.name(),
+        Value::TupleVariant(tuple) => tuple.borrow_ref()?.rtti().name(),
+        actual => return Err(VmError::expected_any(actual.type_info()?)),
+    })
+}
+
+fn fields(ty: Value) -> Option<Vec<String>> {
+    let hash = match ty {
+        Value::Type(hash) => hash,
+        _ => return None,
+    };
+
+    let rtti = runestick::rtti::lookup(hash)?;
+    Some(rtti.fields.as_ref()?.to_vec())
+}
+
+fn run(source: &str) -> Value {
+    let mut module = Module::new(&["rtti"]);
+    module.function(&["type_name"], type_name).unwrap();
+    module.function(&["fields"], fields).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    let vm = vm_with_context(context, source).unwrap();
+    vm.execute(&["main"], ()).unwrap().complete().unwrap()
+}
+
+#[test]
+fn test_rtti_names() {
+    let output = run(r#"
+    struct Person { name }
+    struct Point(x, y);
+    enum Shape { Circle { radius }, Square(side) }
+
+    fn main() {
+        [
+            rtti::type_name(Person { name: "Bob" }),
+            rtti::type_name(Point(1, 2)),
+            rtti::type_name(Shape::Circle { radius: 1 }),
+            rtti::type_name(Shape::Square(2)),
+        ]
+    }
+    "#);
+
+    let output = Vec::<String>::from_value(output).unwrap();
+    assert_eq!(output, vec!["Person", "Point", "Circle", "Square"]);
+}
+
+#[test]
+fn test_rtti_lookup() {
+    let output = run(r#"
+    struct Person { name, age }
+
+    fn main() {
+        rtti::fields(Person)
+    }
+    "#);
+
+    let output = Option::<Vec<String>>::from_value(output).unwrap();
+    assert_eq!(
+        output,
+        Some(vec![String::from("name"), String::from("age")])
+    );
+}
@@ -0,0 +1,126 @@
+use crate::testing::*;
+use runestick::sandbox::{self, Capability};
+use runestick::{Context, Module, Sandbox};
+use std::path::Path;
+use std::sync::Arc;
+
+fn context(sandbox: Option<Sandbox>) -> Context {
+    let mut module = Module::new(&["sandboxed"]);
+
+    module
+        .function(&["run"], |program: &str| {
+            sandbox::check(Capability::Executable(program))
+        })
+        .unwrap();
+
+    module
+        .async_function(&["read"], |path: String| async move {
+            sandbox::check(Capability::Path(Path::new(&path)))
+        })
+        .unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+    context.set_sandbox(sandbox);
+    context
+}
+
+#[test]
+fn test_sandbox_context() {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_executable("git");
+
+    let vm = vm_with_context(
+        context(Some(sandbox)),
+        r#"
+        fn main() {
+            sandboxed::run("git");
+            sandboxed::run("rm");
+        }
+        "#,
+    )
+    .unwrap();
+
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    match error.into_kind() {
+        Unwound { kind, .. } => match *kind {
+            PermissionDenied {
+                denied: sandbox::Denied::Executable(program),
+            } => assert_eq!(program, "rm"),
+            kind => panic!("unexpected error: {:?}", kind),
+        },
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn test_sandbox_vm_override() {
+    let source = r#"fn main() { sandboxed::run("rm") }"#;
+
+    let vm1 = vm_with_context(context(None), source).unwrap();
+    assert!(vm1.execute(&["main"], ()).unwrap().complete().is_ok());
+
+    let mut vm2 = vm_with_context(context(None), source).unwrap();
+    vm2.set_sandbox(Some(Arc::new(Sandbox::new())));
+    let error = vm2.execute(&["main"], ()).unwrap().complete().unwrap_err();
+    assert!(matches!(error.as_unwound().0, PermissionDenied { .. }));
+}
+
+#[test]
+fn test_sandbox_async() {
+    let root = std::env::temp_dir().join("rune-sandbox");
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_path(&root);
+
+    let vm = vm_with_context(
+        context(Some(sandbox)),
+        r#"
+        async fn read(path) {
+            sandboxed::read(path).await
+        }
+
+        async fn main(root) {
+            read(`{root}/allowed.txt`).await;
+            read(`{root}/../denied.txt`).await;
+        }
+        "#,
+    )
+    .unwrap();
+
+    let execution = vm.execute(&["main"], (root.display().to_string(),));
+    let error = block_on(execution.unwrap().async_complete()).unwrap_err();
+
+    match error.as_unwound().0 {
+        PermissionDenied {
+            denied: sandbox::Denied::Path(path),
+        } => assert!(path.ends_with("../denied.txt")),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sandbox_symlink() {
+    let root = std::env::temp_dir().join("rune-sandbox-symlink");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("allowed")).unwrap();
+    std::fs::create_dir_all(root.join("outside/sub")).unwrap();
+    std::fs::write(root.join("outside/secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(root.join("outside/sub"), root.join("allowed/link")).unwrap();
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_path(root.join("allowed"));
+
+    let permits = |path: &str| sandbox.permits(Capability::Path(&root.join(path)));
+
+    assert!(permits("allowed/data.txt"));
+    assert!(permits("allowed/missing/../data.txt"));
+    // NB: `..` is applied after the link is followed, which leaves the root.
+    assert!(!permits("allowed/link/../secret.txt"));
+    assert!(!permits("allowed/link/data.txt"));
+    assert!(!permits("allowed/link/.."));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,188 @@
+use crate::testing::*;
+use runestick::scheduler::{Priority, Scheduler};
+use runestick::{Context, Module, Shared, Value, Vm};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+const SOURCE: &str = r#"
+fn count(n) {
+    let i = 0;
+    while i < n { i += 1; }
+    i
+}
+
+fn forever() {
+    loop {}
+}
+
+async fn spin(n) {
+    let i = 0;
+    while i < n { i += 1; }
+    i
+}
+
+async fn await_spin(n) {
+    spin(n).await
+}
+
+async fn await_forever() {
+    (async { loop {} }).await
+}
+
+async fn sleepy(log, id) {
+    log.push(id);
+    yield_now().await;
+    log.push(id);
+    count(10)
+}
+"#;
+
+/// A future which is pending once, waking up immediately.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn vm() -> Vm {
+    let mut module = Module::empty();
+    module
+        .async_function(&["yield_now"], || YieldNow(false))
+        .unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    vm_with_context(context, SOURCE).unwrap()
+}
+
+#[test]
+fn test_scheduler_interleaves() {
+    let mut scheduler = Scheduler::with_quantum(10);
+
+    let a = scheduler.spawn(vm().execute(&["count"], (100i64,)).unwrap());
+    let b = scheduler.spawn(vm().execute(&["count"], (1000i64,)).unwrap());
+    assert_eq!(scheduler.len(), 2);
+
+    block_on(async {
+        // `a` is much shorter than `b`, so it should finish first.
+        assert!(scheduler.next().await);
+        assert!(a.is_finished());
+        assert!(!b.is_finished());
+    });
+
+    let log = Shared::new(Vec::<Value>::new());
+    let c = scheduler.spawn(vm().execute(&["sleepy"], (log.clone(), 1i64)).unwrap());
+    let d = scheduler.spawn(vm().execute(&["sleepy"], (log.clone(), 2i64)).unwrap());
+    block_on(scheduler.run());
+
+    assert!(scheduler.is_empty());
+
+    let a_value = i64::from_value(a.take_result().unwrap().unwrap()).unwrap();
+    let b_value = i64::from_value(b.take_result().unwrap().unwrap()).unwrap();
+    assert_eq!(a_value, 100);
+    assert_eq!(b_value, 1000);
+    assert!(c.take_result().unwrap().is_ok());
+    assert!(d.take_result().unwrap().is_ok());
+
+    // NB: each task is parked while it's waiting, letting the other run.
+    let log = Vec::<i64>::from_value(Value::from(log)).unwrap();
+    assert_eq!(log, vec![1, 2, 1, 2]);
+    assert!(c.stats().polls > 1);
+
+    let stats = b.stats();
+    assert!(stats.slices > 1);
+    assert!(stats.instructions > 1000);
+    assert!(stats.instructions <= stats.slices * 10);
+}
+
+#[test]
+fn test_scheduler_priority() {
+    let mut scheduler = Scheduler::with_quantum(100);
+
+    let low =
+        scheduler.spawn_with_priority(vm().execute(&["count"], (1000i64,)).unwrap(), Priority::Low);
+    let high = scheduler.spawn_with_priority(
+        vm().execute(&["count"], (1000i64,)).unwrap(),
+        Priority::High,
+    );
+
+    block_on(async {
+        assert!(scheduler.next().await);
+    });
+
+    assert!(high.is_finished());
+    assert!(!low.is_finished());
+    assert!(high.stats().instructions > low.stats().instructions);
+}
+
+#[test]
+fn test_scheduler_cancel() {
+    let mut scheduler = Scheduler::with_quantum(10);
+
+    let forever = scheduler.spawn(vm().execute(&["forever"], ()).unwrap());
+    let count = scheduler.spawn(vm().execute(&["count"], (100i64,)).unwrap());
+
+    block_on(async {
+        assert!(scheduler.next().await);
+        assert!(count.is_finished());
+        assert!(!forever.is_finished());
+
+        forever.cancel();
+        scheduler.run().await;
+    });
+
+    assert!(forever.is_cancelled());
+    let error = forever.take_result().unwrap().unwrap_err();
+    assert!(matches!(error.into_kind(), Cancelled));
+}
+
+#[test]
+fn test_scheduler_awaited() {
+    let mut scheduler = Scheduler::with_quantum(10);
+
+    // NB: the busy loops run in awaited executions, which share the time slice
+    // of the task awaiting them.
+    let forever = scheduler.spawn(vm().execute(&["await_forever"], ()).unwrap());
+    let spin = scheduler.spawn(vm().execute(&["await_spin"], (1000i64,)).unwrap());
+    let count = scheduler.spawn(vm().execute(&["count"], (100i64,)).unwrap());
+
+    block_on(async {
+        assert!(scheduler.next().await);
+        assert!(count.is_finished());
+        assert!(!spin.is_finished());
+        assert!(!forever.is_finished());
+
+        assert!(scheduler.next().await);
+        assert!(spin.is_finished());
+        assert!(!forever.is_finished());
+
+        forever.cancel();
+        scheduler.run().await;
+    });
+
+    let value = i64::from_value(spin.take_result().unwrap().unwrap()).unwrap();
+    assert_eq!(value, 1000);
+
+    let stats = spin.stats();
+    assert!(stats.slices > 1);
+    assert!(stats.instructions > 1000);
+    assert!(stats.instructions <= stats.slices * 10);
+
+    assert!(forever.stats().slices > 1);
+    assert!(matches!(
+        forever.take_result().unwrap().unwrap_err().into_kind(),
+        Cancelled
+    ));
+}
@@ -0,0 +1,231 @@
+use crate::testing::*;
+
+#[test]
+fn test_struct_update() {
+    assert_eq! {
+        rune!((i64, i64, i64) => r#"
+        struct Foo { a, b, c }
+
+        fn main() {
+            let base = Foo { a: 1, b: 2, c: 3 };
+            let foo = Foo { a: 10, ..base };
+            (foo.a, foo.b, foo.c)
+        }
+        "#),
+        (10, 2, 3)
+    };
+
+    // NB: field init shorthand can be combined with the update.
+    assert_eq! {
+        rune!((i64, i64, i64) => r#"
+        enum Shape { Rect { w, h, depth } }
+
+        fn main() {
+            let base = Shape::Rect { w: 1, h: 2, depth: 3 };
+            let h = 20;
+            let shape = Shape::Rect { h, ..base };
+
+            match shape {
+                Shape::Rect { w, h, depth } => (w, h, depth),
+            }
+        }
+        "#),
+        (1, 20, 3)
+    };
+
+    // Update expressions can be used where other values are on the stack.
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        struct Foo { a, b }
+
+        fn main() {
+            let base = Foo { a: 1, b: 2 };
+            let x = [5, 6];
+            [x[0], Foo { a: x[1], ..base }.a, Foo { ..base }.b]
+        }
+        "#),
+        vec![5, 6, 2]
+    };
+
+    assert_eq! {
+        rune!((i64, i64, i64) => r#"
+        struct Foo { a, b }
+
+        impl Foo {
+            fn add(self, a, b) { self.a + a + b }
+        }
+
+        fn main() {
+            let base = Foo { a: 1, b: 2 };
+            let v = [];
+            v.push(Foo { b: 7, ..base });
+            let first = v[0];
+            (first.a, first.b, base.add(Foo { a: 10, ..base }.a, Foo { ..base }.b))
+        }
+        "#),
+        (1, 7, 13)
+    };
+
+    // NB: the base is evaluated after the assigned fields.
+    assert_eq! {
+        rune!(Vec<String> => r#"
+        struct Foo { a, b }
+
+        fn main() {
+            let log = [];
+            let foo = Foo { a: { log.push("a"); 1 }, ..{ log.push("base"); Foo { a: 0, b: 2 } } };
+            log
+        }
+        "#),
+        vec![String::from("a"), String::from("base")]
+    };
+
+    assert_compile_error! {
+        r#"fn main() { let o = #{a: 1}; #{b: 2, ..o} }"#,
+        span, UnsupportedObjectUpdate => {
+            assert_eq!(span, Span::new(37, 39));
+        }
+    };
+
+    assert_compile_error! {
+        r#"struct Foo { a } fn main() { let o = Foo { a: 1 }; Foo { b: 2, ..o } }"#,
+        span, LitObjectNotField { field, .. } => {
+            assert_eq!(span, Span::new(57, 58));
+            assert_eq!(field, "b");
+        }
+    };
+}
+
+#[test]
+fn test_struct_eq() {
+    assert_eq! {
+        rune!(Vec<bool> => r#"
+        struct Point(x, y);
+        struct Named { a, b }
+        struct Marker;
+        enum Shape { Rect { w, h }, Circle(r) }
+
+        fn main() {
+            [
+                Point(1, 2) == Point(1, 2),
+                Point(1, 2) != Point(1, 3),
+                Named { a: 1, b: [2] } == Named { b: [2], a: 1 },
+                Named { a: 1, b: Point(1, 2) } == Named { a: 1, b: Point(2, 1) },
+                Marker == Marker,
+                Shape::Circle(1) == Shape::Circle(1),
+                Shape::Rect { w: 1, h: 2 } == Shape::Rect { w: 1, h: 3 },
+                (1, Some(Point(1, 2))) == (1, Some(Point(1, 2))),
+            ]
+        }
+        "#),
+        vec![true, true, true, false, true, true, false, true]
+    };
+
+    // Structs with the same fields but different types are never equal.
+    assert_eq! {
+        rune!(bool => r#"
+        struct A(a);
+        struct B(a);
+
+        fn main() { A(1) == B(1) }
+        "#),
+        false
+    };
+}
+
+#[test]
+fn test_struct_clone() {
+    assert_eq! {
+        rune!((i64, i64, i64) => r#"
+        struct Point(x, y);
+        struct Named { a, b }
+
+        fn main() {
+            let p = Point(1, 2);
+            let q = p.clone();
+            q.0 = 10;
+
+            let n = Named { a: 3, b: 4 };
+            let m = n.clone();
+            m.a = 30;
+
+            (p.0 + q.0, n.a, m.a)
+        }
+        "#),
+        (11, 3, 30)
+    };
+
+    // NB: a struct can provide its own `clone`.
+    assert_eq! {
+        rune!(i64 => r#"
+        struct Counter(n);
+
+        impl Counter {
+            fn clone(self) { Counter(self.0 + 1) }
+        }
+
+        fn main() { Counter(1).clone().0 }
+        "#),
+        2
+    };
+}
+
+#[test]
+fn test_struct_display() {
+    assert_eq! {
+        rune!(String => r#"
+        struct Point(x, y);
+        struct Named { b, a }
+        struct Marker;
+        enum Shape { Circle(r), Rect { w, h } }
+
+        fn main() {
+            `{Point(1, 2)} {Named { a: "a", b: Marker }} {Shape::Circle(1.5)} {Shape::Rect { h: 2, w: 1 }}`
+        }
+        "#),
+        "Point(1, 2) Named { b: Marker, a: \"a\" } Circle(1.5) Rect { w: 1, h: 2 }"
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        struct Point(x, y);
+        fn main() { format!("{:>12}", Point(1, 2)) }
+        "#),
+        " Point(1, 2)"
+    };
+}
+
+#[test]
+fn test_struct_const() {
+    assert_eq! {
+        rune!((i64, i64, bool) => r#"
+        struct Point(x, y);
+        struct Marker;
+
+        impl Point {
+            const ORIGIN = Point(0, 0);
+            const UNIT = Point(1, Point::ONE);
+            const ONE = 1;
+        }
+
+        const NAMED = Named { a: Point::UNIT, b: Marker };
+        struct Named { a, b }
+
+        fn main() {
+            let origin = Point::ORIGIN;
+            origin.0 = 42;
+            (origin.0 + Point::ORIGIN.0, NAMED.a.1, NAMED.b == Marker)
+        }
+        "#),
+        (42, 1, true)
+    };
+
+    assert_compile_error! {
+        r#"struct Point(x, y); const P = Point(1); fn main() { P }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(30, 38));
+            assert_eq!(expected, 2);
+            assert_eq!(actual, 1);
+        }
+    };
+}
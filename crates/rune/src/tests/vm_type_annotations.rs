@@ -0,0 +1,126 @@
+use crate::testing::*;
+use runestick::VmErrorKind;
+
+/// Call the given function with the given arguments, returning the error it
+/// raised.
+fn call_err<A>(function: &str, args: A, source: &str) -> VmErrorKind
+where
+    A: runestick::Args,
+{
+    let e = run::<_, _, ()>(&[function], args, source).unwrap_err();
+
+    match e {
+        RunError::VmError(e) => e.into_unwound().0.into_kind(),
+        actual => panic!("expected vm error but was `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_annotated_arguments() {
+    const SOURCE: &str = r#"
+    struct Point { x, y }
+
+    fn add(a: int, b: int) -> int { a + b }
+    fn norm(p: Point, _: ()) { p.x + p.y }
+    "#;
+
+    assert_eq!(run::<_, _, i64>(&["add"], (1i64, 2i64), SOURCE).unwrap(), 3);
+
+    match call_err("add", (1i64, String::from("two")), SOURCE) {
+        BadArgumentType {
+            arg,
+            name,
+            expected,
+            actual,
+        } => {
+            assert_eq!(arg, 1);
+            assert_eq!(name.as_deref(), Some("b"));
+            assert_eq!(expected.to_string(), "integer");
+            assert_eq!(actual.to_string(), "String");
+        }
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+
+    match call_err("norm", (1i64, ()), SOURCE) {
+        BadArgumentType { arg, name, .. } => {
+            assert_eq!(arg, 0);
+            assert_eq!(name.as_deref(), Some("p"));
+        }
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+
+    match call_err("norm", (runestick::Object::new(), 1i64), SOURCE) {
+        BadArgumentType { arg, .. } => assert_eq!(arg, 0),
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_annotated_arguments_deferred() {
+    // NB: calling a generator doesn't run its body, so its arguments are only
+    // checked once it's resumed.
+    assert_eq! {
+        rune!(i64 => r#"
+        fn numbers(n: int) { yield n }
+        fn main() { let g = numbers("one"); 1 }
+        "#),
+        1
+    };
+
+    assert_vm_error! {
+        r#"
+        fn numbers(n: int) { yield n }
+        fn main() { let g = numbers("one"); g.next() }
+        "#,
+        BadArgumentType { arg, name, .. } => {
+            assert_eq!(arg, 0);
+            assert_eq!(name.as_deref(), Some("n"));
+        }
+    };
+}
+
+#[test]
+fn test_annotated_return() {
+    assert_eq! {
+        rune!(Option<i64> => r#"
+        fn positive(n: int) -> Option {
+            if n > 0 {
+                return Some(n);
+            }
+
+            None
+        }
+
+        fn main() { positive(1) }
+        "#),
+        Some(1)
+    };
+
+    assert_vm_error!(r#"fn main() -> int { "nope" }"#, BadReturnType { expected, actual } => {
+        assert_eq!(expected.to_string(), "integer");
+        assert_eq!(actual.to_string(), "String");
+    });
+
+    assert_vm_error!(r#"fn main() -> int { }"#, BadReturnType { actual, .. } => {
+        assert_eq!(actual.to_string(), "unit");
+    });
+
+    assert_vm_error!(r#"fn main() -> int { return; }"#, BadReturnType { .. } => {});
+
+    assert_vm_error!(
+        r#"fn main() -> String { let n = Err("bad")?; `{n}` }"#,
+        BadReturnType { actual, .. } => {
+            assert_eq!(actual.to_string(), "Result");
+        }
+    );
+}
+
+#[test]
+fn test_missing_annotated_type() {
+    assert_compile_error! {
+        r#"fn main() -> Missing { }"#,
+        span, MissingType { .. } => {
+            assert_eq!(span, Span::new(13, 20));
+        }
+    };
+}
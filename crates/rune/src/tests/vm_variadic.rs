@@ -0,0 +1,204 @@
+use crate::testing::*;
+use runestick::{Context, Module, Stack, Vm, VmError, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_rest_args() {
+    assert_eq! {
+        rune!((i64, i64, Vec<i64>) => r#"
+        fn sum(first, ..rest) {
+            let total = first;
+
+            for n in rest {
+                total = total + n;
+            }
+
+            total
+        }
+
+        fn rest(..rest) { rest }
+
+        fn main() {
+            (sum(1), sum(1, 2, 3), rest(4, 5))
+        }
+        "#),
+        (1, 6, vec![4, 5])
+    };
+
+    assert_eq! {
+        rune!((i64, i64) => r#"
+        struct Counter(start);
+
+        impl Counter {
+            fn count(self, ..rest) { self.0 + rest.len() }
+        }
+
+        fn main() {
+            let count = |a, ..rest| a + rest.len();
+            (count(1, 2, 3), Counter(10).count(1, 2))
+        }
+        "#),
+        (3, 12)
+    };
+}
+
+#[test]
+fn test_spread_args() {
+    assert_eq! {
+        rune!((i64, i64, i64, i64) => r#"
+        fn sum(first, ..rest) {
+            let total = first;
+
+            for n in rest {
+                total = total + n;
+            }
+
+            total
+        }
+
+        fn add(a, b) { a + b }
+
+        fn main() {
+            let v = [2, 3];
+            let t = (4, 5);
+            (sum(..v), sum(1, ..v, 10, ..t), add(..t), add(1, ..[2]))
+        }
+        "#),
+        (5, 25, 9, 3)
+    };
+
+    assert_eq! {
+        rune!((i64, (i64, i64), i64) => r#"
+        struct Point(x, y);
+
+        impl Point {
+            fn sum(self, ..rest) { self.0 + self.1 + rest.len() }
+        }
+
+        fn main() {
+            let add = |a, b| a + b;
+            let point = Point(..[1, 2]);
+            (add(..[1, 2]), (point.0, point.1), point.sum(..[0, 0]))
+        }
+        "#),
+        (3, (1, 2), 5)
+    };
+}
+
+#[test]
+fn test_variadic_errors() {
+    assert_compile_error! {
+        r#"fn f(..rest, last) {} fn main() {}"#,
+        span, UnsupportedRestArgument => {
+            assert_eq!(span, Span::new(5, 11));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { let v = ..[1]; }"#,
+        span, UnsupportedSpread => {
+            assert_eq!(span, Span::new(20, 25));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn f(a, b) {} fn main() { f(..[1], b = 2) }"#,
+        span, UnsupportedSpreadNamedArgument => {
+            assert_eq!(span, Span::new(35, 40));
+        }
+    };
+
+    assert_vm_error!(
+        r#"fn sum(first, ..rest) { first } fn main() { sum() }"#,
+        BadVariadicArgumentCount { actual, expected } => {
+            assert_eq!((actual, expected), (0, 1));
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn add(a, b) { a + b } fn main() { add(..[1, 2, 3]) }"#,
+        BadArgumentCount { actual, expected } => {
+            assert_eq!((actual, expected), (3, 2));
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn add(a, b) { a + b } fn main() { add(..1) }"#,
+        UnsupportedSpreadOperand { .. } => {}
+    );
+}
+
+#[test]
+fn test_variadic_from_rust() {
+    let context = Arc::new(Context::with_default_modules().unwrap());
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn count(first, ..rest) { rest.len() }
+        fn main() { count }
+        "#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(context, Arc::new(unit));
+
+    let output = vm
+        .clone()
+        .execute(&["count"], (1, 2, 3))
+        .unwrap()
+        .complete();
+    let output = i64::from_value(output.unwrap()).unwrap();
+    assert_eq!(output, 2);
+
+    let count = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let count = runestick::Function::from_value(count).unwrap();
+    assert_eq!(count.call::<_, i64>((1,)).unwrap(), 0);
+    assert_eq!(count.call::<_, i64>((1, 2)).unwrap(), 1);
+
+    let error = count.call::<_, i64>(()).unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        VmErrorKind::BadVariadicArgumentCount {
+            actual: 0,
+            expected: 1
+        }
+    ));
+}
+
+#[test]
+fn test_native_variadic() {
+    fn sum(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+        let mut total = 0;
+
+        for value in stack.pop_sequence(args)? {
+            total += value.into_integer()?;
+        }
+
+        stack.push(total);
+        Ok(())
+    }
+
+    let mut module = Module::new(&["math"]);
+    module.raw_fn(&["sum"], sum).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let v = [3, 4];
+            [math::sum(), math::sum(1), math::sum(1, 2), math::sum(..v), math::sum(1, ..v)]
+        }
+        "#,
+    )
+    .unwrap();
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    let output = Vec::<i64>::from_value(output).unwrap();
+    assert_eq!(output, vec![0, 1, 3, 7, 8]);
+}
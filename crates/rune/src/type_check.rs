@@ -0,0 +1,1155 @@
+//! Optional static checking of type annotations.
+//!
+//! The checker walks the body of a function and infers the types of
+//! expressions where they can be known locally, like literals, annotated
+//! variables, and calls to annotated functions. Everything else is treated
+//! as having an unknown type, which is compatible with everything.
+
+use crate::ast;
+use crate::call_args::{self, CallArg, Param};
+use crate::collections::{HashMap, HashSet};
+use crate::query::Query;
+use crate::CompileResult;
+use crate::{CompileError, CompileErrorKind, Resolve as _, Spanned as _, Storage};
+use runestick::{
+    Call, CompileMeta, CompileMetaKind, Context, ContextSignature, Hash, Item, Source, Span,
+    StaticType, Type, TypeInfo,
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// The statically known type of a value.
+#[derive(Debug, Clone)]
+enum Ty {
+    /// The type of the value is not known.
+    Any,
+    /// One of the built-in types.
+    Static(&'static StaticType),
+    /// A declared or a native type.
+    Type(Type, Item),
+}
+
+impl Ty {
+    /// Construct the type for the given value type.
+    fn new(type_of: Type, item: &Item) -> Self {
+        match static_type(type_of) {
+            Some(ty) => Self::Static(ty),
+            None => Self::Type(type_of, item.clone()),
+        }
+    }
+
+    /// The value type, if known.
+    fn type_of(&self) -> Option<Type> {
+        match self {
+            Self::Any => None,
+            Self::Static(ty) => Some(Type::from(*ty)),
+            Self::Type(type_of, _) => Some(*type_of),
+        }
+    }
+
+    /// Combine the types of two branches.
+    fn unify(self, other: Self) -> Self {
+        match (self.type_of(), other.type_of()) {
+            (Some(a), Some(b)) if a == b => self,
+            _ => Self::Any,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(fmt, "any"),
+            Self::Static(ty) => write!(fmt, "{}", ty.name),
+            Self::Type(_, item) => write!(fmt, "{}", item),
+        }
+    }
+}
+
+/// Find the built-in type corresponding to the given value type.
+fn static_type(type_of: Type) -> Option<&'static StaticType> {
+    let types = [
+        runestick::UNIT_TYPE,
+        runestick::BYTE_TYPE,
+        runestick::BOOL_TYPE,
+        runestick::CHAR_TYPE,
+        runestick::INTEGER_TYPE,
+        runestick::FLOAT_TYPE,
+        runestick::STRING_TYPE,
+        runestick::BYTES_TYPE,
+        runestick::VEC_TYPE,
+        runestick::TUPLE_TYPE,
+        runestick::OBJECT_TYPE,
+        runestick::FUTURE_TYPE,
+        runestick::GENERATOR_TYPE,
+        runestick::GENERATOR_STATE_TYPE,
+        runestick::STREAM_TYPE,
+        runestick::RESULT_TYPE,
+        runestick::OPTION_TYPE,
+        runestick::FUNCTION_TYPE,
+    ];
+
+    types.iter().copied().find(|ty| Type::from(*ty) == type_of)
+}
+
+/// A variable in scope.
+struct Var {
+    /// The type of the variable.
+    ty: Ty,
+    /// If the type of the variable was annotated.
+    annotated: bool,
+}
+
+/// Checks that the type annotations of a function agree with how it's used.
+pub(crate) struct TypeChecker<'a> {
+    context: &'a Context,
+    query: &'a mut Query,
+    storage: &'a Storage,
+    source: Arc<Source>,
+    /// The item of the function being checked.
+    item: Item,
+    /// Variables in scope.
+    scopes: Vec<HashMap<String, Var>>,
+    /// The expected return type of the function or closure being checked.
+    returns: Vec<Ty>,
+    /// Names of variables which are assigned to after being declared. Their
+    /// types can't be trusted unless they are annotated.
+    reassigned: HashSet<String>,
+    /// If errors should be reported.
+    report: bool,
+    /// Type errors found.
+    errors: Vec<CompileError>,
+}
+
+impl<'a> TypeChecker<'a> {
+    /// Construct a new type checker for the function at the given item.
+    pub(crate) fn new(
+        context: &'a Context,
+        query: &'a mut Query,
+        storage: &'a Storage,
+        source: Arc<Source>,
+        item: Item,
+    ) -> Self {
+        Self {
+            context,
+            query,
+            storage,
+            source,
+            item,
+            scopes: Vec::new(),
+            returns: Vec::new(),
+            reassigned: HashSet::new(),
+            report: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Check the given function, returning all type errors found.
+    ///
+    /// `impl_meta` is the type of `self` for instance functions.
+    pub(crate) fn check_fn(
+        mut self,
+        item_fn: &ast::ItemFn,
+        impl_meta: Option<&CompileMeta>,
+    ) -> CompileResult<Vec<CompileError>> {
+        let self_ty = match impl_meta {
+            Some(meta) => match meta.type_of() {
+                Some(type_of) => Ty::new(type_of, meta.item()),
+                None => Ty::Any,
+            },
+            None => Ty::Any,
+        };
+
+        // NB: the first pass only collects variables which are reassigned.
+        self.walk_fn(item_fn, &self_ty)?;
+        self.report = true;
+        self.walk_fn(item_fn, &self_ty)?;
+        Ok(self.errors)
+    }
+
+    fn walk_fn(&mut self, item_fn: &ast::ItemFn, self_ty: &Ty) -> CompileResult<()> {
+        self.scopes = vec![HashMap::new()];
+        self.returns.clear();
+
+        self.args(
+            item_fn.args.items.iter().map(|(arg, _)| arg),
+            self_ty,
+            Self::signature_annotation,
+        )?;
+
+        let output = match &item_fn.output {
+            Some((_, ty)) => self.signature_annotation(ty),
+            None => Ty::Any,
+        };
+
+        self.returns.push(output.clone());
+        let actual = self.block(&item_fn.body)?;
+
+        if let Some(ast::Stmt::Expr(tail)) = item_fn.body.statements.last() {
+            self.check(tail.span(), &output, &actual);
+        }
+
+        Ok(())
+    }
+
+    fn args<'b, I>(
+        &mut self,
+        args: I,
+        self_ty: &Ty,
+        annotation: fn(&mut Self, &ast::Type) -> Ty,
+    ) -> CompileResult<()>
+    where
+        I: IntoIterator<Item = &'b ast::FnArg>,
+    {
+        for arg in args {
+            match arg {
+                ast::FnArg::Self_(..) => {
+                    self.declare("self", self_ty.clone(), false);
+                }
+                ast::FnArg::Ident(ident, ty, _) => {
+                    let name = self.name(ident)?;
+
+                    match ty {
+                        Some((_, ty)) => {
+                            let ty = annotation(self, ty);
+                            self.declare(&name, ty, true);
+                        }
+                        None => self.declare(&name, Ty::Any, false),
+                    }
+                }
+                ast::FnArg::Ignore(_, ty) => {
+                    if let Some((_, ty)) = ty {
+                        annotation(self, ty);
+                    }
+                }
+                ast::FnArg::Rest(_, ident) => {
+                    let name = self.name(ident)?;
+                    self.declare(&name, Ty::Static(runestick::VEC_TYPE), true);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) -> CompileResult<Ty> {
+        Ok(match expr {
+            ast::Expr::Self_(..) => match self.var("self") {
+                Some(var) => var.ty.clone(),
+                None => Ty::Any,
+            },
+            ast::Expr::Path(path) => self.path(path)?,
+            ast::Expr::ExprLit(expr_lit) => self.lit(&expr_lit.lit)?,
+            ast::Expr::ExprGroup(expr_group) => self.expr(&expr_group.expr)?,
+            ast::Expr::ExprBlock(expr_block) => self.block(&expr_block.block)?,
+            ast::Expr::ExprLet(expr_let) => {
+                self.expr_let(expr_let)?;
+                Ty::Any
+            }
+            ast::Expr::ExprBinary(expr_binary) => self.expr_binary(expr_binary)?,
+            ast::Expr::ExprUnary(expr_unary) => {
+                let ty = self.expr(&expr_unary.expr)?;
+
+                match (expr_unary.op, ty) {
+                    (ast::UnaryOp::Not, Ty::Static(ty))
+                        if ty == runestick::BOOL_TYPE || ty == runestick::INTEGER_TYPE =>
+                    {
+                        Ty::Static(ty)
+                    }
+                    _ => Ty::Any,
+                }
+            }
+            ast::Expr::ExprIf(expr_if) => self.expr_if(expr_if)?,
+            ast::Expr::ExprMatch(expr_match) => self.expr_match(expr_match)?,
+            ast::Expr::ExprCall(expr_call) => self.expr_call(expr_call)?,
+            ast::Expr::ExprClosure(expr_closure) => {
+                self.returns.push(Ty::Any);
+                self.scopes.push(HashMap::new());
+                self.args(
+                    expr_closure.args.as_slice().iter().map(|(a, _)| a),
+                    &Ty::Any,
+                    Self::annotation,
+                )?;
+                self.expr(&expr_closure.body)?;
+                self.scopes.pop();
+                self.returns.pop();
+                Ty::Static(runestick::FUNCTION_TYPE)
+            }
+            ast::Expr::ExprAsync(expr_async) => {
+                self.returns.push(Ty::Any);
+                self.block(&expr_async.block)?;
+                self.returns.pop();
+                Ty::Static(runestick::FUTURE_TYPE)
+            }
+            ast::Expr::ExprReturn(expr_return) => {
+                let (span, actual) = match &expr_return.expr {
+                    Some(expr) => (expr.span(), self.expr(expr)?),
+                    None => (expr_return.span(), Ty::Static(runestick::UNIT_TYPE)),
+                };
+
+                if let Some(expected) = self.returns.last().cloned() {
+                    self.check(span, &expected, &actual);
+                }
+
+                Ty::Any
+            }
+            ast::Expr::ExprWhile(expr_while) => {
+                self.scopes.push(HashMap::new());
+                self.condition(&expr_while.condition)?;
+                self.block(&expr_while.body.block)?;
+                self.scopes.pop();
+                Ty::Any
+            }
+            ast::Expr::ExprLoop(expr_loop) => {
+                self.block(&expr_loop.body.block)?;
+                Ty::Any
+            }
+            ast::Expr::ExprFor(expr_for) => {
+                self.expr(&expr_for.iter)?;
+                self.scopes.push(HashMap::new());
+                let name = self.name(&expr_for.var)?;
+                self.declare(&name, Ty::Any, false);
+                self.block(&expr_for.body.block)?;
+                self.scopes.pop();
+                Ty::Any
+            }
+            ast::Expr::ExprBreak(expr_break) => {
+                if let Some(ast::ExprBreakValue::Expr(expr)) = &expr_break.expr {
+                    self.expr(expr)?;
+                }
+
+                Ty::Any
+            }
+            ast::Expr::ExprYield(expr_yield) => {
+                if let Some(expr) = &expr_yield.expr {
+                    self.expr(expr)?;
+                }
+
+                Ty::Any
+            }
+            ast::Expr::ExprIndexSet(expr_index_set) => {
+                self.expr(&expr_index_set.target)?;
+                self.expr(&expr_index_set.index)?;
+                self.expr(&expr_index_set.value)?;
+                Ty::Any
+            }
+            ast::Expr::ExprIndexGet(expr_index_get) => {
+                self.expr(&expr_index_get.target)?;
+                self.expr(&expr_index_get.index)?;
+                Ty::Any
+            }
+            ast::Expr::ExprFieldAccess(expr_field_access) => {
+                self.expr(&expr_field_access.expr)?;
+                Ty::Any
+            }
+            ast::Expr::ExprAwait(expr_await) => {
+                self.expr(&expr_await.expr)?;
+                Ty::Any
+            }
+            ast::Expr::ExprTry(expr_try) => {
+                self.expr(&expr_try.expr)?;
+                Ty::Any
+            }
+            // NB: items are checked separately, and the expansion of macros
+            // and the branches of select are not checked.
+            ast::Expr::ExprSpread(expr_spread) => {
+                self.expr(&expr_spread.expr)?;
+                Ty::Any
+            }
+            ast::Expr::Item(..) | ast::Expr::MacroCall(..) | ast::Expr::ExprSelect(..) => Ty::Any,
+        })
+    }
+
+    fn block(&mut self, block: &ast::Block) -> CompileResult<Ty> {
+        self.scopes.push(HashMap::new());
+
+        let mut ty = Ty::Static(runestick::UNIT_TYPE);
+
+        for stmt in &block.statements {
+            ty = match stmt {
+                ast::Stmt::Item(..) => continue,
+                ast::Stmt::Expr(expr) => self.expr(expr)?,
+                ast::Stmt::Semi(expr, _) => {
+                    self.expr(expr)?;
+                    Ty::Static(runestick::UNIT_TYPE)
+                }
+            };
+        }
+
+        self.scopes.pop();
+        Ok(ty)
+    }
+
+    fn lit(&mut self, lit: &ast::Lit) -> CompileResult<Ty> {
+        let ty = match lit {
+            ast::Lit::Unit(..) => runestick::UNIT_TYPE,
+            ast::Lit::Bool(..) => runestick::BOOL_TYPE,
+            ast::Lit::Byte(..) => runestick::BYTE_TYPE,
+            ast::Lit::Char(..) => runestick::CHAR_TYPE,
+            ast::Lit::Number(lit_number) => {
+                match lit_number.resolve(self.storage, &self.source)? {
+                    ast::Number::Integer(..) => runestick::INTEGER_TYPE,
+                    ast::Number::Float(..) => runestick::FLOAT_TYPE,
+                }
+            }
+            ast::Lit::Str(..) | ast::Lit::Template(..) => runestick::STRING_TYPE,
+            ast::Lit::ByteStr(..) => runestick::BYTES_TYPE,
+            ast::Lit::Vec(lit_vec) => {
+                for expr in &lit_vec.items {
+                    self.expr(expr)?;
+                }
+
+                runestick::VEC_TYPE
+            }
+            ast::Lit::Tuple(lit_tuple) => {
+                for (expr, _) in &lit_tuple.items {
+                    self.expr(expr)?;
+                }
+
+                runestick::TUPLE_TYPE
+            }
+            ast::Lit::Object(lit_object) => {
+                for assign in &lit_object.assignments {
+                    if let Some((_, expr)) = &assign.assign {
+                        self.expr(expr)?;
+                    }
+                }
+
+                if let Some((_, expr)) = &lit_object.rest {
+                    self.expr(expr)?;
+                }
+
+                let path = match &lit_object.ident {
+                    ast::LitObjectIdent::Anonymous(..) => {
+                        return Ok(Ty::Static(runestick::OBJECT_TYPE))
+                    }
+                    ast::LitObjectIdent::Named(path) => path,
+                };
+
+                let item = self.convert_path(path)?;
+
+                return Ok(match self.lookup_meta(&item)? {
+                    Some(meta) => match &meta.kind {
+                        CompileMetaKind::Struct { type_of, object } => {
+                            Ty::new(*type_of, &object.item)
+                        }
+                        CompileMetaKind::StructVariant { enum_item, .. } => {
+                            self.enum_ty(enum_item)?
+                        }
+                        _ => Ty::Any,
+                    },
+                    None => Ty::Any,
+                });
+            }
+        };
+
+        Ok(Ty::Static(ty))
+    }
+
+    fn path(&mut self, path: &ast::Path) -> CompileResult<Ty> {
+        if let Some(ident) = path.try_as_ident() {
+            let name = self.name(ident)?;
+
+            if let Some(var) = self.var(&name) {
+                return Ok(var.ty.clone());
+            }
+        }
+
+        let item = self.convert_path(path)?;
+
+        let meta = match self.lookup_meta(&item)? {
+            Some(meta) => meta,
+            None => return Ok(Ty::Any),
+        };
+
+        Ok(match &meta.kind {
+            CompileMetaKind::Tuple { type_of, tuple } if tuple.args == 0 => {
+                Ty::new(*type_of, &tuple.item)
+            }
+            CompileMetaKind::TupleVariant {
+                enum_item, tuple, ..
+            } if tuple.args == 0 => self.enum_ty(enum_item)?,
+            CompileMetaKind::Function { .. } => Ty::Static(runestick::FUNCTION_TYPE),
+            CompileMetaKind::Const { const_value, .. } => match const_value.type_info() {
+                TypeInfo::StaticType(ty) => Ty::Static(ty),
+                _ => Ty::Any,
+            },
+            _ => Ty::Any,
+        })
+    }
+
+    fn expr_let(&mut self, expr_let: &ast::ExprLet) -> CompileResult<()> {
+        let actual = self.expr(&expr_let.expr)?;
+
+        if let Some((_, block)) = expr_let.else_.as_deref() {
+            self.block(block)?;
+        }
+
+        match expr_let.ty.as_deref() {
+            Some((_, ty)) => {
+                let expected = self.annotation(ty);
+                self.check(expr_let.expr.span(), &expected, &actual);
+                self.bind(&expr_let.pat, expected, true)?;
+            }
+            None => {
+                self.bind(&expr_let.pat, actual, false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expr_binary(&mut self, expr_binary: &ast::ExprBinary) -> CompileResult<Ty> {
+        use ast::BinOp::*;
+
+        let lhs = &*expr_binary.lhs;
+        let rhs = &*expr_binary.rhs;
+
+        match expr_binary.op {
+            Assign | AddAssign | SubAssign | MulAssign | DivAssign | RemAssign | BitAndAssign
+            | BitXorAssign | BitOrAssign | ShlAssign | ShrAssign => {
+                let actual = self.expr(rhs)?;
+
+                let name = match lhs {
+                    ast::Expr::Path(path) => match path.try_as_ident() {
+                        Some(ident) => self.name(ident)?,
+                        None => return Ok(Ty::Any),
+                    },
+                    lhs => {
+                        self.expr(lhs)?;
+                        return Ok(Ty::Any);
+                    }
+                };
+
+                self.reassigned.insert(name.clone());
+
+                if let Assign = expr_binary.op {
+                    let expected = match self.var(&name) {
+                        Some(var) if var.annotated => var.ty.clone(),
+                        _ => return Ok(Ty::Any),
+                    };
+
+                    self.check(rhs.span(), &expected, &actual);
+                }
+
+                Ok(Ty::Any)
+            }
+            op => {
+                let lhs = self.expr(lhs)?;
+                let rhs = self.expr(rhs)?;
+
+                let (a, b) = match (op, lhs, rhs) {
+                    (Eq, ..)
+                    | (Neq, ..)
+                    | (Gt, ..)
+                    | (Lt, ..)
+                    | (Gte, ..)
+                    | (Lte, ..)
+                    | (Is, ..)
+                    | (IsNot, ..)
+                    | (And, ..)
+                    | (Or, ..) => {
+                        return Ok(Ty::Static(runestick::BOOL_TYPE));
+                    }
+                    (_, Ty::Static(a), Ty::Static(b)) if a == b => (op, a),
+                    _ => return Ok(Ty::Any),
+                };
+
+                let numeric = b == runestick::INTEGER_TYPE || b == runestick::FLOAT_TYPE;
+
+                Ok(match a {
+                    Add | Sub | Mul | Div | Rem if numeric => Ty::Static(b),
+                    Add if b == runestick::STRING_TYPE => Ty::Static(b),
+                    Shl | Shr | BitAnd | BitXor | BitOr if b == runestick::INTEGER_TYPE => {
+                        Ty::Static(b)
+                    }
+                    BitAnd | BitXor | BitOr if b == runestick::BOOL_TYPE => Ty::Static(b),
+                    _ => Ty::Any,
+                })
+            }
+        }
+    }
+
+    fn expr_if(&mut self, expr_if: &ast::ExprIf) -> CompileResult<Ty> {
+        self.scopes.push(HashMap::new());
+        self.condition(&expr_if.condition)?;
+        let mut ty = self.block(&expr_if.block.block)?;
+        self.scopes.pop();
+
+        for expr_else_if in &expr_if.expr_else_ifs {
+            self.scopes.push(HashMap::new());
+            self.condition(&expr_else_if.condition)?;
+            ty = ty.unify(self.block(&expr_else_if.block.block)?);
+            self.scopes.pop();
+        }
+
+        Ok(match &expr_if.expr_else {
+            Some(expr_else) => ty.unify(self.block(&expr_else.block.block)?),
+            None => ty.unify(Ty::Static(runestick::UNIT_TYPE)),
+        })
+    }
+
+    fn expr_match(&mut self, expr_match: &ast::ExprMatch) -> CompileResult<Ty> {
+        self.expr(&expr_match.expr)?;
+
+        let mut ty = None;
+
+        for (branch, _) in &expr_match.branches {
+            self.scopes.push(HashMap::new());
+            self.bind(&branch.pat, Ty::Any, false)?;
+
+            if let Some((_, condition)) = &branch.condition {
+                self.expr(condition)?;
+            }
+
+            let branch = self.expr(&branch.body)?;
+            self.scopes.pop();
+
+            ty = Some(match ty {
+                Some(ty) => Ty::unify(ty, branch),
+                None => branch,
+            });
+        }
+
+        Ok(ty.unwrap_or(Ty::Any))
+    }
+
+    fn condition(&mut self, condition: &ast::Condition) -> CompileResult<()> {
+        match condition {
+            ast::Condition::Expr(expr) => {
+                self.expr(expr)?;
+            }
+            ast::Condition::ExprLet(expr_let) => {
+                self.expr(&expr_let.expr)?;
+                self.bind(&expr_let.pat, Ty::Any, false)?;
+            }
+            ast::Condition::Chain(chain) => {
+                for condition in chain.conditions() {
+                    self.condition(condition)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expr_call(&mut self, expr_call: &ast::ExprCall) -> CompileResult<Ty> {
+        let span = expr_call.span();
+
+        let path = match &*expr_call.expr {
+            ast::Expr::Path(path) => path,
+            ast::Expr::ExprFieldAccess(ast::ExprFieldAccess {
+                expr,
+                expr_field: ast::ExprField::Ident(ident),
+                ..
+            }) => {
+                let instance = self.expr(expr)?;
+                let name = self.name(ident)?;
+
+                let mut args = vec![(expr.span(), instance.clone())];
+                args.extend(self.call_args(expr_call)?);
+
+                return self.instance_call(span, &instance, &name, &args);
+            }
+            expr => {
+                self.expr(expr)?;
+                self.call_args(expr_call)?;
+                return Ok(Ty::Any);
+            }
+        };
+
+        let args = self.call_args(expr_call)?;
+
+        if let Some(ident) = path.try_as_ident() {
+            let name = self.name(ident)?;
+
+            if self.var(&name).is_some() {
+                return Ok(Ty::Any);
+            }
+        }
+
+        let item = self.convert_path(path)?;
+
+        let meta = match self.lookup_meta(&item)? {
+            Some(meta) => meta,
+            None => return Ok(Ty::Any),
+        };
+
+        match &meta.kind {
+            CompileMetaKind::Function { item, .. } => {
+                self.fn_call(span, &meta, item, &args, Some(expr_call))
+            }
+            CompileMetaKind::Tuple { type_of, tuple } => Ok(Ty::new(*type_of, &tuple.item)),
+            CompileMetaKind::TupleVariant { enum_item, .. } => self.enum_ty(enum_item),
+            _ => Ok(Ty::Any),
+        }
+    }
+
+    fn call_args(&mut self, expr_call: &ast::ExprCall) -> CompileResult<Vec<(Span, Ty)>> {
+        let mut args = Vec::with_capacity(expr_call.args.items.len());
+
+        for (expr, _) in &expr_call.args.items {
+            let (ident, value) = match call_args::named_arg(expr) {
+                Some(named) => named,
+                None => {
+                    args.push((expr.span(), self.expr(expr)?));
+                    continue;
+                }
+            };
+
+            // NB: this might be an assignment to a local variable, in which
+            // case the value passed isn't known until the call is resolved.
+            if self.var(&self.name(ident)?).is_some() {
+                self.expr(expr)?;
+                args.push((value.span(), Ty::Any));
+            } else {
+                args.push((value.span(), self.expr(value)?));
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Check a call to the given function, returning the type it produces.
+    ///
+    /// `expr_call` is the call being checked if its arguments might need to be
+    /// matched with named parameters.
+    fn fn_call(
+        &mut self,
+        span: Span,
+        meta: &CompileMeta,
+        item: &Item,
+        args: &[(Span, Ty)],
+        expr_call: Option<&ast::ExprCall>,
+    ) -> CompileResult<Ty> {
+        let (named, spread) = match expr_call {
+            Some(expr_call) => (
+                call_args::has_named_args(expr_call),
+                call_args::has_spread(expr_call),
+            ),
+            None => (false, false),
+        };
+
+        let (source, params, output, call) = match self.query.signatures.get(item) {
+            Some(signature) => (
+                signature.source.clone(),
+                signature.args.clone(),
+                signature.output.clone(),
+                signature.call,
+            ),
+            None => {
+                let hash = Hash::type_hash(item);
+
+                // NB: named arguments are matched and reported by the
+                // compiler.
+                if let Some(ContextSignature::Function {
+                    args: Some(expected),
+                    ..
+                }) = self.context.lookup_signature(hash)
+                {
+                    if !named && !spread {
+                        self.check_arg_count(span, meta, *expected, args.len(), false);
+                    }
+                }
+
+                return Ok(Ty::Any);
+            }
+        };
+
+        let params_args = match expr_call {
+            // NB: the number of spread arguments is only known at runtime.
+            Some(..) if spread => Some(Vec::new()),
+            Some(expr_call) => {
+                let resolve_params = Param::from_fn_args(&params, self.storage, &source)?;
+
+                if call_args::needs_resolve(expr_call, &resolve_params) {
+                    let resolved = call_args::resolve(
+                        expr_call,
+                        meta,
+                        &resolve_params,
+                        self.storage,
+                        &self.source,
+                        |name| self.var(name).is_some(),
+                    );
+
+                    // NB: errors are reported by the compiler.
+                    let resolved = match resolved {
+                        Ok(resolved) => resolved,
+                        Err(..) => return Ok(Ty::Any),
+                    };
+
+                    let params_args = resolved
+                        .into_iter()
+                        .map(|arg| match arg {
+                            CallArg::Expr(index, _) => Some(args[index].clone()),
+                            CallArg::Default => None,
+                        })
+                        .collect();
+
+                    Some(params_args)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        let params_args = match params_args {
+            Some(params_args) => params_args,
+            None => {
+                let variadic = matches!(params.last(), Some(arg) if arg.is_rest());
+
+                if !self.check_arg_count(span, meta, params.len(), args.len(), variadic) {
+                    return Ok(Ty::Any);
+                }
+
+                args.iter().cloned().map(Some).collect::<Vec<_>>()
+            }
+        };
+
+        for (arg, param) in params_args.iter().zip(&params) {
+            let (span, actual) = match arg {
+                Some(arg) => arg,
+                None => continue,
+            };
+
+            if let Some(param) = param.ty() {
+                // NB: errors in the annotation are reported when the called
+                // function is checked.
+                let expected = self.resolve_type(item, &source, param).unwrap_or(Ty::Any);
+
+                self.check(*span, &expected, actual);
+            }
+        }
+
+        Ok(match call {
+            Call::Async => Ty::Static(runestick::FUTURE_TYPE),
+            Call::Generator => Ty::Static(runestick::GENERATOR_TYPE),
+            Call::Stream => Ty::Static(runestick::STREAM_TYPE),
+            Call::Immediate => match &output {
+                Some(output) => self.resolve_type(item, &source, output).unwrap_or(Ty::Any),
+                None => Ty::Any,
+            },
+        })
+    }
+
+    /// Check a call to an instance function, where the instance is the first
+    /// argument.
+    fn instance_call(
+        &mut self,
+        span: Span,
+        instance: &Ty,
+        name: &str,
+        args: &[(Span, Ty)],
+    ) -> CompileResult<Ty> {
+        let type_of = match instance.type_of() {
+            Some(type_of) => type_of,
+            None => return Ok(Ty::Any),
+        };
+
+        let hash = Hash::instance_function(type_of, Hash::instance_fn_name(name));
+
+        if self.context.lookup(hash).is_some() {
+            return Ok(Ty::Any);
+        }
+
+        let type_item = match instance {
+            Ty::Type(_, item) => Some(item.clone()),
+            _ => self
+                .context
+                .iter_types()
+                .find(|(_, info)| info.type_of == type_of)
+                .map(|(_, info)| info.name.clone()),
+        };
+
+        if let Some(type_item) = type_item {
+            let item = type_item.extended(name);
+
+            if self.query.signatures.contains_key(&item) {
+                if let Some(meta) = self.lookup_meta(&item)? {
+                    return self.fn_call(span, &meta, &item, args, None);
+                }
+
+                return Ok(Ty::Any);
+            }
+        }
+
+        // NB: structs and enums declared in the unit can be cloned unless
+        // they provide their own `clone`, which is checked for above.
+        if let Ty::Type(_, item) = instance {
+            if name == "clone" && args.len() == 1 && self.is_declared_type(item)? {
+                return Ok(instance.clone());
+            }
+        }
+
+        self.report(
+            span,
+            CompileErrorKind::MissingInstanceFn {
+                name: name.to_owned(),
+                type_name: instance.to_string(),
+            },
+        );
+
+        Ok(Ty::Any)
+    }
+
+    /// Check the number of arguments to a call, returning `true` if it's
+    /// correct.
+    ///
+    /// A variadic function takes at least all arguments but the last.
+    fn check_arg_count(
+        &mut self,
+        span: Span,
+        meta: &CompileMeta,
+        expected: usize,
+        actual: usize,
+        variadic: bool,
+    ) -> bool {
+        if variadic {
+            if actual + 1 >= expected {
+                return true;
+            }
+
+            self.report(
+                span,
+                CompileErrorKind::UnsupportedVariadicArgumentCount {
+                    meta: meta.clone(),
+                    expected: expected - 1,
+                    actual,
+                },
+            );
+
+            return false;
+        }
+
+        if expected == actual {
+            return true;
+        }
+
+        self.report(
+            span,
+            CompileErrorKind::UnsupportedArgumentCount {
+                meta: meta.clone(),
+                expected,
+                actual,
+            },
+        );
+
+        false
+    }
+
+    /// Bind the variables declared in a pattern.
+    fn bind(&mut self, pat: &ast::Pat, ty: Ty, annotated: bool) -> CompileResult<()> {
+        match pat {
+            ast::Pat::PatPath(pat_path) => {
+                if let Some(ident) = pat_path.path.try_as_ident() {
+                    let name = self.name(ident)?;
+                    self.declare(&name, ty, annotated);
+                }
+            }
+            ast::Pat::PatVec(pat_vec) => {
+                for (pat, _) in &pat_vec.items {
+                    self.bind(pat, Ty::Any, false)?;
+                }
+            }
+            ast::Pat::PatTuple(pat_tuple) => {
+                for (pat, _) in &pat_tuple.items {
+                    self.bind(pat, Ty::Any, false)?;
+                }
+            }
+            ast::Pat::PatOr(pat_or) => {
+                for pat in pat_or.alternatives() {
+                    self.bind(pat, Ty::Any, false)?;
+                }
+            }
+            ast::Pat::PatBinding(pat_binding) => {
+                let name = self.name(&pat_binding.ident)?;
+                self.declare(&name, ty, annotated);
+                self.bind(&pat_binding.pat, Ty::Any, false)?;
+            }
+            ast::Pat::PatObject(pat_object) => {
+                for (field, _) in &pat_object.fields {
+                    match (&field.binding, &field.key) {
+                        (Some((_, pat)), _) => self.bind(pat, Ty::Any, false)?,
+                        (None, ast::LitObjectKey::Ident(ident)) => {
+                            let name = self.name(ident)?;
+                            self.declare(&name, Ty::Any, false);
+                        }
+                        (None, ast::LitObjectKey::LitStr(..)) => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a type annotation in the function being checked, reporting an
+    /// error if it doesn't exist.
+    fn annotation(&mut self, ty: &ast::Type) -> Ty {
+        let item = self.item.clone();
+        let source = self.source.clone();
+
+        match self.resolve_type(&item, &source, ty) {
+            Ok(ty) => ty,
+            Err(error) => {
+                if self.report {
+                    self.errors.push(error);
+                }
+
+                Ty::Any
+            }
+        }
+    }
+
+    /// Resolve a type annotation in the signature of the function being
+    /// checked.
+    ///
+    /// NB: these are reported by the compiler if they can't be resolved,
+    /// since it needs them to check arguments and return values at runtime.
+    fn signature_annotation(&mut self, ty: &ast::Type) -> Ty {
+        let item = self.item.clone();
+        let source = self.source.clone();
+        self.resolve_type(&item, &source, ty).unwrap_or(Ty::Any)
+    }
+
+    /// Resolve a type annotation relative to the given item.
+    fn resolve_type(&mut self, base: &Item, source: &Source, ty: &ast::Type) -> CompileResult<Ty> {
+        let path = match ty {
+            ast::Type::Unit(..) => return Ok(Ty::Static(runestick::UNIT_TYPE)),
+            ast::Type::Path(path) => path,
+        };
+
+        let item = self
+            .query
+            .unit
+            .borrow()
+            .convert_path(base, path, self.storage, source)?;
+
+        if let Some(meta) = self.lookup_meta_in(base, &item)? {
+            match &meta.kind {
+                CompileMetaKind::Tuple { .. }
+                | CompileMetaKind::Struct { .. }
+                | CompileMetaKind::Enum { .. } => {
+                    if let Some(type_of) = meta.type_of() {
+                        return Ok(Ty::new(type_of, meta.item()));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Err(CompileError::new(
+            ty,
+            CompileErrorKind::MissingType { item },
+        ))
+    }
+
+    /// Test if the given item is a struct or an enum declared in the unit,
+    /// as opposed to a native type.
+    fn is_declared_type(&mut self, item: &Item) -> CompileResult<bool> {
+        if self.context.lookup_meta(item).is_some() {
+            return Ok(false);
+        }
+
+        Ok(match self.lookup_meta(item)? {
+            Some(meta) => matches!(
+                meta.kind,
+                CompileMetaKind::Tuple { .. }
+                    | CompileMetaKind::Struct { .. }
+                    | CompileMetaKind::Enum { .. }
+            ),
+            None => false,
+        })
+    }
+
+    /// The type of values of the given enum.
+    fn enum_ty(&mut self, enum_item: &Item) -> CompileResult<Ty> {
+        Ok(match self.lookup_meta(enum_item)? {
+            Some(meta) => match &meta.kind {
+                CompileMetaKind::Enum { type_of, item } => Ty::new(*type_of, item),
+                _ => Ty::Any,
+            },
+            None => Ty::Any,
+        })
+    }
+
+    fn lookup_meta(&mut self, item: &Item) -> CompileResult<Option<CompileMeta>> {
+        let base = self.item.clone();
+        self.lookup_meta_in(&base, item)
+    }
+
+    /// Look up meta the same way the compiler does, by walking up from the
+    /// base item.
+    fn lookup_meta_in(&mut self, base: &Item, item: &Item) -> CompileResult<Option<CompileMeta>> {
+        if let Some(meta) = self.context.lookup_meta(item) {
+            return Ok(Some(meta));
+        }
+
+        let mut base = base.clone();
+
+        loop {
+            if let Some(meta) = self.query.query_meta(&base.join(item))? {
+                return Ok(Some(meta));
+            }
+
+            if base.pop().is_none() {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn convert_path(&self, path: &ast::Path) -> CompileResult<Item> {
+        self.query
+            .unit
+            .borrow()
+            .convert_path(&self.item, path, self.storage, &self.source)
+    }
+
+    fn name(&self, ident: &ast::Ident) -> CompileResult<String> {
+        Ok(ident.resolve(self.storage, &self.source)?.into_owned())
+    }
+
+    fn declare(&mut self, name: &str, ty: Ty, annotated: bool) {
+        // NB: the inferred type of a variable can't be trusted if it's
+        // assigned to anywhere.
+        let ty = if annotated || !self.reassigned.contains(name) {
+            ty
+        } else {
+            Ty::Any
+        };
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_owned(), Var { ty, annotated });
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<&Var> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Check that the actual type of an expression is the expected one.
+    fn check(&mut self, span: Span, expected: &Ty, actual: &Ty) {
+        match (expected.type_of(), actual.type_of()) {
+            (Some(a), Some(b)) if a != b => (),
+            _ => return,
+        }
+
+        self.report(
+            span,
+            CompileErrorKind::TypeMismatch {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        );
+    }
+
+    fn report(&mut self, span: Span, kind: CompileErrorKind) {
+        if self.report {
+            self.errors.push(CompileError::new(span, kind));
+        }
+    }
+}
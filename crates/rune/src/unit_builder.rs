@@ -10,9 +10,9 @@ use crate::CompileResult;
 use crate::{Errors, LoadError, Resolve as _, Storage};
 use runestick::debug::{DebugArgs, DebugSignature};
 use runestick::{
-    Call, CompileMeta, CompileMetaKind, Component, Context, DebugInfo, DebugInst, Hash, Inst,
-    IntoComponent, Item, Label, Names, Source, Span, StaticString, Type, Unit, UnitFn,
-    UnitTypeInfo,
+    Call, CompileMeta, CompileMetaKind, Component, ConstValue, Context, DebugInfo, DebugInst, Hash,
+    Inst, IntoComponent, Item, Label, Names, Rtti, Source, Span, StaticString, Type, Unit, UnitFn,
+    UnitTypeInfo, VariantRtti,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -26,6 +26,13 @@ pub enum UnitBuilderError {
         /// The signature of an already existing function.
         existing: DebugSignature,
     },
+    /// Tried to declare a function which doesn't exist as an instance
+    /// function.
+    #[error("missing function `{path}`")]
+    MissingFunction {
+        /// The path to the missing function.
+        path: Item,
+    },
     /// Tried to add an use that conflicts with an existing one.
     #[error("conflicting type already exists `{existing}`")]
     TypeConflict {
@@ -198,6 +205,10 @@ pub struct UnitBuilder {
     functions: HashMap<Hash, UnitFn>,
     /// Declared types.
     types: HashMap<Hash, UnitTypeInfo>,
+    /// Runtime type information for declared types.
+    rtti: HashMap<Hash, Arc<Rtti>>,
+    /// Runtime type information for declared variants.
+    variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
     /// Function by address.
     functions_rev: HashMap<usize, Hash>,
     /// A static string.
@@ -217,6 +228,8 @@ pub struct UnitBuilder {
     static_object_keys: Vec<Box<[String]>>,
     /// Used to detect duplicates in the collection of static object keys.
     static_object_keys_rev: HashMap<Hash, usize>,
+    /// Constants evaluated at compile time, by the hash of their item.
+    constants: HashMap<Hash, ConstValue>,
     /// The current label count.
     label_count: usize,
     /// A collection of required function hashes.
@@ -342,6 +355,9 @@ impl UnitBuilder {
             self.static_strings,
             self.static_bytes,
             self.static_object_keys,
+            self.constants,
+            self.rtti,
+            self.variant_rtti,
             self.debug,
         )
     }
@@ -587,6 +603,10 @@ impl UnitBuilder {
                     });
                 }
 
+                let rtti = Arc::new(Rtti::new(tuple.hash, tuple.item.clone(), None));
+
+                self.rtti.insert(tuple.hash, rtti);
+
                 self.debug_info_mut()
                     .functions
                     .insert(tuple.hash, signature);
@@ -626,6 +646,15 @@ impl UnitBuilder {
                     });
                 }
 
+                let rtti = Arc::new(VariantRtti {
+                    enum_hash,
+                    hash: tuple.hash,
+                    item: tuple.item.clone(),
+                    fields: None,
+                });
+
+                self.variant_rtti.insert(tuple.hash, rtti);
+
                 self.debug_info_mut()
                     .functions
                     .insert(tuple.hash, signature);
@@ -646,6 +675,13 @@ impl UnitBuilder {
                     });
                 }
 
+                let rtti = Arc::new(Rtti::new(
+                    hash,
+                    object.item.clone(),
+                    object.declared_fields.clone(),
+                ));
+
+                self.rtti.insert(hash, rtti);
                 object.item.clone()
             }
             CompileMetaKind::StructVariant {
@@ -665,6 +701,14 @@ impl UnitBuilder {
                     });
                 }
 
+                let rtti = Arc::new(VariantRtti {
+                    enum_hash,
+                    hash,
+                    item: object.item.clone(),
+                    fields: object.declared_fields.clone(),
+                });
+
+                self.variant_rtti.insert(hash, rtti);
                 object.item.clone()
             }
             CompileMetaKind::Enum { item, .. } => {
@@ -687,6 +731,11 @@ impl UnitBuilder {
             CompileMetaKind::Closure { item, .. } => item.clone(),
             CompileMetaKind::AsyncBlock { item, .. } => item.clone(),
             CompileMetaKind::Macro { item, .. } => item.clone(),
+            CompileMetaKind::Const { item, const_value } => {
+                self.constants
+                    .insert(Hash::type_hash(item), const_value.clone());
+                item.clone()
+            }
         };
 
         if let Some(existing) = self.meta.insert(item, meta.clone()) {
@@ -707,9 +756,9 @@ impl UnitBuilder {
     /// Declare a new function at the current instruction pointer.
     pub(crate) fn new_function(
         &mut self,
-        source_id: usize,
         path: Item,
         args: usize,
+        variadic: bool,
         assembly: Assembly,
         call: Call,
         debug_args: Vec<String>,
@@ -718,7 +767,12 @@ impl UnitBuilder {
         let hash = Hash::type_hash(&path);
 
         self.functions_rev.insert(offset, hash);
-        let info = UnitFn::Offset { offset, call, args };
+        let info = UnitFn::Offset {
+            offset,
+            call,
+            args,
+            variadic,
+        };
         let signature = DebugSignature::new(path, debug_args);
 
         if self.functions.insert(hash, info).is_some() {
@@ -728,38 +782,33 @@ impl UnitBuilder {
         }
 
         self.debug_info_mut().functions.insert(hash, signature);
-        self.add_assembly(source_id, assembly)?;
+        self.add_assembly(assembly)?;
         Ok(())
     }
 
-    /// Declare a new instance function at the current instruction pointer.
+    /// Declare the function at the given path, which has been declared
+    /// through [new_function][Self::new_function], as an instance function of
+    /// the given type.
     pub(crate) fn new_instance_function(
         &mut self,
-        source_id: usize,
-        path: Item,
+        path: &Item,
         type_of: Type,
         name: &str,
-        args: usize,
-        assembly: Assembly,
-        call: Call,
-        debug_args: Vec<String>,
     ) -> Result<(), UnitBuilderError> {
         log::trace!("instance fn: {}", path);
 
-        let offset = self.instructions.len();
         let instance_fn = Hash::instance_function(type_of, name);
-        let hash = Hash::type_hash(&path);
+        let hash = Hash::type_hash(path);
 
-        let info = UnitFn::Offset { offset, call, args };
-        let signature = DebugSignature::new(path, debug_args);
+        let info = self.functions.get(&hash).copied();
+        let signature = self.debug_info_mut().functions.get(&hash).cloned();
 
-        if self.functions.insert(instance_fn, info).is_some() {
-            return Err(UnitBuilderError::FunctionConflict {
-                existing: signature,
-            });
-        }
+        let (info, signature) = match (info, signature) {
+            (Some(info), Some(signature)) => (info, signature),
+            _ => return Err(UnitBuilderError::MissingFunction { path: path.clone() }),
+        };
 
-        if self.functions.insert(hash, info).is_some() {
+        if self.functions.insert(instance_fn, info).is_some() {
             return Err(UnitBuilderError::FunctionConflict {
                 existing: signature,
             });
@@ -768,17 +817,29 @@ impl UnitBuilder {
         self.debug_info_mut()
             .functions
             .insert(instance_fn, signature);
-        self.functions_rev.insert(offset, hash);
-        self.add_assembly(source_id, assembly)?;
         Ok(())
     }
 
-    /// Translate the given assembly into instructions.
-    fn add_assembly(
+    /// Register the default value of an argument to an instance function.
+    ///
+    /// Calls with method syntax can't be resolved when they're compiled, so
+    /// the virtual machine looks up the default values of arguments which have
+    /// been left out when the function is called.
+    pub(crate) fn new_instance_default_arg(
         &mut self,
-        source_id: usize,
-        assembly: Assembly,
-    ) -> Result<(), UnitBuilderError> {
+        type_of: Type,
+        name: &str,
+        index: usize,
+        value: ConstValue,
+    ) {
+        let instance_fn = Hash::instance_function(type_of, name);
+        let hash = Hash::instance_default_arg(instance_fn, index);
+        self.constants.insert(hash, value);
+    }
+
+    /// Translate the given assembly into instructions.
+    fn add_assembly(&mut self, assembly: Assembly) -> Result<(), UnitBuilderError> {
+        let source_id = assembly.source_id;
         self.label_count = assembly.label_count;
 
         self.required_functions.extend(assembly.required_functions);
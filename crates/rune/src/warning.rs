@@ -1,10 +1,10 @@
-use runestick::{SourceId, Span};
+use runestick::{Item, SourceId, Span};
 use std::error;
 use std::fmt;
 use thiserror::Error;
 
 /// Compilation warning.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Warning {
     /// The id of the source where the warning happened.
     pub source_id: SourceId,
@@ -21,6 +21,8 @@ impl Warning {
             WarningKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningKind::RemoveTupleCallParams { span, .. } => *span,
             WarningKind::UnecessarySemiColon { span, .. } => *span,
+            WarningKind::UnreachablePattern { span, .. } => *span,
+            WarningKind::NonExhaustiveMatch { span, .. } => *span,
         }
     }
 }
@@ -38,7 +40,7 @@ impl error::Error for Warning {
 }
 
 /// Compilation warning kind.
-#[derive(Debug, Clone, Copy, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum WarningKind {
     /// Item identified by the span is not used.
     #[error("not used")]
@@ -81,7 +83,43 @@ pub enum WarningKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// A match branch can never be reached, because the branches before it
+    /// already match everything it does.
+    #[error("unreachable pattern")]
+    UnreachablePattern {
+        /// The span of the pattern.
+        span: Span,
+        /// The context in which it is used.
+        context: Option<Span>,
+    },
+    /// A match over the variants of an enum doesn't cover all of them.
+    #[error("match is not exhaustive, {} not covered", DisplayVariants(missing))]
+    NonExhaustiveMatch {
+        /// The span of the match.
+        span: Span,
+        /// The variants which are not covered.
+        missing: Vec<Item>,
+    },
+}
+/// Display a list of enum variants, like `` `E::A`, `E::B` ``.
+pub(crate) struct DisplayVariants<'a>(pub(crate) &'a [Item]);
+
+impl fmt::Display for DisplayVariants<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut it = self.0.iter();
+
+        if let Some(item) = it.next() {
+            write!(f, "`{}`", item)?;
+        }
+
+        for item in it {
+            write!(f, ", `{}`", item)?;
+        }
+
+        Ok(())
+    }
 }
+
 /// Compilation warnings.
 #[derive(Debug, Clone, Default)]
 pub struct Warnings {
@@ -200,6 +238,29 @@ impl Warnings {
         }
     }
 
+    /// Indicate that a match branch can never be reached.
+    ///
+    /// Like the second branch in `match v { _ => 1, Foo::Bar => 2 }`.
+    pub fn unreachable_pattern(&mut self, source_id: usize, span: Span, context: Option<Span>) {
+        if let Some(w) = &mut self.warnings {
+            w.push(Warning {
+                source_id,
+                kind: WarningKind::UnreachablePattern { span, context },
+            });
+        }
+    }
+
+    /// Indicate that a match over the variants of an enum is missing some of
+    /// them, and doesn't have a catch-all branch.
+    pub fn non_exhaustive_match(&mut self, source_id: usize, span: Span, missing: Vec<Item>) {
+        if let Some(w) = &mut self.warnings {
+            w.push(Warning {
+                source_id,
+                kind: WarningKind::NonExhaustiveMatch { span, missing },
+            });
+        }
+    }
+
     /// Add a warning about an unecessary semi-colon.
     pub fn uneccessary_semi_colon(&mut self, source_id: usize, span: Span) {
         if let Some(w) = &mut self.warnings {
@@ -5,7 +5,7 @@ use crate::collections::HashMap;
 use crate::index::{Index as _, Indexer};
 use crate::index_scopes::IndexScopes;
 use crate::items::Items;
-use crate::macros::MacroCompiler;
+use crate::macros::{BuiltInMacro, MacroCompiler};
 use crate::query::Query;
 use crate::CompileResult;
 use crate::{
@@ -57,6 +57,8 @@ pub(crate) enum IndexAst {
     Item(ast::Item),
     /// Index a new expression.
     Expr(ast::Expr),
+    /// Index the arguments to a built-in format macro.
+    Format(BuiltInMacro, ast::FormatArgs),
 }
 
 pub(crate) struct Worker<'a> {
@@ -193,7 +195,11 @@ impl<'a> Worker<'a> {
                             Err(error) => Err(error),
                         },
                         IndexAst::Expr(ast) => match indexer.index(&ast) {
-                            Ok(()) => Ok(Some(Expanded::Expr(ast))),
+                            Ok(()) => Ok(Some(Expanded::Expr(Box::new(ast)))),
+                            Err(error) => Err(error),
+                        },
+                        IndexAst::Format(builtin, ast) => match indexer.index(&ast) {
+                            Ok(()) => Ok(Some(Expanded::Format(builtin, Box::new(ast)))),
                             Err(error) => Err(error),
                         },
                     };
@@ -264,6 +270,42 @@ impl<'a> Worker<'a> {
                         }
                     }
 
+                    let builtin = match kind {
+                        MacroKind::Expr => {
+                            match BuiltInMacro::find(&ast, &self.query.storage, &source) {
+                                Ok(builtin) => builtin,
+                                Err(error) => {
+                                    self.errors.push(LoadError::new(source_id, error));
+                                    continue;
+                                }
+                            }
+                        }
+                        MacroKind::Item => None,
+                    };
+
+                    if let Some(builtin) = builtin {
+                        let ast = match BuiltInMacro::parse_args(&ast) {
+                            Ok(ast) => ast,
+                            Err(error) => {
+                                self.errors.push(LoadError::new(source_id, error));
+                                continue;
+                            }
+                        };
+
+                        self.queue.push_back(Task::Index(Index {
+                            root,
+                            item,
+                            items,
+                            source_id,
+                            source,
+                            scopes,
+                            impl_items,
+                            ast: IndexAst::Format(builtin, ast),
+                        }));
+
+                        continue;
+                    }
+
                     let mut macro_context =
                         MacroContext::new(self.query.storage.clone(), source.clone());
 
@@ -323,7 +365,9 @@ impl<'a> Worker<'a> {
 /// An item that has been expanded by a macro.
 pub(crate) enum Expanded {
     /// The expansion resulted in an expression.
-    Expr(ast::Expr),
+    Expr(Box<ast::Expr>),
+    /// The expansion is a built-in format macro with the given arguments.
+    Format(BuiltInMacro, Box<ast::FormatArgs>),
 }
 
 /// Indexing to process.
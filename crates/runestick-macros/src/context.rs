@@ -200,7 +200,7 @@ impl Context {
                 unsafe fn unsafe_from_value(
                     value: #value,
                 ) -> Result<(Self::Output, Self::Guard), #vm_error> {
-                    Ok(value.unsafe_into_any_ref()?)
+                    value.unsafe_into_any_ref()
                 }
 
                 unsafe fn to_arg(output: Self::Output) -> Self {
@@ -215,7 +215,7 @@ impl Context {
                 unsafe fn unsafe_from_value(
                     value: #value,
                 ) -> Result<(Self::Output, Self::Guard), #vm_error> {
-                    Ok(value.unsafe_into_any_mut()?)
+                    value.unsafe_into_any_mut()
                 }
 
                 unsafe fn to_arg(output: Self::Output) -> Self {
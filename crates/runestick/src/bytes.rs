@@ -2,7 +2,9 @@
 //!
 //! [Value::Bytes]: crate::Value::Bytes.
 
-use crate::{FromValue, Mut, Named, RawMut, RawRef, RawStr, Ref, UnsafeFromValue, Value, VmError};
+use crate::{
+    FromValue, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue, Value, VmError,
+};
 
 use std::fmt;
 use std::ops;
@@ -121,6 +123,12 @@ impl FromValue for Bytes {
     }
 }
 
+impl FromValue for Shared<Bytes> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_bytes()
+    }
+}
+
 impl<'a> UnsafeFromValue for &'a Bytes {
     type Output = *const Bytes;
     type Guard = RawRef;
@@ -1,5 +1,5 @@
 use crate::collections::HashSet;
-use crate::{Hash, Item, SourceId, Span, Type};
+use crate::{ConstValue, Hash, Item, SourceId, Span, Type};
 use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -44,6 +44,7 @@ impl CompileMeta {
             CompileMetaKind::Closure { item, .. } => item,
             CompileMetaKind::AsyncBlock { item, .. } => item,
             CompileMetaKind::Macro { item, .. } => item,
+            CompileMetaKind::Const { item, .. } => item,
         }
     }
 
@@ -59,6 +60,7 @@ impl CompileMeta {
             CompileMetaKind::Closure { type_of, .. } => Some(*type_of),
             CompileMetaKind::AsyncBlock { type_of, .. } => Some(*type_of),
             CompileMetaKind::Macro { .. } => None,
+            CompileMetaKind::Const { .. } => None,
         }
     }
 }
@@ -93,6 +95,9 @@ impl fmt::Display for CompileMeta {
             CompileMetaKind::Macro { item, .. } => {
                 write!(fmt, "macro {}", item)?;
             }
+            CompileMetaKind::Const { item, .. } => {
+                write!(fmt, "const {}", item)?;
+            }
         }
 
         Ok(())
@@ -171,6 +176,13 @@ pub enum CompileMetaKind {
         /// The item of the macro.
         item: Item,
     },
+    /// A constant, evaluated at compile time.
+    Const {
+        /// The item of the constant.
+        item: Item,
+        /// The evaluated value of the constant.
+        const_value: ConstValue,
+    },
 }
 
 /// The metadata about a type.
@@ -180,6 +192,8 @@ pub struct CompileMetaStruct {
     pub item: Item,
     /// Fields associated with the type.
     pub fields: Option<HashSet<String>>,
+    /// Fields associated with the type in the order they're declared.
+    pub declared_fields: Option<Box<[String]>>,
 }
 
 /// The metadata about a variant.
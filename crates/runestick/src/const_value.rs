@@ -0,0 +1,99 @@
+use crate::{Bytes, Object, Rtti, Shared, TypeInfo, TypedObject, TypedTuple, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// A constant value, evaluated at compile time and stored in a unit.
+///
+/// Every time a constant is loaded by the virtual machine, a fresh [Value] is
+/// constructed from it, so that mutating a loaded constant can never affect
+/// another use of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConstValue {
+    /// The unit constant `()`.
+    Unit,
+    /// A constant boolean.
+    Bool(bool),
+    /// A constant byte.
+    Byte(u8),
+    /// A constant character.
+    Char(char),
+    /// A constant integer.
+    Integer(i64),
+    /// A constant float.
+    Float(f64),
+    /// A constant string.
+    String(String),
+    /// A constant byte string.
+    Bytes(Vec<u8>),
+    /// A constant vector.
+    Vec(Vec<ConstValue>),
+    /// A constant tuple.
+    Tuple(Box<[ConstValue]>),
+    /// A constant anonymous object.
+    Object(BTreeMap<String, ConstValue>),
+    /// A constant tuple struct declared in Rune.
+    TypedTuple(Arc<Rtti>, Box<[ConstValue]>),
+    /// A constant struct with named fields declared in Rune.
+    TypedObject(Arc<Rtti>, BTreeMap<String, ConstValue>),
+}
+
+impl ConstValue {
+    /// Construct a new value out of the constant.
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Unit => Value::Unit,
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Byte(b) => Value::Byte(*b),
+            Self::Char(c) => Value::Char(*c),
+            Self::Integer(n) => Value::Integer(*n),
+            Self::Float(n) => Value::Float(*n),
+            Self::String(s) => Value::String(Shared::with_heap(s.clone())),
+            Self::Bytes(b) => Value::Bytes(Shared::with_heap(Bytes::from_vec(b.clone()))),
+            Self::Vec(vec) => Value::vec(vec.iter().map(Self::to_value).collect()),
+            Self::Tuple(tuple) => Value::tuple(tuple.iter().map(Self::to_value).collect()),
+            Self::Object(object) => {
+                let mut o = Object::with_capacity(object.len());
+
+                for (key, value) in object {
+                    o.insert(key.clone(), value.to_value());
+                }
+
+                Value::Object(Shared::with_heap(o))
+            }
+            Self::TypedTuple(rtti, tuple) => Value::TypedTuple(Shared::new(TypedTuple {
+                rtti: rtti.clone(),
+                tuple: tuple.iter().map(Self::to_value).collect(),
+            })),
+            Self::TypedObject(rtti, object) => {
+                let mut o = Object::with_capacity(object.len());
+
+                for (key, value) in object {
+                    o.insert(key.clone(), value.to_value());
+                }
+
+                Value::TypedObject(Shared::new(TypedObject::new(rtti.clone(), o)))
+            }
+        }
+    }
+
+    /// Get the type information of the constant.
+    pub fn type_info(&self) -> TypeInfo {
+        TypeInfo::StaticType(match self {
+            Self::TypedTuple(rtti, ..) | Self::TypedObject(rtti, ..) => {
+                return TypeInfo::Hash(rtti.hash)
+            }
+            Self::Unit => crate::UNIT_TYPE,
+            Self::Bool(..) => crate::BOOL_TYPE,
+            Self::Byte(..) => crate::BYTE_TYPE,
+            Self::Char(..) => crate::CHAR_TYPE,
+            Self::Integer(..) => crate::INTEGER_TYPE,
+            Self::Float(..) => crate::FLOAT_TYPE,
+            Self::String(..) => crate::STRING_TYPE,
+            Self::Bytes(..) => crate::BYTES_TYPE,
+            Self::Vec(..) => crate::VEC_TYPE,
+            Self::Tuple(..) => crate::TUPLE_TYPE,
+            Self::Object(..) => crate::OBJECT_TYPE,
+        })
+    }
+}
@@ -4,8 +4,8 @@ use crate::module::{
 };
 use crate::{
     CompileMeta, CompileMetaKind, CompileMetaStruct, CompileMetaTuple, Component, Hash,
-    IntoComponent, Item, Module, Names, Stack, StaticType, Type, TypeCheck, TypeInfo, TypeOf,
-    VmError,
+    IntoComponent, Item, Module, Names, Sandbox, Stack, StaticType, Type, TypeCheck, TypeInfo,
+    TypeOf, VmError,
 };
 use std::any;
 use std::fmt;
@@ -95,6 +95,24 @@ pub enum ContextError {
         /// The instance type.
         instance_type: TypeInfo,
     },
+    /// Error raised when declaring parameters for a function which does not
+    /// exist.
+    #[error("function `{name}` does not exist in module")]
+    MissingFunction {
+        /// The name of the missing function.
+        name: Item,
+    },
+    /// Error raised when the number of declared parameters doesn't match the
+    /// number of arguments a function takes.
+    #[error("function `{name}` takes {expected} arguments, but {actual} parameters were declared")]
+    ParameterCountMismatch {
+        /// The name of the function.
+        name: Item,
+        /// The number of arguments the function takes.
+        expected: usize,
+        /// The number of declared parameters.
+        actual: usize,
+    },
 }
 
 /// A function handler.
@@ -129,12 +147,16 @@ impl fmt::Display for ContextTypeInfo {
 /// A description of a function signature.
 #[derive(Debug, Clone)]
 pub enum ContextSignature {
+    /// A free function.
     Function {
         /// Path to the function.
         path: Item,
         /// Arguments.
         args: Option<usize>,
+        /// Names of the parameters, if they've been declared.
+        params: Option<Box<[&'static str]>>,
     },
+    /// An instance function.
     Instance {
         /// Path to the instance function.
         path: Item,
@@ -150,10 +172,21 @@ pub enum ContextSignature {
 impl fmt::Display for ContextSignature {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Function { path, args } => {
+            Self::Function { path, args, params } => {
                 write!(fmt, "{}(", path)?;
 
-                if let Some(args) = args {
+                if let Some(params) = params {
+                    let mut it = params.iter();
+                    let last = it.next_back();
+
+                    for param in it {
+                        write!(fmt, "{}, ", param)?;
+                    }
+
+                    if let Some(param) = last {
+                        write!(fmt, "{}", param)?;
+                    }
+                } else if let Some(args) = args {
                     let mut it = 0..*args;
                     let last = it.next_back();
 
@@ -222,6 +255,8 @@ pub struct Context {
     internal_enums: HashSet<&'static StaticType>,
     /// All available names in the context.
     names: Names,
+    /// The sandbox used by virtual machines running with this context.
+    sandbox: Option<Arc<Sandbox>>,
 }
 
 impl Context {
@@ -230,6 +265,27 @@ impl Context {
         Context::default()
     }
 
+    /// Set the sandbox to use for all virtual machines running with this
+    /// context.
+    ///
+    /// This can be overridden for individual virtual machines through
+    /// [Vm::set_sandbox][crate::Vm::set_sandbox].
+    pub fn set_sandbox(&mut self, sandbox: Option<Sandbox>) {
+        self.sandbox = sandbox.map(Arc::new);
+    }
+
+    /// Access the sandbox used by virtual machines running with this context,
+    /// if any.
+    pub fn sandbox(&self) -> Option<&Arc<Sandbox>> {
+        self.sandbox.as_ref()
+    }
+
+    /// Lookup information on the type with the given value type.
+    pub fn lookup_type_info(&self, type_of: Type) -> Option<&ContextTypeInfo> {
+        let hash = self.types_rev.get(&type_of)?;
+        self.types.get(hash)
+    }
+
     /// Use the specified type check.
     pub fn type_check_for(&self, item: &Item) -> Option<TypeCheck> {
         let ty = self.types.get(&Hash::type_hash(item))?;
@@ -304,6 +360,11 @@ impl Context {
         self.functions.get(&hash)
     }
 
+    /// Lookup the signature of the given function.
+    pub fn lookup_signature(&self, hash: Hash) -> Option<&ContextSignature> {
+        self.functions_info.get(&hash)
+    }
+
     /// Lookup the given macro handler.
     pub fn lookup_macro(&self, hash: Hash) -> Option<&Arc<Macro>> {
         self.macros.get(&hash)
@@ -404,6 +465,7 @@ impl Context {
                     object: CompileMetaStruct {
                         item: name,
                         fields: None,
+                        declared_fields: None,
                     },
                 },
                 source: None,
@@ -450,6 +512,7 @@ impl Context {
         let signature = ContextSignature::Function {
             path: name.clone(),
             args: f.args,
+            params: f.params.clone(),
         };
 
         if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -639,6 +702,7 @@ impl Context {
             let signature = ContextSignature::Function {
                 path: item,
                 args: Some(variant.args),
+                params: None,
             };
 
             if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -698,6 +762,7 @@ impl Context {
         let signature = ContextSignature::Function {
             path: item,
             args: Some(args),
+            params: None,
         };
 
         if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -0,0 +1,141 @@
+//! Instruction coverage for units.
+//!
+//! Coverage is collected by installing a [Coverage] recorder into a virtual
+//! machine through [Vm::set_coverage][crate::Vm::set_coverage]. Every
+//! instruction executed in the unit the recorder was constructed for is then
+//! counted, and for conditional jumps we also keep track of whether the jump
+//! was taken or not.
+//!
+//! The recorded information can be mapped back to source locations through
+//! the [DebugInfo][crate::DebugInfo] of the unit.
+
+use crate::{Inst, Unit};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Coverage recorded for the instructions in a single unit.
+pub struct Coverage {
+    /// The unit coverage is being recorded for.
+    unit: Arc<Unit>,
+    /// Number of times each instruction has been executed.
+    hits: Vec<AtomicUsize>,
+    /// Number of times each conditional jump has been taken and not taken.
+    branches: Vec<[AtomicUsize; 2]>,
+}
+
+impl Coverage {
+    /// Construct a new coverage recorder for the given unit.
+    pub fn new(unit: Arc<Unit>) -> Self {
+        let len = unit.iter_instructions().count();
+        let hits = (0..len).map(|_| AtomicUsize::new(0)).collect();
+        let branches = (0..len)
+            .map(|_| [AtomicUsize::new(0), AtomicUsize::new(0)])
+            .collect();
+
+        Self {
+            unit,
+            hits,
+            branches,
+        }
+    }
+
+    /// Access the unit that coverage is being recorded for.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// Test if coverage is recorded for the given unit.
+    pub fn is_for(&self, unit: &Arc<Unit>) -> bool {
+        Arc::ptr_eq(&self.unit, unit)
+    }
+
+    /// Get the number of times the instruction at the given instruction
+    /// pointer has been executed.
+    pub fn hits(&self, ip: usize) -> usize {
+        self.hits
+            .get(ip)
+            .map(|hits| hits.load(Ordering::Relaxed))
+            .unwrap_or_default()
+    }
+
+    /// Get the number of times the conditional jump at the given instruction
+    /// pointer has been taken and not taken, in that order.
+    ///
+    /// Returns `None` if the instruction at the given location is not a
+    /// conditional jump.
+    pub fn branch_hits(&self, ip: usize) -> Option<(usize, usize)> {
+        if !is_branch(self.unit.instruction_at(ip)?) {
+            return None;
+        }
+
+        let [taken, not_taken] = self.branches.get(ip)?;
+        Some((
+            taken.load(Ordering::Relaxed),
+            not_taken.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Iterate over all instructions and the number of times they have been
+    /// executed.
+    pub fn iter_hits(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.hits
+            .iter()
+            .enumerate()
+            .map(|(ip, hits)| (ip, hits.load(Ordering::Relaxed)))
+    }
+
+    /// Iterate over all conditional jumps and the number of times they have
+    /// been taken and not taken.
+    pub fn iter_branches(&self) -> impl Iterator<Item = (usize, (usize, usize))> + '_ {
+        (0..self.branches.len()).filter_map(move |ip| Some((ip, self.branch_hits(ip)?)))
+    }
+
+    /// Reset all recorded coverage.
+    pub fn clear(&self) {
+        for hits in &self.hits {
+            hits.store(0, Ordering::Relaxed);
+        }
+
+        for [taken, not_taken] in &self.branches {
+            taken.store(0, Ordering::Relaxed);
+            not_taken.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Record that the instruction at the given location was executed.
+    #[inline]
+    pub(crate) fn record(&self, ip: usize) {
+        if let Some(hits) = self.hits.get(ip) {
+            hits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the outcome of the conditional jump at the given location.
+    #[inline]
+    pub(crate) fn record_branch(&self, ip: usize, taken: bool) {
+        if let Some(branch) = self.branches.get(ip) {
+            branch[if taken { 0 } else { 1 }].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl fmt::Debug for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coverage")
+            .field("unit", &(&*self.unit as *const _))
+            .field("instructions", &self.hits.len())
+            .finish()
+    }
+}
+
+/// Test if the given instruction is a conditional jump.
+fn is_branch(inst: &Inst) -> bool {
+    matches!(
+        inst,
+        Inst::JumpIf { .. }
+            | Inst::JumpIfNot { .. }
+            | Inst::JumpIfBranch { .. }
+            | Inst::PopAndJumpIfNot { .. }
+    )
+}
@@ -44,7 +44,7 @@ pub struct DebugInst {
 }
 
 /// Debug information on function arguments.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DebugArgs {
     /// A tuple, with the given number of arguments.
     TupleArgs(usize),
@@ -53,7 +53,7 @@ pub enum DebugArgs {
 }
 
 /// A description of a function signature.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DebugSignature {
     /// The path of the function.
     pub path: Item,
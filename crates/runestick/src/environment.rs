@@ -0,0 +1,108 @@
+//! The environment of the virtual machine which is currently running.
+//!
+//! While a virtual machine is running, or while a future it awaits is being
+//! polled, its environment is installed in a thread-local. This is what gives
+//! native functions access to the runtime type information of the unit being
+//! executed, the memory tracker, and the sandbox through [rtti::lookup],
+//! [memory::current], and [sandbox::current]. Functions called from native
+//! code inherit the memory tracker, fuel, and sandbox of the environment, and
+//! executions which are awaited share the time slice of the scheduler task
+//! awaiting them.
+//!
+//! [rtti::lookup]: crate::rtti::lookup
+//! [memory::current]: crate::memory::current
+//! [sandbox::current]: crate::sandbox::current
+
+use crate::{Memory, Sandbox, Unit};
+use pin_project::pin_project;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+thread_local!(static ENVIRONMENT: RefCell<Environment> = RefCell::new(Environment::default()));
+
+/// The environment of a virtual machine.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Environment {
+    /// The unit to look up runtime type information in.
+    pub(crate) unit: Option<Arc<Unit>>,
+    /// The memory tracker in use.
+    pub(crate) memory: Option<Arc<Memory>>,
+    /// The remaining fuel, if execution is limited.
+    pub(crate) fuel: Option<Rc<Cell<usize>>>,
+    /// The sandbox in use.
+    pub(crate) sandbox: Option<Arc<Sandbox>>,
+    /// The instructions left in the time slice of the [Scheduler] task being
+    /// run, if any.
+    ///
+    /// [Scheduler]: crate::scheduler::Scheduler
+    pub(crate) slice: Option<Rc<Cell<usize>>>,
+}
+
+/// Access the environment which is currently installed.
+pub(crate) fn current<F, O>(f: F) -> O
+where
+    F: FnOnce(&Environment) -> O,
+{
+    ENVIRONMENT.with(|tls| f(&tls.borrow()))
+}
+
+/// Wrap the given value with an environment.
+///
+/// The value can either be a function, after which you can use
+/// [WithEnvironment::call], or it can be a [Future] which can be polled.
+pub(crate) fn with<T>(environment: Environment, value: T) -> WithEnvironment<T> {
+    WithEnvironment { environment, value }
+}
+
+struct EnvironmentGuard(Environment);
+
+impl Drop for EnvironmentGuard {
+    fn drop(&mut self) {
+        ENVIRONMENT.with(|tls| {
+            *tls.borrow_mut() = std::mem::take(&mut self.0);
+        });
+    }
+}
+
+/// A value wrapped with an environment.
+#[pin_project]
+pub(crate) struct WithEnvironment<T> {
+    /// The environment to install.
+    environment: Environment,
+    /// The wrapped value.
+    #[pin]
+    value: T,
+}
+
+impl<T, O> WithEnvironment<T>
+where
+    T: FnOnce() -> O,
+{
+    /// Call the wrapped function.
+    pub(crate) fn call(self) -> O {
+        ENVIRONMENT.with(|tls| {
+            let _guard = EnvironmentGuard(tls.replace(self.environment));
+            (self.value)()
+        })
+    }
+}
+
+impl<T> Future for WithEnvironment<T>
+where
+    T: Future,
+{
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        ENVIRONMENT.with(|tls| {
+            let _guard = EnvironmentGuard(tls.replace(this.environment.clone()));
+            this.value.poll(cx)
+        })
+    }
+}
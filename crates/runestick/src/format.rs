@@ -0,0 +1,700 @@
+//! Format specifications, as used in template strings like `{value:>8}` and in
+//! the `format!` family of macros.
+
+use crate::{Protocol, TypeInfo, Value, VmError, VmErrorKind};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Write as _;
+use std::num::NonZeroUsize;
+use thiserror::Error;
+
+/// A parsed format specification, like `>8.3` in `{value:>8.3}`.
+///
+/// The supported syntax is a subset of the one used by Rust:
+///
+/// ```text
+/// [[fill]align]['+']['#']['0'][width]['.' precision][type]
+/// ```
+///
+/// Where `align` is one of `<`, `^`, or `>` and `type` is one of `?`, `x`, `X`,
+/// `o`, `b`, `e`, or `E`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatSpec {
+    /// The character used to pad the formatted value.
+    pub fill: char,
+    /// The alignment of the formatted value, if explicitly specified.
+    pub align: Option<Alignment>,
+    /// Always emit the sign of numbers.
+    pub sign_plus: bool,
+    /// The alternate form, as specified with `#`.
+    pub alternate: bool,
+    /// Pad numbers with zeros after their sign.
+    pub zero: bool,
+    /// The minimum width of the formatted value.
+    pub width: Option<NonZeroUsize>,
+    /// The precision of floats, or the maximum length of strings.
+    pub precision: Option<usize>,
+    /// How the value is formatted.
+    pub ty: FormatType,
+}
+
+/// An error raised when parsing a [FormatSpec].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FormatSpecError {
+    /// The specification is not valid.
+    #[error("invalid format specification")]
+    Invalid,
+    /// A width or precision in the specification doesn't fit in a `usize`.
+    #[error("width or precision of format specification is too large")]
+    TooLarge,
+}
+
+impl FormatSpec {
+    /// Parse a format specification.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{FormatSpec, FormatSpecError, FormatType};
+    ///
+    /// let spec = FormatSpec::parse("#x").unwrap();
+    /// assert!(spec.alternate);
+    /// assert_eq!(spec.ty, FormatType::LowerHex);
+    ///
+    /// assert!(FormatSpec::parse("8.3").is_ok());
+    /// assert_eq!(FormatSpec::parse("nope"), Err(FormatSpecError::Invalid));
+    /// assert_eq!(FormatSpec::parse("99999999999999999999"), Err(FormatSpecError::TooLarge));
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self, FormatSpecError> {
+        let mut it = spec.chars().peekable();
+
+        let mut fill = ' ';
+        let mut align = None;
+
+        let mut lookahead = spec.chars();
+
+        match (lookahead.next(), lookahead.next()) {
+            (Some(c), Some(a)) if Alignment::from_char(a).is_some() => {
+                fill = c;
+                align = Alignment::from_char(a);
+                it.next();
+                it.next();
+            }
+            (Some(a), _) if Alignment::from_char(a).is_some() => {
+                align = Alignment::from_char(a);
+                it.next();
+            }
+            _ => (),
+        }
+
+        let sign_plus = it.next_if_eq(&'+').is_some();
+        let alternate = it.next_if_eq(&'#').is_some();
+        let zero = it.next_if_eq(&'0').is_some();
+
+        let width = parse_number(&mut it)?.and_then(NonZeroUsize::new);
+
+        let precision = if it.next_if_eq(&'.').is_some() {
+            match parse_number(&mut it)? {
+                Some(precision) => Some(precision),
+                None => return Err(FormatSpecError::Invalid),
+            }
+        } else {
+            None
+        };
+
+        let ty = match it.next() {
+            None => FormatType::Display,
+            Some('?') => FormatType::Debug,
+            Some('x') => FormatType::LowerHex,
+            Some('X') => FormatType::UpperHex,
+            Some('o') => FormatType::Octal,
+            Some('b') => FormatType::Binary,
+            Some('e') => FormatType::LowerExp,
+            Some('E') => FormatType::UpperExp,
+            Some(..) => return Err(FormatSpecError::Invalid),
+        };
+
+        if it.next().is_some() {
+            return Err(FormatSpecError::Invalid);
+        }
+
+        Ok(Self {
+            fill,
+            align,
+            sign_plus,
+            alternate,
+            zero,
+            width,
+            precision,
+            ty,
+        })
+    }
+
+    /// Format the given value according to this specification, appending the
+    /// result to `out`.
+    ///
+    /// Protocols are called through `protocol`, which returns `false` if the
+    /// value doesn't implement the given protocol.
+    pub fn format<P>(
+        &self,
+        value: &Value,
+        out: &mut String,
+        protocol: &mut P,
+    ) -> Result<(), VmError>
+    where
+        P: FnMut(&Value, Protocol, &mut String) -> Result<bool, VmError>,
+    {
+        let mut buf = String::new();
+
+        let number = match (self.ty, value) {
+            (FormatType::Display, _)
+            | (FormatType::Debug, Value::Integer(..))
+            | (FormatType::Debug, Value::Float(..)) => {
+                self.format_display(value, &mut buf, protocol)?
+            }
+            (FormatType::Debug, _) => {
+                format_debug(value, self.alternate, 0, &mut buf, protocol)?;
+                None
+            }
+            (FormatType::LowerHex, _)
+            | (FormatType::UpperHex, _)
+            | (FormatType::Octal, _)
+            | (FormatType::Binary, _) => Some(self.format_radix(value, &mut buf)?),
+            (FormatType::LowerExp, _) | (FormatType::UpperExp, _) => {
+                Some(self.format_exp(value, &mut buf)?)
+            }
+        };
+
+        self.pad(&buf, number, out);
+        Ok(())
+    }
+
+    /// Format using the display representation of the value.
+    ///
+    /// Returns the length of the sign if the value is a number.
+    fn format_display<P>(
+        &self,
+        value: &Value,
+        buf: &mut String,
+        protocol: &mut P,
+    ) -> Result<Option<usize>, VmError>
+    where
+        P: FnMut(&Value, Protocol, &mut String) -> Result<bool, VmError>,
+    {
+        match value {
+            Value::Integer(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                let digits = buffer.format(integer.unsigned_abs());
+                return Ok(Some(self.number(*integer < 0, "", digits, buf)));
+            }
+            Value::Byte(byte) => {
+                let mut buffer = itoa::Buffer::new();
+                let digits = buffer.format(*byte);
+                return Ok(Some(self.number(false, "", digits, buf)));
+            }
+            Value::Float(float) => {
+                let negative = float.is_sign_negative() && !float.is_nan();
+
+                let digits = match self.precision {
+                    Some(precision) => format!("{:.*}", precision, float.abs()),
+                    None => ryu::Buffer::new().format(float.abs()).to_owned(),
+                };
+
+                return Ok(Some(self.number(negative, "", &digits, buf)));
+            }
+            Value::String(string) => {
+                self.truncated(&string.borrow_ref()?, buf);
+            }
+            Value::StaticString(string) => {
+                self.truncated(string.as_str(), buf);
+            }
+            Value::Char(c) => {
+                buf.push(*c);
+            }
+            Value::Bool(b) => {
+                write!(buf, "{}", b).map_err(|_| VmError::from(VmErrorKind::FormatError))?;
+            }
+            actual => {
+                if !protocol(actual, crate::STRING_DISPLAY, buf)?
+                    && !display_struct(actual, buf, protocol)?
+                {
+                    return Err(VmError::from(VmErrorKind::MissingProtocol {
+                        protocol: crate::STRING_DISPLAY,
+                        actual: actual.type_info()?,
+                    }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Format an integer with a radix, like `{:x}`.
+    fn format_radix(&self, value: &Value, buf: &mut String) -> Result<usize, VmError> {
+        let (prefix, digits) = match (self.ty, value) {
+            (FormatType::LowerHex, Value::Integer(n)) => ("0x", format!("{:x}", n)),
+            (FormatType::LowerHex, Value::Byte(n)) => ("0x", format!("{:x}", n)),
+            (FormatType::UpperHex, Value::Integer(n)) => ("0x", format!("{:X}", n)),
+            (FormatType::UpperHex, Value::Byte(n)) => ("0x", format!("{:X}", n)),
+            (FormatType::Octal, Value::Integer(n)) => ("0o", format!("{:o}", n)),
+            (FormatType::Octal, Value::Byte(n)) => ("0o", format!("{:o}", n)),
+            (FormatType::Binary, Value::Integer(n)) => ("0b", format!("{:b}", n)),
+            (FormatType::Binary, Value::Byte(n)) => ("0b", format!("{:b}", n)),
+            (_, actual) => return Err(self.unsupported(actual.type_info()?)),
+        };
+
+        let prefix = if self.alternate { prefix } else { "" };
+        Ok(self.number(false, prefix, &digits, buf))
+    }
+
+    /// Format a number in scientific notation, like `{:e}`.
+    fn format_exp(&self, value: &Value, buf: &mut String) -> Result<usize, VmError> {
+        let float = match value {
+            Value::Float(float) => *float,
+            Value::Integer(integer) => *integer as f64,
+            actual => return Err(self.unsupported(actual.type_info()?)),
+        };
+
+        let negative = float.is_sign_negative() && !float.is_nan();
+
+        let digits = match (self.ty, self.precision) {
+            (FormatType::UpperExp, Some(precision)) => format!("{:.*E}", precision, float.abs()),
+            (FormatType::UpperExp, None) => format!("{:E}", float.abs()),
+            (_, Some(precision)) => format!("{:.*e}", precision, float.abs()),
+            (_, None) => format!("{:e}", float.abs()),
+        };
+
+        Ok(self.number(negative, "", &digits, buf))
+    }
+
+    /// Write a number with its sign and prefix, returning the length of the
+    /// sign and prefix.
+    fn number(&self, negative: bool, prefix: &str, digits: &str, buf: &mut String) -> usize {
+        if negative {
+            buf.push('-');
+        } else if self.sign_plus {
+            buf.push('+');
+        }
+
+        buf.push_str(prefix);
+        let len = buf.len();
+        buf.push_str(digits);
+        len
+    }
+
+    /// Write a string, truncated to the precision of the specification.
+    fn truncated(&self, string: &str, buf: &mut String) {
+        match self.precision {
+            Some(precision) => buf.extend(string.chars().take(precision)),
+            None => buf.push_str(string),
+        }
+    }
+
+    /// Pad the formatted value to the width of the specification.
+    ///
+    /// `number` is the length of the sign and prefix if the value is a number,
+    /// which zero padding is inserted after.
+    fn pad(&self, buf: &str, number: Option<usize>, out: &mut String) {
+        let len = buf.chars().count();
+
+        let padding = match self.width {
+            Some(width) if width.get() > len => width.get() - len,
+            _ => {
+                out.push_str(buf);
+                return;
+            }
+        };
+
+        if let (true, Some(prefix)) = (self.zero, number) {
+            out.push_str(&buf[..prefix]);
+            fill(out, '0', padding);
+            out.push_str(&buf[prefix..]);
+            return;
+        }
+
+        let align = match (self.align, number) {
+            (Some(align), _) => align,
+            (None, Some(..)) => Alignment::Right,
+            (None, None) => Alignment::Left,
+        };
+
+        let (before, after) = match align {
+            Alignment::Left => (0, padding),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+            Alignment::Right => (padding, 0),
+        };
+
+        fill(out, self.fill, before);
+        out.push_str(buf);
+        fill(out, self.fill, after);
+    }
+
+    fn unsupported(&self, actual: TypeInfo) -> VmError {
+        VmError::from(VmErrorKind::UnsupportedFormat {
+            spec: *self,
+            actual,
+        })
+    }
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            sign_plus: false,
+            alternate: false,
+            zero: false,
+            width: None,
+            precision: None,
+            ty: FormatType::Display,
+        }
+    }
+}
+
+impl fmt::Display for FormatSpec {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(align) = self.align {
+            write!(fmt, "{}{}", self.fill, align)?;
+        }
+
+        if self.sign_plus {
+            write!(fmt, "+")?;
+        }
+
+        if self.alternate {
+            write!(fmt, "#")?;
+        }
+
+        if self.zero {
+            write!(fmt, "0")?;
+        }
+
+        if let Some(width) = self.width {
+            write!(fmt, "{}", width)?;
+        }
+
+        if let Some(precision) = self.precision {
+            write!(fmt, ".{}", precision)?;
+        }
+
+        write!(fmt, "{}", self.ty)
+    }
+}
+
+/// The alignment of a formatted value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Alignment {
+    /// Left alignment, as specified with `<`.
+    Left,
+    /// Center alignment, as specified with `^`.
+    Center,
+    /// Right alignment, as specified with `>`.
+    Right,
+}
+
+impl Alignment {
+    fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            '<' => Self::Left,
+            '^' => Self::Center,
+            '>' => Self::Right,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Alignment {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => write!(fmt, "<"),
+            Self::Center => write!(fmt, "^"),
+            Self::Right => write!(fmt, ">"),
+        }
+    }
+}
+
+/// How a value is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FormatType {
+    /// The display representation, using the `STRING_DISPLAY` protocol.
+    Display,
+    /// The debug representation, as specified with `?`, using the `DEBUG_FMT`
+    /// protocol.
+    Debug,
+    /// Lower-case hexadecimal, as specified with `x`.
+    LowerHex,
+    /// Upper-case hexadecimal, as specified with `X`.
+    UpperHex,
+    /// Octal, as specified with `o`.
+    Octal,
+    /// Binary, as specified with `b`.
+    Binary,
+    /// Lower-case scientific notation, as specified with `e`.
+    LowerExp,
+    /// Upper-case scientific notation, as specified with `E`.
+    UpperExp,
+}
+
+impl fmt::Display for FormatType {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Display => Ok(()),
+            Self::Debug => write!(fmt, "?"),
+            Self::LowerHex => write!(fmt, "x"),
+            Self::UpperHex => write!(fmt, "X"),
+            Self::Octal => write!(fmt, "o"),
+            Self::Binary => write!(fmt, "b"),
+            Self::LowerExp => write!(fmt, "e"),
+            Self::UpperExp => write!(fmt, "E"),
+        }
+    }
+}
+
+/// Display a struct or variant declared in Rune using its debug
+/// representation, like `Point(1, 2)` or `Person { name: "Bob" }`.
+///
+/// Returns `false` if the value is something else.
+pub(crate) fn display_struct<P>(
+    value: &Value,
+    buf: &mut String,
+    protocol: &mut P,
+) -> Result<bool, VmError>
+where
+    P: FnMut(&Value, Protocol, &mut String) -> Result<bool, VmError>,
+{
+    match value {
+        Value::TypedTuple(..)
+        | Value::TupleVariant(..)
+        | Value::TypedObject(..)
+        | Value::VariantObject(..) => {
+            format_debug(value, false, 0, buf, protocol)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Write the debug representation of the given value.
+///
+/// Collections are formatted recursively, values which implement the
+/// `DEBUG_FMT` protocol use it, and anything else falls back to the debug
+/// representation of the value itself.
+fn format_debug<P>(
+    value: &Value,
+    alternate: bool,
+    indent: usize,
+    buf: &mut String,
+    protocol: &mut P,
+) -> Result<(), VmError>
+where
+    P: FnMut(&Value, Protocol, &mut String) -> Result<bool, VmError>,
+{
+    let entries = |values: &[Value]| {
+        values
+            .iter()
+            .map(|value| (None, value.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // NB: fields are sorted in the order they're declared if that's known,
+    // and by name otherwise.
+    let fields = |object: &crate::Object, declared: Option<&[String]>, quoted: bool| {
+        let mut fields = object
+            .iter()
+            .map(|(key, value)| {
+                let key = if quoted {
+                    format!("{:?}", key)
+                } else {
+                    key.clone()
+                };
+
+                (Some(key), value.clone())
+            })
+            .collect::<Vec<_>>();
+
+        match declared {
+            Some(declared) => fields.sort_by_key(|(key, _)| {
+                declared
+                    .iter()
+                    .position(|field| Some(field) == key.as_ref())
+            }),
+            None => fields.sort_by(|a, b| a.0.cmp(&b.0)),
+        }
+
+        fields
+    };
+
+    let (name, open, close, entries) = match value {
+        Value::String(string) => return write_debug(buf, &string.borrow_ref()?),
+        Value::StaticString(string) => return write_debug(buf, string.as_str()),
+        Value::Vec(vec) => (None, "[", "]", entries(&vec.borrow_ref()?)),
+        Value::Tuple(tuple) => {
+            let tuple = tuple.borrow_ref()?;
+
+            // NB: one-tuples need a trailing comma to distinguish them.
+            if let ([value], false) = (&tuple[..], alternate) {
+                buf.push('(');
+                format_debug(value, alternate, indent, buf, protocol)?;
+                buf.push_str(",)");
+                return Ok(());
+            }
+
+            (None, "(", ")", entries(&tuple))
+        }
+        Value::Object(object) => (None, "#{", "}", fields(&*object.borrow_ref()?, None, true)),
+        Value::Option(option) => match &*option.borrow_ref()? {
+            Some(value) => (
+                Some(String::from("Some")),
+                "(",
+                ")",
+                entries(std::slice::from_ref(value)),
+            ),
+            None => {
+                buf.push_str("None");
+                return Ok(());
+            }
+        },
+        Value::Result(result) => match &*result.borrow_ref()? {
+            Ok(value) => (
+                Some(String::from("Ok")),
+                "(",
+                ")",
+                entries(std::slice::from_ref(value)),
+            ),
+            Err(value) => (
+                Some(String::from("Err")),
+                "(",
+                ")",
+                entries(std::slice::from_ref(value)),
+            ),
+        },
+        Value::TypedTuple(tuple) => {
+            let tuple = tuple.borrow_ref()?;
+            let name = tuple.rtti.name();
+            (Some(name), "(", ")", entries(&tuple.tuple))
+        }
+        Value::TupleVariant(tuple) => {
+            let tuple = tuple.borrow_ref()?;
+            let name = tuple.rtti.name();
+            (Some(name), "(", ")", entries(&tuple.tuple))
+        }
+        Value::TypedObject(object) => {
+            let object = object.borrow_ref()?;
+            let name = object.rtti().name();
+            let declared = object.rtti().fields.as_deref();
+            (
+                Some(name),
+                "{",
+                "}",
+                fields(&object.object, declared, false),
+            )
+        }
+        Value::VariantObject(object) => {
+            let object = object.borrow_ref()?;
+            let name = object.rtti.name();
+            let declared = object.rtti.fields.as_deref();
+            (
+                Some(name),
+                "{",
+                "}",
+                fields(&object.object, declared, false),
+            )
+        }
+        value => {
+            if !protocol(value, crate::DEBUG_FMT, buf)? {
+                write!(buf, "{:?}", value).map_err(|_| VmError::from(VmErrorKind::FormatError))?;
+            }
+
+            return Ok(());
+        }
+    };
+
+    if let Some(name) = &name {
+        buf.push_str(name);
+
+        // NB: unit structs and variants only print their name.
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        if open == "{" {
+            buf.push(' ');
+        }
+    }
+
+    buf.push_str(open);
+
+    if entries.is_empty() {
+        buf.push_str(close);
+        return Ok(());
+    }
+
+    let spaced = open == "{";
+
+    for (n, (key, value)) in entries.iter().enumerate() {
+        if alternate {
+            buf.push('\n');
+            fill(buf, ' ', (indent + 1) * 4);
+        } else if n > 0 {
+            buf.push_str(", ");
+        } else if spaced {
+            buf.push(' ');
+        }
+
+        if let Some(key) = key {
+            buf.push_str(key);
+            buf.push_str(": ");
+        }
+
+        format_debug(value, alternate, indent + 1, buf, protocol)?;
+
+        if alternate {
+            buf.push(',');
+        }
+    }
+
+    if alternate {
+        buf.push('\n');
+        fill(buf, ' ', indent * 4);
+    } else if spaced {
+        buf.push(' ');
+    }
+
+    buf.push_str(close);
+    Ok(())
+}
+
+fn write_debug(buf: &mut String, string: &str) -> Result<(), VmError> {
+    write!(buf, "{:?}", string).map_err(|_| VmError::from(VmErrorKind::FormatError))
+}
+
+/// Append `count` copies of the given character.
+fn fill(buf: &mut String, c: char, count: usize) {
+    for _ in 0..count {
+        buf.push(c);
+    }
+}
+
+/// Parse a decimal number out of the given iterator, returning `None` if there
+/// are no digits.
+fn parse_number<I>(it: &mut std::iter::Peekable<I>) -> Result<Option<usize>, FormatSpecError>
+where
+    I: Iterator<Item = char>,
+{
+    let mut number = None::<usize>;
+
+    while let Some(digit) = it.peek().and_then(|c| c.to_digit(10)) {
+        it.next();
+
+        let n = number
+            .unwrap_or_default()
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(digit as usize))
+            .ok_or(FormatSpecError::TooLarge)?;
+
+        number = Some(n);
+    }
+
+    Ok(number)
+}
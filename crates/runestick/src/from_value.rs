@@ -152,6 +152,12 @@ impl FromValue for String {
     }
 }
 
+impl FromValue for Shared<String> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_string()
+    }
+}
+
 impl FromValue for Mut<String> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         match value {
@@ -298,6 +304,12 @@ impl UnsafeFromValue for &Result<Value, Value> {
 
 // Vec impls
 
+impl FromValue for Shared<Vec<Value>> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_vec()
+    }
+}
+
 impl FromValue for Mut<Vec<Value>> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         Ok(value.into_vec()?.into_mut()?)
@@ -343,6 +355,21 @@ impl<'a> UnsafeFromValue for &'a [Value] {
     }
 }
 
+impl UnsafeFromValue for &mut [Value] {
+    type Output = *mut [Value];
+    type Guard = RawMut;
+
+    unsafe fn unsafe_from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let vec = value.into_vec()?;
+        let (vec, guard) = Mut::into_raw(vec.into_mut()?);
+        Ok((&mut **vec, guard))
+    }
+
+    unsafe fn to_arg(output: Self::Output) -> Self {
+        &mut *output
+    }
+}
+
 impl<'a> UnsafeFromValue for &'a Vec<Value> {
     type Output = *const Vec<Value>;
     type Guard = RawRef;
@@ -1,8 +1,8 @@
 use crate::context::Handler;
 use crate::VmErrorKind;
 use crate::{
-    Args, Call, Context, FromValue, Future, Generator, Hash, RawRef, Ref, Shared, Stack, Stream,
-    Tuple, Unit, UnsafeFromValue, Value, Vm, VmCall, VmError, VmHalt,
+    Args, Call, Context, FromValue, Future, Generator, RawRef, Ref, Rtti, Shared, Stack, Stream,
+    Tuple, Unit, UnsafeFromValue, Value, VariantRtti, Vm, VmCall, VmError, VmHalt,
 };
 use std::fmt;
 use std::sync::Arc;
@@ -14,6 +14,10 @@ pub struct Function {
 
 impl Function {
     /// Perform a call over the function represented by this function pointer.
+    ///
+    /// Functions called while a virtual machine is running, like from a native
+    /// function, share the memory tracker, fuel, and sandbox of that virtual
+    /// machine.
     pub fn call<A, T>(&self, args: A) -> Result<T, VmError>
     where
         A: Args,
@@ -32,11 +36,11 @@ impl Function {
                 .call(args, (closure.environment.clone(),))?,
             Inner::FnTuple(tuple) => {
                 Self::check_args(A::count(), tuple.args)?;
-                Value::typed_tuple(tuple.hash, args.into_vec()?)
+                Value::typed_tuple(tuple.rtti.clone(), args.into_vec()?)
             }
             Inner::FnVariantTuple(tuple) => {
                 Self::check_args(A::count(), tuple.args)?;
-                Value::variant_tuple(tuple.enum_hash, tuple.hash, args.into_vec()?)
+                Value::variant_tuple(tuple.rtti.clone(), args.into_vec()?)
             }
         };
 
@@ -76,18 +80,16 @@ impl Function {
             Inner::FnTuple(tuple) => {
                 Self::check_args(args, tuple.args)?;
 
-                let value = Value::typed_tuple(tuple.hash, vm.stack_mut().pop_sequence(args)?);
+                let value =
+                    Value::typed_tuple(tuple.rtti.clone(), vm.stack_mut().pop_sequence(args)?);
                 vm.stack_mut().push(value);
                 None
             }
             Inner::FnVariantTuple(tuple) => {
                 Self::check_args(args, tuple.args)?;
 
-                let value = Value::variant_tuple(
-                    tuple.enum_hash,
-                    tuple.hash,
-                    vm.stack_mut().pop_sequence(args)?,
-                );
+                let value =
+                    Value::variant_tuple(tuple.rtti.clone(), vm.stack_mut().pop_sequence(args)?);
 
                 vm.stack_mut().push(value);
                 None
@@ -111,6 +113,7 @@ impl Function {
         offset: usize,
         call: Call,
         args: usize,
+        variadic: bool,
     ) -> Self {
         Self {
             inner: Inner::FnOffset(FnOffset {
@@ -119,6 +122,7 @@ impl Function {
                 offset,
                 call,
                 args,
+                variadic,
             }),
         }
     }
@@ -130,6 +134,7 @@ impl Function {
         offset: usize,
         call: Call,
         args: usize,
+        variadic: bool,
         environment: Shared<Tuple>,
     ) -> Self {
         Self {
@@ -140,6 +145,7 @@ impl Function {
                     offset,
                     call,
                     args,
+                    variadic,
                 },
                 environment,
             }),
@@ -147,20 +153,16 @@ impl Function {
     }
 
     /// Create a function pointer from an offset.
-    pub(crate) fn from_tuple(hash: Hash, args: usize) -> Self {
+    pub(crate) fn from_tuple(rtti: Arc<Rtti>, args: usize) -> Self {
         Self {
-            inner: Inner::FnTuple(FnTuple { hash, args }),
+            inner: Inner::FnTuple(FnTuple { rtti, args }),
         }
     }
 
     /// Create a function pointer that constructs a tuple variant.
-    pub(crate) fn from_variant_tuple(enum_hash: Hash, hash: Hash, args: usize) -> Self {
+    pub(crate) fn from_variant_tuple(rtti: Arc<VariantRtti>, args: usize) -> Self {
         Self {
-            inner: Inner::FnVariantTuple(FnVariantTuple {
-                enum_hash,
-                hash,
-                args,
-            }),
+            inner: Inner::FnVariantTuple(FnVariantTuple { rtti, args }),
         }
     }
 
@@ -194,13 +196,13 @@ impl fmt::Debug for Function {
                 )?;
             }
             Inner::FnTuple(tuple) => {
-                write!(f, "tuple (type: {})", tuple.hash)?;
+                write!(f, "tuple (type: {})", tuple.rtti.hash)?;
             }
             Inner::FnVariantTuple(tuple) => {
                 write!(
                     f,
                     "variant tuple (enum: {}, type: {})",
-                    tuple.enum_hash, tuple.hash
+                    tuple.rtti.enum_hash, tuple.rtti.hash
                 )?;
             }
         }
@@ -251,6 +253,8 @@ struct FnOffset {
     call: Call,
     /// The number of arguments the function takes.
     args: usize,
+    /// If the last argument collects any remaining arguments.
+    variadic: bool,
 }
 
 impl FnOffset {
@@ -260,12 +264,12 @@ impl FnOffset {
         A: Args,
         E: Args,
     {
-        Function::check_args(A::count(), self.args)?;
-
         let mut vm = Vm::new(self.context.clone(), self.unit.clone());
+        vm.inherit_environment();
 
         vm.set_ip(self.offset);
         args.into_stack(vm.stack_mut())?;
+        Vm::offset_args(vm.stack_mut(), A::count(), self.args, self.variadic)?;
         extra.into_stack(vm.stack_mut())?;
 
         Ok(match self.call {
@@ -284,7 +288,7 @@ impl FnOffset {
     where
         E: Args,
     {
-        Function::check_args(args, self.args)?;
+        let args = Vm::offset_args(vm.stack_mut(), args, self.args, self.variadic)?;
 
         // Fast past, just allocate a call frame and keep running.
         if let Call::Immediate = self.call {
@@ -297,9 +301,10 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain_stack_top(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
-        let mut vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
-        vm.set_ip(self.offset);
-        Ok(Some(VmCall::new(self.call, vm)))
+        let mut new_vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
+        new_vm.inherit(vm);
+        new_vm.set_ip(self.offset);
+        Ok(Some(VmCall::new(self.call, new_vm)))
     }
 }
 
@@ -311,6 +316,7 @@ impl fmt::Debug for FnOffset {
             .field("offset", &self.offset)
             .field("call", &self.call)
             .field("args", &self.args)
+            .field("variadic", &self.variadic)
             .finish()
     }
 }
@@ -325,18 +331,16 @@ struct FnClosureOffset {
 
 #[derive(Debug)]
 struct FnTuple {
-    /// The type of the tuple.
-    hash: Hash,
+    /// The runtime type information of the tuple.
+    rtti: Arc<Rtti>,
     /// The number of arguments the tuple takes.
     args: usize,
 }
 
 #[derive(Debug)]
 struct FnVariantTuple {
-    /// The enum the variant belongs to.
-    enum_hash: Hash,
-    /// The type of the tuple.
-    hash: Hash,
+    /// The runtime type information of the variant.
+    rtti: Arc<VariantRtti>,
     /// The number of arguments the tuple takes.
     args: usize,
 }
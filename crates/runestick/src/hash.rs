@@ -4,7 +4,6 @@ use std::any;
 use std::fmt;
 use std::hash;
 use std::hash::{BuildHasher as _, BuildHasherDefault, Hash as _, Hasher as _};
-use std::mem;
 use twox_hash::XxHash64;
 
 const SEP: usize = 0x7f;
@@ -12,6 +11,7 @@ const TYPE: usize = 1;
 const INSTANCE_FUNCTION: usize = 2;
 const GETTER: usize = 3;
 const OBJECT_KEYS: usize = 4;
+const DEFAULT_ARG: usize = 5;
 
 /// The hash of a primitive thing.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -49,9 +49,11 @@ impl Hash {
 
     /// Construct a hash from a type id.
     pub fn from_type_id(type_id: any::TypeId) -> Self {
-        // Safety: a type id is exactly a 64-bit unsigned integer.
-        // And has an identical bit pattern to `Hash`.
-        unsafe { mem::transmute(type_id) }
+        // A type id is not guaranteed to be 64 bits wide, so fold it into
+        // one through the hasher.
+        let mut hasher = XxHash64::default();
+        type_id.hash(&mut hasher);
+        Self(hasher.finish())
     }
 
     /// Construct a hash to an instance function, where the instance is a
@@ -73,6 +75,12 @@ impl Hash {
         Self::of((GETTER, type_of, SEP, name))
     }
 
+    /// Construct a hash to the default value of the argument at the given
+    /// index of an instance function.
+    pub fn instance_default_arg(instance_fn: Hash, index: usize) -> Self {
+        Self::of((DEFAULT_ARG, instance_fn, SEP, index))
+    }
+
     /// Get the hash corresponding to a static byte array.
     pub fn static_bytes(bytes: &[u8]) -> Hash {
         Self::of(bytes)
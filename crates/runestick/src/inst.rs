@@ -1,4 +1,4 @@
-use crate::{Hash, Value};
+use crate::{FormatSpec, Hash, Value};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -158,6 +158,58 @@ pub enum Inst {
         /// The number of arguments expected on the stack for this call.
         args: usize,
     },
+    /// Perform a function call, where the arguments are the values of the
+    /// vector or tuple on top of the stack.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <args>
+    /// => <ret>
+    /// ```
+    CallSpread {
+        /// The hash of the function to call.
+        hash: Hash,
+    },
+    /// Perform an instance function call, where the arguments are the values
+    /// of the vector or tuple on top of the stack, followed by the instance
+    /// being called on.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <args>
+    /// <value>
+    /// => <ret>
+    /// ```
+    CallInstanceSpread {
+        /// The hash of the name of the function to call.
+        hash: Hash,
+    },
+    /// Perform a function call on a function pointer stored on the stack,
+    /// where the arguments are the values of the vector or tuple below it.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <fn>
+    /// <args>
+    /// => <ret>
+    /// ```
+    CallFnSpread,
+    /// Concatenate the values of `count` vectors or tuples on the stack into a
+    /// single vector.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// => <vec>
+    /// ```
+    Concat {
+        /// The number of vectors or tuples to concatenate.
+        count: usize,
+    },
     /// Perform an index get operation. Pushing the result on the stack.
     ///
     /// # Operation
@@ -208,6 +260,22 @@ pub enum Inst {
         /// The index to fetch.
         index: usize,
     },
+    /// Get the given index counted from the end of a tuple or a vector from
+    /// the given variable slot, where `0` is the last item.
+    /// Errors if the item doesn't exist or the item is not a tuple or a
+    /// vector.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    TupleIndexGetFromEndAt {
+        /// The slot offset to load the tuple from.
+        offset: usize,
+        /// The index from the end to fetch.
+        index: usize,
+    },
     /// Get the given index out of an object on the top of the stack.
     /// Errors if the item doesn't exist or the item is not an object.
     ///
@@ -241,6 +309,23 @@ pub enum Inst {
         /// The static string slot corresponding to the index to fetch.
         slot: usize,
     },
+    /// Pop an object, and push the values of the fields identified by the
+    /// given static object keys slot in order. Errors if any of the fields
+    /// don't exist or the value is not an object.
+    ///
+    /// This is used to fill in the remaining fields of a struct update, like
+    /// `Foo { a: 1, ..other }`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <object>
+    /// => <value..>
+    /// ```
+    ObjectFields {
+        /// The static object keys slot of the fields to push.
+        slot: usize,
+    },
     /// Perform an index set operation.
     ///
     /// # Operation
@@ -385,6 +470,47 @@ pub enum Inst {
         /// Offset to swap value from.
         offset: usize,
     },
+    /// Capture the variable at the given offset in a shared cell, so that
+    /// assignments to it are visible both to the closure capturing it and to
+    /// the enclosing scope.
+    ///
+    /// The variable is converted into a shared cell if it isn't one already,
+    /// and the cell is pushed on the stack.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <cell>
+    /// ```
+    CaptureCell {
+        /// Offset of the variable to capture.
+        offset: usize,
+    },
+    /// Copy a variable which might be captured in a shared cell, reading
+    /// through the cell if it is.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    CopyCell {
+        /// Offset to copy value from.
+        offset: usize,
+    },
+    /// Replace a variable which might be captured in a shared cell with the
+    /// top of the stack, writing through the cell if it is.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => *noop*
+    /// ```
+    ReplaceCell {
+        /// Offset of the variable to replace.
+        offset: usize,
+    },
     /// Pop the current stack frame and restore the instruction pointer from it.
     ///
     /// The stack frame will be cleared, and the value on the top of the stack
@@ -395,6 +521,42 @@ pub enum Inst {
     /// The stack frame will be cleared, and a unit value will be pushed to the
     /// top of the stack.
     ReturnUnit,
+    /// Check that the argument at the given frame offset matches the type
+    /// annotation of its parameter, raising a
+    /// [VmErrorKind::BadArgumentType][crate::VmErrorKind::BadArgumentType]
+    /// error if it doesn't.
+    ///
+    /// In async functions, generators, and streams this runs once they're
+    /// first polled or resumed, since that's when their bodies start running.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => *noop*
+    /// ```
+    CheckArgument {
+        /// Frame offset of the argument to check.
+        offset: usize,
+        /// The type check to perform.
+        type_check: TypeCheck,
+        /// The static string slot of the parameter name, if it has one.
+        slot: Option<usize>,
+    },
+    /// Check that the value on top of the stack matches the return type
+    /// annotation of the current function, raising a
+    /// [VmErrorKind::BadReturnType][crate::VmErrorKind::BadReturnType] error
+    /// if it doesn't.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <value>
+    /// ```
+    CheckReturn {
+        /// The type check to perform.
+        type_check: TypeCheck,
+    },
     /// Compare two values on the stack for lt and push the result as a
     /// boolean on the stack.
     Lt,
@@ -598,6 +760,17 @@ pub enum Inst {
         /// The static byte string slot to load the string from.
         slot: usize,
     },
+    /// Load a constant that was evaluated at compile time.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    Const {
+        /// The hash of the constant to load.
+        hash: Hash,
+    },
     /// Pop the given number of values from the stack, and concatenate a string
     /// from them.
     ///
@@ -615,6 +788,21 @@ pub enum Inst {
         /// The minimum string size used.
         size_hint: usize,
     },
+    /// Pop a value from the stack and format it according to the given
+    /// specification, pushing the resulting string.
+    ///
+    /// This is used by template strings and the `format!` family of macros.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <string>
+    /// ```
+    Format {
+        /// The format specification to use.
+        spec: FormatSpec,
+    },
     /// Test if the top of the stack is an instance of the second item on the
     /// stack.
     ///
@@ -725,6 +913,54 @@ pub enum Inst {
         /// The integer to test against.
         integer: i64,
     },
+    /// Test if the top of the stack is a byte in the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    InRangeByte {
+        /// The start of the range.
+        start: u8,
+        /// The end of the range.
+        end: u8,
+        /// If the end of the range is inclusive.
+        inclusive: bool,
+    },
+    /// Test if the top of the stack is a character in the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    InRangeCharacter {
+        /// The start of the range.
+        start: char,
+        /// The end of the range.
+        end: char,
+        /// If the end of the range is inclusive.
+        inclusive: bool,
+    },
+    /// Test if the top of the stack is an integer in the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    InRangeInteger {
+        /// The start of the range.
+        start: i64,
+        /// The end of the range.
+        end: i64,
+        /// If the end of the range is inclusive.
+        inclusive: bool,
+    },
     /// Compare the top of the stack against a static string slot.
     ///
     /// # Operation
@@ -901,6 +1137,18 @@ impl fmt::Display for Inst {
             Self::CallFn { args } => {
                 write!(fmt, "call-fn {}", args)?;
             }
+            Self::CallSpread { hash } => {
+                write!(fmt, "call-spread {}", hash)?;
+            }
+            Self::CallInstanceSpread { hash } => {
+                write!(fmt, "call-instance-spread {}", hash)?;
+            }
+            Self::CallFnSpread => {
+                write!(fmt, "call-fn-spread")?;
+            }
+            Self::Concat { count } => {
+                write!(fmt, "concat {}", count)?;
+            }
             Self::LoadInstanceFn { hash } => {
                 write!(fmt, "load-instance-fn {}", hash)?;
             }
@@ -916,12 +1164,18 @@ impl fmt::Display for Inst {
             Self::TupleIndexGetAt { offset, index } => {
                 write!(fmt, "tuple-index-get-at {}, {}", offset, index)?;
             }
+            Self::TupleIndexGetFromEndAt { offset, index } => {
+                write!(fmt, "tuple-index-get-from-end-at {}, {}", offset, index)?;
+            }
             Self::ObjectIndexGet { slot } => {
                 write!(fmt, "object-index-get {}", slot)?;
             }
             Self::ObjectIndexGetAt { offset, slot } => {
                 write!(fmt, "object-index-get-at {}, {}", offset, slot)?;
             }
+            Self::ObjectFields { slot } => {
+                write!(fmt, "object-fields {}", slot)?;
+            }
             Self::IndexSet => {
                 write!(fmt, "index-set")?;
             }
@@ -958,12 +1212,35 @@ impl fmt::Display for Inst {
             Self::Replace { offset } => {
                 write!(fmt, "replace {}", offset)?;
             }
+            Self::CaptureCell { offset } => {
+                write!(fmt, "capture-cell {}", offset)?;
+            }
+            Self::CopyCell { offset } => {
+                write!(fmt, "copy-cell {}", offset)?;
+            }
+            Self::ReplaceCell { offset } => {
+                write!(fmt, "replace-cell {}", offset)?;
+            }
             Self::Return => {
                 write!(fmt, "return")?;
             }
             Self::ReturnUnit => {
                 write!(fmt, "return-unit")?;
             }
+            Self::CheckArgument {
+                offset,
+                type_check,
+                slot,
+            } => {
+                write!(fmt, "check-argument {}, {}", offset, type_check)?;
+
+                if let Some(slot) = slot {
+                    write!(fmt, ", {}", slot)?;
+                }
+            }
+            Self::CheckReturn { type_check } => {
+                write!(fmt, "check-return {}", type_check)?;
+            }
             Self::Lt => {
                 write!(fmt, "lt")?;
             }
@@ -1022,9 +1299,15 @@ impl fmt::Display for Inst {
             Self::Bytes { slot } => {
                 write!(fmt, "bytes {}", slot)?;
             }
+            Self::Const { hash } => {
+                write!(fmt, "const {}", hash)?;
+            }
             Self::StringConcat { len, size_hint } => {
                 write!(fmt, "string-concat {}, {}", len, size_hint)?;
             }
+            Self::Format { spec } => {
+                write!(fmt, "format {{:{}}}", spec)?;
+            }
             Self::Is => {
                 write!(fmt, "is")?;
             }
@@ -1055,6 +1338,31 @@ impl fmt::Display for Inst {
             Self::EqInteger { integer } => {
                 write!(fmt, "eq-integer {}", integer)?;
             }
+            Self::InRangeByte {
+                start,
+                end,
+                inclusive,
+            } => {
+                write!(fmt, "in-range-byte {:?}, {:?}, {}", start, end, inclusive)?;
+            }
+            Self::InRangeCharacter {
+                start,
+                end,
+                inclusive,
+            } => {
+                write!(
+                    fmt,
+                    "in-range-character {:?}, {:?}, {}",
+                    start, end, inclusive
+                )?;
+            }
+            Self::InRangeInteger {
+                start,
+                end,
+                inclusive,
+            } => {
+                write!(fmt, "in-range-integer {}, {}, {}", start, end, inclusive)?;
+            }
             Self::EqStaticString { slot } => {
                 write!(fmt, "eq-static-string {}", slot)?;
             }
@@ -57,11 +57,14 @@ mod access;
 mod any_obj;
 mod args;
 mod awaited;
-pub mod budget;
 mod bytes;
 mod call;
 mod compile_meta;
+mod const_value;
+pub mod coverage;
 pub mod debug;
+mod environment;
+pub mod format;
 mod from_value;
 mod function;
 mod future;
@@ -72,6 +75,7 @@ mod hash;
 mod inst;
 mod item;
 mod label;
+pub mod memory;
 pub mod module;
 pub mod modules;
 mod named;
@@ -80,6 +84,9 @@ mod object;
 mod panic;
 mod protocol;
 mod raw_str;
+pub mod rtti;
+pub mod sandbox;
+pub mod scheduler;
 mod select;
 mod serde;
 mod shared;
@@ -116,6 +123,8 @@ pub use self::compile_meta::{
     CompileMeta, CompileMetaCapture, CompileMetaKind, CompileMetaStruct, CompileMetaTuple,
     CompileSource,
 };
+pub use self::const_value::ConstValue;
+pub use self::format::{Alignment, FormatSpec, FormatSpecError, FormatType};
 pub use self::from_value::{FromValue, UnsafeFromValue};
 pub use self::generator::Generator;
 pub use self::generator_state::GeneratorState;
@@ -146,21 +155,28 @@ pub use crate::any::Any;
 pub use crate::awaited::Awaited;
 pub use crate::bytes::Bytes;
 pub use crate::call::Call;
-pub use crate::context::{Context, ContextError};
+pub use crate::context::{Context, ContextError, ContextSignature};
+pub use crate::coverage::Coverage;
 pub use crate::debug::{DebugInfo, DebugInst};
 pub use crate::function::Function;
 pub use crate::future::Future;
 pub use crate::hash::{Hash, IntoTypeHash};
 pub use crate::inst::{Inst, InstOp, InstTarget, InstValue, PanicReason, TypeCheck};
 pub use crate::item::{Component, IntoComponent, Item};
+pub use crate::memory::Memory;
 pub use crate::names::Names;
 pub use crate::object::Object;
 pub use crate::panic::Panic;
 pub use crate::protocol::{
     Protocol, ADD, ADD_ASSIGN, BIT_AND, BIT_AND_ASSIGN, BIT_OR, BIT_OR_ASSIGN, BIT_XOR,
-    BIT_XOR_ASSIGN, DIV, DIV_ASSIGN, INDEX_GET, INDEX_SET, INTO_FUTURE, INTO_ITER, MUL, MUL_ASSIGN,
-    NEXT, REM, REM_ASSIGN, SHL, SHL_ASSIGN, SHR, SHR_ASSIGN, STRING_DISPLAY, SUB, SUB_ASSIGN,
+    BIT_XOR_ASSIGN, CMP, DEBUG_FMT, DIV, DIV_ASSIGN, EQ, INDEX_GET, INDEX_SET, INTO_FUTURE,
+    INTO_ITER, MUL, MUL_ASSIGN, NEXT, REM, REM_ASSIGN, SHL, SHL_ASSIGN, SHR, SHR_ASSIGN,
+    STRING_DISPLAY, SUB, SUB_ASSIGN,
 };
+pub use crate::rtti::{Rtti, VariantRtti};
+pub use crate::sandbox::Sandbox;
+pub use crate::scheduler::Scheduler;
+pub use crate::serde::{EnumRepr, SerializeWith};
 pub use crate::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
 pub use crate::stack::{Stack, StackError};
 pub use crate::type_of::TypeOf;
@@ -0,0 +1,168 @@
+//! Memory accounting for Runestick.
+//!
+//! This module contains methods which allows for limiting the amount of memory
+//! the virtual machine is allowed to allocate.
+//!
+//! By default memory is not accounted for, but can be enabled by installing a
+//! [Memory] tracker into a virtual machine using
+//! [Vm::set_memory][crate::Vm::set_memory]. While the virtual machine is
+//! running, every value allocated through [Shared::new][crate::Shared::new]
+//! is charged to the tracker. Strings, byte arrays, vectors, tuples, and
+//! objects are also charged for the memory they own on the heap when they're
+//! converted into values, and as they grow through functions like `push`,
+//! `push_str`, and `extend`. The charged memory is recorded in the allocation
+//! itself, and is given back to the tracker once it's freed.
+//!
+//! Exceeding the limit causes the virtual machine to error with
+//! [VmErrorKind::MemoryLimitExceeded].
+
+use crate::environment;
+use crate::{Bytes, Object, Tuple, Value, VmError, VmErrorKind};
+use std::fmt;
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A memory tracker with a limit.
+pub struct Memory {
+    /// The maximum number of bytes which can be in use.
+    limit: usize,
+    /// The number of bytes currently in use.
+    usage: AtomicUsize,
+    /// The highest number of bytes which have been in use at any one time.
+    peak: AtomicUsize,
+}
+
+impl Memory {
+    /// Construct a new memory tracker with the given limit in bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            usage: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get the limit of the tracker in bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Get the number of bytes currently in use.
+    pub fn usage(&self) -> usize {
+        self.usage.load(Ordering::Relaxed)
+    }
+
+    /// Get the highest number of bytes which have been in use at any one time.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Test if the limit of the tracker has been exceeded.
+    pub fn is_exceeded(&self) -> bool {
+        self.usage() > self.limit
+    }
+
+    /// Check that the limit of the tracker hasn't been exceeded.
+    pub fn check(&self) -> Result<(), VmError> {
+        if self.is_exceeded() {
+            return Err(self.exceeded());
+        }
+
+        Ok(())
+    }
+
+    /// Charge the given number of bytes to the tracker.
+    pub(crate) fn allocate(&self, bytes: usize) {
+        let usage = self.usage.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(usage, Ordering::Relaxed);
+    }
+
+    /// Give back the given number of bytes to the tracker.
+    pub(crate) fn release(&self, bytes: usize) {
+        self.usage.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    fn exceeded(&self) -> VmError {
+        VmError::from(VmErrorKind::MemoryLimitExceeded {
+            usage: self.usage(),
+            limit: self.limit,
+        })
+    }
+}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("limit", &self.limit)
+            .field("usage", &self.usage())
+            .field("peak", &self.peak())
+            .finish()
+    }
+}
+
+/// A value which owns memory on the heap, which is charged to the memory
+/// tracker when the value is allocated through
+/// [Shared::with_heap][crate::Shared::with_heap] and as it changes through
+/// [Shared::heap_mut][crate::Shared::heap_mut].
+pub(crate) trait HeapSize {
+    /// The number of bytes owned by the value on the heap.
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Bytes {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Vec<Value> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<Value>()
+    }
+}
+
+impl HeapSize for Tuple {
+    fn heap_size(&self) -> usize {
+        self.len() * mem::size_of::<Value>()
+    }
+}
+
+impl HeapSize for Object {
+    fn heap_size(&self) -> usize {
+        self.iter()
+            .map(|(key, _)| key.capacity() + mem::size_of::<(String, Value)>())
+            .sum()
+    }
+}
+
+/// Get the memory tracker which is currently in use, if any.
+pub fn current() -> Option<Arc<Memory>> {
+    environment::current(|env| env.memory.clone())
+}
+
+/// Check that the given number of bytes can be allocated without exceeding the
+/// limit of the current memory tracker.
+///
+/// This doesn't charge anything to the tracker, and is intended to be used
+/// before performing large allocations up front.
+pub fn reserve(bytes: usize) -> Result<(), VmError> {
+    environment::current(|env| {
+        let memory = match &env.memory {
+            Some(memory) => memory,
+            None => return Ok(()),
+        };
+
+        if memory.usage().saturating_add(bytes) > memory.limit {
+            return Err(memory.exceeded());
+        }
+
+        Ok(())
+    })
+}
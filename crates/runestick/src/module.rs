@@ -123,6 +123,7 @@ pub(crate) struct ModuleAssocKey {
 pub(crate) struct ModuleFn {
     pub(crate) handler: Arc<Handler>,
     pub(crate) args: Option<usize>,
+    pub(crate) params: Option<Box<[&'static str]>>,
 }
 
 pub(crate) struct ModuleMacro {
@@ -405,12 +406,61 @@ impl Module {
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                params: None,
             },
         );
 
         Ok(())
     }
 
+    /// Declare the names of the parameters of a function registered with
+    /// [Module::function], [Module::async_function], or [Module::raw_fn], so
+    /// that it can be called with named arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// fn connect(host: String, port: i64, timeout: i64) -> String {
+    ///     format!("{}:{} ({}s)", host, port, timeout)
+    /// }
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = runestick::Module::default();
+    ///
+    /// module.function(&["connect"], connect)?;
+    /// module.params(&["connect"], &["host", "port", "timeout"])?;
+    ///
+    /// assert!(module.params(&["connect"], &["host"]).is_err());
+    /// assert!(module.params(&["missing"], &["host"]).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn params<N>(&mut self, name: N, params: &[&'static str]) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let name = Item::of(name);
+
+        let f = match self.functions.get_mut(&name) {
+            Some(f) => f,
+            None => return Err(ContextError::MissingFunction { name }),
+        };
+
+        if let Some(args) = f.args {
+            if args != params.len() {
+                return Err(ContextError::ParameterCountMismatch {
+                    name,
+                    expected: args,
+                    actual: params.len(),
+                });
+            }
+        }
+
+        f.params = Some(params.into());
+        Ok(())
+    }
+
     /// Register a native macro handler.
     pub fn macro_<N, M, A, B, O>(&mut self, name: N, f: M) -> Result<(), ContextError>
     where
@@ -488,6 +538,7 @@ impl Module {
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                params: None,
             },
         );
 
@@ -496,9 +547,35 @@ impl Module {
 
     /// Register a raw function which interacts directly with the virtual
     /// machine.
+    ///
+    /// Raw functions are passed the number of arguments on the stack, so they
+    /// can be called with any number of arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Stack, VmError};
+    ///
+    /// fn sum(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    ///     let mut total = 0;
+    ///
+    ///     for value in stack.pop_sequence(args)? {
+    ///         total += value.into_integer()?;
+    ///     }
+    ///
+    ///     stack.push(total);
+    ///     Ok(())
+    /// }
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = runestick::Module::default();
+    /// module.raw_fn(&["sum"], sum)?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn raw_fn<F, N>(&mut self, name: N, f: F) -> Result<(), ContextError>
     where
-        F: 'static + Copy + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
+        F: 'static + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
         N: IntoIterator,
         N::Item: IntoComponent,
     {
@@ -511,8 +588,9 @@ impl Module {
         self.functions.insert(
             name,
             ModuleFn {
-                handler: Arc::new(move |stack, args| f(stack, args)),
+                handler: Arc::new(f),
                 args: None,
+                params: None,
             },
         );
 
@@ -1,6 +1,7 @@
 //! `std::bytes` module.
 
-use crate::{Bytes, ContextError, Module};
+use crate::memory;
+use crate::{Bytes, ContextError, Module, Shared, VmError};
 
 /// Construct the `std::bytes` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -8,21 +9,54 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.ty::<Bytes>()?;
     module.function(&["Bytes", "new"], Bytes::new)?;
-    module.function(&["Bytes", "with_capacity"], Bytes::with_capacity)?;
+    module.function(&["Bytes", "with_capacity"], bytes_with_capacity)?;
     module.function(&["Bytes", "from_vec"], Bytes::from_vec)?;
 
     module.inst_fn("into_vec", Bytes::into_vec)?;
-    module.inst_fn("extend", Bytes::extend)?;
-    module.inst_fn("extend_str", Bytes::extend_str)?;
+    module.inst_fn("extend", bytes_extend)?;
+    module.inst_fn("extend_str", bytes_extend_str)?;
     module.inst_fn("pop", Bytes::pop)?;
     module.inst_fn("last", Bytes::last)?;
 
     module.inst_fn("len", Bytes::len)?;
     module.inst_fn("capacity", Bytes::capacity)?;
     module.inst_fn("clear", Bytes::clear)?;
-    module.inst_fn("reserve", Bytes::reserve)?;
-    module.inst_fn("reserve_exact", Bytes::reserve_exact)?;
+    module.inst_fn("reserve", bytes_reserve)?;
+    module.inst_fn("reserve_exact", bytes_reserve_exact)?;
     module.inst_fn("clone", Bytes::clone)?;
-    module.inst_fn("shrink_to_fit", Bytes::shrink_to_fit)?;
+    module.inst_fn("shrink_to_fit", bytes_shrink_to_fit)?;
     Ok(module)
 }
+
+/// with_capacity shim for bytes, which checks the memory limit up front.
+fn bytes_with_capacity(capacity: usize) -> Result<Bytes, VmError> {
+    memory::reserve(capacity)?;
+    Ok(Bytes::with_capacity(capacity))
+}
+
+/// extend shim for bytes, accounting for any growth.
+fn bytes_extend(bytes: Shared<Bytes>, other: &Bytes) -> Result<(), VmError> {
+    bytes.heap_mut(|bytes| bytes.extend(other))
+}
+
+/// extend_str shim for bytes, accounting for any growth.
+fn bytes_extend_str(bytes: Shared<Bytes>, s: &str) -> Result<(), VmError> {
+    bytes.heap_mut(|bytes| bytes.extend_str(s))
+}
+
+/// reserve shim for bytes, accounting for any growth.
+fn bytes_reserve(bytes: Shared<Bytes>, additional: usize) -> Result<(), VmError> {
+    memory::reserve(additional)?;
+    bytes.heap_mut(|bytes| bytes.reserve(additional))
+}
+
+/// reserve_exact shim for bytes, accounting for any growth.
+fn bytes_reserve_exact(bytes: Shared<Bytes>, additional: usize) -> Result<(), VmError> {
+    memory::reserve(additional)?;
+    bytes.heap_mut(|bytes| bytes.reserve_exact(additional))
+}
+
+/// shrink_to_fit shim for bytes, giving back the memory which is freed.
+fn bytes_shrink_to_fit(bytes: Shared<Bytes>) -> Result<(), VmError> {
+    bytes.heap_mut(Bytes::shrink_to_fit)
+}
@@ -1,6 +1,6 @@
 //! The `std::object` module.
 
-use crate::{ContextError, Module, Object, Value};
+use crate::{ContextError, Module, Object, Shared, Value, VmError};
 use std::iter::Rev;
 
 /// Construct the `std::object` module.
@@ -12,8 +12,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Rev<Iter>>()?;
 
     module.inst_fn("len", Object::len)?;
-    module.inst_fn("insert", Object::insert)?;
-    module.inst_fn("clear", Object::clear)?;
+    module.inst_fn("insert", object_insert)?;
+    module.inst_fn("clear", object_clear)?;
     module.inst_fn("contains_key", contains_key)?;
     module.inst_fn("get", get)?;
 
@@ -50,6 +50,20 @@ impl DoubleEndedIterator for Iter {
     }
 }
 
+/// Insert a value into the object, accounting for any growth.
+fn object_insert(
+    object: Shared<Object>,
+    key: String,
+    value: Value,
+) -> Result<Option<Value>, VmError> {
+    object.heap_grow(|object| object.insert_heap(key, value))
+}
+
+/// Clear the object, releasing the memory of its entries.
+fn object_clear(object: Shared<Object>) -> Result<(), VmError> {
+    object.heap_mut(Object::clear)
+}
+
 fn object_iter(object: &Object) -> Iter {
     Iter {
         iter: object
@@ -1,6 +1,7 @@
 //! The `std::string` module.
 
-use crate::{Bytes, ContextError, Module};
+use crate::memory;
+use crate::{Bytes, ContextError, Module, Shared, VmError};
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,27 +11,60 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function(&["String", "from_str"], <String as From<&str>>::from)?;
     module.function(&["String", "new"], String::new)?;
-    module.function(&["String", "with_capacity"], String::with_capacity)?;
+    module.function(&["String", "with_capacity"], string_with_capacity)?;
 
     module.inst_fn("len", String::len)?;
     module.inst_fn("capacity", String::capacity)?;
     module.inst_fn("clear", String::clear)?;
-    module.inst_fn("push", String::push)?;
-    module.inst_fn("push_str", String::push_str)?;
-    module.inst_fn("reserve", String::reserve)?;
-    module.inst_fn("reserve_exact", String::reserve_exact)?;
+    module.inst_fn("push", string_push)?;
+    module.inst_fn("push_str", string_push_str)?;
+    module.inst_fn("reserve", string_reserve)?;
+    module.inst_fn("reserve_exact", string_reserve_exact)?;
     module.inst_fn("into_bytes", into_bytes)?;
     module.inst_fn("clone", String::clone)?;
-    module.inst_fn("shrink_to_fit", String::shrink_to_fit)?;
+    module.inst_fn("shrink_to_fit", string_shrink_to_fit)?;
     module.inst_fn("char_at", char_at)?;
     module.inst_fn(crate::ADD, add)?;
-    module.inst_fn(crate::ADD_ASSIGN, String::push_str)?;
+    module.inst_fn(crate::ADD_ASSIGN, string_push_str)?;
     Ok(module)
 }
 
 #[derive(Debug, Clone, Copy)]
 struct NotCharBoundary(());
 
+/// with_capacity shim for strings, which checks the memory limit up front.
+fn string_with_capacity(capacity: usize) -> Result<String, VmError> {
+    memory::reserve(capacity)?;
+    Ok(String::with_capacity(capacity))
+}
+
+/// push shim for strings, accounting for any growth.
+fn string_push(s: Shared<String>, c: char) -> Result<(), VmError> {
+    s.heap_mut(|s| s.push(c))
+}
+
+/// push_str shim for strings, accounting for any growth.
+fn string_push_str(s: Shared<String>, other: &str) -> Result<(), VmError> {
+    s.heap_mut(|s| s.push_str(other))
+}
+
+/// reserve shim for strings, accounting for any growth.
+fn string_reserve(s: Shared<String>, additional: usize) -> Result<(), VmError> {
+    memory::reserve(additional)?;
+    s.heap_mut(|s| s.reserve(additional))
+}
+
+/// reserve_exact shim for strings, accounting for any growth.
+fn string_reserve_exact(s: Shared<String>, additional: usize) -> Result<(), VmError> {
+    memory::reserve(additional)?;
+    s.heap_mut(|s| s.reserve_exact(additional))
+}
+
+/// shrink_to_fit shim for strings, giving back the memory which is freed.
+fn string_shrink_to_fit(s: Shared<String>) -> Result<(), VmError> {
+    s.heap_mut(String::shrink_to_fit)
+}
+
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
@@ -44,12 +78,13 @@ fn char_at(s: &str, index: usize) -> Result<Option<char>, NotCharBoundary> {
     Ok(s[index..].chars().next())
 }
 
-/// The add operation for strings.
-fn add(a: &str, b: &str) -> String {
+/// The add operation for strings, which checks the memory limit up front.
+fn add(a: &str, b: &str) -> Result<String, VmError> {
+    memory::reserve(a.len() + b.len())?;
     let mut string = String::with_capacity(a.len() + b.len());
     string.push_str(a);
     string.push_str(b);
-    string
+    Ok(string)
 }
 
 crate::__internal_impl_any!(NotCharBoundary);
@@ -1,6 +1,6 @@
 //! The `std::vec` module.
 
-use crate::{ContextError, Module, Value};
+use crate::{ContextError, Module, Shared, Value, VmError};
 use std::iter::Rev;
 
 /// Construct the `std::vec` module.
@@ -14,7 +14,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["Vec", "new"], Vec::<Value>::new)?;
     module.inst_fn("iter", vec_iter)?;
     module.inst_fn("len", Vec::<Value>::len)?;
-    module.inst_fn("push", Vec::<Value>::push)?;
+    module.inst_fn("push", vec_push)?;
     module.inst_fn("clear", Vec::<Value>::clear)?;
     module.inst_fn("pop", Vec::<Value>::pop)?;
 
@@ -50,6 +50,11 @@ impl DoubleEndedIterator for Iter {
     }
 }
 
+/// Push a value onto the vector, accounting for any growth.
+fn vec_push(vec: Shared<Vec<Value>>, value: Value) -> Result<(), VmError> {
+    vec.heap_mut(|vec| vec.push(value))
+}
+
 fn vec_iter(vec: &[Value]) -> Iter {
     Iter {
         iter: vec.to_vec().into_iter(),
@@ -1,5 +1,7 @@
 use crate::collections::HashMap;
-use crate::{FromValue, Mut, Named, RawMut, RawRef, RawStr, Ref, UnsafeFromValue, Value, VmError};
+use crate::{
+    FromValue, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue, Value, VmError,
+};
 use std::borrow;
 use std::cmp;
 use std::fmt;
@@ -100,6 +102,18 @@ impl Object {
         self.inner.insert(k, v)
     }
 
+    /// Inserts a key-value pair into the object, also returning the number of
+    /// bytes the object grew by on the heap as counted by its
+    /// [HeapSize][crate::memory::HeapSize] implementation.
+    pub(crate) fn insert_heap(&mut self, k: String, v: Value) -> (Option<Value>, usize) {
+        let grown = k.capacity() + std::mem::size_of::<(String, Value)>();
+
+        match self.inner.insert(k, v) {
+            Some(old) => (Some(old), 0),
+            None => (None, grown),
+        }
+    }
+
     /// Clears the object, removing all key-value pairs. Keeps the allocated
     /// memory for reuse.
     pub fn clear(&mut self) {
@@ -180,6 +194,12 @@ impl FromValue for Object {
     }
 }
 
+impl FromValue for Shared<Object> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_object()
+    }
+}
+
 impl FromValue for Mut<Object> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         let object = value.into_object()?;
@@ -182,6 +182,13 @@ pub const STRING_DISPLAY: Protocol = Protocol {
     hash: Hash::new(0x811b62957ea9d9f9),
 };
 
+/// Protocol function used by the debug representation of values, as in
+/// template strings like `{value:?}`.
+pub const DEBUG_FMT: Protocol = Protocol {
+    name: "debug_fmt",
+    hash: Hash::new(0x4064e3867aaa0717),
+};
+
 /// Function used to convert an argument into an iterator.
 pub const INTO_ITER: Protocol = Protocol {
     name: "into_iter",
@@ -199,3 +206,19 @@ pub const INTO_FUTURE: Protocol = Protocol {
     name: "into_future",
     hash: Hash::new(0x596e6428deabfda2),
 };
+
+/// The function to implement for equality comparisons of native types, used by
+/// `==` and `!=`. It must return a boolean.
+pub const EQ: Protocol = Protocol {
+    name: "eq",
+    hash: Hash::new(0x7fcebcf00c49d711),
+};
+
+/// The function to implement for ordering comparisons of native types, used by
+/// `<`, `<=`, `>`, and `>=`. It must return an integer which is negative, zero,
+/// or positive if the value is less than, equal to, or greater than the other
+/// value.
+pub const CMP: Protocol = Protocol {
+    name: "cmp",
+    hash: Hash::new(0xfce882fef68b9070),
+};
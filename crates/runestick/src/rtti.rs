@@ -0,0 +1,98 @@
+//! Runtime type information for types declared in Rune.
+//!
+//! Instances of types declared in a [Unit][crate::Unit] carry their runtime type
+//! information, which allows native functions to for example access the name
+//! of a struct or the variant of an enum when serializing them.
+//!
+//! While the virtual machine is running, native functions can also look up the
+//! runtime type information of types declared in the unit which is being
+//! executed through [lookup].
+
+use crate::environment;
+use crate::{Hash, Item};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Runtime information on a struct declared in a unit.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rtti {
+    /// The type hash of the struct.
+    pub hash: Hash,
+    /// The item of the struct.
+    pub item: Item,
+    /// The named fields of the struct in the order they're declared, or `None`
+    /// if it's a tuple struct.
+    pub fields: Option<Box<[String]>>,
+    /// The named fields of the struct sorted by name, used to look them up.
+    sorted_fields: Option<Box<[String]>>,
+}
+
+impl Rtti {
+    /// Construct runtime type information for a struct with the given named
+    /// fields in the order they're declared.
+    pub fn new(hash: Hash, item: Item, fields: Option<Box<[String]>>) -> Self {
+        let sorted_fields = fields.as_ref().map(|fields| {
+            let mut fields = fields.to_vec();
+            fields.sort();
+            fields.into_boxed_slice()
+        });
+
+        Self {
+            hash,
+            item,
+            fields,
+            sorted_fields,
+        }
+    }
+
+    /// Test if the struct has a named field with the given name.
+    pub fn has_field(&self, name: &str) -> bool {
+        match &self.sorted_fields {
+            Some(fields) => fields
+                .binary_search_by(|field| field.as_str().cmp(name))
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    /// Get the name of the struct.
+    pub fn name(&self) -> String {
+        name(&self.item)
+    }
+}
+
+/// Runtime information on a variant of an enum declared in a unit.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VariantRtti {
+    /// The type hash of the enum.
+    pub enum_hash: Hash,
+    /// The type hash of the variant.
+    pub hash: Hash,
+    /// The item of the variant.
+    pub item: Item,
+    /// The named fields of the variant in the order they're declared, or
+    /// `None` if it's a tuple variant.
+    pub fields: Option<Box<[String]>>,
+}
+
+impl VariantRtti {
+    /// Get the name of the variant.
+    pub fn name(&self) -> String {
+        name(&self.item)
+    }
+}
+
+/// Look up the runtime type information of a struct declared in the unit which
+/// is currently being executed.
+///
+/// This always returns `None` if called outside of a running virtual machine.
+pub fn lookup(hash: Hash) -> Option<Arc<Rtti>> {
+    environment::current(|env| env.unit.as_ref()?.lookup_rtti(hash).cloned())
+}
+
+fn name(item: &Item) -> String {
+    match item.last() {
+        Some(last) => last.to_string(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,234 @@
+//! Sandboxing of native modules.
+//!
+//! A [Sandbox] is a policy describing which capabilities native functions are
+//! permitted to use, like which parts of the filesystem can be accessed, which
+//! executables can be launched, and which hosts can be connected to.
+//!
+//! A sandbox can be set for all virtual machines using a context through
+//! [Context::set_sandbox][crate::Context::set_sandbox], or for a single virtual
+//! machine through [Vm::set_sandbox][crate::Vm::set_sandbox]. While the virtual
+//! machine is running, native functions check the current policy through
+//! [check] before using a capability. If no sandbox has been set, everything
+//! is permitted.
+//!
+//! Denied capabilities cause the virtual machine to error with
+//! [VmErrorKind::PermissionDenied].
+
+use crate::collections::HashSet;
+use crate::environment;
+use crate::{VmError, VmErrorKind};
+use std::env;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// A capability which can be requested by a native function.
+#[derive(Debug, Clone, Copy)]
+pub enum Capability<'a> {
+    /// Access to the given filesystem path.
+    Path(&'a Path),
+    /// Launching the given executable.
+    Executable(&'a str),
+    /// Connecting to the given host.
+    Host(&'a str),
+}
+
+/// A capability which was denied.
+#[derive(Debug, Clone)]
+pub enum Denied {
+    /// Access to the given filesystem path was denied.
+    Path(PathBuf),
+    /// Launching the given executable was denied.
+    Executable(String),
+    /// Connecting to the given host was denied.
+    Host(String),
+    /// Changing the given environment variable of a launched executable was
+    /// denied, since it affects which executable is launched.
+    Environment(String),
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "access to path `{}`", path.display()),
+            Self::Executable(program) => write!(f, "executing `{}`", program),
+            Self::Host(host) => write!(f, "connecting to host `{}`", host),
+            Self::Environment(key) => write!(f, "changing environment variable `{}`", key),
+        }
+    }
+}
+
+/// A policy for which capabilities native functions are permitted to use.
+///
+/// A new sandbox denies everything, and capabilities are granted by adding
+/// them to the corresponding allow-list.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::sandbox::{Capability, Sandbox};
+/// use std::path::Path;
+///
+/// let mut sandbox = Sandbox::new();
+/// sandbox.allow_path("/srv/scripts");
+/// sandbox.allow_executable("git");
+/// sandbox.allow_host("*.example.com");
+///
+/// assert!(sandbox.permits(Capability::Path(Path::new("/srv/scripts/data.txt"))));
+/// assert!(!sandbox.permits(Capability::Path(Path::new("/srv/scripts/../secret"))));
+/// assert!(sandbox.permits(Capability::Executable("git")));
+/// assert!(!sandbox.permits(Capability::Executable("/usr/bin/git")));
+/// assert!(sandbox.permits(Capability::Host("api.example.com")));
+/// assert!(!sandbox.permits(Capability::Host("example.org")));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Sandbox {
+    /// Filesystem roots which can be accessed, including everything below
+    /// them.
+    paths: Vec<PathBuf>,
+    /// Executables which can be launched.
+    executables: HashSet<String>,
+    /// Hosts which can be connected to.
+    hosts: HashSet<String>,
+}
+
+impl Sandbox {
+    /// Construct a new sandbox which denies everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Permit access to the given path and everything below it.
+    ///
+    /// Paths are resolved before they are checked, so a path which escapes the
+    /// root through `..` components or symbolic links is not permitted.
+    pub fn allow_path<P>(&mut self, root: P) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        self.paths.push(resolve(root.as_ref()));
+        self
+    }
+
+    /// Permit launching the given executable.
+    ///
+    /// The executable must be launched exactly as specified, so permitting
+    /// `git` doesn't permit launching `/usr/bin/git`.
+    pub fn allow_executable<S>(&mut self, program: S) -> &mut Self
+    where
+        S: AsRef<str>,
+    {
+        self.executables.insert(program.as_ref().to_owned());
+        self
+    }
+
+    /// Permit connecting to the given host.
+    ///
+    /// A host starting with `*.` permits connecting to any subdomain of it.
+    pub fn allow_host<S>(&mut self, host: S) -> &mut Self
+    where
+        S: AsRef<str>,
+    {
+        self.hosts.insert(host.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Test if the sandbox permits the given capability.
+    pub fn permits(&self, capability: Capability<'_>) -> bool {
+        match capability {
+            Capability::Path(path) => {
+                let path = resolve(path);
+                self.paths.iter().any(|root| path.starts_with(root))
+            }
+            Capability::Executable(program) => self.executables.contains(program),
+            Capability::Host(host) => {
+                let host = host.to_ascii_lowercase();
+
+                if self.hosts.contains(&host) {
+                    return true;
+                }
+
+                self.hosts
+                    .iter()
+                    .any(|allowed| match allowed.strip_prefix("*.") {
+                        Some(domain) => host
+                            .strip_suffix(domain)
+                            .map(|rest| rest.ends_with('.'))
+                            .unwrap_or_default(),
+                        None => false,
+                    })
+            }
+        }
+    }
+
+    /// Check that the sandbox permits the given capability.
+    pub fn check(&self, capability: Capability<'_>) -> Result<(), VmError> {
+        if self.permits(capability) {
+            return Ok(());
+        }
+
+        let denied = match capability {
+            Capability::Path(path) => Denied::Path(path.to_owned()),
+            Capability::Executable(program) => Denied::Executable(program.to_owned()),
+            Capability::Host(host) => Denied::Host(host.to_owned()),
+        };
+
+        Err(VmError::from(VmErrorKind::PermissionDenied { denied }))
+    }
+}
+
+/// Get the sandbox which is currently in use, if any.
+pub fn current() -> Option<Arc<Sandbox>> {
+    environment::current(|env| env.sandbox.clone())
+}
+
+/// Check that the current sandbox permits the given capability.
+///
+/// This is intended to be called by native functions before they use a
+/// capability, and always succeeds if no sandbox is in use.
+pub fn check(capability: Capability<'_>) -> Result<(), VmError> {
+    environment::current(|env| match &env.sandbox {
+        Some(sandbox) => sandbox.check(capability),
+        None => Ok(()),
+    })
+}
+
+/// Resolve the given path into an absolute path without any `.` or `..`
+/// components, following symbolic links for the part of the path which
+/// exists.
+fn resolve(path: &Path) -> PathBuf {
+    let path = match env::current_dir() {
+        Ok(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_owned(),
+    };
+
+    let components = path.components().collect::<Vec<_>>();
+
+    // NB: the longest prefix which exists is canonicalized as a whole, so that
+    // symbolic links are followed before any `..` components after them are
+    // applied. Only the part which doesn't exist is normalized lexically.
+    for n in (1..=components.len()).rev() {
+        let existing = components[..n].iter().collect::<PathBuf>();
+
+        if let Ok(canonical) = existing.canonicalize() {
+            return normalize(canonical, &components[n..]);
+        }
+    }
+
+    normalize(PathBuf::new(), &components)
+}
+
+/// Lexically append the given components to a path.
+fn normalize(mut path: PathBuf, components: &[Component<'_>]) -> PathBuf {
+    for c in components {
+        match c {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                path.pop();
+            }
+            c => path.push(c),
+        }
+    }
+
+    path
+}
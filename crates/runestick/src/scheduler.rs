@@ -0,0 +1,364 @@
+//! Cooperative scheduling of many virtual machine executions.
+//!
+//! The [Scheduler] interleaves any number of [VmExecution]s on a single task
+//! of the async runtime it's running on. Each execution is run for a time
+//! slice, measured in instructions, before yielding to the runtime and giving
+//! the next execution a turn. An execution which awaits a future that isn't
+//! ready doesn't block any of the others.
+//!
+//! The time slice of a task is shared by everything it runs, including async
+//! functions which it awaits, so a busy loop in an awaited function still
+//! yields to the other tasks. Time slices don't bound the total amount of work
+//! a task does, so [fuel][crate::Vm::set_fuel] should still be used to bound
+//! untrusted code.
+
+use crate::environment::{self, Environment};
+use crate::{Value, VmError, VmErrorKind, VmExecution};
+use futures_util::stream::{FuturesUnordered, StreamExt as _};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// The default number of instructions a task with [Priority::Normal] is
+/// allowed to execute per time slice.
+pub const DEFAULT_QUANTUM: usize = 1000;
+
+/// The dyn future driving a single task.
+type DynFuture = dyn Future<Output = Result<Value, VmError>>;
+
+/// The priority of a task.
+///
+/// Tasks with a higher priority are given a larger time slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// A quarter of the quantum of the scheduler.
+    Low,
+    /// The quantum of the scheduler.
+    Normal,
+    /// Four times the quantum of the scheduler.
+    High,
+}
+
+impl Priority {
+    /// Calculate the time slice for the given quantum.
+    fn slice(self, quantum: usize) -> usize {
+        match self {
+            Self::Low => usize::max(quantum / 4, 1),
+            Self::Normal => quantum,
+            Self::High => quantum.saturating_mul(4),
+        }
+    }
+}
+
+/// The identifier of a task in a scheduler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(usize);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task#{}", self.0)
+    }
+}
+
+/// Statistics collected for a single task.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TaskStats {
+    /// The number of instructions executed.
+    pub instructions: usize,
+    /// The number of time slices the task has been given.
+    pub slices: usize,
+    /// The number of times the task has been polled.
+    pub polls: usize,
+    /// The time spent polling the task.
+    pub busy: Duration,
+}
+
+/// Shared state of a task.
+#[derive(Default)]
+struct TaskState {
+    cancelled: bool,
+    waker: Option<Waker>,
+    stats: TaskStats,
+    result: Option<Result<Value, VmError>>,
+    finished: bool,
+}
+
+/// A handle to a task spawned in a [Scheduler].
+///
+/// The handle can be used to inspect and cancel the task, and to take the
+/// result once it's finished.
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: TaskId,
+    priority: Priority,
+    state: Rc<RefCell<TaskState>>,
+}
+
+impl TaskHandle {
+    /// Get the identifier of the task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Get the priority of the task.
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Cancel the task.
+    ///
+    /// The task will be stopped the next time the scheduler gets to it, even
+    /// if it's waiting on a future, and finish with
+    /// [VmErrorKind::Cancelled]. This does nothing if the task has already
+    /// finished.
+    pub fn cancel(&self) {
+        let mut state = self.state.borrow_mut();
+
+        if state.finished {
+            return;
+        }
+
+        state.cancelled = true;
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Test if the task has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.borrow().cancelled
+    }
+
+    /// Test if the task has finished, either by completing, erroring, or being
+    /// cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.state.borrow().finished
+    }
+
+    /// Get the statistics collected for the task so far.
+    pub fn stats(&self) -> TaskStats {
+        self.state.borrow().stats
+    }
+
+    /// Take the result of the task.
+    ///
+    /// Returns `None` if the task hasn't finished, or if the result has
+    /// already been taken.
+    pub fn take_result(&self) -> Option<Result<Value, VmError>> {
+        self.state.borrow_mut().result.take()
+    }
+}
+
+impl fmt::Debug for TaskHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.borrow();
+
+        f.debug_struct("TaskHandle")
+            .field("id", &self.id)
+            .field("priority", &self.priority)
+            .field("cancelled", &state.cancelled)
+            .field("finished", &state.finished)
+            .field("stats", &state.stats)
+            .finish()
+    }
+}
+
+/// A scheduler which runs many virtual machine executions concurrently.
+///
+/// Tasks are spawned with [spawn][Self::spawn], after which they are driven by
+/// awaiting [run][Self::run] or [next][Self::next]. Tasks take turns running
+/// for a time slice, with tasks of a higher [Priority] being given more
+/// instructions per slice.
+pub struct Scheduler {
+    /// The number of instructions a task with normal priority executes per
+    /// time slice.
+    quantum: usize,
+    /// The identifier of the next task to be spawned.
+    next_id: usize,
+    /// Tasks which haven't finished yet.
+    tasks: FuturesUnordered<Task>,
+}
+
+impl Scheduler {
+    /// Construct a new scheduler using the [DEFAULT_QUANTUM].
+    pub fn new() -> Self {
+        Self::with_quantum(DEFAULT_QUANTUM)
+    }
+
+    /// Construct a new scheduler, where tasks with normal priority execute
+    /// `quantum` instructions per time slice.
+    pub fn with_quantum(quantum: usize) -> Self {
+        Self {
+            quantum: usize::max(quantum, 1),
+            next_id: 0,
+            tasks: FuturesUnordered::new(),
+        }
+    }
+
+    /// Get the quantum of the scheduler.
+    pub fn quantum(&self) -> usize {
+        self.quantum
+    }
+
+    /// Get the number of unfinished tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Test if there are no unfinished tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Spawn the given execution with [Priority::Normal].
+    pub fn spawn(&mut self, execution: VmExecution) -> TaskHandle {
+        self.spawn_with_priority(execution, Priority::Normal)
+    }
+
+    /// Spawn the given execution with the given priority.
+    pub fn spawn_with_priority(
+        &mut self,
+        execution: VmExecution,
+        priority: Priority,
+    ) -> TaskHandle {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        let state = Rc::new(RefCell::new(TaskState::default()));
+        let remaining = Rc::new(Cell::new(0));
+
+        let environment = Environment {
+            slice: Some(remaining.clone()),
+            ..Environment::default()
+        };
+
+        self.tasks.push(Task {
+            state: state.clone(),
+            slice: priority.slice(self.quantum),
+            remaining,
+            future: Box::pin(environment::with(environment, run_task(execution))),
+        });
+
+        TaskHandle {
+            id,
+            priority,
+            state,
+        }
+    }
+
+    /// Run until the next task finishes, returning `false` if there are no
+    /// tasks left to run.
+    pub async fn next(&mut self) -> bool {
+        self.tasks.next().await.is_some()
+    }
+
+    /// Run all tasks until they have finished.
+    ///
+    /// Results are available through the [TaskHandle] returned when each task
+    /// was spawned.
+    pub async fn run(&mut self) {
+        while self.next().await {}
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("quantum", &self.quantum)
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+/// A single task in the scheduler.
+struct Task {
+    state: Rc<RefCell<TaskState>>,
+    /// The number of instructions in a time slice of the task.
+    slice: usize,
+    /// The instructions left in the current time slice, shared with every
+    /// execution run by the task through its environment.
+    remaining: Rc<Cell<usize>>,
+    future: Pin<Box<DynFuture>>,
+}
+
+impl Future for Task {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+
+        {
+            let mut state = this.state.borrow_mut();
+
+            if state.cancelled {
+                state.result = Some(Err(VmError::from(VmErrorKind::Cancelled)));
+                state.finished = true;
+                return Poll::Ready(());
+            }
+
+            state.waker = Some(cx.waker().clone());
+
+            // NB: the task yields once its time slice is used up, so it's
+            // given a new one when it's polled again.
+            if this.remaining.get() == 0 {
+                this.remaining.set(this.slice);
+                state.stats.slices += 1;
+            }
+        }
+
+        let before = this.remaining.get();
+        let start = Instant::now();
+        let poll = this.future.as_mut().poll(cx);
+
+        let mut state = this.state.borrow_mut();
+        state.stats.instructions += before - this.remaining.get();
+        state.stats.polls += 1;
+        state.stats.busy += start.elapsed();
+
+        match poll {
+            Poll::Ready(result) => {
+                state.waker = None;
+                state.result = Some(result);
+                state.finished = true;
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Drive the given execution to completion.
+///
+/// The execution yields whenever the time slice of the task is used up.
+async fn run_task(mut execution: VmExecution) -> Result<Value, VmError> {
+    execution.async_complete().await
+}
+
+/// Yield to the runtime once, waking up immediately.
+#[derive(Default)]
+pub(crate) struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{Bytes, Object, Shared, Value};
+use serde::ser::Serialize as _;
 use serde::{de, ser};
 use std::fmt;
 
@@ -12,16 +13,71 @@ impl<'de> de::Deserialize<'de> for Value {
     }
 }
 
+/// How the variants of enums are represented when serialized.
+///
+/// This follows the representations supported by `serde`, and `Result` values
+/// are treated as an enum with the variants `Ok` and `Err`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumRepr {
+    /// The variant is a single-entry map keyed by the name of the variant, like
+    /// `{"Circle": {"radius": 1.0}}`. Unit variants are just the name.
+    External,
+    /// The name of the variant is stored in the given field next to the
+    /// fields of the variant, like `{"type": "Circle", "radius": 1.0}`.
+    ///
+    /// Tuple variants with fields can't be represented like this.
+    Internal {
+        /// The field to store the name of the variant in.
+        tag: String,
+    },
+    /// The name and content of the variant are stored in two separate fields,
+    /// like `{"type": "Circle", "content": {"radius": 1.0}}`.
+    Adjacent {
+        /// The field to store the name of the variant in.
+        tag: String,
+        /// The field to store the content of the variant in.
+        content: String,
+    },
+    /// Only the content of the variant is stored, like `{"radius": 1.0}`.
+    Untagged,
+}
+
+/// A value which serializes with a specific [EnumRepr].
+///
+/// Constructed through [Value::serialize_with].
+pub struct SerializeWith<'a> {
+    value: &'a Value,
+    repr: &'a EnumRepr,
+}
+
+impl Value {
+    /// Serialize the value with the given representation of enums.
+    pub fn serialize_with<'a>(&'a self, repr: &'a EnumRepr) -> SerializeWith<'a> {
+        SerializeWith { value: self, repr }
+    }
+}
+
 /// Serialize implementation for value pointers.
 impl ser::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.serialize_with(&EnumRepr::External)
+            .serialize(serializer)
+    }
+}
+
+impl ser::Serialize for SerializeWith<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
         use serde::ser::SerializeMap as _;
-        use serde::ser::SerializeSeq as _;
 
-        match self {
+        let repr = self.repr;
+
+        match self.value {
             Value::Unit => serializer.serialize_unit(),
             Value::Bool(b) => serializer.serialize_bool(*b),
             Value::Char(c) => serializer.serialize_char(*c),
@@ -39,43 +95,66 @@ impl ser::Serialize for Value {
             }
             Value::Vec(vec) => {
                 let vec = vec.borrow_ref().map_err(ser::Error::custom)?;
-                let mut serializer = serializer.serialize_seq(Some(vec.len()))?;
-
-                for value in &*vec {
-                    serializer.serialize_element(value)?;
-                }
-
-                serializer.end()
+                serialize_seq(serializer, &vec, repr)
             }
             Value::Tuple(tuple) => {
                 let tuple = tuple.borrow_ref().map_err(ser::Error::custom)?;
-                let mut serializer = serializer.serialize_seq(Some(tuple.len()))?;
-
-                for value in tuple.iter() {
-                    serializer.serialize_element(value)?;
-                }
-
-                serializer.end()
+                serialize_seq(serializer, &tuple, repr)
             }
             Value::Object(object) => {
                 let object = object.borrow_ref().map_err(ser::Error::custom)?;
                 let mut serializer = serializer.serialize_map(Some(object.len()))?;
 
                 for (key, value) in &*object {
-                    serializer.serialize_entry(key, value)?;
+                    serializer.serialize_entry(key, &value.serialize_with(repr))?;
                 }
 
                 serializer.end()
             }
             Value::Option(option) => {
                 let option = option.borrow_ref().map_err(ser::Error::custom)?;
-                <Option<Value>>::serialize(&*option, serializer)
+
+                match &*option {
+                    Some(value) => serializer.serialize_some(&value.serialize_with(repr)),
+                    None => serializer.serialize_none(),
+                }
+            }
+            Value::TypedTuple(tuple) => {
+                let tuple = tuple.borrow_ref().map_err(ser::Error::custom)?;
+
+                match &*tuple.tuple {
+                    [] => serializer.serialize_unit(),
+                    [value] => value.serialize_with(repr).serialize(serializer),
+                    values => serialize_seq(serializer, values, repr),
+                }
+            }
+            Value::TypedObject(object) => {
+                let object = object.borrow_ref().map_err(ser::Error::custom)?;
+                let mut serializer = serializer.serialize_map(Some(object.object.len()))?;
+                serialize_fields(&mut serializer, &object.object, repr)?;
+                serializer.end()
+            }
+            Value::TupleVariant(tuple) => {
+                let tuple = tuple.borrow_ref().map_err(ser::Error::custom)?;
+                let content = Content::Tuple(&tuple.tuple);
+                serialize_variant(serializer, &tuple.rtti.name(), content, repr)
+            }
+            Value::VariantObject(object) => {
+                let object = object.borrow_ref().map_err(ser::Error::custom)?;
+                let content = Content::Object(&object.object);
+                serialize_variant(serializer, &object.rtti.name(), content, repr)
+            }
+            Value::Result(result) => {
+                let result = result.borrow_ref().map_err(ser::Error::custom)?;
+
+                let (name, value) = match &*result {
+                    Ok(value) => ("Ok", value),
+                    Err(value) => ("Err", value),
+                };
+
+                let content = Content::Tuple(std::slice::from_ref(value));
+                serialize_variant(serializer, name, content, repr)
             }
-            Value::TypedTuple(..) => Err(ser::Error::custom("cannot serialize tuple types")),
-            Value::TupleVariant(..) => Err(ser::Error::custom("cannot serialize variant tuples")),
-            Value::TypedObject(..) => Err(ser::Error::custom("cannot serialize object types")),
-            Value::VariantObject(..) => Err(ser::Error::custom("cannot serialize variant objects")),
-            Value::Result(..) => Err(ser::Error::custom("cannot serialize results")),
             Value::Type(..) => Err(ser::Error::custom("cannot serialize types")),
             Value::Future(..) => Err(ser::Error::custom("cannot serialize futures")),
             Value::Stream(..) => Err(ser::Error::custom("cannot serialize streams")),
@@ -84,11 +163,141 @@ impl ser::Serialize for Value {
                 Err(ser::Error::custom("cannot serialize generator states"))
             }
             Value::Function(..) => Err(ser::Error::custom("cannot serialize function pointers")),
+            Value::Cell(cell) => {
+                let value = cell.borrow_ref().map_err(ser::Error::custom)?;
+                value.serialize_with(repr).serialize(serializer)
+            }
             Value::Any(..) => Err(ser::Error::custom("cannot serialize external objects")),
         }
     }
 }
 
+/// The content of a variant being serialized.
+#[derive(Clone, Copy)]
+enum Content<'a> {
+    Tuple(&'a [Value]),
+    Object(&'a Object),
+}
+
+impl Content<'_> {
+    fn is_unit(self) -> bool {
+        matches!(self, Content::Tuple([]))
+    }
+}
+
+/// Serialize the content of a variant, where a variant with a single field
+/// is serialized as that field.
+struct SerializeContent<'a> {
+    content: Content<'a>,
+    repr: &'a EnumRepr,
+}
+
+impl ser::Serialize for SerializeContent<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        match self.content {
+            Content::Tuple([]) => serializer.serialize_unit(),
+            Content::Tuple([value]) => value.serialize_with(self.repr).serialize(serializer),
+            Content::Tuple(values) => serialize_seq(serializer, values, self.repr),
+            Content::Object(object) => {
+                let mut serializer = serializer.serialize_map(Some(object.len()))?;
+                serialize_fields(&mut serializer, object, self.repr)?;
+                serializer.end()
+            }
+        }
+    }
+}
+
+fn serialize_seq<S>(serializer: S, values: &[Value], repr: &EnumRepr) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    use serde::ser::SerializeSeq as _;
+
+    let mut serializer = serializer.serialize_seq(Some(values.len()))?;
+
+    for value in values {
+        serializer.serialize_element(&value.serialize_with(repr))?;
+    }
+
+    serializer.end()
+}
+
+/// Serialize the fields of an object sorted by name, so that the output of
+/// typed objects is stable.
+fn serialize_fields<M>(serializer: &mut M, object: &Object, repr: &EnumRepr) -> Result<(), M::Error>
+where
+    M: ser::SerializeMap,
+{
+    let mut fields = object.iter().collect::<Vec<_>>();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (key, value) in fields {
+        serializer.serialize_entry(key, &value.serialize_with(repr))?;
+    }
+
+    Ok(())
+}
+
+fn serialize_variant<S>(
+    serializer: S,
+    name: &str,
+    content: Content<'_>,
+    repr: &EnumRepr,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    use serde::ser::SerializeMap as _;
+
+    let value = SerializeContent { content, repr };
+
+    match repr {
+        EnumRepr::External => {
+            if content.is_unit() {
+                return serializer.serialize_str(name);
+            }
+
+            let mut serializer = serializer.serialize_map(Some(1))?;
+            serializer.serialize_entry(name, &value)?;
+            serializer.end()
+        }
+        EnumRepr::Internal { tag } => match content {
+            Content::Tuple([]) => {
+                let mut serializer = serializer.serialize_map(Some(1))?;
+                serializer.serialize_entry(tag, name)?;
+                serializer.end()
+            }
+            Content::Tuple(..) => Err(ser::Error::custom(format!(
+                "cannot serialize tuple variant `{}` with internal tagging",
+                name
+            ))),
+            Content::Object(object) => {
+                let mut serializer = serializer.serialize_map(Some(object.len() + 1))?;
+                serializer.serialize_entry(tag, name)?;
+                serialize_fields(&mut serializer, object, repr)?;
+                serializer.end()
+            }
+        },
+        EnumRepr::Adjacent { tag, content: key } => {
+            let len = if content.is_unit() { 1 } else { 2 };
+            let mut serializer = serializer.serialize_map(Some(len))?;
+            serializer.serialize_entry(tag, name)?;
+
+            if !content.is_unit() {
+                serializer.serialize_entry(key, &value)?;
+            }
+
+            serializer.end()
+        }
+        EnumRepr::Untagged => value.serialize(serializer),
+    }
+}
+
 struct VmVisitor;
 
 impl<'de> de::Visitor<'de> for VmVisitor {
@@ -103,7 +312,9 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::String(Shared::new(value.to_owned())))
+        Ok(Value::String(
+            Shared::try_with_heap(value.to_owned()).map_err(de::Error::custom)?,
+        ))
     }
 
     #[inline]
@@ -111,7 +322,9 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::String(Shared::new(value)))
+        Ok(Value::String(
+            Shared::try_with_heap(value).map_err(de::Error::custom)?,
+        ))
     }
 
     #[inline]
@@ -119,7 +332,9 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Bytes(Shared::new(Bytes::from_vec(v.to_vec()))))
+        Ok(Value::Bytes(
+            Shared::try_with_heap(Bytes::from_vec(v.to_vec())).map_err(de::Error::custom)?,
+        ))
     }
 
     #[inline]
@@ -127,7 +342,9 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Bytes(Shared::new(Bytes::from_vec(v))))
+        Ok(Value::Bytes(
+            Shared::try_with_heap(Bytes::from_vec(v)).map_err(de::Error::custom)?,
+        ))
     }
 
     #[inline]
@@ -210,6 +427,22 @@ impl<'de> de::Visitor<'de> for VmVisitor {
         Ok(Value::Integer(v as i64))
     }
 
+    #[inline]
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v as f64))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v))
+    }
+
     #[inline]
     fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
     where
@@ -245,7 +478,9 @@ impl<'de> de::Visitor<'de> for VmVisitor {
             vec.push(elem);
         }
 
-        Ok(Value::Vec(Shared::new(vec)))
+        Ok(Value::Vec(
+            Shared::try_with_heap(vec).map_err(de::Error::custom)?,
+        ))
     }
 
     #[inline]
@@ -259,6 +494,8 @@ impl<'de> de::Visitor<'de> for VmVisitor {
             object.insert(key, value);
         }
 
-        Ok(Value::Object(Shared::new(object)))
+        Ok(Value::Object(
+            Shared::try_with_heap(object).map_err(de::Error::custom)?,
+        ))
     }
 }
@@ -1,17 +1,19 @@
 use crate::access::{
     Access, AccessError, AccessKind, BorrowMut, BorrowRef, RawExclusiveGuard, RawSharedGuard,
 };
-use crate::{Any, AnyObj, Hash};
+use crate::memory::{self, HeapSize, Memory};
+use crate::{Any, AnyObj, Hash, VmError};
 use std::any;
 use std::cell::{Cell, UnsafeCell};
 use std::fmt;
 use std::future::Future;
 use std::marker;
-use std::mem::ManuallyDrop;
+use std::mem::{self, ManuallyDrop};
 use std::ops;
 use std::pin::Pin;
 use std::process;
 use std::ptr;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// A shared value.
@@ -21,10 +23,21 @@ pub struct Shared<T: ?Sized> {
 
 impl<T> Shared<T> {
     /// Construct a new shared value.
+    ///
+    /// If a [Memory] tracker is in use, the allocation is charged to it. The
+    /// limit of the tracker isn't checked, see [try_new][Self::try_new].
     pub fn new(data: T) -> Self {
+        let memory = memory::current();
+
+        if let Some(memory) = &memory {
+            memory.allocate(mem::size_of::<SharedBox<T>>());
+        }
+
         let inner = Box::leak(Box::new(SharedBox {
             access: Access::new(false),
             count: Cell::new(1),
+            memory: Cell::new(memory),
+            heap: Cell::new(0),
             data: data.into(),
         }));
 
@@ -33,6 +46,94 @@ impl<T> Shared<T> {
         }
     }
 
+    /// Construct a new shared value, erroring instead if the allocation would
+    /// exceed the limit of the [Memory] tracker in use.
+    pub fn try_new(data: T) -> Result<Self, VmError> {
+        memory::reserve(mem::size_of::<SharedBox<T>>())?;
+        Ok(Self::new(data))
+    }
+
+    /// Construct a new shared value which owns memory on the heap, like the
+    /// buffer of a string.
+    ///
+    /// If a [Memory] tracker is in use, both the allocation and the memory
+    /// owned on the heap are charged to it. Further growth is accounted for
+    /// through [heap_mut][Self::heap_mut].
+    pub(crate) fn with_heap(data: T) -> Self
+    where
+        T: HeapSize,
+    {
+        let heap = data.heap_size();
+        let shared = Self::new(data);
+
+        // NB: just like with `new`, the limit is checked by the virtual
+        // machine once the current instruction has completed.
+        //
+        // Safety: the value was just allocated, so the box is alive.
+        let _ = unsafe { shared.inner.as_ref().resize_heap(heap) };
+        shared
+    }
+
+    /// Construct a new shared value which owns memory on the heap like
+    /// [with_heap][Self::with_heap], erroring instead if it would exceed the
+    /// limit of the [Memory] tracker in use.
+    pub(crate) fn try_with_heap(data: T) -> Result<Self, VmError>
+    where
+        T: HeapSize,
+    {
+        memory::reserve(mem::size_of::<SharedBox<T>>().saturating_add(data.heap_size()))?;
+        Ok(Self::with_heap(data))
+    }
+
+    /// Mutably access the value, accounting for any change in the memory it
+    /// owns on the heap, like when a string grows.
+    pub(crate) fn heap_mut<F, O>(&self, f: F) -> Result<O, VmError>
+    where
+        T: HeapSize,
+        F: FnOnce(&mut T) -> O,
+    {
+        let mut data = self.borrow_mut()?;
+        let output = f(&mut data);
+        let heap = data.heap_size();
+        drop(data);
+
+        // Safety: the box is alive for as long as this reference is.
+        unsafe { self.inner.as_ref().resize_heap(heap)? };
+        Ok(output)
+    }
+
+    /// Mutably access the value like [heap_mut][Self::heap_mut], where the
+    /// closure reports how many bytes the value grew by on the heap.
+    ///
+    /// This avoids recalculating the size of values where doing so is costly,
+    /// like objects. Only an allocation which hasn't been charged to a memory
+    /// tracker yet has its size calculated in full.
+    pub(crate) fn heap_grow<F, O>(&self, f: F) -> Result<O, VmError>
+    where
+        T: HeapSize,
+        F: FnOnce(&mut T) -> (O, usize),
+    {
+        // Safety: the box is alive for as long as this reference is.
+        let inner = unsafe { self.inner.as_ref() };
+
+        let mut data = self.borrow_mut()?;
+        let (output, grown) = f(&mut data);
+
+        let memory = inner.memory.take();
+
+        let heap = if memory.is_some() {
+            inner.heap.get().saturating_add(grown)
+        } else {
+            data.heap_size()
+        };
+
+        inner.memory.set(memory);
+        drop(data);
+
+        inner.resize_heap(heap)?;
+        Ok(output)
+    }
+
     /// Return a debug formatter, that when printed will display detailed
     /// diagnostics of this shared type.
     pub fn debug(&self) -> SharedDebug<'_, T> {
@@ -437,6 +538,8 @@ impl Shared<AnyObj> {
         let inner = ptr::NonNull::from(Box::leak(Box::new(SharedBox {
             access: Access::new(true),
             count: Cell::new(2),
+            memory: Cell::new(None),
+            heap: Cell::new(0),
             data: any.into(),
         })));
 
@@ -723,12 +826,49 @@ struct SharedBox<T: ?Sized> {
     access: Access,
     /// The number of strong references to the shared data.
     count: Cell<usize>,
+    /// The memory tracker the allocation was charged to, if any.
+    memory: Cell<Option<Arc<Memory>>>,
+    /// The number of bytes owned by the value on the heap which have been
+    /// charged to the memory tracker.
+    heap: Cell<usize>,
     /// The value being held. Guarded by the `access` field to determine if it
     /// can be access shared or exclusively.
     data: UnsafeCell<T>,
 }
 
 impl<T: ?Sized> SharedBox<T> {
+    /// Update the number of bytes owned by the value on the heap, charging or
+    /// releasing the difference.
+    ///
+    /// An allocation which hasn't been charged to a memory tracker, like one
+    /// made before the virtual machine started running, is charged in full to
+    /// the current one.
+    fn resize_heap(&self, heap: usize) -> Result<(), VmError> {
+        let memory = match self.memory.take() {
+            Some(memory) => memory,
+            None => match memory::current() {
+                Some(memory) => {
+                    memory.allocate(mem::size_of_val(self));
+                    self.heap.set(0);
+                    memory
+                }
+                None => return Ok(()),
+            },
+        };
+
+        let before = self.heap.replace(heap);
+
+        if heap > before {
+            memory.allocate(heap - before);
+        } else {
+            memory.release(before - heap);
+        }
+
+        let result = memory.check();
+        self.memory.set(Some(memory));
+        result
+    }
+
     /// Increment the reference count of the inner value.
     unsafe fn inc(this: *const Self) {
         let count = (*this).count.get();
@@ -761,6 +901,10 @@ impl<T: ?Sized> SharedBox<T> {
             return false;
         }
 
+        if let Some(memory) = (*this).memory.take() {
+            memory.release(mem::size_of_val(&*this) + (*this).heap.get());
+        }
+
         if (*this).access.is_taken() {
             // NB: This prevents the inner `T` from being dropped in case it
             // has already been taken (as indicated by `is_taken`).
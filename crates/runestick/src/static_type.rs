@@ -92,6 +92,7 @@ pub static STRING_TYPE: &StaticType = &StaticType {
 
 impl_static_type!(String => STRING_TYPE);
 impl_static_type!(str => STRING_TYPE);
+impl_static_type!(crate::Shared<String> => STRING_TYPE);
 
 /// The specialized type information for a bytes type.
 pub static BYTES_TYPE: &StaticType = &StaticType {
@@ -101,6 +102,7 @@ pub static BYTES_TYPE: &StaticType = &StaticType {
 
 impl_static_type!(crate::Bytes => BYTES_TYPE);
 impl_static_type!([u8] => BYTES_TYPE);
+impl_static_type!(crate::Shared<crate::Bytes> => BYTES_TYPE);
 
 /// The specialized type information for a vector type.
 pub static VEC_TYPE: &StaticType = &StaticType {
@@ -111,6 +113,7 @@ pub static VEC_TYPE: &StaticType = &StaticType {
 impl_static_type!(impl<T> Vec<T> => VEC_TYPE);
 impl_static_type!([crate::Value] => VEC_TYPE);
 impl_static_type!(impl<T> crate::VecTuple<T> => VEC_TYPE);
+impl_static_type!(crate::Shared<Vec<crate::Value>> => VEC_TYPE);
 
 /// The specialized type information for an anonymous tuple type.
 pub static TUPLE_TYPE: &StaticType = &StaticType {
@@ -127,6 +130,7 @@ pub static OBJECT_TYPE: &StaticType = &StaticType {
 };
 
 impl_static_type!(crate::Object => OBJECT_TYPE);
+impl_static_type!(crate::Shared<crate::Object> => OBJECT_TYPE);
 
 /// The specialized type information for a future type.
 pub static FUTURE_TYPE: &StaticType = &StaticType {
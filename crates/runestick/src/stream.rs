@@ -1,13 +1,26 @@
 use crate::{
-    FromValue, GeneratorState, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue,
-    Value, Vm, VmError, VmErrorKind, VmExecution,
+    FromValue, GeneratorState, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared, ToValue,
+    UnsafeFromValue, Value, Vm, VmError, VmErrorKind, VmExecution,
 };
+use futures_util::stream::StreamExt as _;
 use std::fmt;
 use std::mem;
+use std::pin::Pin;
 
-/// A stream with a stored virtual machine.
+/// dyn stream alias.
+type DynStream = dyn futures_core::Stream<Item = Result<Value, VmError>> + 'static;
+
+/// The source of values for a stream.
+enum Inner {
+    /// A stream backed by a virtual machine.
+    Vm(VmExecution),
+    /// A stream backed by a native stream.
+    Native(Pin<Box<DynStream>>),
+}
+
+/// A stream with a stored virtual machine, or a native stream.
 pub struct Stream {
-    execution: Option<VmExecution>,
+    inner: Option<Inner>,
     first: bool,
 }
 
@@ -15,7 +28,25 @@ impl Stream {
     /// Construct a stream from a virtual machine.
     pub(crate) fn new(vm: Vm) -> Self {
         Self {
-            execution: Some(VmExecution::new(vm)),
+            inner: Some(Inner::Vm(VmExecution::new(vm))),
+            first: true,
+        }
+    }
+
+    /// Construct a stream from a native stream.
+    ///
+    /// This allows native functions to produce values which can be consumed
+    /// like any other stream in Rune. Values passed into a native stream
+    /// through [resume][Self::resume] are ignored.
+    pub fn from_stream<T, O>(stream: T) -> Self
+    where
+        T: 'static + futures_core::Stream<Item = Result<O, VmError>>,
+        O: ToValue,
+    {
+        let stream = stream.map(|item| item?.to_value());
+
+        Self {
+            inner: Some(Inner::Native(Box::pin(stream))),
             first: true,
         }
     }
@@ -30,21 +61,25 @@ impl Stream {
 
     /// Get the next value produced by this stream.
     pub async fn resume(&mut self, value: Value) -> Result<GeneratorState, VmError> {
-        let execution = match &mut self.execution {
-            Some(execution) => execution,
+        let state = match &mut self.inner {
+            Some(Inner::Vm(execution)) => {
+                if !mem::take(&mut self.first) {
+                    execution.vm_mut()?.stack_mut().push(value);
+                }
+
+                execution.async_resume().await?
+            }
+            Some(Inner::Native(stream)) => match stream.next().await {
+                Some(value) => GeneratorState::Yielded(value?),
+                None => GeneratorState::Complete(Value::Unit),
+            },
             None => {
                 return Err(VmError::from(VmErrorKind::GeneratorComplete));
             }
         };
 
-        if !mem::take(&mut self.first) {
-            execution.vm_mut()?.stack_mut().push(value);
-        }
-
-        let state = execution.async_resume().await?;
-
         if state.is_complete() {
-            self.execution = None;
+            self.inner = None;
         }
 
         Ok(state)
@@ -54,7 +89,7 @@ impl Stream {
 impl fmt::Debug for Stream {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Stream")
-            .field("completed", &self.execution.is_none())
+            .field("completed", &self.inner.is_none())
             .finish()
     }
 }
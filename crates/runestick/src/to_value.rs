@@ -124,7 +124,7 @@ where
             vec.push(value.to_value()?);
         }
 
-        Ok(Value::from(Shared::new(vec)))
+        Ok(Value::from(Shared::try_with_heap(vec)?))
     }
 }
 
@@ -4,7 +4,10 @@
 //! metadata like function locations.
 
 use crate::collections::HashMap;
-use crate::{Call, DebugInfo, Hash, Inst, StaticString, Type, VmError, VmErrorKind};
+use crate::{
+    Call, ConstValue, DebugInfo, Hash, Inst, Rtti, StaticString, Type, VariantRtti, VmError,
+    VmErrorKind,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
@@ -29,12 +32,19 @@ pub struct Unit {
     ///
     /// All keys are sorted with the default string sort.
     static_object_keys: Vec<Box<[String]>>,
+    /// Constants evaluated at compile time.
+    constants: HashMap<Hash, ConstValue>,
+    /// Runtime information for types.
+    rtti: HashMap<Hash, Arc<Rtti>>,
+    /// Runtime information for variants.
+    variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
     /// Debug info if available for unit.
     debug: Option<Box<DebugInfo>>,
 }
 
 impl Unit {
     /// Construct a new unit with the given content.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         instructions: Vec<Inst>,
         functions: HashMap<Hash, UnitFn>,
@@ -42,6 +52,9 @@ impl Unit {
         static_strings: Vec<Arc<StaticString>>,
         static_bytes: Vec<Vec<u8>>,
         static_object_keys: Vec<Box<[String]>>,
+        constants: HashMap<Hash, ConstValue>,
+        rtti: HashMap<Hash, Arc<Rtti>>,
+        variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
     ) -> Self {
         Self {
@@ -51,6 +64,9 @@ impl Unit {
             static_strings,
             static_bytes,
             static_object_keys,
+            constants,
+            rtti,
+            variant_rtti,
             debug,
         }
     }
@@ -60,6 +76,16 @@ impl Unit {
         self.types.get(&hash)
     }
 
+    /// Lookup the runtime information of the type with the given hash.
+    pub fn lookup_rtti(&self, hash: Hash) -> Option<&Arc<Rtti>> {
+        self.rtti.get(&hash)
+    }
+
+    /// Lookup the runtime information of the variant with the given hash.
+    pub fn lookup_variant_rtti(&self, hash: Hash) -> Option<&Arc<VariantRtti>> {
+        self.variant_rtti.get(&hash)
+    }
+
     /// Access debug information for the given location if it is available.
     pub fn debug_info(&self) -> Option<&DebugInfo> {
         let debug = self.debug.as_ref()?;
@@ -123,6 +149,13 @@ impl Unit {
         self.static_object_keys.get(slot).map(|keys| &keys[..])
     }
 
+    /// Lookup the constant with the given hash, if it exists.
+    pub fn lookup_const(&self, hash: Hash) -> Result<&ConstValue, VmError> {
+        self.constants
+            .get(&hash)
+            .ok_or_else(|| VmError::from(VmErrorKind::MissingConstValue { hash }))
+    }
+
     /// Lookup information of a function.
     pub fn lookup(&self, hash: Hash) -> Option<UnitFn> {
         self.functions.get(&hash).copied()
@@ -140,6 +173,9 @@ pub enum UnitFn {
         call: Call,
         /// The number of arguments the function takes.
         args: usize,
+        /// If the last argument collects any remaining arguments into a
+        /// vector.
+        variadic: bool,
     },
     /// A tuple constructor.
     Tuple {
@@ -162,8 +198,17 @@ pub enum UnitFn {
 impl fmt::Display for UnitFn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Offset { offset, call, args } => {
+            Self::Offset {
+                offset,
+                call,
+                args,
+                variadic,
+            } => {
                 write!(f, "offset {}, {}, {}", offset, call, args)?;
+
+                if *variadic {
+                    write!(f, ", variadic")?;
+                }
             }
             Self::Tuple { hash, args } => {
                 write!(f, "tuple {}, {}", hash, args)?;
@@ -1,16 +1,16 @@
 use crate::access::AccessKind;
 use crate::{
     Any, AnyObj, Bytes, Function, Future, Generator, GeneratorState, Hash, Mut, Object, RawMut,
-    RawRef, Ref, Shared, StaticString, Stream, Tuple, Type, TypeInfo, VmError,
+    RawRef, Ref, Rtti, Shared, StaticString, Stream, Tuple, Type, TypeInfo, VariantRtti, VmError,
 };
 use std::fmt;
 use std::sync::Arc;
 
 /// A tuple with a well-defined type.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct TypedTuple {
-    /// The type hash of the tuple.
-    pub(crate) hash: Hash,
+    /// The runtime type information of the tuple.
+    pub(crate) rtti: Arc<Rtti>,
     /// Content of the tuple.
     pub(crate) tuple: Box<[Value]>,
 }
@@ -18,7 +18,17 @@ pub struct TypedTuple {
 impl TypedTuple {
     /// Get type info for the typed tuple.
     pub fn type_info(&self) -> TypeInfo {
-        TypeInfo::Hash(self.hash)
+        TypeInfo::Hash(self.rtti.hash)
+    }
+
+    /// Get the runtime type information of the tuple.
+    pub fn rtti(&self) -> &Arc<Rtti> {
+        &self.rtti
+    }
+
+    /// Get the content of the tuple.
+    pub fn as_slice(&self) -> &[Value] {
+        &self.tuple
     }
 
     /// Get the value at the given index in the tuple.
@@ -32,13 +42,20 @@ impl TypedTuple {
     }
 }
 
+impl fmt::Debug for TypedTuple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedTuple")
+            .field("item", &format_args!("{}", self.rtti.item))
+            .field("tuple", &self.tuple)
+            .finish()
+    }
+}
+
 /// A tuple with a well-defined type as a variant of an enum.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct TupleVariant {
-    /// The type hash of the enum.
-    pub(crate) enum_hash: Hash,
-    /// The variant type hash of the tuple.
-    pub(crate) hash: Hash,
+    /// The runtime type information of the variant.
+    pub(crate) rtti: Arc<VariantRtti>,
     /// Content of the tuple.
     pub(crate) tuple: Box<[Value]>,
 }
@@ -46,34 +63,63 @@ pub struct TupleVariant {
 impl TupleVariant {
     /// Get type info for the typed tuple.
     pub fn type_info(&self) -> TypeInfo {
-        TypeInfo::Hash(self.enum_hash)
+        TypeInfo::Hash(self.rtti.enum_hash)
+    }
+
+    /// Get the runtime type information of the variant.
+    pub fn rtti(&self) -> &Arc<VariantRtti> {
+        &self.rtti
+    }
+
+    /// Get the content of the tuple.
+    pub fn as_slice(&self) -> &[Value] {
+        &self.tuple
+    }
+}
+
+impl fmt::Debug for TupleVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TupleVariant")
+            .field("item", &format_args!("{}", self.rtti.item))
+            .field("tuple", &self.tuple)
+            .finish()
     }
 }
 
 /// An object with a well-defined type.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct TypedObject {
-    /// The type hash of the object.
-    hash: Hash,
+    /// The runtime type information of the object.
+    rtti: Arc<Rtti>,
     /// Content of the object.
     pub(crate) object: Object,
 }
 
 impl TypedObject {
-    /// Construct a new typed object with the given type hash.
-    pub fn new(hash: Hash, object: Object) -> Self {
-        Self { hash, object }
+    /// Construct a new typed object with the given runtime type information.
+    pub fn new(rtti: Arc<Rtti>, object: Object) -> Self {
+        Self { rtti, object }
     }
 
     /// Get type info for the typed object.
     pub fn type_info(&self) -> TypeInfo {
-        TypeInfo::Hash(self.hash)
+        TypeInfo::Hash(self.rtti.hash)
     }
 
     /// Get the type hash of the object.
     #[inline]
     pub fn type_hash(&self) -> Hash {
-        self.hash
+        self.rtti.hash
+    }
+
+    /// Get the runtime type information of the object.
+    pub fn rtti(&self) -> &Arc<Rtti> {
+        &self.rtti
+    }
+
+    /// Get the content of the object.
+    pub fn as_object(&self) -> &Object {
+        &self.object
     }
 
     /// Get the given key in the object.
@@ -95,13 +141,20 @@ impl TypedObject {
     }
 }
 
+impl fmt::Debug for TypedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedObject")
+            .field("item", &format_args!("{}", self.rtti.item))
+            .field("object", &self.object)
+            .finish()
+    }
+}
+
 /// An object with a well-defined variant of an enum.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct VariantObject {
-    /// The type hash of the enum.
-    pub enum_hash: Hash,
-    /// The type variant hash.
-    pub hash: Hash,
+    /// The runtime type information of the variant.
+    pub rtti: Arc<VariantRtti>,
     /// Content of the object.
     pub object: Object,
 }
@@ -109,7 +162,7 @@ pub struct VariantObject {
 impl VariantObject {
     /// Get type info for the typed object.
     pub fn type_info(&self) -> TypeInfo {
-        TypeInfo::Hash(self.enum_hash)
+        TypeInfo::Hash(self.rtti.enum_hash)
     }
 
     /// Get the given key in the object.
@@ -131,6 +184,15 @@ impl VariantObject {
     }
 }
 
+impl fmt::Debug for VariantObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariantObject")
+            .field("item", &format_args!("{}", self.rtti.item))
+            .field("object", &self.object)
+            .finish()
+    }
+}
+
 /// An entry on the stack.
 #[derive(Clone)]
 pub enum Value {
@@ -189,6 +251,11 @@ pub enum Value {
     VariantObject(Shared<VariantObject>),
     /// A stored function pointer.
     Function(Shared<Function>),
+    /// A shared cell holding a variable which has been captured by a closure.
+    ///
+    /// Cells are only ever stored in the slots of variables, and are read and
+    /// written through by the virtual machine.
+    Cell(Shared<Value>),
     /// An opaque value that can be downcasted.
     Any(Shared<AnyObj>),
 }
@@ -196,27 +263,26 @@ pub enum Value {
 impl Value {
     /// Construct a vector.
     pub fn vec(vec: Vec<Value>) -> Self {
-        Self::Vec(Shared::new(vec))
+        Self::Vec(Shared::with_heap(vec))
     }
 
     /// Construct a tuple.
     pub fn tuple(vec: Vec<Value>) -> Self {
-        Self::Tuple(Shared::new(Tuple::from(vec)))
+        Self::Tuple(Shared::with_heap(Tuple::from(vec)))
     }
 
     /// Construct a typed tuple.
-    pub fn typed_tuple(hash: Hash, vec: Vec<Value>) -> Self {
+    pub fn typed_tuple(rtti: Arc<Rtti>, vec: Vec<Value>) -> Self {
         Self::TypedTuple(Shared::new(TypedTuple {
-            hash,
+            rtti,
             tuple: vec.into_boxed_slice(),
         }))
     }
 
     /// Construct a typed tuple.
-    pub fn variant_tuple(enum_hash: Hash, hash: Hash, vec: Vec<Value>) -> Self {
+    pub fn variant_tuple(rtti: Arc<VariantRtti>, vec: Vec<Value>) -> Self {
         Self::TupleVariant(Shared::new(TupleVariant {
-            enum_hash,
-            hash,
+            rtti,
             tuple: vec.into_boxed_slice(),
         }))
     }
@@ -465,16 +531,17 @@ impl Value {
             Self::Option(..) => Type::from(crate::OPTION_TYPE),
             Self::Function(..) => Type::from(crate::FUNCTION_TYPE),
             Self::Type(hash) => Type::from(*hash),
-            Self::TypedObject(object) => Type::from(object.borrow_ref()?.hash),
+            Self::TypedObject(object) => Type::from(object.borrow_ref()?.rtti.hash),
             Self::VariantObject(object) => {
                 let object = object.borrow_ref()?;
-                Type::from(object.enum_hash)
+                Type::from(object.rtti.enum_hash)
             }
-            Self::TypedTuple(tuple) => Type::from(tuple.borrow_ref()?.hash),
+            Self::TypedTuple(tuple) => Type::from(tuple.borrow_ref()?.rtti.hash),
             Self::TupleVariant(tuple) => {
                 let tuple = tuple.borrow_ref()?;
-                Type::from(tuple.enum_hash)
+                Type::from(tuple.rtti.enum_hash)
             }
+            Self::Cell(cell) => cell.borrow_ref()?.type_of()?,
             Self::Any(any) => Type::from(any.borrow_ref()?.type_hash()),
         })
     }
@@ -506,6 +573,7 @@ impl Value {
             Self::VariantObject(object) => object.borrow_ref()?.type_info(),
             Self::TypedTuple(tuple) => tuple.borrow_ref()?.type_info(),
             Self::TupleVariant(tuple) => tuple.borrow_ref()?.type_info(),
+            Self::Cell(cell) => cell.borrow_ref()?.type_info()?,
             Self::Any(any) => TypeInfo::Any(any.borrow_ref()?.type_name()),
         })
     }
@@ -521,42 +589,42 @@ impl Value {
             (Self::Bool(a), Self::Bool(b)) => a == b,
             (Self::Integer(a), Self::Integer(b)) => a == b,
             (Self::Float(a), Self::Float(b)) => a == b,
-            (Self::Vec(a), Self::Vec(b)) => {
+            (Self::Vec(a), Self::Vec(b)) => Self::slice_eq(&a.borrow_ref()?, &b.borrow_ref()?)?,
+            (Self::Tuple(a), Self::Tuple(b)) => Self::slice_eq(&a.borrow_ref()?, &b.borrow_ref()?)?,
+            (Self::Object(a), Self::Object(b)) => {
+                Self::object_eq(&*a.borrow_ref()?, &*b.borrow_ref()?)?
+            }
+            (Self::Option(a), Self::Option(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Some(a), Some(b)) => Self::value_ptr_eq(a, b)?,
+                (None, None) => true,
+                _ => false,
+            },
+            (Self::Result(a), Self::Result(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Ok(a), Ok(b)) => Self::value_ptr_eq(a, b)?,
+                (Err(a), Err(b)) => Self::value_ptr_eq(a, b)?,
+                _ => false,
+            },
+            // structs and variants declared in rune are equal if they have the
+            // same type and all of their fields are equal.
+            (Self::TypedTuple(a), Self::TypedTuple(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-
-                if a.len() != b.len() {
-                    return Ok(false);
-                }
-
-                for (a, b) in a.iter().zip(b.iter()) {
-                    if !Self::value_ptr_eq(a, b)? {
-                        return Ok(false);
-                    }
-                }
-
-                true
+                a.rtti.hash == b.rtti.hash && Self::slice_eq(&a.tuple, &b.tuple)?
             }
-            (Self::Object(a), Self::Object(b)) => {
+            (Self::TupleVariant(a), Self::TupleVariant(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-
-                if a.len() != b.len() {
-                    return Ok(false);
-                }
-
-                for (key, a) in a.iter() {
-                    let b = match b.get(key) {
-                        Some(b) => b,
-                        None => return Ok(false),
-                    };
-
-                    if !Self::value_ptr_eq(a, b)? {
-                        return Ok(false);
-                    }
-                }
-
-                true
+                a.rtti.hash == b.rtti.hash && Self::slice_eq(&a.tuple, &b.tuple)?
+            }
+            (Self::TypedObject(a), Self::TypedObject(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                a.rtti.hash == b.rtti.hash && Self::object_eq(&a.object, &b.object)?
+            }
+            (Self::VariantObject(a), Self::VariantObject(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                a.rtti.hash == b.rtti.hash && Self::object_eq(&a.object, &b.object)?
             }
             (Self::String(a), Self::String(b)) => {
                 let a = a.borrow_ref()?;
@@ -579,6 +647,62 @@ impl Value {
             _ => false,
         })
     }
+
+    /// Test if two slices of values are deeply equal to each other.
+    fn slice_eq(a: &[Value], b: &[Value]) -> Result<bool, VmError> {
+        if a.len() != b.len() {
+            return Ok(false);
+        }
+
+        for (a, b) in a.iter().zip(b.iter()) {
+            if !Self::value_ptr_eq(a, b)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Test if two objects are deeply equal to each other.
+    fn object_eq(a: &Object, b: &Object) -> Result<bool, VmError> {
+        if a.len() != b.len() {
+            return Ok(false);
+        }
+
+        for (key, a) in a.iter() {
+            let b = match b.get(key) {
+                Some(b) => b,
+                None => return Ok(false),
+            };
+
+            if !Self::value_ptr_eq(a, b)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Clone a struct or variant declared in Rune, returning `None` if the
+    /// value is something else.
+    ///
+    /// This is a shallow copy, so the fields of the clone refer to the same
+    /// values as the original.
+    pub(crate) fn try_clone_struct(&self) -> Result<Option<Value>, VmError> {
+        Ok(Some(match self {
+            Self::TypedTuple(tuple) => Self::TypedTuple(Shared::new(tuple.borrow_ref()?.clone())),
+            Self::TupleVariant(tuple) => {
+                Self::TupleVariant(Shared::new(tuple.borrow_ref()?.clone()))
+            }
+            Self::TypedObject(object) => {
+                Self::TypedObject(Shared::new(object.borrow_ref()?.clone()))
+            }
+            Self::VariantObject(object) => {
+                Self::VariantObject(Shared::new(object.borrow_ref()?.clone()))
+            }
+            _ => return Ok(None),
+        }))
+    }
 }
 
 impl fmt::Debug for Value {
@@ -656,6 +780,9 @@ impl fmt::Debug for Value {
             Value::Function(value) => {
                 write!(f, "{:?}", value)?;
             }
+            Value::Cell(value) => {
+                write!(f, "{:?}", value)?;
+            }
             Value::Any(value) => {
                 write!(f, "{:?}", value)?;
             }
@@ -717,11 +844,15 @@ impl_from!(Arc<StaticString>, StaticString);
 
 macro_rules! impl_from_shared {
     (Shared<$ty:ty>, $variant:ident) => {
+        impl_from_shared!(Shared<$ty>, $variant, new);
+    };
+
+    (Shared<$ty:ty>, $variant:ident, $new:ident) => {
         impl_from!(Shared<$ty>, $variant);
 
         impl From<$ty> for Value {
             fn from(value: $ty) -> Self {
-                Self::$variant(Shared::new(value))
+                Self::$variant(Shared::$new(value))
             }
         }
 
@@ -733,11 +864,11 @@ macro_rules! impl_from_shared {
     };
 }
 
-impl_from_shared!(Shared<Bytes>, Bytes);
-impl_from_shared!(Shared<String>, String);
+impl_from_shared!(Shared<Bytes>, Bytes, with_heap);
+impl_from_shared!(Shared<String>, String, with_heap);
 impl_from!(Shared<Vec<Value>>, Vec);
-impl_from_shared!(Shared<Tuple>, Tuple);
-impl_from_shared!(Shared<Object>, Object);
+impl_from_shared!(Shared<Tuple>, Tuple, with_heap);
+impl_from_shared!(Shared<Object>, Object, with_heap);
 impl_from_shared!(Shared<Future>, Future);
 impl_from_shared!(Shared<Stream>, Stream);
 impl_from_shared!(Shared<Generator>, Generator);
@@ -1,14 +1,20 @@
-use crate::budget;
+use crate::coverage::Coverage;
+use crate::environment::{self, Environment};
 use crate::future::SelectFuture;
+use crate::memory::Memory;
+use crate::sandbox::Sandbox;
 use crate::unit::UnitFn;
 use crate::{
-    Args, Awaited, BorrowMut, Bytes, Call, Context, FromValue, Function, Future, Generator,
-    GuardedArgs, Hash, Inst, InstFnNameHash, InstOp, InstTarget, IntoTypeHash, Object, Panic,
-    Select, Shared, Stack, Stream, Tuple, TypeCheck, TypedObject, Unit, Value, VariantObject,
-    VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr,
+    Args, Awaited, BorrowMut, Bytes, Call, Context, FormatSpec, FromValue, Function, Future,
+    Generator, GuardedArgs, Hash, Inst, InstFnNameHash, InstOp, InstTarget, IntoTypeHash, Object,
+    Panic, Protocol, Select, Shared, Stack, Stream, Tuple, Type, TypeCheck, TypeInfo, TypedObject,
+    Unit, Value, VariantObject, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr,
 };
+use std::cell::Cell;
+use std::cmp::Ordering;
 use std::fmt;
 use std::mem;
+use std::rc::Rc;
 use std::sync::Arc;
 
 macro_rules! target_value {
@@ -16,7 +22,16 @@ macro_rules! target_value {
         let rhs = $vm.stack.pop()?;
 
         let lhs = match $target {
-            InstTarget::Offset(offset) => $vm.stack.at_offset_mut(offset)?,
+            InstTarget::Offset(offset) => match $vm.stack.at_offset(offset)? {
+                // NB: variables captured in shared cells are assigned through
+                // the cell.
+                Value::Cell(cell) => {
+                    $lhs = Value::Cell(cell.clone());
+                    $guard = Vm::cell_borrow_mut(&$lhs)?;
+                    &mut *$guard
+                }
+                _ => $vm.stack.at_offset_mut(offset)?,
+            },
             InstTarget::TupleField(index) => {
                 $lhs = $vm.stack.pop()?;
 
@@ -61,6 +76,14 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: Vec<CallFrame>,
+    /// Coverage recorder, if coverage is being collected.
+    coverage: Option<Arc<Coverage>>,
+    /// Memory tracker, if memory is being accounted for.
+    memory: Option<Arc<Memory>>,
+    /// Remaining fuel, if execution is limited.
+    fuel: Option<Rc<Cell<usize>>>,
+    /// Sandbox overriding the one in the context, if any.
+    sandbox: Option<Arc<Sandbox>>,
 }
 
 impl Vm {
@@ -77,6 +100,10 @@ impl Vm {
             ip: 0,
             stack,
             call_frames: Vec::new(),
+            coverage: None,
+            memory: None,
+            fuel: None,
+            sandbox: None,
         }
     }
 
@@ -133,6 +160,163 @@ impl Vm {
         &self.unit
     }
 
+    /// Set the coverage recorder to use for this virtual machine.
+    ///
+    /// Coverage is only recorded if the recorder was constructed for the unit
+    /// associated with the virtual machine, and is inherited by any virtual
+    /// machines which are spawned from this one to run async functions,
+    /// generators, or streams.
+    pub fn set_coverage(&mut self, coverage: Option<Arc<Coverage>>) {
+        self.coverage = coverage.filter(|c| c.is_for(&self.unit));
+    }
+
+    /// Access the coverage recorder used by this virtual machine, if any.
+    pub fn coverage(&self) -> Option<&Arc<Coverage>> {
+        self.coverage.as_ref()
+    }
+
+    /// Set the memory tracker to use for this virtual machine.
+    ///
+    /// Allocations performed while the virtual machine is running are charged
+    /// to the tracker, and the virtual machine errors with
+    /// [VmErrorKind::MemoryLimitExceeded] once its limit is exceeded. The
+    /// tracker is shared with any virtual machines which are spawned from this
+    /// one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Context, Memory, Unit, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(Context::with_default_modules().unwrap());
+    /// let unit = Arc::new(Unit::default());
+    ///
+    /// let mut vm = Vm::new(context, unit);
+    /// vm.set_memory(Some(Arc::new(Memory::new(1024 * 1024))));
+    /// assert_eq!(vm.memory_usage(), Some(0));
+    /// ```
+    pub fn set_memory(&mut self, memory: Option<Arc<Memory>>) {
+        self.memory = memory;
+    }
+
+    /// Access the memory tracker used by this virtual machine, if any.
+    pub fn memory(&self) -> Option<&Arc<Memory>> {
+        self.memory.as_ref()
+    }
+
+    /// Get the number of bytes currently in use by this virtual machine.
+    ///
+    /// Returns `None` unless a memory tracker has been set with
+    /// [set_memory][Self::set_memory].
+    pub fn memory_usage(&self) -> Option<usize> {
+        Some(self.memory.as_ref()?.usage())
+    }
+
+    /// Set the amount of fuel available to this virtual machine.
+    ///
+    /// Every instruction executed consumes one unit of fuel, and once it runs
+    /// out the virtual machine halts with [VmHalt::Limited]. Execution can be
+    /// continued after more fuel has been added with
+    /// [add_fuel][Self::add_fuel]. Fuel is shared with any virtual machines
+    /// which are spawned from this one. Setting it to `None` removes the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Context, Unit, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(Context::with_default_modules().unwrap());
+    /// let unit = Arc::new(Unit::default());
+    ///
+    /// let mut vm = Vm::new(context, unit);
+    /// assert_eq!(vm.remaining_fuel(), None);
+    /// vm.set_fuel(Some(100));
+    /// vm.add_fuel(10);
+    /// assert_eq!(vm.remaining_fuel(), Some(110));
+    /// ```
+    pub fn set_fuel(&mut self, fuel: Option<usize>) {
+        match (&self.fuel, fuel) {
+            (Some(cell), Some(fuel)) => cell.set(fuel),
+            (None, Some(fuel)) => self.fuel = Some(Rc::new(Cell::new(fuel))),
+            (_, None) => self.fuel = None,
+        }
+    }
+
+    /// Get the amount of fuel remaining, or `None` if execution is not limited.
+    pub fn remaining_fuel(&self) -> Option<usize> {
+        Some(self.fuel.as_ref()?.get())
+    }
+
+    /// Add fuel to the virtual machine.
+    ///
+    /// This does nothing unless a limit has been set with
+    /// [set_fuel][Self::set_fuel].
+    pub fn add_fuel(&mut self, fuel: usize) {
+        if let Some(cell) = &self.fuel {
+            cell.set(cell.get().saturating_add(fuel));
+        }
+    }
+
+    /// Set the sandbox to use for this virtual machine.
+    ///
+    /// This overrides the sandbox set in the context, and is inherited by any
+    /// virtual machines which are spawned from this one.
+    pub fn set_sandbox(&mut self, sandbox: Option<Arc<Sandbox>>) {
+        self.sandbox = sandbox;
+    }
+
+    /// Access the sandbox which is in effect for this virtual machine, if any.
+    ///
+    /// This is either the sandbox set through
+    /// [set_sandbox][Self::set_sandbox], or the one set in the context.
+    pub fn sandbox(&self) -> Option<&Arc<Sandbox>> {
+        self.sandbox.as_ref().or_else(|| self.context.sandbox())
+    }
+
+    /// The environment to install while this virtual machine is running.
+    ///
+    /// The time slice of the environment which is currently installed is
+    /// carried over, since it belongs to the scheduler task and not to any
+    /// one virtual machine.
+    pub(crate) fn environment(&self) -> Environment {
+        Environment {
+            unit: Some(self.unit.clone()),
+            memory: self.memory.clone(),
+            fuel: self.fuel.clone(),
+            sandbox: self.sandbox().cloned(),
+            slice: environment::current(|env| env.slice.clone()),
+        }
+    }
+
+    /// Inherit the memory tracker, fuel, and sandbox of the virtual machine
+    /// which is currently running, if any.
+    pub(crate) fn inherit_environment(&mut self) {
+        environment::current(|env| {
+            self.memory = env.memory.clone();
+            self.fuel = env.fuel.clone();
+
+            if env.sandbox.is_some() {
+                self.sandbox = env.sandbox.clone();
+            }
+        });
+    }
+
+    /// Share the fuel of another virtual machine.
+    pub(crate) fn share_fuel(&mut self, other: &Vm) {
+        self.fuel = other.fuel.clone();
+    }
+
+    /// Inherit execution settings, like coverage, from the virtual machine
+    /// which spawned this one.
+    pub(crate) fn inherit(&mut self, parent: &Vm) {
+        self.set_coverage(parent.coverage.clone());
+        self.memory = parent.memory.clone();
+        self.fuel = parent.fuel.clone();
+        self.sandbox = parent.sandbox.clone();
+    }
+
     /// Reset this virtual machine, freeing all memory used.
     pub fn clear(&mut self) {
         self.ip = 0;
@@ -193,8 +377,13 @@ impl Vm {
         N: IntoTypeHash,
         A: Args,
     {
-        self.set_entrypoint(name, A::count())?;
+        let variadic = self.set_entrypoint(name, A::count())?;
         args.into_stack(&mut self.stack)?;
+
+        if let Some(expected) = variadic {
+            Self::collect_rest_args(&mut self.stack, A::count(), expected)?;
+        }
+
         Ok(VmExecution::new(self))
     }
 
@@ -217,11 +406,15 @@ impl Vm {
         N: IntoTypeHash,
         A: GuardedArgs,
     {
-        self.set_entrypoint(name, A::count())?;
+        let variadic = self.set_entrypoint(name, A::count())?;
 
         // Safety: We hold onto the guard until the vm has completed.
         let guard = unsafe { args.unsafe_into_stack(&mut self.stack)? };
 
+        if let Some(expected) = variadic {
+            Self::collect_rest_args(&mut self.stack, A::count(), expected)?;
+        }
+
         let value = VmExecution::new(self).complete()?;
 
         // Note: this might panic if something in the vm is holding on to a
@@ -251,11 +444,15 @@ impl Vm {
         N: IntoTypeHash,
         A: GuardedArgs,
     {
-        self.set_entrypoint(name, A::count())?;
+        let variadic = self.set_entrypoint(name, A::count())?;
 
         // Safety: We hold onto the guard until the vm has completed.
         let guard = unsafe { args.unsafe_into_stack(&mut self.stack)? };
 
+        if let Some(expected) = variadic {
+            Self::collect_rest_args(&mut self.stack, A::count(), expected)?;
+        }
+
         let value = VmExecution::new(self).complete()?;
 
         // Note: this might panic if something in the vm is holding on to a
@@ -267,7 +464,11 @@ impl Vm {
 
     // Update the instruction pointer to match the function matching the given
     // name and check that the number of argument matches.
-    fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<(), VmError>
+    //
+    // Returns the number of arguments of the function if it's variadic, in
+    // which case the remaining arguments have to be collected once they've
+    // been pushed.
+    fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<Option<usize>, VmError>
     where
         N: IntoTypeHash,
     {
@@ -280,16 +481,17 @@ impl Vm {
             })
         })?;
 
-        let offset = match info {
+        let (offset, variadic) = match info {
             // NB: we ignore the calling convention.
             // everything is just async when called externally.
             UnitFn::Offset {
                 offset,
                 args: expected,
+                variadic,
                 ..
             } => {
-                Self::check_args(count, expected)?;
-                offset
+                Self::check_offset_args(count, expected, variadic)?;
+                (offset, if variadic { Some(expected) } else { None })
             }
             _ => {
                 return Err(VmError::from(VmErrorKind::MissingFunction { hash }));
@@ -298,7 +500,7 @@ impl Vm {
 
         self.ip = offset;
         self.stack.clear();
-        Ok(())
+        Ok(variadic)
     }
 
     fn op_await(&mut self) -> Result<Shared<Future>, VmError> {
@@ -354,11 +556,13 @@ impl Vm {
             offset,
             call,
             args: expected,
+            variadic,
         }) = self.unit.lookup(hash)
         {
-            Self::check_args(count, expected)?;
+            Self::check_offset_args(count, expected, variadic)?;
             self.stack.push(target.clone());
             args.into_stack(&mut self.stack)?;
+            let count = Self::offset_args(&mut self.stack, count, expected, variadic)?;
             self.call_offset_fn(offset, call, count)?;
             return Ok(true);
         }
@@ -375,6 +579,36 @@ impl Vm {
         Ok(true)
     }
 
+    /// Helper function to call a protocol function implemented by a native
+    /// type, returning its result.
+    ///
+    /// Returns `None` if the type of the target doesn't implement the
+    /// protocol natively.
+    fn call_native_protocol<H, A>(
+        &mut self,
+        target: &Value,
+        hash: H,
+        args: A,
+    ) -> Result<Option<Value>, VmError>
+    where
+        H: IntoTypeHash,
+        A: Args,
+    {
+        let count = A::count() + 1;
+        let hash = Hash::instance_function(target.type_of()?, hash.into_type_hash());
+
+        let handler = match self.context.lookup(hash) {
+            Some(handler) => handler,
+            None => return Ok(None),
+        };
+
+        self.stack.push(target.clone());
+        args.into_stack(&mut self.stack)?;
+
+        handler(&mut self.stack, count)?;
+        Ok(Some(self.stack.pop()?))
+    }
+
     /// Helper function to call an external getter.
     fn call_getter<H, A>(&mut self, target: &Value, hash: H, args: A) -> Result<bool, VmError>
     where
@@ -403,14 +637,14 @@ impl Vm {
     }
 
     /// pop-and-jump-if-not instruction.
-    fn op_pop_and_jump_if_not(&mut self, count: usize, offset: isize) -> Result<(), VmError> {
+    fn op_pop_and_jump_if_not(&mut self, count: usize, offset: isize) -> Result<bool, VmError> {
         if self.stack.pop()?.into_bool()? {
-            return Ok(());
+            return Ok(false);
         }
 
         self.stack.popn(count)?;
         self.modify_ip(offset)?;
-        Ok(())
+        Ok(true)
     }
 
     /// Pop a number of values from the stack, while preserving the top of the
@@ -452,10 +686,63 @@ impl Vm {
         Ok(())
     }
 
+    /// Convert the variable at the given offset into a shared cell if it isn't
+    /// one already, and push the cell on the stack.
+    fn op_capture_cell(&mut self, offset: usize) -> Result<(), VmError> {
+        let stack_value = self.stack.at_offset_mut(offset)?;
+
+        if !matches!(stack_value, Value::Cell(..)) {
+            let value = mem::replace(stack_value, Value::Unit);
+            *stack_value = Value::Cell(Shared::new(value));
+        }
+
+        let cell = stack_value.clone();
+        self.stack.push(cell);
+        Ok(())
+    }
+
+    /// Copy a variable which might be stored in a shared cell.
+    fn op_copy_cell(&mut self, offset: usize) -> Result<(), VmError> {
+        let value = match self.stack.at_offset(offset)? {
+            Value::Cell(cell) => cell.borrow_ref()?.clone(),
+            value => value.clone(),
+        };
+
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Replace a variable which might be stored in a shared cell.
+    fn op_replace_cell(&mut self, offset: usize) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        match self.stack.at_offset_mut(offset)? {
+            Value::Cell(cell) => {
+                *cell.borrow_mut()? = value;
+            }
+            stack_value => {
+                *stack_value = value;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Mutably borrow the value stored in a shared cell.
+    fn cell_borrow_mut(cell: &Value) -> Result<BorrowMut<'_, Value>, VmError> {
+        match cell {
+            Value::Cell(cell) => Ok(cell.borrow_mut()?),
+            value => Err(VmError::from(VmErrorKind::ExpectedCell {
+                actual: value.type_info()?,
+            })),
+        }
+    }
+
     fn internal_boolean_ops(
         &mut self,
         int_op: impl FnOnce(i64, i64) -> bool,
         float_op: impl FnOnce(f64, f64) -> bool,
+        ordering_op: impl FnOnce(Ordering) -> bool,
         op: &'static str,
     ) -> Result<(), VmError> {
         let rhs = self.stack.pop()?;
@@ -464,13 +751,16 @@ impl Vm {
         let out = match (lhs, rhs) {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
-            (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
-            }
+            (lhs, rhs) => match self.call_native_protocol(&lhs, crate::CMP, (&rhs,))? {
+                Some(ordering) => ordering_op(ordering.into_integer()?.cmp(&0)),
+                None => {
+                    return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                        op,
+                        lhs: lhs.type_info()?,
+                        rhs: rhs.type_info()?,
+                    }))
+                }
+            },
         };
 
         self.stack.push(out);
@@ -478,22 +768,22 @@ impl Vm {
     }
 
     fn op_gt(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">")?;
+        self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, Ordering::is_gt, ">")?;
         Ok(())
     }
 
     fn op_gte(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=")?;
+        self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, Ordering::is_ge, ">=")?;
         Ok(())
     }
 
     fn op_lt(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<")?;
+        self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, Ordering::is_lt, "<")?;
         Ok(())
     }
 
     fn op_lte(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=")?;
+        self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, Ordering::is_le, "<=")?;
         Ok(())
     }
 
@@ -533,7 +823,8 @@ impl Vm {
     fn op_eq(&mut self) -> Result<(), VmError> {
         let b = self.stack.pop()?;
         let a = self.stack.pop()?;
-        self.stack.push(Value::value_ptr_eq(&a, &b)?);
+        let out = self.internal_eq(&a, &b)?;
+        self.stack.push(out);
         Ok(())
     }
 
@@ -542,10 +833,29 @@ impl Vm {
     fn op_neq(&mut self) -> Result<(), VmError> {
         let b = self.stack.pop()?;
         let a = self.stack.pop()?;
-        self.stack.push(!Value::value_ptr_eq(&a, &b)?);
+        let out = !self.internal_eq(&a, &b)?;
+        self.stack.push(out);
         Ok(())
     }
 
+    /// Test if two values are equal, using the [EQ][crate::EQ] protocol for
+    /// native types which implement it.
+    fn internal_eq(&mut self, a: &Value, b: &Value) -> Result<bool, VmError> {
+        if let Value::Any(..) = a {
+            // NB: values of different types are never equal, so native `EQ`
+            // implementations only have to deal with their own type.
+            if a.type_of()? != b.type_of()? {
+                return Ok(false);
+            }
+
+            if let Some(out) = self.call_native_protocol(a, crate::EQ, (b,))? {
+                return out.into_bool();
+            }
+        }
+
+        Value::value_ptr_eq(a, b)
+    }
+
     /// Perform a jump operation.
     #[inline]
     fn op_jump(&mut self, offset: isize) -> Result<(), VmError> {
@@ -554,43 +864,60 @@ impl Vm {
     }
 
     /// Perform a conditional jump operation.
+    ///
+    /// Returns `true` if the jump was taken.
     #[inline]
-    fn op_jump_if(&mut self, offset: isize) -> Result<(), VmError> {
+    fn op_jump_if(&mut self, offset: isize) -> Result<bool, VmError> {
         if self.stack.pop()?.into_bool()? {
             self.modify_ip(offset)?;
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Perform a conditional jump operation.
+    ///
+    /// Returns `true` if the jump was taken.
     #[inline]
-    fn op_jump_if_not(&mut self, offset: isize) -> Result<(), VmError> {
+    fn op_jump_if_not(&mut self, offset: isize) -> Result<bool, VmError> {
         if !self.stack.pop()?.into_bool()? {
             self.modify_ip(offset)?;
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Perform a branch-conditional jump operation.
+    ///
+    /// Returns `true` if the jump was taken.
     #[inline]
-    fn op_jump_if_branch(&mut self, branch: i64, offset: isize) -> Result<(), VmError> {
+    fn op_jump_if_branch(&mut self, branch: i64, offset: isize) -> Result<bool, VmError> {
         if let Some(Value::Integer(current)) = self.stack.peek() {
             if *current == branch {
                 self.modify_ip(offset)?;
                 self.stack.pop()?;
+                return Ok(true);
             }
         }
 
-        Ok(())
+        Ok(false)
+    }
+
+    /// Record the outcome of a conditional jump if coverage is enabled.
+    #[inline]
+    fn record_branch(&self, ip: usize, taken: bool) {
+        if let Some(coverage) = &self.coverage {
+            coverage.record_branch(ip, taken);
+        }
     }
 
     /// Construct a new vec.
     #[inline]
     fn op_vec(&mut self, count: usize) -> Result<(), VmError> {
         let vec = self.stack.pop_sequence(count)?;
-        self.stack.push(Shared::new(vec));
+        self.stack.push(Shared::try_with_heap(vec)?);
         Ok(())
     }
 
@@ -830,8 +1157,7 @@ impl Vm {
 
             match &target {
                 Value::Object(object) => {
-                    let mut object = object.borrow_mut()?;
-                    object.insert(field.to_owned(), value);
+                    object.heap_grow(|object| object.insert_heap(field.to_owned(), value))?;
                     return Ok(());
                 }
                 Value::TypedObject(typed_object) => {
@@ -890,6 +1216,76 @@ impl Vm {
         Ok(exit)
     }
 
+    #[inline]
+    fn op_check_argument(
+        &mut self,
+        offset: usize,
+        type_check: TypeCheck,
+        slot: Option<usize>,
+    ) -> Result<(), VmError> {
+        let value = self.stack.at_offset(offset)?.clone();
+
+        if self.is_type_check(type_check, &value)? {
+            return Ok(());
+        }
+
+        let name = match slot {
+            Some(slot) => Some(self.unit.lookup_string(slot)?.as_str().to_owned()),
+            None => None,
+        };
+
+        Err(VmError::from(VmErrorKind::BadArgumentType {
+            arg: offset,
+            name,
+            expected: self.type_check_info(type_check),
+            actual: value.type_info()?,
+        }))
+    }
+
+    #[inline]
+    fn op_check_return(&mut self, type_check: TypeCheck) -> Result<(), VmError> {
+        let value = self.stack.last()?.clone();
+
+        if self.is_type_check(type_check, &value)? {
+            return Ok(());
+        }
+
+        Err(VmError::from(VmErrorKind::BadReturnType {
+            expected: self.type_check_info(type_check),
+            actual: value.type_info()?,
+        }))
+    }
+
+    /// Test if the given value passes the type check.
+    fn is_type_check(&mut self, type_check: TypeCheck, value: &Value) -> Result<bool, VmError> {
+        Ok(match (type_check, value) {
+            (TypeCheck::Type(hash), value) => value.type_of()? == Type::from(hash),
+            (TypeCheck::Object, Value::Object(..)) => true,
+            (type_check, value) => self.on_tuple(type_check, value, |_| ())?.is_some(),
+        })
+    }
+
+    /// Get type information on the type matched by the given type check.
+    fn type_check_info(&self, type_check: TypeCheck) -> TypeInfo {
+        let hash = match type_check {
+            TypeCheck::Unit => return TypeInfo::StaticType(crate::UNIT_TYPE),
+            TypeCheck::Tuple => return TypeInfo::StaticType(crate::TUPLE_TYPE),
+            TypeCheck::Object => return TypeInfo::StaticType(crate::OBJECT_TYPE),
+            TypeCheck::Vec => return TypeInfo::StaticType(crate::VEC_TYPE),
+            TypeCheck::Option(..) => return TypeInfo::StaticType(crate::OPTION_TYPE),
+            TypeCheck::Result(..) => return TypeInfo::StaticType(crate::RESULT_TYPE),
+            TypeCheck::GeneratorState(..) => {
+                return TypeInfo::StaticType(crate::GENERATOR_STATE_TYPE)
+            }
+            TypeCheck::Type(hash) | TypeCheck::Variant(hash) => hash,
+        };
+
+        match self.context.lookup_type_info(Type::from(hash)) {
+            Some(info) => info.type_info,
+            None => TypeInfo::Hash(hash),
+        }
+    }
+
     #[inline]
     fn op_load_instance_fn(&mut self, hash: Hash) -> Result<(), VmError> {
         let instance = self.stack.pop()?;
@@ -1280,6 +1676,46 @@ impl Vm {
         }))
     }
 
+    /// Get the value at the given index counted from the end of a tuple or
+    /// vector stored at the given offset.
+    #[inline]
+    fn op_tuple_index_get_from_end_at(
+        &mut self,
+        offset: usize,
+        index: usize,
+    ) -> Result<(), VmError> {
+        let value = self.stack.at_offset(offset)?;
+
+        let value = match value {
+            Value::Tuple(tuple) => {
+                let tuple = tuple.borrow_ref()?;
+                Self::index_from_end(&tuple[..], index)
+            }
+            Value::Vec(vec) => {
+                let vec = vec.borrow_ref()?;
+                Self::index_from_end(&vec[..], index)
+            }
+            _ => None,
+        };
+
+        if let Some(value) = value {
+            self.stack.push(value);
+            return Ok(());
+        }
+
+        let value = self.stack.at_offset(offset)?;
+
+        Err(VmError::from(VmErrorKind::UnsupportedTupleIndexGet {
+            target: value.type_info()?,
+        }))
+    }
+
+    /// Get the value at the given index counted from the end of a slice.
+    fn index_from_end(values: &[Value], index: usize) -> Option<Value> {
+        let index = values.len().checked_sub(index + 1)?;
+        values.get(index).cloned()
+    }
+
     /// Implementation of getting a string index on an object-like type.
     fn try_object_slot_index_get(
         &mut self,
@@ -1369,6 +1805,55 @@ impl Vm {
         }))
     }
 
+    /// Operation to push the given fields of an object.
+    fn op_object_fields(&mut self, slot: usize) -> Result<(), VmError> {
+        let target = self.stack.pop()?;
+
+        let keys = self
+            .unit
+            .lookup_object_keys(slot)
+            .ok_or_else(|| VmError::from(VmErrorKind::MissingStaticObjectKeys { slot }))?;
+
+        let values = match &target {
+            Value::Object(object) => Self::object_fields(&*object.borrow_ref()?, keys),
+            Value::TypedObject(object) => Self::object_fields(&object.borrow_ref()?.object, keys),
+            Value::VariantObject(object) => Self::object_fields(&object.borrow_ref()?.object, keys),
+            target => {
+                return Err(VmError::from(VmErrorKind::UnsupportedObjectSlotIndexGet {
+                    target: target.type_info()?,
+                }));
+            }
+        };
+
+        let values = match values {
+            Ok(values) => values,
+            Err(field) => {
+                return Err(VmError::from(VmErrorKind::MissingField {
+                    target: target.type_info()?,
+                    field,
+                }));
+            }
+        };
+
+        self.stack.extend(values);
+        Ok(())
+    }
+
+    /// Get the values of the given fields in an object, or the name of the
+    /// first field which is missing.
+    fn object_fields(object: &Object, keys: &[String]) -> Result<Vec<Value>, String> {
+        let mut values = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            match object.get(key) {
+                Some(value) => values.push(value.clone()),
+                None => return Err(key.clone()),
+            }
+        }
+
+        Ok(values)
+    }
+
     /// Operation to allocate an object.
     #[inline]
     fn op_object(&mut self, slot: usize) -> Result<(), VmError> {
@@ -1384,7 +1869,7 @@ impl Vm {
             object.insert(key.clone(), value);
         }
 
-        self.stack.push(Shared::new(object));
+        self.stack.push(Shared::try_with_heap(object)?);
         Ok(())
     }
 
@@ -1404,7 +1889,12 @@ impl Vm {
             object.insert(key.clone(), value);
         }
 
-        self.stack.push(TypedObject::new(hash, object));
+        let rtti = self
+            .unit
+            .lookup_rtti(hash)
+            .ok_or_else(|| VmError::from(VmErrorKind::MissingRtti { hash }))?;
+
+        self.stack.push(TypedObject::new(rtti.clone(), object));
         Ok(())
     }
 
@@ -1428,9 +1918,15 @@ impl Vm {
             object.insert(key.clone(), value);
         }
 
+        let rtti = self
+            .unit
+            .lookup_variant_rtti(hash)
+            .ok_or_else(|| VmError::from(VmErrorKind::MissingVariantRtti { hash }))?;
+
+        debug_assert_eq!(rtti.enum_hash, enum_hash);
+
         self.stack.push(VariantObject {
-            enum_hash,
-            hash,
+            rtti: rtti.clone(),
             object,
         });
 
@@ -1451,6 +1947,13 @@ impl Vm {
         Ok(())
     }
 
+    #[inline]
+    fn op_const(&mut self, hash: Hash) -> Result<(), VmError> {
+        let value = self.unit.lookup_const(hash)?.to_value();
+        self.stack.push(value);
+        Ok(())
+    }
+
     /// Optimize operation to perform string concatenation.
     #[inline]
     fn op_string_concat(&mut self, len: usize, size_hint: usize) -> Result<(), VmError> {
@@ -1481,10 +1984,21 @@ impl Vm {
                         crate::STRING_DISPLAY,
                         (Value::String(b.clone()),),
                     )? {
-                        return Err(VmError::from(VmErrorKind::MissingProtocol {
-                            protocol: crate::STRING_DISPLAY,
-                            actual: actual.type_info()?,
-                        }));
+                        buf = b.take()?;
+
+                        let displayed =
+                            crate::format::display_struct(&actual, &mut buf, &mut |v, p, b| {
+                                self.call_format_protocol(v, p, b)
+                            })?;
+
+                        if !displayed {
+                            return Err(VmError::from(VmErrorKind::MissingProtocol {
+                                protocol: crate::STRING_DISPLAY,
+                                actual: actual.type_info()?,
+                            }));
+                        }
+
+                        continue;
                     }
 
                     let value = fmt::Result::from_value(self.stack.pop()?)?;
@@ -1502,6 +2016,45 @@ impl Vm {
         Ok(())
     }
 
+    /// Format the value on top of the stack.
+    fn op_format(&mut self, spec: FormatSpec) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+        let mut buf = String::new();
+
+        spec.format(&value, &mut buf, &mut |value, protocol, buf| {
+            self.call_format_protocol(value, protocol, buf)
+        })?;
+
+        self.stack.push(buf);
+        Ok(())
+    }
+
+    /// Call a natively implemented formatting protocol, like `STRING_DISPLAY`
+    /// or `DEBUG_FMT`, which appends to the given buffer.
+    ///
+    /// Returns `false` if the value doesn't implement the protocol.
+    fn call_format_protocol(
+        &mut self,
+        value: &Value,
+        protocol: Protocol,
+        buf: &mut String,
+    ) -> Result<bool, VmError> {
+        let b = Shared::new(std::mem::take(buf));
+        let result = self.call_native_protocol(value, protocol, (Value::String(b.clone()),))?;
+        *buf = b.take()?;
+
+        let result = match result {
+            Some(result) => result,
+            None => return Ok(false),
+        };
+
+        if let Err(fmt::Error) = fmt::Result::from_value(result)? {
+            return Err(VmError::from(VmErrorKind::FormatError));
+        }
+
+        Ok(true)
+    }
+
     #[inline]
     fn op_unwrap(&mut self) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -1663,6 +2216,64 @@ impl Vm {
         Ok(())
     }
 
+    #[inline]
+    fn op_in_range_byte(&mut self, start: u8, end: u8, inclusive: bool) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Byte(actual) => Self::in_range(actual, start, end, inclusive),
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    #[inline]
+    fn op_in_range_character(
+        &mut self,
+        start: char,
+        end: char,
+        inclusive: bool,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Char(actual) => Self::in_range(actual, start, end, inclusive),
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    #[inline]
+    fn op_in_range_integer(
+        &mut self,
+        start: i64,
+        end: i64,
+        inclusive: bool,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Integer(actual) => Self::in_range(actual, start, end, inclusive),
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    /// Test if the given value is in the given range.
+    fn in_range<T>(value: T, start: T, end: T, inclusive: bool) -> bool
+    where
+        T: PartialOrd,
+    {
+        if inclusive {
+            start <= value && value <= end
+        } else {
+            start <= value && value < end
+        }
+    }
+
     /// Test if the top of stack is equal to the string at the given static
     /// string location.
     #[inline]
@@ -1776,7 +2387,7 @@ impl Vm {
             (TypeCheck::Type(hash), Value::TypedTuple(typed_tuple)) => {
                 let typed_tuple = typed_tuple.borrow_ref()?;
 
-                if typed_tuple.hash != hash {
+                if typed_tuple.rtti.hash != hash {
                     return Ok(None);
                 }
 
@@ -1785,7 +2396,7 @@ impl Vm {
             (TypeCheck::Variant(hash), Value::TupleVariant(variant_tuple)) => {
                 let variant_tuple = variant_tuple.borrow_ref()?;
 
-                if variant_tuple.hash != hash {
+                if variant_tuple.rtti.hash != hash {
                     return Ok(None);
                 }
 
@@ -1828,7 +2439,7 @@ impl Vm {
             (TypeCheck::Variant(hash), Value::VariantObject(variant_object)) => {
                 let variant_object = variant_object.borrow_ref()?;
 
-                if variant_object.hash == hash {
+                if variant_object.rtti.hash == hash {
                     return Ok(Some(f(&variant_object.object, keys)));
                 }
             }
@@ -1842,6 +2453,7 @@ impl Vm {
     fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Generator::new(vm));
         Ok(())
@@ -1851,6 +2463,7 @@ impl Vm {
     fn call_stream_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Stream::new(vm));
         Ok(())
@@ -1860,6 +2473,7 @@ impl Vm {
     fn call_async_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Future::new(vm.async_complete()));
         Ok(())
@@ -1887,19 +2501,35 @@ impl Vm {
     fn op_load_fn(&mut self, hash: Hash) -> Result<(), VmError> {
         let function = match self.unit.lookup(hash) {
             Some(info) => match info {
-                UnitFn::Offset { offset, call, args } => Function::from_offset(
+                UnitFn::Offset {
+                    offset,
+                    call,
+                    args,
+                    variadic,
+                } => Function::from_offset(
                     self.context.clone(),
                     self.unit.clone(),
                     offset,
                     call,
                     args,
+                    variadic,
                 ),
-                UnitFn::Tuple { hash, args } => Function::from_tuple(hash, args),
-                UnitFn::TupleVariant {
-                    enum_hash,
-                    hash,
-                    args,
-                } => Function::from_variant_tuple(enum_hash, hash, args),
+                UnitFn::Tuple { hash, args } => {
+                    let rtti = self
+                        .unit
+                        .lookup_rtti(hash)
+                        .ok_or_else(|| VmError::from(VmErrorKind::MissingRtti { hash }))?;
+
+                    Function::from_tuple(rtti.clone(), args)
+                }
+                UnitFn::TupleVariant { hash, args, .. } => {
+                    let rtti = self
+                        .unit
+                        .lookup_variant_rtti(hash)
+                        .ok_or_else(|| VmError::from(VmErrorKind::MissingVariantRtti { hash }))?;
+
+                    Function::from_variant_tuple(rtti.clone(), args)
+                }
             },
             None => {
                 let handler = self
@@ -1922,8 +2552,13 @@ impl Vm {
             .lookup(hash)
             .ok_or_else(|| VmError::from(VmErrorKind::MissingFunction { hash }))?;
 
-        let (offset, call, args) = match info {
-            UnitFn::Offset { offset, call, args } => (offset, call, args),
+        let (offset, call, args, variadic) = match info {
+            UnitFn::Offset {
+                offset,
+                call,
+                args,
+                variadic,
+            } => (offset, call, args, variadic),
             _ => return Err(VmError::from(VmErrorKind::MissingFunction { hash })),
         };
 
@@ -1936,6 +2571,7 @@ impl Vm {
             offset,
             call,
             args,
+            variadic,
             environment,
         );
 
@@ -1951,8 +2587,9 @@ impl Vm {
                     offset,
                     call,
                     args: expected,
+                    variadic,
                 } => {
-                    Self::check_args(args, expected)?;
+                    let args = Self::offset_args(&mut self.stack, args, expected, variadic)?;
                     self.call_offset_fn(offset, call, args)?;
                 }
                 UnitFn::Tuple {
@@ -1961,17 +2598,29 @@ impl Vm {
                 } => {
                     Self::check_args(args, expected)?;
                     let tuple = self.stack.pop_sequence(args)?;
-                    let value = Value::typed_tuple(hash, tuple);
+
+                    let rtti = self
+                        .unit
+                        .lookup_rtti(hash)
+                        .ok_or_else(|| VmError::from(VmErrorKind::MissingRtti { hash }))?;
+
+                    let value = Value::typed_tuple(rtti.clone(), tuple);
                     self.stack.push(value);
                 }
                 UnitFn::TupleVariant {
-                    enum_hash,
                     hash,
                     args: expected,
+                    ..
                 } => {
                     Self::check_args(args, expected)?;
                     let tuple = self.stack.pop_sequence(args)?;
-                    let value = Value::variant_tuple(enum_hash, hash, tuple);
+
+                    let rtti = self
+                        .unit
+                        .lookup_variant_rtti(hash)
+                        .ok_or_else(|| VmError::from(VmErrorKind::MissingVariantRtti { hash }))?;
+
+                    let value = Value::variant_tuple(rtti.clone(), tuple);
                     self.stack.push(value);
                 }
             },
@@ -2005,8 +2654,10 @@ impl Vm {
                     offset,
                     call,
                     args: expected,
+                    variadic,
                 } => {
-                    Self::check_args(args, expected)?;
+                    let args = self.push_default_args(hash, args, expected, variadic);
+                    let args = Self::offset_args(&mut self.stack, args, expected, variadic)?;
                     self.call_offset_fn(offset, call, args)?;
                 }
                 _ => {
@@ -2020,6 +2671,16 @@ impl Vm {
                 let handler = match self.context.lookup(hash) {
                     Some(handler) => handler,
                     None => {
+                        // NB: structs and variants declared in rune can be
+                        // cloned unless they provide their own `clone`.
+                        if args == 1 && hash == Hash::instance_function(type_of, "clone") {
+                            if let Some(value) = instance.try_clone_struct()? {
+                                self.stack.pop()?;
+                                self.stack.push(value);
+                                return Ok(());
+                            }
+                        }
+
                         return Err(VmError::from(VmErrorKind::MissingInstanceFunction {
                             instance: instance.type_info()?,
                             hash,
@@ -2034,6 +2695,34 @@ impl Vm {
         Ok(())
     }
 
+    /// Push the default values of the arguments which have been left out of
+    /// a call to the instance function with the given hash, returning the
+    /// new number of arguments.
+    fn push_default_args(
+        &mut self,
+        instance_fn: Hash,
+        mut args: usize,
+        expected: usize,
+        variadic: bool,
+    ) -> usize {
+        // NB: the rest argument of a variadic function has no default value.
+        let expected = if variadic { expected - 1 } else { expected };
+
+        while args < expected {
+            let hash = Hash::instance_default_arg(instance_fn, args);
+
+            let value = match self.unit.lookup_const(hash) {
+                Ok(value) => value.to_value(),
+                Err(..) => break,
+            };
+
+            self.stack.push(value);
+            args += 1;
+        }
+
+        args
+    }
+
     fn op_call_fn(&mut self, args: usize) -> Result<Option<VmHalt>, VmError> {
         let function = self.stack.pop()?;
 
@@ -2055,24 +2744,115 @@ impl Vm {
         Ok(None)
     }
 
+    /// Call a function with the spread arguments on top of the stack.
+    fn op_call_spread(&mut self, hash: Hash) -> Result<(), VmError> {
+        let args = self.spread_args()?;
+        self.op_call(hash, args)
+    }
+
+    /// Call an instance function with the spread arguments on top of the
+    /// stack.
+    fn op_call_instance_spread(&mut self, hash: Hash) -> Result<(), VmError> {
+        let args = self.spread_args()?;
+        self.op_call_instance(hash, args)
+    }
+
+    /// Call the function on top of the stack with the spread arguments below
+    /// it.
+    fn op_call_fn_spread(&mut self) -> Result<Option<VmHalt>, VmError> {
+        let function = self.stack.pop()?;
+        let args = self.spread_args()?;
+        self.stack.push(function);
+        self.op_call_fn(args)
+    }
+
+    /// Concatenate vectors or tuples into a single vector.
+    fn op_concat(&mut self, count: usize) -> Result<(), VmError> {
+        let mut vec = Vec::new();
+
+        for value in self.stack.pop_sequence(count)? {
+            Self::extend_spread(&mut vec, value)?;
+        }
+
+        self.stack.push(Shared::try_with_heap(vec)?);
+        Ok(())
+    }
+
+    /// Push the values of the vector or tuple on top of the stack as
+    /// arguments, returning the number of arguments.
+    fn spread_args(&mut self) -> Result<usize, VmError> {
+        let value = self.stack.pop()?;
+        let mut args = Vec::new();
+        Self::extend_spread(&mut args, value)?;
+
+        let count = args.len();
+        self.stack.extend(args);
+        Ok(count)
+    }
+
+    /// Extend the output with the values of a vector or tuple being spread.
+    fn extend_spread(output: &mut Vec<Value>, value: Value) -> Result<(), VmError> {
+        match value {
+            Value::Vec(vec) => {
+                output.extend(vec.borrow_ref()?.iter().cloned());
+            }
+            Value::Tuple(tuple) => {
+                output.extend(tuple.borrow_ref()?.iter().cloned());
+            }
+            actual => {
+                return Err(VmError::from(VmErrorKind::UnsupportedSpreadOperand {
+                    actual: actual.type_info()?,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Advance the instruction pointer.
     pub(crate) fn advance(&mut self) {
         self.ip = self.ip.overflowing_add(1).0;
     }
 
-    /// Evaluate a single instruction.
-    pub(crate) fn run(&mut self) -> Result<VmHalt, VmError> {
+    /// Evaluate instructions until the virtual machine halts, or the given
+    /// limit of instructions has been used up if one is specified.
+    ///
+    /// The limit is decremented for every instruction executed, and hitting it
+    /// halts with [VmHalt::Limited], just like running out of fuel.
+    pub(crate) fn run_for(&mut self, limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
+        let environment = self.environment();
+        environment::with(environment, || self.run_inner(limit)).call()
+    }
+
+    fn run_inner(&mut self, mut limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
         loop {
-            if !budget::take() {
-                return Ok(VmHalt::Limited);
+            if let Some(limit) = &mut limit {
+                if **limit == 0 {
+                    return Ok(VmHalt::Limited);
+                }
+
+                **limit -= 1;
+            }
+
+            if let Some(fuel) = &self.fuel {
+                match fuel.get() {
+                    0 => return Ok(VmHalt::Limited),
+                    n => fuel.set(n - 1),
+                }
             }
 
+            let ip = self.ip;
+
             let inst = *self
                 .unit
-                .instruction_at(self.ip)
+                .instruction_at(ip)
                 .ok_or_else(|| VmError::from(VmErrorKind::IpOutOfBounds))?;
 
-            log::trace!("{}: {}", self.ip, inst);
+            log::trace!("{}: {}", ip, inst);
+
+            if let Some(coverage) = &self.coverage {
+                coverage.record(ip);
+            }
 
             match inst {
                 Inst::Not => {
@@ -2092,6 +2872,20 @@ impl Vm {
                         return Ok(reason);
                     }
                 }
+                Inst::CallSpread { hash } => {
+                    self.op_call_spread(hash)?;
+                }
+                Inst::CallInstanceSpread { hash } => {
+                    self.op_call_instance_spread(hash)?;
+                }
+                Inst::CallFnSpread => {
+                    if let Some(reason) = self.op_call_fn_spread()? {
+                        return Ok(reason);
+                    }
+                }
+                Inst::Concat { count } => {
+                    self.op_concat(count)?;
+                }
                 Inst::LoadInstanceFn { hash } => {
                     self.op_load_instance_fn(hash)?;
                 }
@@ -2107,12 +2901,18 @@ impl Vm {
                 Inst::TupleIndexGetAt { offset, index } => {
                     self.op_tuple_index_get_at(offset, index)?;
                 }
+                Inst::TupleIndexGetFromEndAt { offset, index } => {
+                    self.op_tuple_index_get_from_end_at(offset, index)?;
+                }
                 Inst::ObjectIndexGet { slot } => {
                     self.op_object_index_get(slot)?;
                 }
                 Inst::ObjectIndexGetAt { offset, slot } => {
                     self.op_object_index_get_at(offset, slot)?;
                 }
+                Inst::ObjectFields { slot } => {
+                    self.op_object_fields(slot)?;
+                }
                 Inst::IndexSet => {
                     self.op_index_set()?;
                 }
@@ -2128,6 +2928,16 @@ impl Vm {
                         return Ok(VmHalt::Exited);
                     }
                 }
+                Inst::CheckArgument {
+                    offset,
+                    type_check,
+                    slot,
+                } => {
+                    self.op_check_argument(offset, type_check, slot)?;
+                }
+                Inst::CheckReturn { type_check } => {
+                    self.op_check_return(type_check)?;
+                }
                 Inst::Await => {
                     let future = self.op_await()?;
                     // NB: the future itself will advance the virtual machine.
@@ -2152,7 +2962,8 @@ impl Vm {
                     self.op_popn(count)?;
                 }
                 Inst::PopAndJumpIfNot { count, offset } => {
-                    self.op_pop_and_jump_if_not(count, offset)?;
+                    let taken = self.op_pop_and_jump_if_not(count, offset)?;
+                    self.record_branch(ip, taken);
                 }
                 Inst::Clean { count } => {
                     self.op_clean(count)?;
@@ -2169,6 +2980,15 @@ impl Vm {
                 Inst::Replace { offset } => {
                     self.op_replace(offset)?;
                 }
+                Inst::CaptureCell { offset } => {
+                    self.op_capture_cell(offset)?;
+                }
+                Inst::CopyCell { offset } => {
+                    self.op_copy_cell(offset)?;
+                }
+                Inst::ReplaceCell { offset } => {
+                    self.op_replace_cell(offset)?;
+                }
                 Inst::Gt => {
                     self.op_gt()?;
                 }
@@ -2191,13 +3011,16 @@ impl Vm {
                     self.op_jump(offset)?;
                 }
                 Inst::JumpIf { offset } => {
-                    self.op_jump_if(offset)?;
+                    let taken = self.op_jump_if(offset)?;
+                    self.record_branch(ip, taken);
                 }
                 Inst::JumpIfNot { offset } => {
-                    self.op_jump_if_not(offset)?;
+                    let taken = self.op_jump_if_not(offset)?;
+                    self.record_branch(ip, taken);
                 }
                 Inst::JumpIfBranch { branch, offset } => {
-                    self.op_jump_if_branch(branch, offset)?;
+                    let taken = self.op_jump_if_branch(branch, offset)?;
+                    self.record_branch(ip, taken);
                 }
                 Inst::Vec { count } => {
                     self.op_vec(count)?;
@@ -2227,9 +3050,15 @@ impl Vm {
                 Inst::Bytes { slot } => {
                     self.op_bytes(slot)?;
                 }
+                Inst::Const { hash } => {
+                    self.op_const(hash)?;
+                }
                 Inst::StringConcat { len, size_hint } => {
                     self.op_string_concat(len, size_hint)?;
                 }
+                Inst::Format { spec } => {
+                    self.op_format(spec)?;
+                }
                 Inst::Is => {
                     self.op_is()?;
                 }
@@ -2260,6 +3089,27 @@ impl Vm {
                 Inst::EqInteger { integer } => {
                     self.op_eq_integer(integer)?;
                 }
+                Inst::InRangeByte {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    self.op_in_range_byte(start, end, inclusive)?;
+                }
+                Inst::InRangeCharacter {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    self.op_in_range_character(start, end, inclusive)?;
+                }
+                Inst::InRangeInteger {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    self.op_in_range_integer(start, end, inclusive)?;
+                }
                 Inst::EqStaticString { slot } => {
                     self.op_eq_static_string(slot)?;
                 }
@@ -2299,6 +3149,10 @@ impl Vm {
                 }
             }
 
+            if let Some(memory) = &self.memory {
+                memory.check()?;
+            }
+
             self.advance();
         }
     }
@@ -2542,6 +3396,51 @@ impl Vm {
 
         Ok(())
     }
+
+    /// Check the arguments to a function, where a variadic function takes at
+    /// least all arguments but the last.
+    fn check_offset_args(args: usize, expected: usize, variadic: bool) -> Result<(), VmError> {
+        if !variadic {
+            return Self::check_args(args, expected);
+        }
+
+        if args + 1 < expected {
+            return Err(VmError::from(VmErrorKind::BadVariadicArgumentCount {
+                actual: args,
+                expected: expected - 1,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Collect the remaining arguments on top of the stack into a vector,
+    /// which is passed as the last argument of a variadic function.
+    fn collect_rest_args(stack: &mut Stack, args: usize, expected: usize) -> Result<(), VmError> {
+        let rest = stack.pop_sequence(args + 1 - expected)?;
+        stack.push(Shared::try_with_heap(rest)?);
+        Ok(())
+    }
+
+    /// Check the arguments on top of the stack for a call to a function,
+    /// collecting the remaining arguments of a variadic function.
+    ///
+    /// Returns the number of arguments the function is called with.
+    pub(crate) fn offset_args(
+        stack: &mut Stack,
+        args: usize,
+        expected: usize,
+        variadic: bool,
+    ) -> Result<usize, VmError> {
+        Self::check_offset_args(args, expected, variadic)?;
+
+        if !variadic {
+            return Ok(args);
+        }
+
+        Self::collect_rest_args(stack, args, expected)?;
+        Ok(expected)
+    }
 }
 
 /// A call frame.
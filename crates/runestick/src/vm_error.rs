@@ -1,6 +1,8 @@
 use crate::panic::BoxedPanic;
+use crate::sandbox::Denied;
 use crate::{
-    AccessError, Hash, Item, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit, Value, VmHaltInfo,
+    AccessError, FormatSpec, Hash, Item, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit,
+    Value, VmHaltInfo,
 };
 use std::fmt;
 use std::sync::Arc;
@@ -31,6 +33,7 @@ impl VmError {
     {
         Ok(Self::from(VmErrorKind::BadArgumentType {
             arg,
+            name: None,
             expected: T::type_info(),
             actual: value.type_info()?,
         }))
@@ -108,11 +111,16 @@ impl VmError {
     ///
     /// Returns `true` if the error should be propagated.
     fn is_critical(&self) -> bool {
-        match &*self.kind {
-            VmErrorKind::Panic { .. } => true,
-            VmErrorKind::Unwound { .. } => true,
-            _ => false,
-        }
+        matches!(
+            &*self.kind,
+            VmErrorKind::Panic { .. }
+                | VmErrorKind::Unwound { .. }
+                | VmErrorKind::MemoryLimitExceeded { .. }
+                | VmErrorKind::PermissionDenied { .. }
+                | VmErrorKind::Halted {
+                    halt: VmHaltInfo::Limited,
+                }
+        )
     }
 }
 
@@ -158,9 +166,27 @@ pub enum VmErrorKind {
         /// The reason why the virtual machine stopped.
         halt: VmHaltInfo,
     },
+    /// A native function was denied a capability by the sandbox.
+    #[error("permission denied: {denied}")]
+    PermissionDenied {
+        /// The capability which was denied.
+        denied: Denied,
+    },
+    /// The task running the virtual machine was cancelled.
+    #[error("task was cancelled")]
+    Cancelled,
     /// Error raised when external format function results in error.
     #[error("failed to format argument")]
     FormatError,
+    /// Error raised when a value can't be formatted with a format
+    /// specification.
+    #[error("`{actual}` can't be formatted with `{{:{spec}}}`")]
+    UnsupportedFormat {
+        /// The format specification.
+        spec: FormatSpec,
+        /// The value being formatted.
+        actual: TypeInfo,
+    },
     /// Error raised when interacting with the stack.
     #[error("stack error: {error}")]
     StackError {
@@ -177,6 +203,14 @@ pub enum VmErrorKind {
     /// The virtual machine encountered a divide-by-zero.
     #[error("division by zero")]
     DivideByZero,
+    /// The virtual machine exceeded the memory limit it was configured with.
+    #[error("memory limit exceeded, using `{usage}` bytes out of `{limit}`")]
+    MemoryLimitExceeded {
+        /// The number of bytes in use.
+        usage: usize,
+        /// The configured limit in bytes.
+        limit: usize,
+    },
     /// Failure to lookup function.
     #[error("missing entry `{item}` with hash `{hash}`")]
     MissingEntry {
@@ -191,6 +225,18 @@ pub enum VmErrorKind {
         /// Hash of function to look up.
         hash: Hash,
     },
+    /// Failure to lookup runtime type information.
+    #[error("missing runtime information for type with hash `{hash}`")]
+    MissingRtti {
+        /// Hash of the type to look up.
+        hash: Hash,
+    },
+    /// Failure to lookup runtime variant information.
+    #[error("missing runtime information for variant with hash `{hash}`")]
+    MissingVariantRtti {
+        /// Hash of the variant to look up.
+        hash: Hash,
+    },
     /// Failure to lookup instance function.
     #[error("missing instance function `{hash}` for `{instance}`")]
     MissingInstanceFunction {
@@ -240,6 +286,12 @@ pub enum VmErrorKind {
         /// Slot which is missing a static string.
         slot: usize,
     },
+    /// Indicates that a constant value is missing for the given hash.
+    #[error("constant value with hash `{hash}` does not exist")]
+    MissingConstValue {
+        /// Hash of the missing constant.
+        hash: Hash,
+    },
     /// Indicates that a static object keys is missing for the given slot.
     #[error("static object keys slot `{slot}` does not exist")]
     MissingStaticObjectKeys {
@@ -254,16 +306,37 @@ pub enum VmErrorKind {
         /// The expected number of arguments.
         expected: usize,
     },
+    /// Too few arguments provided in a call to a variadic function.
+    #[error("wrong number of arguments `{actual}`, expected at least `{expected}`")]
+    BadVariadicArgumentCount {
+        /// The actual number of arguments.
+        actual: usize,
+        /// The minimum number of arguments.
+        expected: usize,
+    },
     /// Failure to convert from one type to another.
-    #[error("bad argument #{arg}, expected `{expected}` but got `{actual}`")]
+    #[error(
+        "bad argument #{arg}{}, expected `{expected}` but got `{actual}`",
+        .name.as_ref().map(|name| format!(" `{}`", name)).unwrap_or_default()
+    )]
     BadArgumentType {
         /// The argument location that was converted.
         arg: usize,
+        /// The name of the parameter, if known.
+        name: Option<String>,
         /// The argument type we expected.
         expected: TypeInfo,
         /// The argument type we got.
         actual: TypeInfo,
     },
+    /// A value returned from a function doesn't match its return type.
+    #[error("bad return value, expected `{expected}` but got `{actual}`")]
+    BadReturnType {
+        /// The return type we expected.
+        expected: TypeInfo,
+        /// The return type we got.
+        actual: TypeInfo,
+    },
     /// Failure to convert from one type to another.
     #[error("bad argument #{arg} (expected `{to}`): {error}")]
     BadArgument {
@@ -394,6 +467,12 @@ pub enum VmErrorKind {
         /// The actual operand.
         actual: TypeInfo,
     },
+    /// Tried to spread a value which isn't a vector or a tuple.
+    #[error("`{actual}` can't be spread as arguments")]
+    UnsupportedSpreadOperand {
+        /// The actual operand.
+        actual: TypeInfo,
+    },
     /// Trying to resume a generator that has completed.
     #[error("cannot resume a generator that has completed")]
     GeneratorComplete,
@@ -442,6 +521,13 @@ pub enum VmErrorKind {
         /// The expected tuple length.
         expected: usize,
     },
+    /// Error raised when a variable was expected to be captured in a shared
+    /// cell.
+    #[error("expected a shared cell, but found `{actual}`")]
+    ExpectedCell {
+        /// The actual type found.
+        actual: TypeInfo,
+    },
     /// Internal error that happens when we run out of items in a list.
     #[error("unexpectedly ran out of items to iterate over")]
     IterationError,
@@ -1,4 +1,5 @@
-use crate::budget;
+use crate::environment;
+use crate::scheduler::YieldNow;
 use crate::{GeneratorState, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo};
 
 /// The execution environment for a virtual machine.
@@ -28,6 +29,38 @@ impl VmExecution {
         }
     }
 
+    /// Set the amount of fuel available to the execution.
+    ///
+    /// See [Vm::set_fuel].
+    pub fn set_fuel(&mut self, fuel: Option<usize>) -> Result<(), VmError> {
+        let (first, rest) = match self.vms.split_first_mut() {
+            Some(vms) => vms,
+            None => return Err(VmError::from(VmErrorKind::NoRunningVm)),
+        };
+
+        first.set_fuel(fuel);
+
+        for vm in rest {
+            vm.share_fuel(first);
+        }
+
+        Ok(())
+    }
+
+    /// Get the amount of fuel remaining, or `None` if the execution is not
+    /// limited.
+    pub fn remaining_fuel(&self) -> Result<Option<usize>, VmError> {
+        Ok(self.vm()?.remaining_fuel())
+    }
+
+    /// Add fuel to the execution.
+    ///
+    /// See [Vm::add_fuel].
+    pub fn add_fuel(&mut self, fuel: usize) -> Result<(), VmError> {
+        self.vm_mut()?.add_fuel(fuel);
+        Ok(())
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// This will error if the execution is suspended through yielding.
@@ -54,34 +87,75 @@ impl VmExecution {
     }
 
     /// Resume the current execution with support for async instructions.
+    ///
+    /// This will error if the execution runs out of fuel, see
+    /// [async_resume_limited][Self::async_resume_limited] for a version which
+    /// permits continuing after more fuel has been added.
     pub async fn async_resume(&mut self) -> Result<GeneratorState, VmError> {
+        match self.async_resume_limited().await? {
+            Some(state) => Ok(state),
+            None => Err(VmError::from(VmErrorKind::Halted {
+                halt: VmHaltInfo::Limited,
+            })),
+        }
+    }
+
+    /// Resume the current execution with support for async instructions,
+    /// returning `None` if it ran out of fuel.
+    ///
+    /// The execution can be continued by calling this again after fuel has
+    /// been added with [add_fuel][Self::add_fuel]. Note that running out of
+    /// fuel inside of a nested async function, generator, or stream still
+    /// results in an error, since these can't be suspended.
+    ///
+    /// When run as a task in a [Scheduler], this yields to the scheduler
+    /// every time the time slice of the task has been used up. This also
+    /// applies to executions awaited by the task, like calls to async
+    /// functions.
+    ///
+    /// [Scheduler]: crate::scheduler::Scheduler
+    pub async fn async_resume_limited(&mut self) -> Result<Option<GeneratorState>, VmError> {
         loop {
             let len = self.vms.len();
             let vm = self.vm_mut()?;
+            let slice = environment::current(|env| env.slice.clone());
 
-            match Self::run(vm)? {
+            let halt = match &slice {
+                Some(slice) => {
+                    let mut limit = slice.get();
+                    let halt = Self::run_for(vm, Some(&mut limit));
+                    slice.set(limit);
+                    halt?
+                }
+                None => Self::run(vm)?,
+            };
+
+            match halt {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    awaited.into_vm(vm).await?;
+                    let environment = vm.environment();
+                    environment::with(environment, awaited.into_vm(vm)).await?;
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
                     vm_call.into_execution(self)?;
                     continue;
                 }
-                VmHalt::Yielded => return Ok(GeneratorState::Yielded(vm.stack_mut().pop()?)),
-                halt => {
-                    return Err(VmError::from(VmErrorKind::Halted {
-                        halt: halt.into_info(),
-                    }))
+                VmHalt::Yielded => return Ok(Some(GeneratorState::Yielded(vm.stack_mut().pop()?))),
+                // NB: the time slice was used up rather than the fuel, so the
+                // scheduler refills it once it gets back to this task.
+                VmHalt::Limited if slice.map(|slice| slice.get() == 0).unwrap_or_default() => {
+                    YieldNow::default().await;
+                    continue;
                 }
+                VmHalt::Limited => return Ok(None),
             }
 
             if len == 1 {
                 let value = vm.stack_mut().pop()?;
                 debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
                 self.vms.clear();
-                return Ok(GeneratorState::Complete(value));
+                return Ok(Some(GeneratorState::Complete(value)));
             }
 
             self.pop_vm()?;
@@ -90,8 +164,25 @@ impl VmExecution {
 
     /// Resume the current execution without support for async instructions.
     ///
-    /// If any async instructions are encountered, this will error.
+    /// If any async instructions are encountered, this will error. This will
+    /// also error if the execution runs out of fuel, see
+    /// [resume_limited][Self::resume_limited] for a version which permits
+    /// continuing after more fuel has been added.
     pub fn resume(&mut self) -> Result<GeneratorState, VmError> {
+        match self.resume_limited()? {
+            Some(state) => Ok(state),
+            None => Err(VmError::from(VmErrorKind::Halted {
+                halt: VmHaltInfo::Limited,
+            })),
+        }
+    }
+
+    /// Resume the current execution without support for async instructions,
+    /// returning `None` if it ran out of fuel.
+    ///
+    /// The execution can be continued by calling this again after fuel has
+    /// been added with [add_fuel][Self::add_fuel].
+    pub fn resume_limited(&mut self) -> Result<Option<GeneratorState>, VmError> {
         loop {
             let len = self.vms.len();
             let vm = self.vm_mut()?;
@@ -102,7 +193,8 @@ impl VmExecution {
                     vm_call.into_execution(self)?;
                     continue;
                 }
-                VmHalt::Yielded => return Ok(GeneratorState::Yielded(vm.stack_mut().pop()?)),
+                VmHalt::Yielded => return Ok(Some(GeneratorState::Yielded(vm.stack_mut().pop()?))),
+                VmHalt::Limited => return Ok(None),
                 halt => {
                     return Err(VmError::from(VmErrorKind::Halted {
                         halt: halt.into_info(),
@@ -114,7 +206,7 @@ impl VmExecution {
                 let value = vm.stack_mut().pop()?;
                 debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
                 self.vms.clear();
-                return Ok(GeneratorState::Complete(value));
+                return Ok(Some(GeneratorState::Complete(value)));
             }
 
             self.pop_vm()?;
@@ -129,7 +221,7 @@ impl VmExecution {
         let len = self.vms.len();
         let vm = self.vm_mut()?;
 
-        match budget::with(1, || Self::run(vm)).call()? {
+        match Self::run_for(vm, Some(&mut 1))? {
             VmHalt::Exited => (),
             VmHalt::VmCall(vm_call) => {
                 vm_call.into_execution(self)?;
@@ -159,10 +251,11 @@ impl VmExecution {
         let len = self.vms.len();
         let vm = self.vm_mut()?;
 
-        match budget::with(1, || Self::run(vm)).call()? {
+        match Self::run_for(vm, Some(&mut 1))? {
             VmHalt::Exited => (),
             VmHalt::Awaited(awaited) => {
-                awaited.into_vm(vm).await?;
+                let environment = vm.environment();
+                environment::with(environment, awaited.into_vm(vm)).await?;
                 return Ok(None);
             }
             VmHalt::VmCall(vm_call) => {
@@ -212,7 +305,12 @@ impl VmExecution {
 
     #[inline]
     fn run(vm: &mut Vm) -> Result<VmHalt, VmError> {
-        match vm.run() {
+        Self::run_for(vm, None)
+    }
+
+    #[inline]
+    fn run_for(vm: &mut Vm, limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
+        match vm.run_for(limit) {
             Ok(reason) => Ok(reason),
             Err(error) => Err(error.into_unwinded(vm.unit(), vm.ip())),
         }